@@ -1,13 +1,7 @@
 use bdaproto::{bda_client::BdaClient, Resource};
 use clap::Args;
-use std::{
-    error::Error,
-    fs::{self, File},
-    io::BufReader,
-    path::PathBuf,
-};
+use std::{error::Error, path::PathBuf};
 use tonic::{transport::Channel, Request};
-use yaml_rust::{YamlEmitter, YamlLoader};
 
 #[derive(Args, Debug, PartialEq)]
 #[clap(author, version, about, long_about = None)]
@@ -16,6 +10,8 @@ pub struct Config {
     pub file: Vec<PathBuf>,
     #[clap(short, long, min_values = 1, required_unless_present_all = ["file"])]
     pub json: Vec<Resource>,
+    #[clap(short = 'R', long)]
+    pub recursive: bool,
     #[clap(short, long)]
     pub debug: bool,
 }
@@ -39,48 +35,11 @@ pub async fn cmd(
 }
 fn collect_resources(cfg: &Config) -> Result<Vec<Resource>, Box<dyn Error>> {
     let mut resources = cfg.json.clone();
-    if !cfg.file.is_empty() {
-        for f in &cfg.file {
-            match f.extension() {
-                None => continue,
-                Some(x) if x == "json" => {
-                    if cfg.debug {
-                        eprintln!("Reading JSON file: {}...", f.to_str().unwrap_or("None"))
-                    }
-                    File::open(f)
-                        .and_then(|f| Ok(BufReader::new(f)))
-                        .and_then(|r| Ok(serde_json::from_reader(r)?))
-                        .and_then(|r| {
-                            if cfg.debug {
-                                eprintln!("    Adding resource: {:?}...", r)
-                            }
-                            Ok(resources.push(r))
-                        })?;
-                }
-                Some(x) if x == "yaml" || x == "yml" => {
-                    if cfg.debug {
-                        eprintln!("Reading YAML file: {}...", f.to_str().unwrap_or("None"))
-                    }
-                    for ref y in fs::read_to_string(f)
-                        .and_then(|ref s| Ok(YamlLoader::load_from_str(s)))??
-                    {
-                        let mut out_str = String::new();
-                        let mut emitter = YamlEmitter::new(&mut out_str);
-                        emitter.dump(y).unwrap();
-                        let r: Resource = serde_yaml::from_str(&out_str)?;
-                        if cfg.debug {
-                            eprintln!("    Adding resource: {:?}...", r)
-                        }
-                        resources.push(r);
-                    }
-                }
-                _ => eprintln!(
-                    "Ignoring file: {}. Only extensions json|yaml|yml are accepted",
-                    f.to_str().unwrap_or("None")
-                ),
-            }
-        }
-    }
+    resources.append(&mut crate::input::collect_resources(
+        &cfg.file,
+        cfg.recursive,
+        cfg.debug,
+    )?);
     Ok(resources)
 }
 
@@ -88,6 +47,7 @@ fn collect_resources(cfg: &Config) -> Result<Vec<Resource>, Box<dyn Error>> {
 mod test_super {
     use super::*;
     use clap::StructOpt;
+    use std::collections::HashMap;
     #[test]
     fn test_apply_miss() {
         if let Err(_) = crate::Config::try_parse_from(["bdacli", "apply"]) {
@@ -101,12 +61,26 @@ mod test_super {
         let exp = Config {
             file: vec![PathBuf::from("file.json")],
             json: vec![],
+            recursive: false,
             debug: false,
         };
         let cfg = crate::Config::try_parse_from(["bdacli", "apply", "-f", "file.json"]).unwrap();
         assert_eq!(cfg.command, crate::Command::Apply(exp))
     }
 
+    #[test]
+    fn test_apply_stdin_recursive() {
+        let exp = Config {
+            file: vec![PathBuf::from("-"), PathBuf::from("dir/")],
+            json: vec![],
+            recursive: true,
+            debug: false,
+        };
+        let cfg = crate::Config::try_parse_from(["bdacli", "apply", "-R", "-f", "-", "-f", "dir/"])
+            .unwrap();
+        assert_eq!(cfg.command, crate::Command::Apply(exp))
+    }
+
     #[test]
     fn test_apply_json() {
         let exp = Config {
@@ -118,8 +92,11 @@ mod test_super {
                 description: String::new(),
                 tags: vec![],
                 attributes: None,
+                references: vec![],
+                labels: HashMap::new(),
                 resource_kind: None,
             }],
+            recursive: false,
             debug: false,
         };
         let cfg =
@@ -138,8 +115,11 @@ mod test_super {
                 description: String::new(),
                 tags: vec![],
                 attributes: None,
+                references: vec![],
+                labels: HashMap::new(),
                 resource_kind: None,
             }],
+            recursive: false,
             debug: false,
         };
         let cfg = crate::Config::try_parse_from([
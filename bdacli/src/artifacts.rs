@@ -0,0 +1,158 @@
+use bdaproto::engine_client::EngineClient;
+use bdaproto::{GetArtifactRequest, ListArtifactsRequest};
+use clap::{Args, Subcommand};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+use tonic::Request;
+use url::Url;
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub command: ArtifactsCommand,
+    /// Engine storing the artifacts
+    #[clap(long, default_value = crate::DEFAULT_ENGINE_ENDPOINT)]
+    pub engine: Url,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum ArtifactsCommand {
+    /// Lists the artifacts, most recent first
+    Ls {
+        /// Run to list the produced artifacts of
+        #[clap(short, long)]
+        run: Option<String>,
+    },
+    /// Downloads an artifact
+    Get {
+        digest: String,
+        /// Path to write the artifact to, a file artifact is written to stdout by default
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+pub async fn cmd(cfg: &Config) -> Result<(), Box<dyn Error>> {
+    let mut client = EngineClient::connect(cfg.engine.to_string()).await?;
+    match cfg.command {
+        ArtifactsCommand::Ls { ref run } => {
+            let request = ListArtifactsRequest {
+                run: run.clone().unwrap_or_default(),
+            };
+            let response = client
+                .list_artifacts(Request::new(request))
+                .await?
+                .into_inner();
+            for a in response.artifacts {
+                let kind = if a.directory { "dir" } else { "file" };
+                let line = format!("{}\t{}\t{}", a.digest, kind, a.size);
+                match a.output.as_str() {
+                    "" => println!("{}", line),
+                    output => println!("{}\t{}", line, output),
+                }
+            }
+        }
+        ArtifactsCommand::Get {
+            ref digest,
+            ref output,
+        } => {
+            let request = GetArtifactRequest {
+                digest: digest.clone(),
+            };
+            let mut stream = client
+                .get_artifact(Request::new(request))
+                .await?
+                .into_inner();
+            let mut current: Option<(String, Box<dyn Write>)> = None;
+            while let Some(chunk) = stream.message().await? {
+                let target = target_path(output.as_deref(), &chunk.path)?;
+                if chunk.directory {
+                    let target = target.ok_or_else(|| {
+                        format!("artifact {} is a directory, give --output", digest)
+                    })?;
+                    fs::create_dir_all(target)?;
+                    continue;
+                }
+                if current
+                    .as_ref()
+                    .map_or(true, |(path, _)| path != &chunk.path)
+                {
+                    let file: Box<dyn Write> = match target {
+                        None => Box::new(io::stdout()),
+                        Some(target) => {
+                            if let Some(parent) = target.parent() {
+                                fs::create_dir_all(parent)?;
+                            }
+                            Box::new(fs::File::create(target)?)
+                        }
+                    };
+                    current = Some((chunk.path.clone(), file));
+                }
+                if let Some((_, ref mut file)) = current {
+                    file.write_all(&chunk.content)?;
+                }
+            }
+            if let Some((_, mut file)) = current {
+                file.flush()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where a path of an artifact is written, `None` for stdout. Paths leaving the output are
+/// refused.
+fn target_path(output: Option<&Path>, path: &str) -> Result<Option<PathBuf>, String> {
+    let relative = Path::new(path);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("invalid artifact path {:?}", path));
+    }
+    match output {
+        None if path.is_empty() => Ok(None),
+        None => Err("the artifact is a directory, give --output".to_string()),
+        Some(output) if path.is_empty() => Ok(Some(output.to_path_buf())),
+        Some(output) => Ok(Some(output.join(relative))),
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_artifacts_get() {
+        let exp = Config {
+            command: ArtifactsCommand::Get {
+                digest: "ab".to_string(),
+                output: Some(PathBuf::from("out")),
+            },
+            engine: Url::parse(crate::DEFAULT_ENGINE_ENDPOINT).unwrap(),
+        };
+        let cfg = crate::Config::try_parse_from(["bdacli", "artifacts", "get", "ab", "-o", "out"])
+            .unwrap();
+        assert_eq!(cfg.command, crate::Command::Artifacts(exp))
+    }
+
+    #[test]
+    fn test_target_path() {
+        let out = Path::new("out");
+        assert_eq!(target_path(None, ""), Ok(None));
+        assert_eq!(target_path(Some(out), ""), Ok(Some(out.to_path_buf())));
+        assert_eq!(
+            target_path(Some(out), "a/b.txt"),
+            Ok(Some(out.join("a").join("b.txt")))
+        );
+        assert!(target_path(None, "a").is_err());
+        assert!(target_path(Some(out), "../a").is_err());
+        assert!(target_path(Some(out), "/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use bdacore::logic::{self, bundle, validation};
+use bdaproto::resource::ResourceKind;
+use bdaproto::{
+    bda_client::BdaClient, GetResourceRequest, ListInstalledRequest, PutResourceRequest,
+};
+use clap::{Args, Subcommand};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+use tonic::{transport::Channel, Request};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub command: BundleCommand,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum BundleCommand {
+    /// Writes a bundle and its resources as a tar archive
+    Export {
+        name: String,
+        #[clap(short, long, default_value = logic::DEFAULT_NAMESPACE)]
+        namespace: String,
+        #[clap(short, long, default_value = logic::DEFAULT_VERSION)]
+        version: String,
+        /// File to write the archive to, stdout by default
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Puts the resources of a bundle archive, then the bundle
+    Import {
+        /// Archive to read, - for stdin
+        file: PathBuf,
+        /// Namespace to install the bundle into, the namespace of the archive by default
+        #[clap(short, long)]
+        namespace: Option<String>,
+    },
+    /// Lists the resources installed by bundles, with the bundle version
+    Installed {
+        /// Namespaces of the bundles, comma separated or all
+        #[clap(short, long, default_value = "all")]
+        namespaces: String,
+    },
+}
+
+pub async fn cmd(client: &mut BdaClient<Channel>, cfg: &Config) -> Result<(), Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    match cfg.command {
+        BundleCommand::Export {
+            ref name,
+            ref namespace,
+            ref version,
+            ref output,
+        } => {
+            let b = get(client, version, namespace, logic::BUNDLE_KIND, name).await?;
+            let ids = match b.resource_kind {
+                Some(ResourceKind::Bundle(ref b)) => b.resources.clone(),
+                _ => Err(format!("resource {} is not a bundle", name))?,
+            };
+            let mut resources = Vec::new();
+            for id in ids.iter() {
+                let (version, namespace, kind, name) = logic::parse_resource_id(id)?;
+                resources.push(get(client, &version, &namespace, &kind, &name).await?);
+            }
+            let out: Box<dyn Write> = match output {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(io::stdout()),
+            };
+            let manifest = bundle::export(&b, &resources, out)?;
+            if cfg.debug {
+                eprintln!("{:?}", manifest);
+            }
+        }
+        BundleCommand::Import {
+            ref file,
+            ref namespace,
+        } => {
+            let input: Box<dyn Read> = match file.to_str() {
+                Some("-") => Box::new(io::stdin()),
+                _ => Box::new(File::open(file)?),
+            };
+            let (b, resources) = bundle::import(input, namespace.as_deref())?;
+            // nothing is put unless the whole bundle is valid
+            for r in resources.iter().chain([&b]) {
+                validation::validate(r)?;
+            }
+            for r in resources.into_iter().chain([b]) {
+                client
+                    .put_resource(Request::new(PutResourceRequest { resource: Some(r) }))
+                    .await?;
+            }
+        }
+        BundleCommand::Installed { ref namespaces } => {
+            let request = ListInstalledRequest {
+                namespaces: namespaces.clone(),
+            };
+            let response = client
+                .list_installed(Request::new(request))
+                .await?
+                .into_inner();
+            for i in response.resources {
+                println!("{}\t{}@{}", i.resource, i.bundle, i.semver);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn get(
+    client: &mut BdaClient<Channel>,
+    version: &str,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<bdaproto::Resource, Box<dyn Error>> {
+    let request = Request::new(GetResourceRequest {
+        version: version.to_string(),
+        namespace: namespace.to_string(),
+        kind: kind.to_string(),
+        name: name.to_string(),
+    });
+    Ok(client.get_resource(request).await?.into_inner())
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+
+    #[test]
+    fn test_bundle_export() {
+        let exp = Config {
+            command: BundleCommand::Export {
+                name: "ci".to_string(),
+                namespace: "default".to_string(),
+                version: "latest".to_string(),
+                output: Some(PathBuf::from("ci.tar")),
+            },
+            debug: false,
+        };
+        let cfg =
+            crate::Config::try_parse_from(["bdacli", "bundle", "export", "ci", "-o", "ci.tar"])
+                .unwrap();
+        assert_eq!(cfg.command, crate::Command::Bundle(exp))
+    }
+
+    #[test]
+    fn test_bundle_import() {
+        let exp = Config {
+            command: BundleCommand::Import {
+                file: PathBuf::from("ci.tar"),
+                namespace: Some("staging".to_string()),
+            },
+            debug: false,
+        };
+        let cfg = crate::Config::try_parse_from([
+            "bdacli", "bundle", "import", "ci.tar", "-n", "staging",
+        ])
+        .unwrap();
+        assert_eq!(cfg.command, crate::Command::Bundle(exp))
+    }
+}
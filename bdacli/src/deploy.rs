@@ -0,0 +1,214 @@
+use bdacore::data::EntityID;
+use bdacore::logic;
+use bdaproto::deployment::Status;
+use bdaproto::engine_client::EngineClient;
+use bdaproto::{
+    ApproveDeploymentRequest, Deployment, ListDeploymentsRequest, PromoteRequest, ReconcileRequest,
+    RollbackRequest,
+};
+use clap::{Args, Subcommand};
+use std::error::Error;
+use tonic::Request;
+use url::Url;
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub command: DeployCommand,
+    /// Namespace of the deployers, appliers and bundles
+    #[clap(short, long, default_value = logic::DEFAULT_NAMESPACE)]
+    pub namespace: String,
+    /// Engine recording the deployments
+    #[clap(long, default_value = crate::DEFAULT_ENGINE_ENDPOINT)]
+    pub engine: Url,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum DeployCommand {
+    /// Deploys a bundle to the next stage of a deployer
+    Promote {
+        deployer: String,
+        bundle: String,
+        /// Version of the bundle resource
+        #[clap(short, long, default_value = logic::DEFAULT_VERSION)]
+        version: String,
+        /// Stage to deploy to, the stage after the last one the bundle is deployed to by default
+        #[clap(short, long)]
+        stage: Option<String>,
+    },
+    /// Approves a deployment awaiting approval, starting it
+    Approve { id: String },
+    /// Deploys back the bundle of a stage deployed before the current one
+    Rollback { deployer: String, stage: String },
+    /// Lists the deployments, most recent first
+    Ls {
+        /// Deployer to list the deployments of
+        deployer: Option<String>,
+        /// Only the bundle deployed in each stage
+        #[clap(short, long)]
+        current: bool,
+    },
+    /// Promotes the bundles an applier wants in the stages of its deployer
+    Reconcile { applier: String },
+}
+
+pub async fn cmd(cfg: &Config) -> Result<(), Box<dyn Error>> {
+    let mut client = EngineClient::connect(cfg.engine.to_string()).await?;
+    let id =
+        |kind: &str, version: &str, name: &str| resource_id(version, &cfg.namespace, kind, name);
+    let deployments = match cfg.command {
+        DeployCommand::Promote {
+            ref deployer,
+            ref bundle,
+            ref version,
+            ref stage,
+        } => {
+            let request = PromoteRequest {
+                deployer: id(logic::DEPLOYER_KIND, logic::DEFAULT_VERSION, deployer)?,
+                bundle: id(logic::BUNDLE_KIND, version, bundle)?,
+                stage: stage.clone().unwrap_or_default(),
+            };
+            vec![client.promote(Request::new(request)).await?.into_inner()]
+        }
+        DeployCommand::Approve { ref id } => {
+            let request = ApproveDeploymentRequest { id: id.clone() };
+            vec![client
+                .approve_deployment(Request::new(request))
+                .await?
+                .into_inner()]
+        }
+        DeployCommand::Rollback {
+            ref deployer,
+            ref stage,
+        } => {
+            let request = RollbackRequest {
+                deployer: id(logic::DEPLOYER_KIND, logic::DEFAULT_VERSION, deployer)?,
+                stage: stage.clone(),
+            };
+            vec![client.rollback(Request::new(request)).await?.into_inner()]
+        }
+        DeployCommand::Ls {
+            ref deployer,
+            current,
+        } => {
+            let deployer = match deployer {
+                Some(d) => id(logic::DEPLOYER_KIND, logic::DEFAULT_VERSION, d)?,
+                None => String::new(),
+            };
+            let request = ListDeploymentsRequest { deployer, current };
+            client
+                .list_deployments(Request::new(request))
+                .await?
+                .into_inner()
+                .deployments
+        }
+        DeployCommand::Reconcile { ref applier } => {
+            let request = ReconcileRequest {
+                applier: id(logic::APPLIER_KIND, logic::DEFAULT_VERSION, applier)?,
+            };
+            client
+                .reconcile(Request::new(request))
+                .await?
+                .into_inner()
+                .deployments
+        }
+    };
+    for d in deployments.iter() {
+        println!("{}", format_deployment(d));
+    }
+    Ok(())
+}
+
+fn resource_id(version: &str, namespace: &str, kind: &str, name: &str) -> Result<String, String> {
+    match logic::resource_id_builder(version, namespace, kind, name)? {
+        EntityID::ResourceID(id) => Ok(id),
+    }
+}
+
+fn format_deployment(d: &Deployment) -> String {
+    let status = match Status::from_i32(d.status) {
+        Some(Status::AwaitingApproval) => "awaiting approval",
+        Some(Status::Deploying) => "deploying",
+        Some(Status::Deployed) if d.rollback => "rolled back",
+        Some(Status::Deployed) => "deployed",
+        Some(Status::Failed) => "failed",
+        None => "unknown",
+    };
+    let mut line = format!(
+        "{}\t{}\t{}\t{}@{}\t{}",
+        d.id, d.deployer, d.stage, d.bundle, d.semver, status
+    );
+    if !d.error.is_empty() {
+        line.push('\t');
+        line.push_str(&d.error);
+    }
+    line
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+
+    #[test]
+    fn test_deploy_promote() {
+        let exp = Config {
+            command: DeployCommand::Promote {
+                deployer: "web".to_string(),
+                bundle: "web".to_string(),
+                version: "1.2.0".to_string(),
+                stage: Some("prod".to_string()),
+            },
+            namespace: "default".to_string(),
+            engine: Url::parse(crate::DEFAULT_ENGINE_ENDPOINT).unwrap(),
+        };
+        let cfg = crate::Config::try_parse_from([
+            "bdacli", "deploy", "promote", "web", "web", "-v", "1.2.0", "-s", "prod",
+        ])
+        .unwrap();
+        assert_eq!(cfg.command, crate::Command::Deploy(exp))
+    }
+
+    #[test]
+    fn test_deploy_ls() {
+        let exp = Config {
+            command: DeployCommand::Ls {
+                deployer: Some("web".to_string()),
+                current: true,
+            },
+            namespace: "staging".to_string(),
+            engine: Url::parse(crate::DEFAULT_ENGINE_ENDPOINT).unwrap(),
+        };
+        let cfg = crate::Config::try_parse_from([
+            "bdacli",
+            "deploy",
+            "-n",
+            "staging",
+            "ls",
+            "web",
+            "--current",
+        ])
+        .unwrap();
+        assert_eq!(cfg.command, crate::Command::Deploy(exp))
+    }
+
+    #[test]
+    fn test_format_deployment() {
+        let d = Deployment {
+            id: "d1".to_string(),
+            deployer: "/latest/default/deployer/web".to_string(),
+            stage: "prod".to_string(),
+            bundle: "/latest/default/bundle/web".to_string(),
+            semver: "1.2.0".to_string(),
+            status: Status::Failed as i32,
+            error: "deploy run r1 ended Failed".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_deployment(&d),
+            "d1\t/latest/default/deployer/web\tprod\t/latest/default/bundle/web@1.2.0\tfailed\t\
+             deploy run r1 ended Failed"
+        );
+    }
+}
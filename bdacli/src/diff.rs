@@ -0,0 +1,248 @@
+use bdacore::{data::EntityID, logic};
+use bdaproto::{bda_client::BdaClient, GetResourceRequest, Resource};
+use clap::Args;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::{collections::BTreeSet, error::Error, path::PathBuf};
+use tonic::{transport::Channel, Code, Request};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(short, long, min_values = 1, required = true)]
+    pub file: Vec<PathBuf>,
+    #[clap(short = 'R', long)]
+    pub recursive: bool,
+    #[clap(long)]
+    pub no_color: bool,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Modified {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+/// Compares the resources declared in the files against the live state of the datastore.
+/// Returns true when at least one resource differs.
+pub async fn cmd(
+    client: &mut BdaClient<Channel>,
+    cfg: &crate::diff::Config,
+) -> Result<bool, Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    if cfg.no_color {
+        colored::control::set_override(false);
+    }
+    let mut differs = false;
+    for mut local in crate::input::collect_resources(&cfg.file, cfg.recursive, cfg.debug)? {
+        logic::defaults(&mut local);
+        // live secrets are redacted, and local values must never be printed
+        logic::secret::redact(&mut local);
+        let id = logic::resource_id(&local)?;
+        let changes = match get_live(client, &local).await? {
+            Some(mut live) => {
+                logic::defaults(&mut live);
+                diff_resources(&live, &local)?
+            }
+            None => diff_values(
+                "",
+                &Value::Object(Map::new()),
+                &serde_json::to_value(&local)?,
+            ),
+        };
+        if !changes.is_empty() {
+            differs = true;
+            show(&id, &changes);
+        } else if cfg.debug {
+            eprintln!("No changes for {:?}", id);
+        }
+    }
+    Ok(differs)
+}
+
+async fn get_live(
+    client: &mut BdaClient<Channel>,
+    r: &Resource,
+) -> Result<Option<Resource>, Box<dyn Error>> {
+    let request = Request::new(GetResourceRequest {
+        version: r.version.to_string(),
+        namespace: r.namespace.to_string(),
+        kind: logic::resource_kind_to_string(r).ok_or("resource kind not specified")?,
+        name: r.name.to_string(),
+    });
+    match client.get_resource(request).await {
+        Ok(response) => Ok(Some(response.into_inner())),
+        Err(status) if status.code() == Code::NotFound => Ok(None),
+        Err(status) => Err(Box::new(status)),
+    }
+}
+
+pub fn diff_resources(live: &Resource, local: &Resource) -> Result<Vec<Change>, Box<dyn Error>> {
+    Ok(diff_values(
+        "",
+        &serde_json::to_value(live)?,
+        &serde_json::to_value(local)?,
+    ))
+}
+
+pub fn diff_values(path: &str, old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    collect_changes(path, old, new, &mut changes);
+    changes
+}
+
+fn collect_changes(path: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for k in keys {
+                let path = format!("{}.{}", path, k);
+                match (a.get(k), b.get(k)) {
+                    (Some(x), Some(y)) => collect_changes(&path, x, y, changes),
+                    (Some(x), None) => changes.push(Change::Removed {
+                        path,
+                        value: x.clone(),
+                    }),
+                    (None, Some(y)) => changes.push(Change::Added {
+                        path,
+                        value: y.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let path = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => collect_changes(&path, x, y, changes),
+                    (Some(x), None) => changes.push(Change::Removed {
+                        path,
+                        value: x.clone(),
+                    }),
+                    (None, Some(y)) => changes.push(Change::Added {
+                        path,
+                        value: y.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (a, b) if a != b => changes.push(Change::Modified {
+            path: path.to_string(),
+            old: a.clone(),
+            new: b.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn show(id: &EntityID, changes: &[Change]) {
+    match id {
+        EntityID::ResourceID(id) => println!("{}", id.bold()),
+    }
+    show_changes(changes)
+}
+
+/// Prints changes, removed values in red and added ones in green.
+pub fn show_changes(changes: &[Change]) {
+    for change in changes {
+        match change {
+            Change::Added { path, value } => {
+                println!("{}", format!("+ {}: {}", path, value).green())
+            }
+            Change::Removed { path, value } => {
+                println!("{}", format!("- {}: {}", path, value).red())
+            }
+            Change::Modified { path, old, new } => {
+                println!("{}", format!("- {}: {}", path, old).red());
+                println!("{}", format!("+ {}: {}", path, new).green());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_file() {
+        let exp = Config {
+            file: vec![PathBuf::from("file.yaml")],
+            recursive: false,
+            no_color: false,
+            debug: false,
+        };
+        let cfg = crate::Config::try_parse_from(["bdacli", "diff", "-f", "file.yaml"]).unwrap();
+        assert_eq!(cfg.command, crate::Command::Diff(exp))
+    }
+
+    #[test]
+    fn test_diff_miss() {
+        if let Ok(_) = crate::Config::try_parse_from(["bdacli", "diff"]) {
+            assert!(false, "parse should have generated an error")
+        }
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let old = json!({
+            "name": "a",
+            "tags": ["x", "y"],
+            "attributes": {"key1": "value1", "key2": ["a", "b"]}
+        });
+        let new = json!({
+            "name": "a",
+            "tags": ["x"],
+            "description": "d",
+            "attributes": {"key1": "other", "key2": ["a", "b"]}
+        });
+        assert_eq!(
+            diff_values("", &old, &new),
+            vec![
+                Change::Modified {
+                    path: ".attributes.key1".to_string(),
+                    old: json!("value1"),
+                    new: json!("other"),
+                },
+                Change::Added {
+                    path: ".description".to_string(),
+                    value: json!("d"),
+                },
+                Change::Removed {
+                    path: ".tags[1]".to_string(),
+                    value: json!("y"),
+                },
+            ]
+        );
+        assert_eq!(diff_values("", &old, &old), vec![]);
+    }
+
+    #[test]
+    fn test_diff_resources_after_defaults() {
+        let mut live = logic::new_resource_function("name");
+        let mut local = live.clone();
+        local.namespace = String::new();
+        logic::defaults(&mut local);
+        logic::defaults(&mut live);
+        assert_eq!(diff_resources(&live, &local).unwrap(), vec![]);
+    }
+}
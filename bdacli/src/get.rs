@@ -13,10 +13,20 @@ pub struct Config {
     pub names: String,
     #[clap(short, long, default_value = bdacore::logic::DEFAULT_NAMESPACE)]
     pub namespace: String,
+    /// Version, `latest` for the highest release, or a semver range like `^1.2`
     #[clap(short, long, default_value = bdacore::logic::DEFAULT_VERSION)]
     pub version: String,
     #[clap(short, long, default_value = "")]
     pub bql: String,
+    /// Only the resources referencing a resource id, like /latest/default/function/build
+    #[clap(long, default_value = "")]
+    pub references: String,
+    /// Only the resources a resource id references, like /latest/default/dag/pipeline
+    #[clap(long, default_value = "")]
+    pub referenced_by: String,
+    /// Label selector, like `env in (prod,staging),!deprecated`
+    #[clap(short, long, default_value = "")]
+    pub labels: String,
     #[clap(short, long)]
     pub debug: bool,
 }
@@ -40,6 +50,9 @@ fn get_resources_request_from_get_cfg(cfg: &crate::get::Config) -> GetResourcesR
         names: cfg.names.to_string(),
         kinds: cfg.kinds.to_string(),
         bql: cfg.bql.to_string(),
+        references: cfg.references.to_string(),
+        referenced_by: cfg.referenced_by.to_string(),
+        labels: cfg.labels.to_string(),
     }
 }
 
@@ -49,3 +62,47 @@ fn show(rs: &Vec<Resource>) -> Result<(), Box<dyn Error>> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+
+    #[test]
+    fn test_get_references() {
+        let cfg = crate::Config::try_parse_from([
+            "bdacli",
+            "get",
+            "dag",
+            "--references",
+            "/^1.2/default/function/build",
+        ])
+        .unwrap();
+        let cfg = match cfg.command {
+            crate::Command::Get(cfg) => cfg,
+            _ => unreachable!(),
+        };
+        let request = get_resources_request_from_get_cfg(&cfg);
+        assert_eq!(request.kinds, "dag");
+        assert_eq!(request.references, "/^1.2/default/function/build");
+        assert_eq!(request.referenced_by, "");
+    }
+
+    #[test]
+    fn test_get_labels() {
+        let cfg = crate::Config::try_parse_from([
+            "bdacli",
+            "get",
+            "function",
+            "-l",
+            "env in (prod,staging),!deprecated",
+        ])
+        .unwrap();
+        let cfg = match cfg.command {
+            crate::Command::Get(cfg) => cfg,
+            _ => unreachable!(),
+        };
+        let request = get_resources_request_from_get_cfg(&cfg);
+        assert_eq!(request.labels, "env in (prod,staging),!deprecated");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::diff;
+use bdaproto::resource_revision::Operation;
+use bdaproto::{bda_client::BdaClient, GetResourceRequest, ResourceRevision};
+use clap::Args;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::error::Error;
+use tonic::{transport::Channel, Request};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(required = true, index = 1)]
+    pub kind: String,
+    #[clap(required = true, index = 2)]
+    pub name: String,
+    #[clap(short, long, default_value = bdacore::logic::DEFAULT_NAMESPACE)]
+    pub namespace: String,
+    #[clap(short, long, default_value = bdacore::logic::DEFAULT_VERSION)]
+    pub version: String,
+    #[clap(long)]
+    pub no_color: bool,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+/// Prints the changes made to a resource, oldest first, each one as a diff against the previous
+/// revision.
+pub async fn cmd(
+    client: &mut BdaClient<Channel>,
+    cfg: &crate::history::Config,
+) -> Result<(), Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    if cfg.no_color {
+        colored::control::set_override(false);
+    }
+    let request = Request::new(GetResourceRequest {
+        version: cfg.version.to_string(),
+        namespace: cfg.namespace.to_string(),
+        kind: cfg.kind.to_string(),
+        name: cfg.name.to_string(),
+    });
+    let revisions = client
+        .get_resource_history(request)
+        .await?
+        .into_inner()
+        .revisions;
+    let mut previous = Value::Object(Map::new());
+    for r in revisions.iter() {
+        println!("{}", header(r).bold());
+        let current = match r.resource {
+            Some(ref resource) => serde_json::to_value(resource)?,
+            None => Value::Object(Map::new()),
+        };
+        diff::show_changes(&diff::diff_values("", &previous, &current));
+        previous = current;
+    }
+    Ok(())
+}
+
+fn header(r: &ResourceRevision) -> String {
+    let operation = match Operation::from_i32(r.operation) {
+        Some(Operation::Create) => "created",
+        Some(Operation::Update) => "updated",
+        Some(Operation::Delete) => "deleted",
+        None => "changed",
+    };
+    let time = r
+        .time
+        .as_ref()
+        .and_then(|t| serde_json::to_value(t).ok())
+        .and_then(|t| t.as_str().map(|t| t.to_string()))
+        .unwrap_or_else(|| "-".to_string());
+    format!("revision {} {} {}", r.revision, time, operation)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+    use pbjson_types::Timestamp;
+
+    #[test]
+    fn test_history_args() {
+        let exp = Config {
+            kind: "config".to_string(),
+            name: "deploy".to_string(),
+            namespace: "prod".to_string(),
+            version: bdacore::logic::DEFAULT_VERSION.to_string(),
+            no_color: false,
+            debug: false,
+        };
+        let cfg =
+            crate::Config::try_parse_from(["bdacli", "history", "config", "deploy", "-n", "prod"])
+                .unwrap();
+        assert_eq!(cfg.command, crate::Command::History(exp))
+    }
+
+    #[test]
+    fn test_header() {
+        let mut r = ResourceRevision {
+            revision: 2,
+            time: Some(Timestamp {
+                seconds: 1700000000,
+                nanos: 0,
+            }),
+            operation: Operation::Update as i32,
+            resource: None,
+        };
+        assert_eq!(header(&r), "revision 2 2023-11-14T22:13:20+00:00 updated");
+        r.time = None;
+        r.operation = Operation::Delete as i32;
+        assert_eq!(header(&r), "revision 2 - deleted");
+    }
+}
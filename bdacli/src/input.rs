@@ -0,0 +1,352 @@
+use bdaproto::Resource;
+use serde_json::Value as JValue;
+use std::{
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
+use yaml_rust::{Yaml, YamlLoader};
+
+const STDIN: &str = "-";
+const STDIN_SOURCE: &str = "<stdin>";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Yaml,
+}
+
+/// Reads every resource from the given inputs. An input may be a file, a directory, a glob
+/// pattern or `-` for stdin. Directories are read one level deep unless `recursive` is set.
+pub fn collect_resources(
+    inputs: &[PathBuf],
+    recursive: bool,
+    debug: bool,
+) -> Result<Vec<Resource>, Box<dyn Error>> {
+    let mut resources = Vec::new();
+    for input in inputs {
+        if input.as_os_str() == STDIN {
+            if debug {
+                eprintln!("Reading stdin...")
+            }
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s)?;
+            resources.append(&mut parse_str(STDIN_SOURCE, &s, detect_format(&s), debug)?);
+            continue;
+        }
+        for f in expand(input, recursive)? {
+            match format_from_extension(&f) {
+                Some(format) => {
+                    if debug {
+                        eprintln!("Reading {:?} file: {}...", format, f.display())
+                    }
+                    let s = fs::read_to_string(&f)?;
+                    resources.append(&mut parse_str(&f.display().to_string(), &s, format, debug)?);
+                }
+                None => eprintln!(
+                    "Ignoring file: {}. Only extensions json|jsonl|ndjson|yaml|yml are accepted",
+                    f.display()
+                ),
+            }
+        }
+    }
+    Ok(resources)
+}
+
+pub fn parse_str(
+    source: &str,
+    s: &str,
+    format: Format,
+    debug: bool,
+) -> Result<Vec<Resource>, Box<dyn Error>> {
+    let resources = match format {
+        Format::Json => parse_json(source, s)?,
+        Format::Yaml => parse_yaml(source, s)?,
+    };
+    if debug {
+        for r in resources.iter() {
+            eprintln!("    Adding resource: {:?}...", r)
+        }
+    }
+    Ok(resources)
+}
+
+pub fn format_from_extension(f: &Path) -> Option<Format> {
+    match f.extension()?.to_str()? {
+        "json" | "jsonl" | "ndjson" => Some(Format::Json),
+        "yaml" | "yml" => Some(Format::Yaml),
+        _ => None,
+    }
+}
+
+fn detect_format(s: &str) -> Format {
+    match s.trim_start().chars().next() {
+        Some('{') | Some('[') => Format::Json,
+        _ => Format::Yaml,
+    }
+}
+
+fn is_glob(s: &str) -> bool {
+    s.contains(|c| c == '*' || c == '?' || c == '[')
+}
+
+fn expand(input: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let pattern = input
+        .to_str()
+        .ok_or_else(|| format!("invalid path: {}", input.display()))?;
+    if is_glob(pattern) {
+        let mut files = Vec::new();
+        for entry in glob::glob(pattern)? {
+            let path = entry?;
+            if path.is_dir() {
+                files.append(&mut walk(&path, recursive)?);
+            } else {
+                files.push(path);
+            }
+        }
+        if files.is_empty() {
+            Err(format!("no files match pattern: {}", pattern))?
+        }
+        Ok(files)
+    } else if input.is_dir() {
+        walk(input, recursive)
+    } else {
+        Ok(vec![input.to_path_buf()])
+    }
+}
+
+fn walk(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    let mut files = Vec::new();
+    for path in entries {
+        if path.is_dir() {
+            if recursive {
+                files.append(&mut walk(&path, recursive)?);
+            }
+        } else if format_from_extension(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Parses a JSON object, a JSON array of objects, or a sequence of them (JSON lines).
+fn parse_json(source: &str, s: &str) -> Result<Vec<Resource>, Box<dyn Error>> {
+    let mut resources = Vec::new();
+    let mut stream = serde_json::Deserializer::from_str(s).into_iter::<JValue>();
+    let mut document = 0;
+    loop {
+        let offset = stream.byte_offset();
+        let value = match stream.next() {
+            None => break,
+            Some(value) => value,
+        };
+        let start = offset + s[offset..].len() - s[offset..].trim_start().len();
+        document += 1;
+        let line = s[..start].matches('\n').count() + 1;
+        let value = value.map_err(|e| located(source, document, line, e))?;
+        // parse again with the lines before the document preserved so errors report absolute lines
+        let doc = format!(
+            "{}{}",
+            "\n".repeat(line - 1),
+            &s[start..stream.byte_offset()]
+        );
+        let mut parsed = match value {
+            JValue::Array(_) => serde_json::from_str::<Vec<Resource>>(&doc),
+            _ => serde_json::from_str::<Resource>(&doc).map(|r| vec![r]),
+        }
+        .map_err(|e| located(source, document, line, e))?;
+        resources.append(&mut parsed);
+    }
+    Ok(resources)
+}
+
+/// Parses a YAML stream with one or more documents. Each document may hold a resource or a
+/// sequence of resources, documents without content are skipped.
+fn parse_yaml(source: &str, s: &str) -> Result<Vec<Resource>, Box<dyn Error>> {
+    let documents = YamlLoader::load_from_str(s).map_err(|e| format!("{}: {}", source, e))?;
+    let lines = yaml_document_lines(s);
+    let mut resources = Vec::new();
+    for (n, doc) in documents.iter().enumerate() {
+        let (document, line) = (n + 1, lines.get(n).copied().unwrap_or(1));
+        let value = yaml_to_json(doc).map_err(|e| located(source, document, line, e))?;
+        let mut parsed = match value {
+            JValue::Null => continue,
+            JValue::Array(_) => serde_json::from_value::<Vec<Resource>>(value),
+            _ => serde_json::from_value::<Resource>(value).map(|r| vec![r]),
+        }
+        .map_err(|e| located(source, document, line, e))?;
+        resources.append(&mut parsed);
+    }
+    Ok(resources)
+}
+
+/// Returns the line each document of a YAML stream starts at, the line of its first node.
+fn yaml_document_lines(s: &str) -> Vec<usize> {
+    struct Starts(Vec<Option<usize>>);
+    impl MarkedEventReceiver for Starts {
+        fn on_event(&mut self, ev: Event, mark: Marker) {
+            match ev {
+                Event::DocumentStart => self.0.push(None),
+                Event::Scalar(..)
+                | Event::SequenceStart(_)
+                | Event::MappingStart(_)
+                | Event::Alias(_) => {
+                    if let Some(start @ None) = self.0.last_mut() {
+                        *start = Some(mark.line())
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut starts = Starts(Vec::new());
+    // syntax errors are reported by the loader, with their line
+    let _ = Parser::new(s.chars()).load(&mut starts, true);
+    starts.0.into_iter().map(|l| l.unwrap_or(1)).collect()
+}
+
+/// Converts a YAML document to JSON, so that resources are read like JSON ones.
+fn yaml_to_json(y: &Yaml) -> Result<JValue, String> {
+    Ok(match y {
+        Yaml::Null => JValue::Null,
+        Yaml::Boolean(b) => JValue::Bool(*b),
+        Yaml::Integer(i) => JValue::from(*i),
+        Yaml::Real(r) => y
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map(JValue::Number)
+            .ok_or_else(|| format!("unsupported number {:?}", r))?,
+        Yaml::String(s) => JValue::String(s.clone()),
+        Yaml::Array(a) => JValue::Array(a.iter().map(yaml_to_json).collect::<Result<_, _>>()?),
+        Yaml::Hash(h) => {
+            let mut object = serde_json::Map::new();
+            for (k, v) in h.iter() {
+                let key = match k {
+                    Yaml::String(s) | Yaml::Real(s) => s.clone(),
+                    Yaml::Integer(i) => i.to_string(),
+                    Yaml::Boolean(b) => b.to_string(),
+                    _ => Err(format!("unsupported key {:?}", k))?,
+                };
+                object.insert(key, yaml_to_json(v)?);
+            }
+            JValue::Object(object)
+        }
+        Yaml::Alias(_) | Yaml::BadValue => Err(format!("unsupported value {:?}", y))?,
+    })
+}
+
+fn located<E: fmt::Display>(source: &str, document: usize, line: usize, e: E) -> String {
+    format!(
+        "{}: document {} (starting at line {}): {}",
+        source, document, line, e
+    )
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_parse_json_single_array_and_lines() {
+        let single = r#"{"name":"a"}"#;
+        assert_eq!(parse_json("t", single).unwrap().len(), 1);
+        let array = r#"[{"name":"a"},{"name":"b"}]"#;
+        let rs = parse_json("t", array).unwrap();
+        assert_eq!(rs.len(), 2);
+        assert_eq!(rs[1].name, "b");
+        let lines = "{\"name\":\"a\"}\n{\"name\":\"b\"}\n{\"name\":\"c\"}\n";
+        let rs = parse_json("t", lines).unwrap();
+        assert_eq!(rs.len(), 3);
+        assert_eq!(rs[2].name, "c");
+    }
+
+    #[test]
+    fn test_parse_json_error_location() {
+        let lines = "{\"name\":\"a\"}\n{\"name\":\"b\"}\n{\"nome\":\"c\"}\n";
+        let e = parse_json("file.jsonl", lines).unwrap_err().to_string();
+        assert!(
+            e.starts_with("file.jsonl: document 3 (starting at line 3)"),
+            "{}",
+            e
+        );
+        assert!(e.contains("line 3"), "{}", e);
+    }
+
+    #[test]
+    fn test_parse_yaml_documents() {
+        let s = "---\nname: a\n---\n# a comment\n---\nname: b\n---\n- name: c\n- name: d\n";
+        let rs = parse_yaml("t", s).unwrap();
+        assert_eq!(
+            rs.iter().map(|r| r.name.as_str()).collect::<Vec<&str>>(),
+            vec!["a", "b", "c", "d"]
+        );
+        let s = "name: a\ndescription: |\n  before\n  ---\n  after\n--- {name: b}\n";
+        let rs = parse_yaml("t", s).unwrap();
+        assert_eq!(rs.len(), 2);
+        assert_eq!(rs[0].description, "before\n---\nafter\n");
+        assert_eq!(rs[1].name, "b");
+    }
+
+    #[test]
+    fn test_parse_yaml_error_location() {
+        let s = "name: a\n---\nname: b\n---\nname: c\nfunction:\n  baseComand: []\n";
+        let e = parse_yaml("file.yaml", s).unwrap_err().to_string();
+        assert!(
+            e.starts_with("file.yaml: document 3 (starting at line 5)"),
+            "{}",
+            e
+        );
+        assert!(e.contains("baseComand"), "{}", e);
+        let e = parse_yaml("file.yaml", "name: a\n---\nname: [b\n")
+            .unwrap_err()
+            .to_string();
+        assert!(e.starts_with("file.yaml: "), "{}", e);
+        assert!(e.contains("line 4"), "{}", e);
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("  {\"name\":\"a\"}"), Format::Json);
+        assert_eq!(detect_format("[]"), Format::Json);
+        assert_eq!(detect_format("name: a"), Format::Yaml);
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            format_from_extension(Path::new("a.jsonl")),
+            Some(Format::Json)
+        );
+        assert_eq!(
+            format_from_extension(Path::new("a.yml")),
+            Some(Format::Yaml)
+        );
+        assert_eq!(format_from_extension(Path::new("a.txt")), None);
+        assert_eq!(format_from_extension(Path::new("a")), None);
+    }
+
+    #[test]
+    fn test_expand_directory() {
+        let tmp = TempDir::new("bdacli-input").unwrap();
+        let dir = tmp.path().to_path_buf();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.yaml"), "name: a\n").unwrap();
+        fs::write(dir.join("b.txt"), "ignored").unwrap();
+        fs::write(dir.join("sub").join("c.json"), r#"{"name":"c"}"#).unwrap();
+        assert_eq!(expand(&dir, false).unwrap(), vec![dir.join("a.yaml")]);
+        assert_eq!(
+            expand(&dir, true).unwrap(),
+            vec![dir.join("a.yaml"), dir.join("sub").join("c.json")]
+        );
+        let pattern = dir.join("*.yaml");
+        assert_eq!(expand(&pattern, false).unwrap(), vec![dir.join("a.yaml")]);
+    }
+}
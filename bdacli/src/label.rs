@@ -0,0 +1,85 @@
+use bdacore::logic::{self, label};
+use bdaproto::{bda_client::BdaClient, GetResourceRequest, PutResourceRequest};
+use clap::Args;
+use std::error::Error;
+use tonic::{transport::Channel, Request};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(required = true, index = 1)]
+    pub kind: String,
+    #[clap(required = true, index = 2)]
+    pub name: String,
+    /// Label changes, `key=value` to set a label and `key-` to remove it
+    #[clap(required = true, index = 3)]
+    pub changes: Vec<String>,
+    #[clap(short, long, default_value = logic::DEFAULT_NAMESPACE)]
+    pub namespace: String,
+    /// Version, `latest` for the highest release, or a semver range like `^1.2`
+    #[clap(short, long, default_value = logic::DEFAULT_VERSION)]
+    pub version: String,
+    /// Changes the value of labels already set instead of failing
+    #[clap(long)]
+    pub overwrite: bool,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+/// Adds and removes labels of a resource in place, putting it back at the version it is stored at.
+pub async fn cmd(client: &mut BdaClient<Channel>, cfg: &Config) -> Result<(), Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    let request = Request::new(GetResourceRequest {
+        version: cfg.version.to_string(),
+        namespace: cfg.namespace.to_string(),
+        kind: cfg.kind.to_string(),
+        name: cfg.name.to_string(),
+    });
+    let mut resource = client.get_resource(request).await?.into_inner();
+    label::update(&mut resource.labels, &cfg.changes, cfg.overwrite)?;
+    let id = logic::resource_id(&resource)?;
+    client
+        .put_resource(Request::new(PutResourceRequest {
+            resource: Some(resource),
+        }))
+        .await?;
+    println!("{:?}", id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+
+    #[test]
+    fn test_label() {
+        let cfg = crate::Config::try_parse_from([
+            "bdacli",
+            "label",
+            "function",
+            "build",
+            "env=prod",
+            "deprecated-",
+            "-n",
+            "ci",
+            "--overwrite",
+        ])
+        .unwrap();
+        assert_eq!(
+            cfg.command,
+            crate::Command::Label(Config {
+                kind: "function".to_string(),
+                name: "build".to_string(),
+                changes: vec!["env=prod".to_string(), "deprecated-".to_string()],
+                namespace: "ci".to_string(),
+                version: logic::DEFAULT_VERSION.to_string(),
+                overwrite: true,
+                debug: false,
+            })
+        );
+        assert!(crate::Config::try_parse_from(["bdacli", "label", "function", "build"]).is_err());
+    }
+}
@@ -1,6 +1,16 @@
 pub mod apply;
+pub mod artifacts;
+pub mod bundle;
+pub mod deploy;
+pub mod diff;
 pub mod get;
+pub mod history;
+pub mod input;
+pub mod label;
+pub mod logs;
+pub mod matching;
 pub mod show;
+pub mod validate;
 
 use std::error::Error;
 
@@ -10,6 +20,7 @@ use tonic::transport::Channel;
 use url::Url;
 
 const DEFAULT_DATASTORE_ENDPOINT: &str = "http://127.0.0.1:7000";
+pub const DEFAULT_ENGINE_ENDPOINT: &str = "http://127.0.0.1:7001";
 #[derive(Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
 pub struct Config {
@@ -28,6 +39,15 @@ pub enum Command {
     Get(get::Config),
     Show(show::Config),
     Apply(apply::Config),
+    Diff(diff::Config),
+    Validate(validate::Config),
+    Match(matching::Config),
+    Logs(logs::Config),
+    Artifacts(artifacts::Config),
+    History(history::Config),
+    Bundle(bundle::Config),
+    Deploy(deploy::Config),
+    Label(label::Config),
 }
 
 pub async fn connect(cfg: &DatastoreConn) -> Result<BdaClient<Channel>, Box<dyn Error>> {
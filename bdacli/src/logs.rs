@@ -0,0 +1,158 @@
+use bdaproto::engine_client::EngineClient;
+use bdaproto::{RunLog, StreamRunLogsRequest};
+use clap::Args;
+use std::{error::Error, time::Duration};
+use tonic::{transport::Channel, Code, Request, Status};
+use url::Url;
+
+/// Delay before reattaching to a followed log after a disconnection.
+const REATTACH_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    /// Id of the run
+    pub run: String,
+    /// Follows the log until the run finishes, reattaching after disconnections
+    #[clap(short, long)]
+    pub follow: bool,
+    /// Offset of the first line, to resume after the last line read
+    #[clap(short, long, default_value = "0")]
+    pub offset: u64,
+    /// Attempt the offset belongs to, the last one by default
+    #[clap(short, long, default_value = "0")]
+    pub attempt: u32,
+    /// Prefixes the lines with their time, attempt and offset
+    #[clap(short, long)]
+    pub timestamps: bool,
+    /// Engine serving the run
+    #[clap(long, default_value = crate::DEFAULT_ENGINE_ENDPOINT)]
+    pub engine: Url,
+}
+
+/// Prints the log of a run, stdout lines to stdout and stderr lines to stderr.
+pub async fn cmd(cfg: &Config) -> Result<(), Box<dyn Error>> {
+    let mut client = EngineClient::connect(cfg.engine.to_string()).await?;
+    let (mut attempt, mut offset) = (cfg.attempt, cfg.offset);
+    loop {
+        let request = StreamRunLogsRequest {
+            id: cfg.run.clone(),
+            offset,
+            follow: cfg.follow,
+            attempt,
+        };
+        match print_logs(
+            &mut client,
+            request,
+            cfg.timestamps,
+            &mut attempt,
+            &mut offset,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(s) if cfg.follow && reattachable(&s) => {
+                eprintln!(
+                    "disconnected: {}, reattaching at offset {} of attempt {}",
+                    s.message(),
+                    offset,
+                    attempt
+                );
+                tokio::time::sleep(REATTACH_DELAY).await;
+            }
+            Err(s) => Err(s.message().to_string())?,
+        }
+    }
+}
+
+/// Prints the lines of a stream, keeping the position to reattach at.
+async fn print_logs(
+    client: &mut EngineClient<Channel>,
+    request: StreamRunLogsRequest,
+    timestamps: bool,
+    attempt: &mut u32,
+    offset: &mut u64,
+) -> Result<(), Status> {
+    let mut stream = client
+        .stream_run_logs(Request::new(request))
+        .await?
+        .into_inner();
+    while let Some(log) = stream.message().await? {
+        *attempt = log.attempt;
+        *offset = log.offset + 1;
+        match log.stream.as_str() {
+            "stderr" => eprintln!("{}", format_line(&log, timestamps)),
+            _ => println!("{}", format_line(&log, timestamps)),
+        }
+    }
+    Ok(())
+}
+
+fn reattachable(status: &Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unavailable
+            | Code::Unknown
+            | Code::Cancelled
+            | Code::DeadlineExceeded
+            | Code::Aborted
+    )
+}
+
+fn format_line(log: &RunLog, timestamps: bool) -> String {
+    if !timestamps {
+        return log.line.clone();
+    }
+    let time = match log.time {
+        Some(ref t) => format!("{}.{:03}", t.seconds, t.nanos / 1_000_000),
+        None => "-".to_string(),
+    };
+    format!("{} {}:{} {}", time, log.attempt, log.offset, log.line)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::Parser;
+    use pbjson_types::Timestamp;
+
+    #[test]
+    fn test_logs_follow() {
+        let exp = Config {
+            run: "r1".to_string(),
+            follow: true,
+            offset: 12,
+            attempt: 2,
+            timestamps: false,
+            engine: Url::parse(crate::DEFAULT_ENGINE_ENDPOINT).unwrap(),
+        };
+        let cfg =
+            crate::Config::try_parse_from(["bdacli", "logs", "r1", "-f", "-o", "12", "-a", "2"])
+                .unwrap();
+        assert_eq!(cfg.command, crate::Command::Logs(exp))
+    }
+
+    #[test]
+    fn test_format_line() {
+        let mut log = RunLog {
+            stream: "stdout".to_string(),
+            line: "done".to_string(),
+            offset: 7,
+            time: Some(Timestamp {
+                seconds: 1700000000,
+                nanos: 42_000_000,
+            }),
+            attempt: 2,
+        };
+        assert_eq!(format_line(&log, false), "done");
+        assert_eq!(format_line(&log, true), "1700000000.042 2:7 done");
+        log.time = None;
+        assert_eq!(format_line(&log, true), "- 2:7 done");
+    }
+
+    #[test]
+    fn test_reattachable() {
+        assert!(reattachable(&Status::unavailable("connection reset")));
+        assert!(!reattachable(&Status::not_found("run not found: r1")));
+    }
+}
@@ -1,15 +1,46 @@
-use bdacli::{self, apply, get, show, Command};
+use bdacli::{
+    self, apply, artifacts, bundle, deploy, diff, get, history, label, logs, matching, show,
+    validate, Command,
+};
 use clap::Parser;
 use std::error::Error;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let ref cfg = bdacli::Config::parse();
+    // validation runs offline, without connecting to the datastore
+    if let Command::Validate(ref cfg) = cfg.command {
+        if !validate::cmd(cfg)? {
+            std::process::exit(1)
+        }
+        return Ok(());
+    }
+    // logs, artifacts and deployments come from the engine
+    match cfg.command {
+        Command::Logs(ref cfg) => return logs::cmd(cfg).await,
+        Command::Artifacts(ref cfg) => return artifacts::cmd(cfg).await,
+        Command::Deploy(ref cfg) => return deploy::cmd(cfg).await,
+        _ => {}
+    }
     let mut client = bdacli::connect(&cfg.datastore_conn).await?;
     match cfg.command {
         Command::Get(ref cfg) => get::cmd(&mut client, cfg).await?,
         Command::Show(ref cfg) => show::cmd(&mut client, cfg).await?,
         Command::Apply(ref cfg) => apply::cmd(&mut client, cfg).await?,
+        Command::Diff(ref cfg) => {
+            if diff::cmd(&mut client, cfg).await? {
+                std::process::exit(1)
+            }
+        }
+        Command::Match(ref cfg) => {
+            if !matching::cmd(&mut client, cfg).await? {
+                std::process::exit(1)
+            }
+        }
+        Command::History(ref cfg) => history::cmd(&mut client, cfg).await?,
+        Command::Bundle(ref cfg) => bundle::cmd(&mut client, cfg).await?,
+        Command::Label(ref cfg) => label::cmd(&mut client, cfg).await?,
+        Command::Validate(_) | Command::Logs(_) | Command::Artifacts(_) | Command::Deploy(_) => {}
     }
     Ok(())
 }
@@ -0,0 +1,108 @@
+use bdacore::logic::{self, scheduler};
+use bdaproto::resource::ResourceKind;
+use bdaproto::{bda_client::BdaClient, GetResourceRequest, GetResourcesRequest};
+use clap::{Args, Subcommand};
+use std::error::Error;
+use tonic::{transport::Channel, Request};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub target: Target,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Target {
+    /// Lists the runtimes able to run a function, best candidates first
+    Function {
+        name: String,
+        #[clap(short, long, default_value = bdacore::logic::DEFAULT_NAMESPACE)]
+        namespace: String,
+        #[clap(short, long, default_value = bdacore::logic::DEFAULT_VERSION)]
+        version: String,
+    },
+}
+
+/// Prints the runtimes matching the target, or why none does. Returns true when one matches.
+pub async fn cmd(
+    client: &mut BdaClient<Channel>,
+    cfg: &crate::matching::Config,
+) -> Result<bool, Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    match cfg.target {
+        Target::Function {
+            ref name,
+            ref namespace,
+            ref version,
+        } => match_function(client, name, namespace, version).await,
+    }
+}
+
+async fn match_function(
+    client: &mut BdaClient<Channel>,
+    name: &str,
+    namespace: &str,
+    version: &str,
+) -> Result<bool, Box<dyn Error>> {
+    let resource = client
+        .get_resource(Request::new(GetResourceRequest {
+            version: version.to_string(),
+            namespace: namespace.to_string(),
+            kind: "function".to_string(),
+            name: name.to_string(),
+        }))
+        .await?
+        .into_inner();
+    let id = logic::resource_id(&resource)?;
+    let f = match resource.resource_kind {
+        Some(ResourceKind::Function(ref f)) => f,
+        _ => Err(format!("resource {:?} is not a function", id))?,
+    };
+    let runtimes = |bql: String| GetResourcesRequest {
+        version: resource.version.clone(),
+        namespaces: "all".to_string(),
+        names: String::new(),
+        kinds: "runtime".to_string(),
+        bql,
+        ..Default::default()
+    };
+    let matched = client
+        .get_resources(Request::new(runtimes(scheduler::capabilities_bql(f))))
+        .await?
+        .into_inner()
+        .resources;
+    let candidates = scheduler::rank(&resource, f, &matched)?;
+    for c in candidates.iter() {
+        if c.extra.is_empty() {
+            println!("{}", c.id);
+        } else {
+            println!("{}\textra capabilities: {}", c.id, c.extra.join(", "));
+        }
+    }
+    if !candidates.is_empty() {
+        return Ok(true);
+    }
+    println!(
+        "no runtime can run {:?}, which requires capabilities: {}",
+        id,
+        f.runtime_capabilities.join(", ")
+    );
+    let all = client
+        .get_resources(Request::new(runtimes(String::new())))
+        .await?
+        .into_inner()
+        .resources;
+    let mismatches = scheduler::explain(f, &all)?;
+    if mismatches.is_empty() {
+        println!("  no runtime is defined in version {}", resource.version);
+    }
+    for m in mismatches {
+        println!("  {} lacks {}", m.id, m.missing.join(", "));
+    }
+    Ok(false)
+}
@@ -1,4 +1,7 @@
-use bdaproto::{bda_client::BdaClient, GetKindsRequest, GetNamespacesRequest, GetVersionsRequest};
+use bdaproto::{
+    bda_client::BdaClient, GetKindsRequest, GetNamespacesRequest, GetVersionsRequest,
+    ResourceVersions,
+};
 use clap::{ArgEnum, Args};
 use std::error::Error;
 use tonic::{transport::Channel, Request};
@@ -42,16 +45,57 @@ pub async fn cmd(
         ),
         ShowType::Versions => show(
             &client
-                .get_versions(Request::new(GetVersionsRequest {}))
+                .get_versions(Request::new(GetVersionsRequest::default()))
                 .await?
                 .get_ref()
-                .versions,
+                .resources
+                .iter()
+                .map(format_versions)
+                .collect(),
         ),
     }
     Ok(())
 }
+/// Formats the versions of a resource on a line, with the version `latest` resolves to.
+fn format_versions(v: &ResourceVersions) -> String {
+    let mut line = format!(
+        "{}\t{}\t{}\t{}",
+        v.namespace,
+        v.kind,
+        v.name,
+        v.versions.join(" ")
+    );
+    if !v.latest.is_empty() {
+        line.push_str(&format!("\tlatest={}", v.latest));
+    }
+    line
+}
 fn show(items: &Vec<String>) {
     for item in items {
         println!("{}", item)
     }
 }
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_format_versions() {
+        let v = ResourceVersions {
+            namespace: "default".to_string(),
+            kind: "function".to_string(),
+            name: "build".to_string(),
+            versions: vec![
+                "1.2.0".to_string(),
+                "2.0.0-rc.1".to_string(),
+                "latest".to_string(),
+            ],
+            latest: "1.2.0".to_string(),
+        };
+        assert_eq!(
+            format_versions(&v),
+            "default\tfunction\tbuild\t1.2.0 2.0.0-rc.1 latest\tlatest=1.2.0"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use bdacore::{
+    data::EntityID,
+    logic::{self, validation},
+};
+use clap::Args;
+use std::{error::Error, path::PathBuf};
+
+#[derive(Args, Debug, PartialEq)]
+#[clap(author, version, about, long_about = None)]
+pub struct Config {
+    #[clap(short, long, min_values = 1, required = true)]
+    pub file: Vec<PathBuf>,
+    #[clap(short = 'R', long)]
+    pub recursive: bool,
+    #[clap(short, long)]
+    pub debug: bool,
+}
+
+/// Validates the resources declared in the files without contacting the datastore.
+/// Returns true when every resource is valid.
+pub fn cmd(cfg: &crate::validate::Config) -> Result<bool, Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("{:?}", cfg);
+    }
+    let mut valid = true;
+    for mut r in crate::input::collect_resources(&cfg.file, cfg.recursive, cfg.debug)? {
+        logic::defaults(&mut r);
+        let id = match logic::resource_id(&r) {
+            Ok(EntityID::ResourceID(id)) => id,
+            Err(_) => format!("/{}/{}/?/{}", r.version, r.namespace, r.name),
+        };
+        match validation::validate(&r) {
+            Ok(()) => {
+                if cfg.debug {
+                    eprintln!("{}: valid", id)
+                }
+            }
+            Err(e) => {
+                valid = false;
+                println!("{}", id);
+                for error in e.errors {
+                    println!("    {}", error);
+                }
+            }
+        }
+    }
+    Ok(valid)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use clap::StructOpt;
+
+    #[test]
+    fn test_validate_file() {
+        let exp = Config {
+            file: vec![PathBuf::from("file.yaml"), PathBuf::from("dir")],
+            recursive: true,
+            debug: false,
+        };
+        let cfg = crate::Config::try_parse_from([
+            "bdacli",
+            "validate",
+            "-R",
+            "-f",
+            "file.yaml",
+            "-f",
+            "dir",
+        ])
+        .unwrap();
+        assert_eq!(cfg.command, crate::Command::Validate(exp))
+    }
+
+    #[test]
+    fn test_validate_miss() {
+        if let Ok(_) = crate::Config::try_parse_from(["bdacli", "validate"]) {
+            assert!(false, "parse should have generated an error")
+        }
+    }
+}
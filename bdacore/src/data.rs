@@ -1,10 +1,19 @@
 pub mod datastore;
 pub mod query;
 
-use crate::{data::query::Query, logic};
-use bdaindex::bql::Value;
-use bdaproto::Resource;
-use std::{error::Error, fmt::Debug, sync::Arc};
+use crate::{
+    data::query::Query,
+    logic::{self, identifier, reference, secret::SecretKey, version},
+};
+use bdaindex::bql::{Value, BQL};
+use bdaproto::resource::ResourceKind;
+use bdaproto::{DeletePolicy, Resource, Secret};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Debug},
+    sync::Arc,
+};
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
@@ -22,6 +31,8 @@ pub trait Datastore {
         kind: &'a EntityKind,
         field: &'a str,
     ) -> Result<ValueIterator, Box<dyn Error>>;
+    /// Changes applied to an entity by `set`, oldest first, kept after it is deleted.
+    fn history<'a>(&self, id: &'a EntityID) -> Result<Vec<Revision>, Box<dyn Error>>;
 }
 
 pub fn new(datastore: Arc<dyn Datastore + Sync + Send>) -> Data {
@@ -61,19 +72,84 @@ impl Entity {
     }
 }
 
+/// Outcome of `Data::migrate_identifiers`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IdentifierMigration {
+    pub renamed: Vec<(EntityID, EntityID)>,
+    pub conflicts: Vec<(EntityID, EntityID)>,
+    pub invalid: Vec<(EntityID, String)>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Op {
     Create { new: Entity },
     Update { new: Entity, old: Entity },
     Delete { id: EntityID, old: Entity },
 }
+/// Resource installed by a bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Installation {
+    pub resource: String,
+    pub bundle: String,
+    pub semver: String,
+}
+/// Versions a resource is stored at, in `version::sort` order, with the one `latest` resolves to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versions {
+    pub namespace: String,
+    pub kind: String,
+    pub name: String,
+    pub versions: Vec<String>,
+    pub latest: Option<String>,
+}
+/// Error of a delete rejected because resources not deleted reference deleted ones, listed as
+/// (referencing, referenced) pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Referenced {
+    pub references: Vec<(EntityID, EntityID)>,
+}
+impl fmt::Display for Referenced {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let references: Vec<String> = self
+            .references
+            .iter()
+            .map(|(from, to)| match (from, to) {
+                (EntityID::ResourceID(from), EntityID::ResourceID(to)) => {
+                    format!("{} is referenced by {}", to, from)
+                }
+            })
+            .collect();
+        write!(f, "{}", references.join("; "))
+    }
+}
+impl Error for Referenced {}
+/// Change made to an entity, as kept in its history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    /// Position of the change in the history of the entity, from 1.
+    pub revision: u64,
+    /// Milliseconds since the unix epoch.
+    pub time: u64,
+    pub op: Op,
+}
+
 pub struct Data {
     datastore: Arc<dyn Datastore + Sync + Send + 'static>,
+    secret_key: Option<Arc<SecretKey>>,
 }
 
 impl Data {
     pub fn new(datastore: Arc<dyn Datastore + Sync + Send>) -> Data {
-        Data { datastore }
+        Data {
+            datastore,
+            secret_key: None,
+        }
+    }
+
+    /// Key encrypting the values of secrets, without which secrets cannot be put.
+    pub fn with_secret_key(mut self, secret_key: Arc<SecretKey>) -> Self {
+        self.secret_key = Some(secret_key);
+        self
     }
 
     fn get<'a>(&self, id: &'a EntityID) -> Result<Option<Entity>, Box<dyn Error>> {
@@ -107,21 +183,239 @@ impl Data {
         }
     }
 
+    /// Returns a resource, with the values of secrets redacted.
     pub fn get_resource<'a>(&self, id: &'a EntityID) -> Result<Option<Resource>, Box<dyn Error>> {
         self.get(id).map(|oe| {
             oe.map(|entity| match entity {
-                Entity::Resource(_, r) => r,
+                Entity::Resource(_, mut r) => {
+                    logic::secret::redact(&mut r);
+                    r
+                }
             })
         })
     }
 
+    /// Resolves a version selector, like `latest` or `^1.2`, among the versions a resource is
+    /// stored at. The namespace, kind and name must be valid.
+    pub fn resolve_version(
+        &self,
+        selector: &str,
+        namespace: &str,
+        kind: &str,
+        name: &str,
+    ) -> Result<Option<String>, Box<dyn Error>> {
+        if version::is_exact(selector) {
+            return Ok(Some(selector.to_string()));
+        }
+        let versions = self.stored_versions(namespace, kind, name)?;
+        Ok(version::resolve(
+            selector,
+            versions.iter().map(|v| v.as_str()),
+        ))
+    }
+
+    fn stored_versions(
+        &self,
+        namespace: &str,
+        kind: &str,
+        name: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let bql = query::bql_join(vec![
+            query::bql_from_namespaces(namespace),
+            query::bql_from_kinds(kind),
+            query::bql_from_names(name),
+        ])
+        .ok_or("could not build versions query")?;
+        let (namespace, name) = (
+            identifier::normalize(namespace),
+            identifier::normalize(name),
+        );
+        Ok(self
+            .versions(&Query::from_bql(&bql)?)?
+            .into_iter()
+            .find(|v| v.namespace == namespace && v.kind == kind && v.name == name)
+            .map(|v| v.versions)
+            .unwrap_or_default())
+    }
+
+    /// Returns a resource at the version a selector resolves to, see `resolve_version`, with the
+    /// values of secrets redacted.
+    pub fn get_resource_at(
+        &self,
+        selector: &str,
+        namespace: &str,
+        kind: &str,
+        name: &str,
+    ) -> Result<Option<Resource>, Box<dyn Error>> {
+        match self.resolve_version(selector, namespace, kind, name)? {
+            Some(v) => self.get_resource(&logic::resource_id_builder(&v, namespace, kind, name)?),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the resources referencing a resource, with the values of secrets redacted. A
+    /// reference whose version is a selector references the version it resolves to.
+    pub fn referencing(&self, r: &Resource) -> Result<Vec<Resource>, Box<dyn Error>> {
+        let kind = logic::resource_kind_to_string(r).ok_or("resource kind not specified")?;
+        let versions = self.stored_versions(&r.namespace, &kind, &r.name)?;
+        let bql = format!(
+            ".references.kind=='{}'&&.references.namespace=='{}'&&.references.name=='{}'",
+            kind, r.namespace, r.name
+        );
+        // the index matches the fields of different references, the candidates are checked
+        Ok(self
+            .resources(&Query::from_bql(&bql)?)?
+            .into_iter()
+            .filter(|c| {
+                reference::references(c)
+                    .iter()
+                    .any(|x| reference::designates(x, r, &versions))
+            })
+            .collect())
+    }
+
+    /// Returns the resources referenced by a resource and stored, with the values of secrets
+    /// redacted.
+    pub fn referenced(&self, r: &Resource) -> Result<Vec<Resource>, Box<dyn Error>> {
+        let mut referenced = Vec::new();
+        for x in reference::references(r) {
+            if let Some(found) = self.get_resource_at(&x.version, &x.namespace, &x.kind, &x.name)? {
+                referenced.push(found);
+            }
+        }
+        Ok(referenced)
+    }
+
+    /// Deletes resources, returning the changes made. Resources referencing a deleted one are
+    /// deleted with it when the policy cascades, and fail the delete when it rejects, unless they
+    /// are deleted too. Nothing is deleted when the delete fails.
+    pub fn delete(
+        &self,
+        ids: &[EntityID],
+        policy: DeletePolicy,
+    ) -> Result<Vec<Op>, Box<dyn Error>> {
+        let mut deleted: BTreeMap<EntityID, Resource> = BTreeMap::new();
+        let mut references: Vec<(EntityID, EntityID)> = Vec::new();
+        let mut pending: Vec<EntityID> = ids.to_vec();
+        while let Some(id) = pending.pop() {
+            if deleted.contains_key(&id) {
+                continue;
+            }
+            let r = match self.get(&id)? {
+                Some(Entity::Resource(_, r)) => r,
+                None => continue,
+            };
+            if policy != DeletePolicy::Orphan {
+                for c in self.referencing(&r)? {
+                    let from = logic::resource_id(&c)?;
+                    match policy {
+                        DeletePolicy::Cascade => pending.push(from),
+                        _ => references.push((from, id.clone())),
+                    }
+                }
+            }
+            deleted.insert(id, r);
+        }
+        references.retain(|(from, _)| !deleted.contains_key(from));
+        if !references.is_empty() {
+            return Err(Box::new(Referenced { references }));
+        }
+        let mut ops = Vec::new();
+        for id in deleted.keys() {
+            if let Some(op) = self.del(id)? {
+                ops.push(op);
+            }
+        }
+        Ok(ops)
+    }
+
+    /// Returns the changes made to a resource, oldest first, with the values of secrets redacted.
+    pub fn history<'a>(&self, id: &'a EntityID) -> Result<Vec<Revision>, Box<dyn Error>> {
+        let mut history = self.datastore.history(id)?;
+        for revision in history.iter_mut() {
+            match revision.op {
+                Op::Create { ref mut new } => redact(new),
+                Op::Update {
+                    ref mut new,
+                    ref mut old,
+                } => {
+                    redact(new);
+                    redact(old);
+                }
+                Op::Delete { ref mut old, .. } => redact(old),
+            }
+        }
+        Ok(history)
+    }
+
+    /// Returns a secret with its values encrypted, as stored.
+    pub fn get_secret<'a>(&self, id: &'a EntityID) -> Result<Option<Secret>, Box<dyn Error>> {
+        match self.get(id)? {
+            None => Ok(None),
+            Some(Entity::Resource(_, r)) => match r.resource_kind {
+                Some(ResourceKind::Secret(s)) => Ok(Some(s)),
+                _ => Err(format!("resource {:?} is not a secret", id))?,
+            },
+        }
+    }
+
+    /// Validates and stores a resource. The values of secrets are encrypted with the secret key.
     pub fn put_resource<'a>(&self, r: &Resource) -> Result<Option<Op>, Box<dyn Error>> {
         let mut validated = r.to_owned();
         logic::defaults(&mut validated);
-        self.put(&Entity::Resource(
-            logic::resource_id(&validated)?,
-            validated,
-        ))
+        logic::validation::validate(&validated)?;
+        let id = logic::resource_id(&validated)?;
+        if let Some(ResourceKind::Secret(_)) = validated.resource_kind {
+            let key = self
+                .secret_key
+                .as_ref()
+                .ok_or("the datastore has no secret key to encrypt secrets")?;
+            let stored = match self.get(&id)? {
+                Some(Entity::Resource(_, stored)) => Some(stored),
+                None => None,
+            };
+            key.seal(&mut validated, stored.as_ref())?;
+        }
+        self.put(&Entity::Resource(id, validated))
+    }
+
+    /// Moves resources stored before the identifier policy to their canonical ID. Resources whose
+    /// canonical ID is already taken, or that cannot be made canonical, are left untouched and
+    /// reported.
+    pub fn migrate_identifiers(&self) -> Result<IdentifierMigration, Box<dyn Error>> {
+        let mut migration = IdentifierMigration::default();
+        for id in self.ids(&Query::new(EntityKind::Resource, BQL::IsPresent))? {
+            let old = match self.get(&id)? {
+                Some(old) => old,
+                None => continue,
+            };
+            let Entity::Resource(_, ref r) = old;
+            let mut canonical = r.clone();
+            logic::defaults(&mut canonical);
+            let new_id = match logic::resource_id(&canonical) {
+                Ok(new_id) => new_id,
+                Err(e) => {
+                    migration.invalid.push((id, e));
+                    continue;
+                }
+            };
+            if new_id == id {
+                continue;
+            }
+            if self.get(&new_id)?.is_some() {
+                migration.conflicts.push((id, new_id));
+                continue;
+            }
+            self.datastore.set(Op::Delete {
+                id: id.clone(),
+                old,
+            })?;
+            self.datastore.set(Op::Create {
+                new: Entity::Resource(new_id.clone(), canonical),
+            })?;
+            migration.renamed.push((id, new_id));
+        }
+        Ok(migration)
     }
 
     pub fn search<'a>(&self, query: &'a Query) -> Result<EntityIDIterator, Box<dyn Error>> {
@@ -146,7 +440,8 @@ impl Data {
                     item.and_then(|ref id| {
                         self.datastore.get(id).and_then(|r| match r {
                             Some(e) => match e {
-                                Entity::Resource(_, r) => {
+                                Entity::Resource(_, mut r) => {
+                                    logic::secret::redact(&mut r);
                                     acc.push(r);
                                     Ok(acc)
                                 }
@@ -159,6 +454,77 @@ impl Data {
         }
     }
 
+    /// Same as `resources`, keeping for every resource name the version the selector resolves
+    /// to among the versions found, see `version::select`.
+    pub fn resources_at<'a>(
+        &self,
+        query: &'a Query,
+        selector: &str,
+    ) -> Result<Vec<Resource>, Box<dyn Error>> {
+        Ok(version::select(self.resources(query)?, selector))
+    }
+
+    /// Returns the versions of the resources matching a query, by namespace, kind and name.
+    pub fn versions<'a>(&self, query: &'a Query) -> Result<Vec<Versions>, Box<dyn Error>> {
+        let mut found: BTreeMap<(String, String, String), Vec<String>> = BTreeMap::new();
+        for id in self.ids(query)? {
+            let EntityID::ResourceID(ref id) = id;
+            let (version, namespace, kind, name) = logic::parse_resource_id(id)?;
+            found
+                .entry((namespace, kind, name))
+                .or_default()
+                .push(version);
+        }
+        Ok(found
+            .into_iter()
+            .map(|((namespace, kind, name), mut versions)| {
+                version::sort(&mut versions);
+                let latest = version::resolve("", versions.iter().map(|v| v.as_str()));
+                Versions {
+                    namespace,
+                    kind,
+                    name,
+                    versions,
+                    latest,
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the resources listed by the bundles of the namespaces, comma separated or all,
+    /// with the bundle and its version. Resources listed by several bundles are returned once per
+    /// bundle, resources no longer stored are left out.
+    pub fn installed(&self, namespaces: &str) -> Result<Vec<Installation>, Box<dyn Error>> {
+        let bql = query::bql_join(vec![
+            query::bql_from_namespaces(namespaces),
+            query::bql_from_kinds(logic::BUNDLE_KIND),
+        ])
+        .ok_or("could not build bundle query")?;
+        let bundles = self.resources(&Query {
+            kind: EntityKind::Resource,
+            ast: bdaindex::bql::from_str(&bql)?,
+        })?;
+        let mut installed = Vec::new();
+        for r in bundles {
+            if let Some(ResourceKind::Bundle(ref b)) = r.resource_kind {
+                let bundle = match logic::resource_id(&r)? {
+                    EntityID::ResourceID(id) => id,
+                };
+                for resource in b.resources.iter() {
+                    if self.get(&EntityID::ResourceID(resource.clone()))?.is_some() {
+                        installed.push(Installation {
+                            resource: resource.clone(),
+                            bundle: bundle.clone(),
+                            semver: b.semver.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        installed.sort_by(|a, b| (&a.resource, &a.bundle).cmp(&(&b.resource, &b.bundle)));
+        Ok(installed)
+    }
+
     pub fn values<'a>(
         &self,
         kind: &'a EntityKind,
@@ -224,12 +590,17 @@ impl Data {
     }
 }
 
+fn redact(e: &mut Entity) {
+    match e {
+        Entity::Resource(_, r) => logic::secret::redact(r),
+    }
+}
+
 #[cfg(test)]
 mod test_super {
     use crate::logic;
 
     use super::*;
-    use bdaindex::bql::BQL;
 
     #[test]
     fn test_data_search() {
@@ -343,4 +714,283 @@ mod test_super {
         let data = new(Arc::new(mock));
         assert_eq!(Some(op), data.del(&id).unwrap());
     }
+    #[test]
+    fn test_data_migrate_identifiers() {
+        let mixed = EntityID::ResourceID("/latest/default/function/MyName".to_owned());
+        let canonical = EntityID::ResourceID("/latest/default/function/myname".to_owned());
+        let mut r = logic::new_resource_function("name");
+        r.name = "MyName".to_owned();
+        let old = Entity::Resource(mixed.clone(), r.clone());
+        let new_entity =
+            Entity::Resource(canonical.clone(), logic::new_resource_function("myname"));
+        let mut mock = MockDatastore::new();
+        let ids = vec![mixed.clone()];
+        mock.expect_search()
+            .times(1)
+            .returning(move |_| Ok(Box::new(ids.clone().into_iter().map(|x| Ok(x)))));
+        let get_old = old.clone();
+        mock.expect_get()
+            .with(eq(mixed.clone()))
+            .times(1)
+            .returning(move |_| Ok(Some(get_old.clone())));
+        mock.expect_get()
+            .with(eq(canonical.clone()))
+            .times(1)
+            .returning(|_| Ok(None));
+        mock.expect_set()
+            .with(eq(Op::Delete {
+                id: mixed.clone(),
+                old: old.clone(),
+            }))
+            .times(1)
+            .returning(|op| Ok(op));
+        mock.expect_set()
+            .with(eq(Op::Create { new: new_entity }))
+            .times(1)
+            .returning(|op| Ok(op));
+        let data = new(Arc::new(mock));
+        assert_eq!(
+            data.migrate_identifiers().unwrap(),
+            IdentifierMigration {
+                renamed: vec![(mixed, canonical)],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_data_put_secret() {
+        let mut r = logic::new_resource_secret("registry");
+        if let Some(ResourceKind::Secret(ref mut s)) = r.resource_kind {
+            s.data.insert("token".to_string(), "s3cr3t".to_string());
+        }
+        let id = logic::resource_id(&r).unwrap();
+        let data = new(Arc::new(MockDatastore::new()));
+        assert!(data.put_resource(&r).is_err());
+
+        let key = Arc::new(SecretKey::new(&[1u8; 32]).unwrap());
+        let mut mock = MockDatastore::new();
+        mock.expect_get()
+            .with(eq(id.clone()))
+            .times(2)
+            .returning(|_| Ok(None));
+        let sealed = |r: &Resource| match r.resource_kind {
+            Some(ResourceKind::Secret(ref s)) => {
+                s.data["token"].starts_with(logic::secret::ENCRYPTED_PREFIX)
+            }
+            _ => false,
+        };
+        mock.expect_set()
+            .withf(move |op| match op {
+                Op::Create {
+                    new: Entity::Resource(_, r),
+                } => sealed(r),
+                _ => false,
+            })
+            .times(1)
+            .returning(|op| Ok(op));
+        let data = new(Arc::new(mock)).with_secret_key(key.clone());
+        assert!(data.put_resource(&r).unwrap().is_some());
+
+        let mut stored = r.clone();
+        key.seal(&mut stored, None).unwrap();
+        let mut mock = MockDatastore::new();
+        let get_return = Some(Entity::Resource(id.clone(), stored.clone()));
+        mock.expect_get()
+            .with(eq(id.clone()))
+            .times(2)
+            .returning(move |_| Ok(get_return.clone()));
+        let data = new(Arc::new(mock)).with_secret_key(key.clone());
+        let mut redacted = r.clone();
+        logic::secret::redact(&mut redacted);
+        assert_eq!(data.get_resource(&id).unwrap(), Some(redacted));
+        let secret = data.get_secret(&id).unwrap().unwrap();
+        assert_eq!(key.open(&secret).unwrap()["token"], "s3cr3t");
+    }
+
+    #[test]
+    fn test_data_history_redacts_secrets() {
+        let mut r = logic::new_resource_secret("registry");
+        if let Some(ResourceKind::Secret(ref mut s)) = r.resource_kind {
+            s.data
+                .insert("token".to_string(), "enc:v1:AAAA".to_string());
+        }
+        let id = logic::resource_id(&r).unwrap();
+        let entity = Entity::Resource(id.clone(), r.clone());
+        let history = vec![Revision {
+            revision: 1,
+            time: 0,
+            op: Op::Create { new: entity },
+        }];
+        let mut mock = MockDatastore::new();
+        mock.expect_history()
+            .with(eq(id.clone()))
+            .times(1)
+            .returning(move |_| Ok(history.clone()));
+        let data = new(Arc::new(mock));
+        logic::secret::redact(&mut r);
+        assert_eq!(
+            data.history(&id).unwrap(),
+            vec![Revision {
+                revision: 1,
+                time: 0,
+                op: Op::Create {
+                    new: Entity::Resource(id.clone(), r),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_data_installed() {
+        let mut b = logic::new_resource_bundle("ci");
+        if let Some(ResourceKind::Bundle(ref mut b)) = b.resource_kind {
+            b.semver = "1.2.0".to_string();
+            b.resources = vec![
+                "/latest/default/function/build".to_string(),
+                "/latest/default/function/gone".to_string(),
+            ];
+        }
+        let bundle = logic::resource_id(&b).unwrap();
+        let f = logic::new_resource_function("build");
+        let mut mock = MockDatastore::new();
+        let found = bundle.clone();
+        mock.expect_search()
+            .times(1)
+            .returning(move |_| Ok(Box::new(vec![Ok(found.clone())].into_iter())));
+        mock.expect_get().returning(move |id| {
+            Ok(match id {
+                EntityID::ResourceID(s) if s == "/latest/default/bundle/ci" => {
+                    Some(Entity::Resource(id.clone(), b.clone()))
+                }
+                EntityID::ResourceID(s) if s == "/latest/default/function/build" => {
+                    Some(Entity::Resource(id.clone(), f.clone()))
+                }
+                _ => None,
+            })
+        });
+        let data = new(Arc::new(mock));
+        assert_eq!(
+            data.installed("default").unwrap(),
+            vec![Installation {
+                resource: "/latest/default/function/build".to_string(),
+                bundle: "/latest/default/bundle/ci".to_string(),
+                semver: "1.2.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_data_versions() {
+        let at = |version: &str| {
+            let mut f = logic::new_resource_function("build");
+            f.version = version.to_string();
+            f
+        };
+        let ids: Vec<EntityID> = ["1.2.0", "latest", "1.10.0", "2.0.0-rc.1"]
+            .iter()
+            .map(|v| logic::resource_id(&at(v)).unwrap())
+            .collect();
+        let mut mock = MockDatastore::new();
+        mock.expect_search()
+            .returning(move |_| Ok(Box::new(ids.clone().into_iter().map(Ok))));
+        mock.expect_get().returning(move |id| {
+            let EntityID::ResourceID(s) = id;
+            let version = s.split('/').nth(1).unwrap();
+            Ok(Some(Entity::Resource(id.clone(), at(version))))
+        });
+        let data = new(Arc::new(mock));
+        let query = Query::new(EntityKind::Resource, BQL::IsPresent);
+        assert_eq!(
+            data.versions(&query).unwrap(),
+            vec![Versions {
+                namespace: "default".to_string(),
+                kind: "function".to_string(),
+                name: "build".to_string(),
+                versions: vec![
+                    "1.2.0".to_string(),
+                    "1.10.0".to_string(),
+                    "2.0.0-rc.1".to_string(),
+                    "latest".to_string()
+                ],
+                latest: Some("1.10.0".to_string()),
+            }]
+        );
+        let resolved = data
+            .get_resource_at("^1.2", "default", "function", "build")
+            .unwrap();
+        assert_eq!(resolved.map(|r| r.version), Some("1.10.0".to_string()));
+        let resolved = data
+            .get_resource_at("~1.2", "default", "function", "build")
+            .unwrap();
+        assert_eq!(resolved.map(|r| r.version), Some("1.2.0".to_string()));
+        let resolved = data
+            .get_resource_at("^3", "default", "function", "build")
+            .unwrap();
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn test_data_delete() {
+        let mut f = logic::new_resource_function("build");
+        f.version = "1.2.0".to_string();
+        let mut d = logic::new_resource_dag("pipeline");
+        if let Some(ResourceKind::Dag(ref mut d)) = d.resource_kind {
+            d.nodes = vec![bdaproto::DagNode {
+                name: "build".to_string(),
+                function: "/^1.2/default/function/build".to_string(),
+                inputs: None,
+            }];
+        }
+        let (function, dag) = (
+            logic::resource_id(&f).unwrap(),
+            logic::resource_id(&d).unwrap(),
+        );
+        let stored: BTreeMap<EntityID, Resource> = vec![(function.clone(), f), (dag.clone(), d)]
+            .into_iter()
+            .collect();
+        let mut mock = MockDatastore::new();
+        let ids: Vec<EntityID> = stored.keys().cloned().collect();
+        mock.expect_search()
+            .returning(move |_| Ok(Box::new(ids.clone().into_iter().map(Ok))));
+        mock.expect_get().returning(move |id| {
+            Ok(stored
+                .get(id)
+                .map(|r| Entity::Resource(id.clone(), r.clone())))
+        });
+        mock.expect_set().returning(|op| Ok(op));
+        let data = new(Arc::new(mock));
+        let deleted = |ids: &[EntityID], policy| -> Result<Vec<EntityID>, String> {
+            data.delete(ids, policy)
+                .map(|ops| {
+                    ops.into_iter()
+                        .map(|op| match op {
+                            Op::Delete { id, .. } => id,
+                            _ => unreachable!(),
+                        })
+                        .collect()
+                })
+                .map_err(|e| e.to_string())
+        };
+        assert_eq!(
+            deleted(&[function.clone()], DeletePolicy::Reject),
+            Err(
+                "/1.2.0/default/function/build is referenced by /latest/default/dag/pipeline"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            deleted(&[function.clone()], DeletePolicy::Orphan),
+            Ok(vec![function.clone()])
+        );
+        assert_eq!(
+            deleted(&[function.clone()], DeletePolicy::Cascade),
+            Ok(vec![function.clone(), dag.clone()])
+        );
+        assert_eq!(
+            deleted(&[function.clone(), dag.clone()], DeletePolicy::Reject),
+            Ok(vec![function, dag.clone()])
+        );
+        assert_eq!(deleted(&[dag.clone()], DeletePolicy::Reject), Ok(vec![dag]));
+    }
 }
@@ -1,12 +1,19 @@
-use bdaindex::{backend::Backend, Index};
+use bdaindex::{backend::Backend, policy::IndexPolicy, Index};
 
 pub mod kvstore;
 
 use crate::data::*;
+use crate::logic::{self, config, reference, secret};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct MemDatastore<T: Backend> {
     kvstore: kvstore::KvStore,
     index: Index<T>,
+    history: Mutex<BTreeMap<EntityID, Vec<Revision>>>,
+    policy: IndexPolicy,
+    policy_config: Option<EntityID>,
 }
 
 pub fn new<T: Backend>(index_backend: Arc<T>) -> MemDatastore<T> {
@@ -15,11 +22,101 @@ pub fn new<T: Backend>(index_backend: Arc<T>) -> MemDatastore<T> {
 
 impl<T: Backend> MemDatastore<T> {
     pub fn new(index_backend: Arc<T>) -> Self {
+        Self::with_index_policy(index_backend, IndexPolicy::default())
+    }
+    pub fn with_index_policy(index_backend: Arc<T>, policy: IndexPolicy) -> Self {
         MemDatastore {
             kvstore: kvstore::new(),
-            index: Index::new(index_backend),
+            index: Index::with_policy(index_backend, queried(policy.clone())),
+            history: Mutex::new(BTreeMap::new()),
+            policy,
+            policy_config: None,
+        }
+    }
+
+    /// Reads the index policy from a config resource each time it is put, reindexing the
+    /// resources. Deleting the config brings back the policy the datastore was created with.
+    pub fn with_policy_config(mut self, id: EntityID) -> Self {
+        self.policy_config = Some(id);
+        self
+    }
+
+    /// Index policy an op sets, when it puts or deletes the policy config.
+    fn policy_set_by(&self, op: &Op) -> Result<Option<IndexPolicy>, Box<dyn Error>> {
+        let config = match self.policy_config {
+            Some(ref id) => id,
+            None => return Ok(None),
+        };
+        match op {
+            Op::Create {
+                new: Entity::Resource(id, r),
+            }
+            | Op::Update {
+                new: Entity::Resource(id, r),
+                ..
+            } if id == config => index_policy(r).map(Some),
+            Op::Delete { id, .. } if id == config => Ok(Some(self.policy.clone())),
+            _ => Ok(None),
         }
     }
+
+    fn reindex(&self, policy: IndexPolicy) -> Result<(), Box<dyn Error>> {
+        let documents = self.kvstore.entities().into_iter().map(|e| match e {
+            Entity::Resource(EntityID::ResourceID(id), r) => (id, indexed(&r)),
+        });
+        self.index.set_policy(queried(policy), documents)
+    }
+
+    fn record(&self, op: &Op) -> Result<(), Box<dyn Error>> {
+        let id = match op {
+            Op::Create { new } | Op::Update { new, .. } => new.id(),
+            Op::Delete { id, .. } => id.clone(),
+        };
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let mut history = self.history.lock().map_err(|e| e.to_string())?;
+        let revisions = history.entry(id).or_default();
+        revisions.push(Revision {
+            revision: revisions.len() as u64 + 1,
+            time,
+            op: op.clone(),
+        });
+        Ok(())
+    }
+}
+
+/// Reads an index policy from the values of a config resource, set like in the policy files given
+/// to the datastore: `include`, `exclude`, `maxValueLength` and `kinds`.
+fn index_policy(r: &Resource) -> Result<IndexPolicy, Box<dyn Error>> {
+    match r.resource_kind {
+        Some(ResourceKind::Config(ref c)) => serde_json::from_value(config::values_json(c)?)
+            .map_err(|e| format!("invalid index policy in config {}: {}", r.name, e).into()),
+        _ => Err(format!("index policy resource {} is not a config", r.name).into()),
+    }
+}
+
+/// Keeps indexed, whatever the policy, the fields the datastore queries itself to resolve versions,
+/// find the resources referencing another one and select labels.
+fn queried(policy: IndexPolicy) -> IndexPolicy {
+    let kinds: Vec<String> = logic::resource_kinds_iter()
+        .map(|k| format!(".{}", k))
+        .collect();
+    policy.requiring(
+        &[".namespace", ".name", ".version", ".references", ".labels"],
+        &kinds,
+    )
+}
+
+/// Copy of a resource given to the index, with the values of secrets redacted so that they are
+/// never indexed, and with all its references so that the resources referencing another one are
+/// searched by `.references`.
+fn indexed(r: &Resource) -> Resource {
+    let mut r = r.clone();
+    secret::redact(&mut r);
+    r.references = reference::references(&r);
+    r
 }
 
 impl<T: Backend> Datastore for MemDatastore<T> {
@@ -27,13 +124,15 @@ impl<T: Backend> Datastore for MemDatastore<T> {
         Ok(self.kvstore.get(id))
     }
     fn set(&self, action: Op) -> Result<Op, Box<dyn Error>> {
+        // an invalid policy config is rejected before anything changes
+        let policy = self.policy_set_by(&action)?;
         match action {
             Op::Create { ref new } => {
                 match new {
                     Entity::Resource(id, r) => {
                         self.kvstore.put(new);
                         match id {
-                            EntityID::ResourceID(id) => self.index.insert(id, r.clone()),
+                            EntityID::ResourceID(id) => self.index.insert(id, indexed(r)),
                         }
                     }
                 }?;
@@ -41,14 +140,14 @@ impl<T: Backend> Datastore for MemDatastore<T> {
             Op::Update { ref new, ref old } => {
                 match old {
                     Entity::Resource(id, r) => match id {
-                        EntityID::ResourceID(id) => self.index.remove(id, r.clone()),
+                        EntityID::ResourceID(id) => self.index.remove(id, indexed(r)),
                     },
                 }?;
                 match new {
                     Entity::Resource(id, r) => {
                         self.kvstore.put(new);
                         match id {
-                            EntityID::ResourceID(id) => self.index.insert(id, r.clone()),
+                            EntityID::ResourceID(id) => self.index.insert(id, indexed(r)),
                         }
                     }
                 }?;
@@ -56,12 +155,16 @@ impl<T: Backend> Datastore for MemDatastore<T> {
             Op::Delete { ref id, ref old } => {
                 match old {
                     Entity::Resource(id, r) => match id {
-                        EntityID::ResourceID(id) => self.index.remove(id, r.clone()),
+                        EntityID::ResourceID(id) => self.index.remove(id, indexed(r)),
                     },
                 }?;
                 self.kvstore.del(id);
             }
         };
+        if let Some(policy) = policy {
+            self.reindex(policy)?;
+        }
+        self.record(&action)?;
         Ok(action)
     }
     fn search<'a>(&self, query: &'a Query) -> Result<EntityIDIterator, Box<dyn Error>> {
@@ -88,4 +191,193 @@ impl<T: Backend> Datastore for MemDatastore<T> {
     ) -> Result<ValueIterator, Box<dyn Error>> {
         self.index.values(field)
     }
+
+    fn history<'a>(&self, id: &'a EntityID) -> Result<Vec<Revision>, Box<dyn Error>> {
+        let history = self.history.lock().map_err(|e| e.to_string())?;
+        Ok(history.get(id).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+    use bdaindex::backend::llrb::LLRBBackend;
+
+    #[test]
+    fn test_history() {
+        let datastore = new(Arc::new(LLRBBackend::new()));
+        let r = logic::new_resource_config("deploy");
+        let id = logic::resource_id(&r).unwrap();
+        let created = Entity::Resource(id.clone(), r.clone());
+        let mut updated = r.clone();
+        updated.description = "deploy settings".to_string();
+        let updated = Entity::Resource(id.clone(), updated);
+        datastore
+            .set(Op::Create {
+                new: created.clone(),
+            })
+            .unwrap();
+        datastore
+            .set(Op::Update {
+                new: updated.clone(),
+                old: created.clone(),
+            })
+            .unwrap();
+        datastore
+            .set(Op::Delete {
+                id: id.clone(),
+                old: updated.clone(),
+            })
+            .unwrap();
+        assert_eq!(datastore.get(&id).unwrap(), None);
+        let history = datastore.history(&id).unwrap();
+        let revisions: Vec<u64> = history.iter().map(|r| r.revision).collect();
+        assert_eq!(revisions, vec![1, 2, 3]);
+        assert_eq!(
+            history[1].op,
+            Op::Update {
+                new: updated,
+                old: created
+            }
+        );
+        let other = EntityID::ResourceID("/latest/default/config/other".to_string());
+        assert_eq!(datastore.history(&other).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_search_references() {
+        let datastore = new(Arc::new(LLRBBackend::new()));
+        let mut d = logic::new_resource_dag("pipeline");
+        if let Some(ResourceKind::Dag(ref mut d)) = d.resource_kind {
+            d.nodes = vec![bdaproto::DagNode {
+                name: "build".to_string(),
+                function: "/^1.2/default/function/build".to_string(),
+                inputs: None,
+            }];
+        }
+        let id = logic::resource_id(&d).unwrap();
+        datastore
+            .set(Op::Create {
+                new: Entity::Resource(id.clone(), d.clone()),
+            })
+            .unwrap();
+        let search = |bql: &str| -> Vec<EntityID> {
+            datastore
+                .search(&Query::from_bql(bql).unwrap())
+                .unwrap()
+                .map(|id| id.unwrap())
+                .collect()
+        };
+        assert_eq!(
+            search(".references.kind=='function'&&.references.name=='build'"),
+            vec![id.clone()]
+        );
+        assert_eq!(search(".references.name=='test'"), vec![]);
+        assert_eq!(datastore.get(&id).unwrap(), Some(Entity::Resource(id, d)));
+    }
+
+    #[test]
+    fn test_policy_config() {
+        let mut policy = logic::new_resource_config("index-policy");
+        let policy_id = logic::resource_id(&policy).unwrap();
+        let datastore = new(Arc::new(LLRBBackend::new())).with_policy_config(policy_id.clone());
+        let mut f = logic::new_resource_function("build");
+        f.description = "builds".to_string();
+        let id = logic::resource_id(&f).unwrap();
+        let f = Entity::Resource(id.clone(), f);
+        datastore.set(Op::Create { new: f.clone() }).unwrap();
+        let search = |bql: &str| {
+            datastore
+                .search(&Query::from_bql(bql).unwrap())
+                .map(|ids| ids.map(|id| id.unwrap()).collect::<Vec<EntityID>>())
+        };
+        assert_eq!(search(".description").unwrap(), vec![id.clone()]);
+
+        let exclude = |fields: Vec<&str>| {
+            let values = fields
+                .into_iter()
+                .map(|f| pbjson_types::Value {
+                    kind: Some(pbjson_types::value::Kind::StringValue(f.to_string())),
+                })
+                .collect();
+            pbjson_types::Value {
+                kind: Some(pbjson_types::value::Kind::ListValue(
+                    pbjson_types::ListValue { values },
+                )),
+            }
+        };
+        if let Some(ResourceKind::Config(ref mut c)) = policy.resource_kind {
+            c.values
+                .insert("exclude".to_string(), exclude(vec![".description"]));
+        }
+        let policy = Entity::Resource(policy_id.clone(), policy);
+        datastore
+            .set(Op::Create {
+                new: policy.clone(),
+            })
+            .unwrap();
+        assert!(search(".description").is_err());
+        assert_eq!(search(".function").unwrap(), vec![id.clone()]);
+
+        // entries indexed under the previous policy go away with the resource
+        datastore
+            .set(Op::Delete {
+                id: id.clone(),
+                old: f,
+            })
+            .unwrap();
+        datastore
+            .set(Op::Delete {
+                id: policy_id.clone(),
+                old: policy,
+            })
+            .unwrap();
+        assert_eq!(search(".description").unwrap(), vec![]);
+        assert_eq!(search(".function").unwrap(), vec![]);
+
+        let mut invalid = logic::new_resource_config("index-policy");
+        if let Some(ResourceKind::Config(ref mut c)) = invalid.resource_kind {
+            c.values
+                .insert("maxValueLength".to_string(), exclude(vec![]));
+        }
+        let invalid = Entity::Resource(policy_id.clone(), invalid);
+        assert!(datastore.set(Op::Create { new: invalid }).is_err());
+        assert_eq!(datastore.get(&policy_id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_delete_under_policy() {
+        let policy = serde_json::from_value(serde_json::json!({"include": [".description"]}));
+        let datastore =
+            MemDatastore::with_index_policy(Arc::new(LLRBBackend::new()), policy.unwrap());
+        let mut f = logic::new_resource_function("build");
+        f.version = "1.2.0".to_string();
+        let mut d = logic::new_resource_dag("pipeline");
+        if let Some(ResourceKind::Dag(ref mut d)) = d.resource_kind {
+            d.nodes = vec![bdaproto::DagNode {
+                name: "build".to_string(),
+                function: "/^1.2/default/function/build".to_string(),
+                inputs: None,
+            }];
+        }
+        let function = logic::resource_id(&f).unwrap();
+        for r in [f, d] {
+            let id = logic::resource_id(&r).unwrap();
+            datastore
+                .set(Op::Create {
+                    new: Entity::Resource(id, r),
+                })
+                .unwrap();
+        }
+        let data = crate::data::new(Arc::new(datastore));
+        assert_eq!(
+            data.delete(&[function], DeletePolicy::Reject)
+                .map_err(|e| e.to_string()),
+            Err(
+                "/1.2.0/default/function/build is referenced by /latest/default/dag/pipeline"
+                    .to_string()
+            )
+        );
+    }
 }
@@ -22,4 +22,10 @@ impl KvStore {
     pub fn get(&self, id: &EntityID) -> Option<Entity> {
         Some(self.db.get(id).ok()?.0)
     }
+    pub fn entities(&self) -> Vec<Entity> {
+        match self.db.range(..) {
+            Ok(items) => items.map(|(_, entity)| entity).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
 }
@@ -1,7 +1,10 @@
-use crate::logic;
+use crate::logic::{identifier, label, version};
+use bdaindex::backend::llrb::LLRBBackend;
 use bdaindex::bql;
 use bdaindex::bql::BQL;
-use bdaproto::{DelResourcesRequest, GetResourcesRequest};
+use bdaproto::{DelResourcesRequest, GetResourcesRequest, GetVersionsRequest, Resource};
+use std::error::Error;
+use std::sync::Arc;
 
 use super::EntityKind;
 
@@ -19,12 +22,19 @@ impl Query {
     pub fn new(kind: EntityKind, ast: BQL) -> Query {
         Query { kind, ast }
     }
+    pub fn from_bql(bql: &str) -> Result<Query, String> {
+        Ok(Query {
+            kind: EntityKind::Resource,
+            ast: bql::from_str(bql)?,
+        })
+    }
     pub fn from_get_resources_request(request: &GetResourcesRequest) -> Result<Query, String> {
         bql_join(vec![
             bql_from_namespaces(&request.namespaces),
             bql_from_version(&request.version),
             bql_from_kinds(&request.kinds),
             bql_from_names(&request.names),
+            bql_from_labels(&request.labels)?,
             bql_from_str(&request.bql),
         ])
         .ok_or_else(|| format!("could not build query from request {:?}", request))
@@ -36,12 +46,29 @@ impl Query {
         })
     }
 
+    pub fn from_get_versions_request(request: &GetVersionsRequest) -> Result<Query, String> {
+        bql_join(vec![
+            bql_from_namespaces(&request.namespaces),
+            bql_from_version(""),
+            bql_from_kinds(&request.kinds),
+            bql_from_names(&request.names),
+        ])
+        .ok_or_else(|| format!("could not build query from request {:?}", request))
+        .and_then(|ref bql| {
+            Ok(Query {
+                kind: EntityKind::Resource,
+                ast: bql::from_str(bql)?,
+            })
+        })
+    }
+
     pub fn from_del_resources_request(request: &DelResourcesRequest) -> Result<Query, String> {
         bql_join(vec![
             bql_from_namespaces(&request.namespaces),
             bql_from_version(&request.version),
             bql_from_kinds(&request.kinds),
             bql_from_names(&request.names),
+            bql_from_labels(&request.labels)?,
             bql_from_str(&request.bql),
         ])
         .ok_or_else(|| format!("could not build query from request {:?}", request))
@@ -54,23 +81,41 @@ impl Query {
     }
 }
 
+/// Tells whether a resource matches a query, by searching an index holding that resource alone.
+pub fn matches(ast: &BQL, r: &Resource) -> Result<bool, Box<dyn Error>> {
+    let index = bdaindex::new(Arc::new(LLRBBackend::new()));
+    index.insert("resource", r.clone())?;
+    match index.search(Box::new(ast.clone()))?.next() {
+        Some(found) => found.map(|_| true),
+        None => Ok(false),
+    }
+}
+
 pub fn bql_from_namespaces(s: &str) -> Option<String> {
     let mut ns: Vec<String> = Vec::new();
     for n in s.split(",") {
         if n == "" || n == "all" {
             return None; //does not filter namespaces
         } else {
-            ns.push(format!("'{}'", n.to_lowercase().replace("'", "\\'")));
+            ns.push(format!(
+                "'{}'",
+                identifier::normalize(n).replace("'", "\\'")
+            ));
         }
     }
     Some(format!(".namespace@any[{}]", ns.join(",")))
 }
+/// Filters on the version when it names one. Blank, `latest` and ranges match every version,
+/// the version they resolve to being selected afterwards, see `version::select`.
 pub fn bql_from_version(s: &str) -> Option<String> {
-    let version = match s.split(",").next() {
-        Some(v) if v != "" => v.to_lowercase().replace("'", "\\'"),
-        _ => logic::DEFAULT_VERSION.to_string(),
-    };
-    Some(format!(".version=='{}'", version))
+    if version::is_exact(s) {
+        Some(format!(
+            ".version=='{}'",
+            identifier::normalize(s).replace("'", "\\'")
+        ))
+    } else {
+        Some(".version".to_string())
+    }
 }
 pub fn bql_from_kinds(s: &str) -> Option<String> {
     let mut ns: Vec<String> = Vec::new();
@@ -89,12 +134,20 @@ pub fn bql_from_names(s: &str) -> Option<String> {
         if n == "" {
             return None;
         } else {
-            ns.push(format!(".name=='{}'", n.to_lowercase().replace("'", "\'")));
+            ns.push(format!(
+                ".name=='{}'",
+                identifier::normalize(n).replace("'", "\'")
+            ));
         }
     }
     Some(format!("{}", ns.join("||")))
 }
 
+/// Compiles a label selector, like `env in (prod,staging),!deprecated`, see `label::parse`.
+pub fn bql_from_labels(s: &str) -> Result<Option<String>, String> {
+    Ok(label::bql(&label::parse(s)?))
+}
+
 pub fn bql_from_str(s: &str) -> Option<String> {
     if s == "" {
         None
@@ -122,6 +175,7 @@ pub fn bql_join(expressions: Vec<Option<String>>) -> Option<String> {
 #[cfg(test)]
 mod test_super {
     use super::*;
+    use crate::logic;
 
     #[test]
     fn test_bdaql_and() {
@@ -133,13 +187,11 @@ mod test_super {
         assert_eq!(namespaces, None);
 
         let version = bql_from_version("");
-        assert_eq!(
-            version,
-            Some(format!(
-                ".version=='{}'",
-                logic::DEFAULT_VERSION.to_string()
-            ))
-        );
+        assert_eq!(version, Some(".version".to_string()));
+        let version = bql_from_version("^1.2");
+        assert_eq!(version, Some(".version".to_string()));
+        let version = bql_from_version(" 1.2.0");
+        assert_eq!(version, Some(".version=='1.2.0'".to_string()));
 
         let kinds = bql_from_kinds("");
         assert_eq!(kinds, None);
@@ -154,6 +206,19 @@ mod test_super {
         assert_eq!(names, Some(".name=='aname\''".to_string()));
         let names = bql_from_names("namea,nameb");
         assert_eq!(names, Some(".name=='namea'||.name=='nameb'".to_string()));
+        let names = bql_from_names(" MyName");
+        assert_eq!(names, Some(".name=='myname'".to_string()));
+
+        let labels = bql_from_labels("");
+        assert_eq!(labels, Ok(None));
+        let labels = bql_from_labels("env=prod,tier in (web)");
+        assert_eq!(
+            labels,
+            Ok(Some(
+                "( .labels.env=='prod' )&&( .labels.tier@any['web'] )".to_string()
+            ))
+        );
+        assert!(bql_from_labels("env in (prod").is_err());
 
         let bdaql = bql_from_str("");
         assert_eq!(bdaql, None);
@@ -162,14 +227,8 @@ mod test_super {
 
         let namespaces = bql_from_namespaces("ns1,ns2");
         assert_eq!(namespaces, Some(".namespace@any['ns1','ns2']".to_string()));
-        let version = bql_from_version("");
-        assert_eq!(
-            version,
-            Some(format!(
-                ".version=='{}'",
-                logic::DEFAULT_VERSION.to_string()
-            ))
-        );
+        let version = bql_from_version("dev");
+        assert_eq!(version, Some(".version=='dev'".to_string()));
         let kinds = bql_from_kinds("function,runtime.container");
         assert_eq!(kinds, Some(".function||.runtime.container".to_string()));
         let bdaql = bql_from_str("");
@@ -180,7 +239,7 @@ mod test_super {
             Some(format!(
                 "( {} )&&( {} )&&( {} )",
                 ".namespace@any['ns1','ns2']".to_string(),
-                format!(".version=='{}'", logic::DEFAULT_VERSION.to_string()),
+                ".version=='dev'".to_string(),
                 ".function||.runtime.container".to_string()
             ))
         );
@@ -194,4 +253,32 @@ mod test_super {
         let and = bql_join(vec![None, None, None, None]);
         assert_eq!(and, None)
     }
+
+    #[test]
+    fn test_matches() {
+        let f = logic::new_resource_function("build");
+        let query = |s: &str| bql::from_str(s).unwrap();
+        assert!(matches(&query(".function"), &f).unwrap());
+        assert!(matches(&query(".name=='build'&&.namespace=='default'"), &f).unwrap());
+        assert!(!matches(&query(".dag"), &f).unwrap());
+        assert!(!matches(&query(".name=='other'"), &f).unwrap());
+    }
+
+    #[test]
+    fn test_matches_labels() {
+        let mut f = logic::new_resource_function("build");
+        f.labels.insert("env".to_string(), "prod".to_string());
+        f.labels.insert("app.tier".to_string(), "web".to_string());
+        let selected = |s: &str| {
+            let query = bql::from_str(&bql_from_labels(s).unwrap().unwrap()).unwrap();
+            matches(&query, &f).unwrap()
+        };
+        assert!(selected("env in (prod,staging)"));
+        assert!(selected("env=prod,app.tier"));
+        assert!(selected("!deprecated,zone!=eu,os notin (windows)"));
+        assert!(!selected("env notin (prod)"));
+        assert!(!selected("env!=prod"));
+        assert!(!selected("!env"));
+        assert!(!selected("deprecated"));
+    }
 }
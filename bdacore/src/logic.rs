@@ -1,15 +1,54 @@
+pub mod binding;
+pub mod bundle;
+pub mod config;
+pub mod cron;
+pub mod dag;
+pub mod identifier;
+pub mod label;
+pub mod listener;
+pub mod reference;
+pub mod scheduler;
+pub mod secret;
+pub mod validation;
+pub mod version;
+
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 
+use bdaproto::parameter::ParameterKind;
 use bdaproto::resource::ResourceKind;
 use bdaproto::runtime::RuntimeKind;
-use bdaproto::{Container, DelResourceRequest, Function, GetResourceRequest, Resource, Runtime};
+use bdaproto::{
+    Applier, Bundle, Config, Container, Dag, DelResourceRequest, Deployer, Function,
+    GetResourceRequest, GetSecretRequest, Listener, Parameter, Resource, Runtime, Secret,
+};
+use pbjson_types::Value;
 
 use crate::data::EntityID;
 
-const FUNCTION_KIND: &str = "function";
+pub const FUNCTION_KIND: &str = "function";
 const RUNTIME_CONTAINER_KIND: &str = "runtime.container";
-const KINDS: [&str; 2] = [FUNCTION_KIND, RUNTIME_CONTAINER_KIND];
+pub const DAG_KIND: &str = "dag";
+const SECRET_KIND: &str = "secret";
+pub const CONFIG_KIND: &str = "config";
+pub const BUNDLE_KIND: &str = "bundle";
+pub const LISTENER_KIND: &str = "listener";
+pub const DEPLOYER_KIND: &str = "deployer";
+pub const APPLIER_KIND: &str = "applier";
+const KINDS: [&str; 9] = [
+    FUNCTION_KIND,
+    RUNTIME_CONTAINER_KIND,
+    DAG_KIND,
+    SECRET_KIND,
+    CONFIG_KIND,
+    BUNDLE_KIND,
+    LISTENER_KIND,
+    DEPLOYER_KIND,
+    APPLIER_KIND,
+];
+/// Inputs given to the deploy function of a deployer, besides the inputs of the stage.
+pub const DEPLOY_INPUTS: [&str; 3] = ["bundle", "semver", "stage"];
 pub const DEFAULT_NAMESPACE: &str = "default";
 pub const DEFAULT_VERSION: &str = "latest";
 pub const DEFAULT_DOCKERFILE: &str = "Dockerfile";
@@ -46,7 +85,35 @@ pub fn new_resource_runtime_container(name: &str) -> Resource {
 }
 
 pub fn new_resource_function(name: &str) -> Resource {
-    new_resource(name, Some(ResourceKind::Function(new_function())))
+    new_resource(name, Some(ResourceKind::Function(new_function(&[]))))
+}
+
+pub fn new_resource_dag(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Dag(Dag::default())))
+}
+
+pub fn new_resource_secret(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Secret(Secret::default())))
+}
+
+pub fn new_resource_config(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Config(Config::default())))
+}
+
+pub fn new_resource_bundle(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Bundle(Bundle::default())))
+}
+
+pub fn new_resource_listener(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Listener(Listener::default())))
+}
+
+pub fn new_resource_deployer(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Deployer(Deployer::default())))
+}
+
+pub fn new_resource_applier(name: &str) -> Resource {
+    new_resource(name, Some(ResourceKind::Applier(Applier::default())))
 }
 
 fn new_container() -> Container {
@@ -62,12 +129,39 @@ fn new_runtime(runtime_kind: Option<RuntimeKind>) -> Runtime {
     }
 }
 
-fn new_function() -> Function {
+/// Function running `base_command`, with no parameters, capabilities, run policy nor secrets.
+pub fn new_function(base_command: &[&str]) -> Function {
     Function {
         inputs: Vec::new(),
         outputs: Vec::new(),
-        base_command: Vec::new(),
+        base_command: base_command.iter().map(|s| s.to_string()).collect(),
         runtime_capabilities: Vec::new(),
+        run_policy: None,
+        secrets: Vec::new(),
+    }
+}
+
+/// Parameter of a function, required as it has no default value.
+pub fn new_parameter(name: &str, kind: ParameterKind) -> Parameter {
+    Parameter {
+        name: name.to_owned(),
+        description: String::new(),
+        parameter_kind: kind as i32,
+        default_value: None,
+        minimum: None,
+        maximum: None,
+    }
+}
+
+/// Parameter of a function, optional as it defaults to `default_value`.
+pub fn new_parameter_with_default(
+    name: &str,
+    kind: ParameterKind,
+    default_value: Value,
+) -> Parameter {
+    Parameter {
+        default_value: Some(default_value),
+        ..new_parameter(name, kind)
     }
 }
 
@@ -79,6 +173,8 @@ fn new_resource(name: &str, kind: Option<ResourceKind>) -> Resource {
         description: String::new(),
         tags: Vec::new(),
         attributes: None,
+        references: Vec::new(),
+        labels: HashMap::new(),
         resource_kind: kind,
     };
     defaults(&mut r);
@@ -86,6 +182,14 @@ fn new_resource(name: &str, kind: Option<ResourceKind>) -> Resource {
 }
 
 pub fn defaults(r: &mut Resource) {
+    r.version = identifier::normalize(&r.version);
+    r.namespace = identifier::normalize(&r.namespace);
+    r.name = identifier::normalize(&r.name);
+    r.labels = r
+        .labels
+        .drain()
+        .map(|(k, v)| (identifier::normalize(&k), v.trim().to_string()))
+        .collect();
     default_string_if_empty(&mut r.namespace, DEFAULT_NAMESPACE);
     default_string_if_empty(&mut r.version, DEFAULT_VERSION);
     if let Some(ResourceKind::Runtime(r)) = &mut r.resource_kind {
@@ -111,6 +215,13 @@ pub fn resource_kind_to_string(r: &Resource) -> Option<String> {
         ResourceKind::Runtime(rt) => match rt.runtime_kind.as_ref()? {
             RuntimeKind::Container(_) => Some(RUNTIME_CONTAINER_KIND.to_string()),
         },
+        ResourceKind::Dag(_) => Some(DAG_KIND.to_string()),
+        ResourceKind::Secret(_) => Some(SECRET_KIND.to_string()),
+        ResourceKind::Config(_) => Some(CONFIG_KIND.to_string()),
+        ResourceKind::Bundle(_) => Some(BUNDLE_KIND.to_string()),
+        ResourceKind::Listener(_) => Some(LISTENER_KIND.to_string()),
+        ResourceKind::Deployer(_) => Some(DEPLOYER_KIND.to_string()),
+        ResourceKind::Applier(_) => Some(APPLIER_KIND.to_string()),
     }
 }
 
@@ -120,18 +231,62 @@ pub fn resource_id_builder(
     kind: &str,
     name: &str,
 ) -> Result<EntityID, String> {
+    if !KINDS.contains(&kind) {
+        return Err(format!(
+            "invalid kind {:?}, expected one of: {}",
+            kind,
+            KINDS.join(", ")
+        ));
+    }
     Ok(EntityID::ResourceID(format!(
         "/{}/{}/{}/{}",
-        version, namespace, kind, name
+        identifier::canonical("version", version)?,
+        identifier::canonical("namespace", namespace)?,
+        kind,
+        identifier::canonical("name", name)?
     )))
 }
 
 pub fn resource_id_from_get_request(r: &GetResourceRequest) -> Result<EntityID, String> {
     return resource_id_builder(&r.version, &r.namespace, &r.kind, &r.name);
 }
+/// Checks a get request, its version being a selector resolved by the datastore, like `^1.2`.
+pub fn check_get_request(r: &GetResourceRequest) -> Result<(), String> {
+    parse_resource_id(&format!(
+        "/{}/{}/{}/{}",
+        r.version, r.namespace, r.kind, r.name
+    ))
+    .map(|_| ())
+}
 pub fn resource_id_from_del_request(r: &DelResourceRequest) -> Result<EntityID, String> {
     return resource_id_builder(&r.version, &r.namespace, &r.kind, &r.name);
 }
+pub fn resource_id_from_get_secret_request(r: &GetSecretRequest) -> Result<EntityID, String> {
+    resource_id_builder(&r.version, &r.namespace, SECRET_KIND, &r.name)
+}
+
+/// Splits a resource id, like `/latest/default/function/name`, into its version, namespace,
+/// kind and name. The version may be a selector resolved by the datastore, like `^1.2`.
+pub fn parse_resource_id(id: &str) -> Result<(String, String, String, String), String> {
+    let parts: Vec<&str> = id.split('/').collect();
+    match parts[..] {
+        ["", version, namespace, kind, name] => {
+            version::check_selector(version)
+                .map_err(|e| format!("invalid version {:?}: {}", version, e))?;
+            resource_id_builder(DEFAULT_VERSION, namespace, kind, name)?;
+            Ok((
+                version.to_string(),
+                namespace.to_string(),
+                kind.to_string(),
+                name.to_string(),
+            ))
+        }
+        _ => Err(format!(
+            "invalid resource id {:?}, expected /version/namespace/kind/name",
+            id
+        )),
+    }
+}
 
 pub fn resource_id(r: &Resource) -> Result<EntityID, String> {
     return resource_id_builder(
@@ -165,5 +320,43 @@ mod tests {
                 assert_eq!(c.dockerfile, DEFAULT_DOCKERFILE);
             }
         }
+        let a = new_resource_function(" MyFunction");
+        assert_eq!(a.name, "myfunction");
+        let mut a = new_resource_function("function");
+        a.labels.insert(" Env".to_string(), "prod ".to_string());
+        defaults(&mut a);
+        assert_eq!(a.labels.get("env"), Some(&"prod".to_string()));
+    }
+    #[test]
+    fn test_resource_id_builder() {
+        assert_eq!(
+            resource_id_builder("latest", "Default", FUNCTION_KIND, "MyFunction"),
+            Ok(EntityID::ResourceID(
+                "/latest/default/function/myfunction".to_string()
+            ))
+        );
+        assert!(resource_id_builder("latest", "default", FUNCTION_KIND, "a/b").is_err());
+        assert!(resource_id_builder("latest", "default", FUNCTION_KIND, "").is_err());
+        assert!(resource_id_builder("latest", "default", "unknown", "name").is_err());
+    }
+    #[test]
+    fn test_parse_resource_id() {
+        assert_eq!(
+            parse_resource_id("/latest/default/function/build"),
+            Ok((
+                "latest".to_string(),
+                "default".to_string(),
+                "function".to_string(),
+                "build".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_resource_id("/^1.2/default/function/build").map(|p| p.0),
+            Ok("^1.2".to_string())
+        );
+        assert!(parse_resource_id("/^x/default/function/build").is_err());
+        assert!(parse_resource_id("latest/default/function/build").is_err());
+        assert!(parse_resource_id("/latest/default/unknown/build").is_err());
+        assert!(parse_resource_id("/latest/default/function/a/b").is_err());
     }
 }
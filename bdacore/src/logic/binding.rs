@@ -0,0 +1,475 @@
+use super::validation::ValidationError;
+use bdaproto::parameter::ParameterKind;
+use bdaproto::Parameter;
+use pbjson_types::{value::Kind, Value};
+use serde_json::{Number, Value as JValue};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+/// File, in the run working directory, holding every bound input as a JSON object.
+pub const INPUTS_FILE: &str = "inputs.json";
+/// Prefix of the environment variables holding each bound input.
+pub const INPUT_ENV_PREFIX: &str = "BDA_INPUT_";
+/// Environment variable holding the path of `INPUTS_FILE`.
+pub const INPUTS_FILE_ENV: &str = "BDA_INPUTS_FILE";
+/// File, in the run working directory, where a function may write its outputs as a JSON object.
+pub const OUTPUTS_FILE: &str = "outputs.json";
+/// Environment variable holding the path of `OUTPUTS_FILE`.
+pub const OUTPUTS_FILE_ENV: &str = "BDA_OUTPUTS_FILE";
+/// Directory, in the run working directory, where a function may write one file per output.
+pub const OUTPUTS_DIR: &str = "outputs";
+/// Environment variable holding the path of `OUTPUTS_DIR`.
+pub const OUTPUTS_DIR_ENV: &str = "BDA_OUTPUTS_DIR";
+/// Prefix of the `PATH` values referring to an artifact of the engine store by its sha256
+/// digest, materialized in the run working directory before the run starts.
+pub const ARTIFACT_PREFIX: &str = "artifact:";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingErrors {
+    pub errors: Vec<ValidationError>,
+}
+impl fmt::Display for BindingErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let errors: Vec<String> = self.errors.iter().map(|e| e.to_string()).collect();
+        write!(f, "invalid inputs: {}", errors.join("; "))
+    }
+}
+impl Error for BindingErrors {}
+
+/// Inputs of a function bound to their parameters, coerced to the parameter kinds.
+///
+/// Inputs reach the process in three ways:
+/// - `$(inputs.<name>)` references in `baseCommand` words are replaced by the input text;
+/// - the `BDA_INPUT_<NAME>` environment variable holds the input text;
+/// - the `inputs.json` file, in the working directory, holds every input as JSON.
+///
+/// The text of an input is the string itself for texts, URLs and paths, or its JSON otherwise.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Binding {
+    pub inputs: BTreeMap<String, JValue>,
+}
+
+impl Binding {
+    /// Replaces the `$(inputs.<name>)` references in the command words.
+    pub fn command(&self, base_command: &[String]) -> Result<Vec<String>, String> {
+        base_command
+            .iter()
+            .map(|word| self.substitute(word))
+            .collect()
+    }
+
+    fn substitute(&self, word: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut rest = word;
+        while let Some(start) = rest.find("$(inputs.") {
+            let end = rest[start..]
+                .find(')')
+                .map(|end| start + end)
+                .ok_or_else(|| format!("unterminated input reference in {:?}", word))?;
+            let name = &rest[start + "$(inputs.".len()..end];
+            let value = self
+                .inputs
+                .get(name)
+                .ok_or_else(|| format!("unknown input {:?} referenced in {:?}", name, word))?;
+            out.push_str(&rest[..start]);
+            out.push_str(&text(value));
+            rest = &rest[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Environment variables exposing the inputs.
+    pub fn env(&self) -> Vec<(String, String)> {
+        self.inputs
+            .iter()
+            .map(|(name, value)| (input_env_name(name), text(value)))
+            .collect()
+    }
+
+    /// Content of `INPUTS_FILE`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&self.inputs)
+    }
+}
+
+pub fn input_env_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", INPUT_ENV_PREFIX, name)
+}
+
+pub(crate) fn text(v: &JValue) -> String {
+    match v {
+        JValue::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// Binds the arguments to the parameters: applies `defaultValue` to missing arguments and
+/// coerces every value to its parameter kind. Relative paths are resolved against `base_dir`.
+/// Returns every error found, with `.inputs.<name>` field paths.
+pub fn bind(
+    params: &[Parameter],
+    args: &BTreeMap<String, JValue>,
+    base_dir: &Path,
+) -> Result<Binding, BindingErrors> {
+    bind_parameters("inputs", params, args, base_dir).map(|inputs| Binding { inputs })
+}
+
+/// Checks the values produced by a run against the output parameters, like `bind` does for
+/// inputs. Values not declared as outputs are ignored, and relative paths are resolved against
+/// the run working directory. Errors have `.outputs.<name>` field paths.
+pub fn bind_outputs(
+    params: &[Parameter],
+    values: &BTreeMap<String, JValue>,
+    work_dir: &Path,
+) -> Result<BTreeMap<String, JValue>, BindingErrors> {
+    let declared = values
+        .iter()
+        .filter(|(name, _)| params.iter().any(|p| &p.name == *name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    bind_parameters("outputs", params, &declared, work_dir)
+}
+
+fn bind_parameters(
+    field: &str,
+    params: &[Parameter],
+    args: &BTreeMap<String, JValue>,
+    base_dir: &Path,
+) -> Result<BTreeMap<String, JValue>, BindingErrors> {
+    let mut errors = Vec::new();
+    let mut error = |name: &str, message: String| {
+        errors.push(ValidationError {
+            field: format!(".{}.{}", field, name),
+            message,
+        })
+    };
+    for name in args.keys() {
+        if !params.iter().any(|p| &p.name == name) {
+            error(name, "unknown parameter".to_string());
+        }
+    }
+    let mut inputs = BTreeMap::new();
+    for p in params {
+        let value = match args.get(&p.name) {
+            Some(v) => v.clone(),
+            None => match p.default_value.as_ref().map(serde_json::to_value) {
+                Some(Ok(JValue::Null)) | None => {
+                    error(&p.name, "required value is missing".to_string());
+                    continue;
+                }
+                Some(Ok(v)) => v,
+                Some(Err(e)) => {
+                    error(&p.name, format!("invalid default value: {}", e));
+                    continue;
+                }
+            },
+        };
+        match coerce(p, value, base_dir) {
+            Ok(v) => {
+                inputs.insert(p.name.clone(), v);
+            }
+            Err(e) => error(&p.name, e),
+        }
+    }
+    if errors.is_empty() {
+        Ok(inputs)
+    } else {
+        Err(BindingErrors { errors })
+    }
+}
+
+/// Converts a value to the representation of the kind of a parameter. Numbers must also be within
+/// the bounds of the parameter.
+pub fn coerce(p: &Parameter, value: JValue, base_dir: &Path) -> Result<JValue, String> {
+    let kind = ParameterKind::from_i32(p.parameter_kind).unwrap_or(ParameterKind::Generic);
+    let value = convert(kind, value, base_dir)?;
+    if let (ParameterKind::Number, Some(n)) = (kind, value.as_f64()) {
+        let min = number_bound("minimum", &p.minimum)?;
+        let max = number_bound("maximum", &p.maximum)?;
+        match (min, max) {
+            (Some(min), _) if n < min => Err(format!("{} is lower than the minimum {}", n, min))?,
+            (_, Some(max)) if n > max => Err(format!("{} is greater than the maximum {}", n, max))?,
+            _ => {}
+        }
+    }
+    Ok(value)
+}
+
+/// Value of the `minimum` or `maximum` bound of a `NUMBER` parameter, included, none when unset.
+pub fn number_bound(name: &str, value: &Option<Value>) -> Result<Option<f64>, String> {
+    match value.as_ref().and_then(|v| v.kind.as_ref()) {
+        None | Some(Kind::NullValue(_)) => Ok(None),
+        Some(Kind::NumberValue(n)) => Ok(Some(*n)),
+        Some(_) => Err(format!("{} must be a number", name)),
+    }
+}
+
+fn convert(kind: ParameterKind, value: JValue, base_dir: &Path) -> Result<JValue, String> {
+    match (kind, value) {
+        (ParameterKind::Generic, v) => Ok(v),
+        (ParameterKind::Number, JValue::Number(n)) => Ok(JValue::Number(n)),
+        (ParameterKind::Number, JValue::String(s)) => s
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(JValue::Number)
+            .ok_or_else(|| format!("{:?} is not a finite number", s)),
+        (ParameterKind::Boolean, JValue::Bool(b)) => Ok(JValue::Bool(b)),
+        (ParameterKind::Boolean, JValue::String(s)) => match s.trim().to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(JValue::Bool(true)),
+            "false" | "no" | "0" => Ok(JValue::Bool(false)),
+            _ => Err(format!("{:?} is not a boolean", s)),
+        },
+        (ParameterKind::Text, JValue::String(s)) => Ok(JValue::String(s)),
+        (ParameterKind::Text, v @ JValue::Number(_))
+        | (ParameterKind::Text, v @ JValue::Bool(_)) => Ok(JValue::String(v.to_string())),
+        (ParameterKind::Json, JValue::String(s)) => {
+            serde_json::from_str(&s).map_err(|e| format!("invalid JSON document: {}", e))
+        }
+        (ParameterKind::Json, v) => Ok(v),
+        (ParameterKind::Url, JValue::String(s)) => Url::parse(s.trim())
+            .map(|u| JValue::String(u.to_string()))
+            .map_err(|e| format!("invalid URL {:?}: {}", s, e)),
+        (ParameterKind::Path, JValue::String(s)) if s.starts_with(ARTIFACT_PREFIX) => {
+            let value = JValue::String(s);
+            if artifact_digest(&value).is_some() {
+                Ok(value)
+            } else {
+                Err(format!("{} is not a sha256 artifact reference", value))
+            }
+        }
+        (ParameterKind::Path, JValue::String(s)) if !s.is_empty() => {
+            let path = normalize_path(&base_dir.join(&s));
+            if path.exists() {
+                Ok(JValue::String(path.display().to_string()))
+            } else {
+                Err(format!("path {} does not exist", path.display()))
+            }
+        }
+        (kind, v) => Err(format!("cannot use {} as {:?}", v, kind)),
+    }
+}
+
+/// Digest of an artifact reference, a string made of `ARTIFACT_PREFIX` and a sha256 digest.
+pub fn artifact_digest(value: &JValue) -> Option<&str> {
+    let digest = value.as_str()?.strip_prefix(ARTIFACT_PREFIX)?;
+    if digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(digest)
+    } else {
+        None
+    }
+}
+
+/// Resolves `.` and `..` components without touching the filesystem.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            c => normalized.push(c.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{new_parameter, new_parameter_with_default};
+    use serde_json::json;
+
+    fn fields(e: BindingErrors) -> Vec<String> {
+        e.errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn test_bind() {
+        let dir = std::env::temp_dir();
+        let params = vec![
+            new_parameter("n", ParameterKind::Number),
+            new_parameter_with_default(
+                "b",
+                ParameterKind::Boolean,
+                Value {
+                    kind: Some(Kind::BoolValue(true)),
+                },
+            ),
+            new_parameter("u", ParameterKind::Url),
+            new_parameter("p", ParameterKind::Path),
+            new_parameter("j", ParameterKind::Json),
+        ];
+        let args = BTreeMap::from([
+            ("n".to_string(), json!("1.5")),
+            ("u".to_string(), json!("https://github.com/gracig/bda")),
+            ("p".to_string(), json!("./x/..")),
+            ("j".to_string(), json!(r#"{"a":[1]}"#)),
+        ]);
+        let binding = bind(&params, &args, &dir).unwrap();
+        assert_eq!(
+            binding.inputs,
+            BTreeMap::from([
+                ("n".to_string(), json!(1.5)),
+                ("b".to_string(), json!(true)),
+                ("u".to_string(), json!("https://github.com/gracig/bda")),
+                ("p".to_string(), json!(dir.display().to_string())),
+                ("j".to_string(), json!({"a": [1]})),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_bind_artifact() {
+        let params = vec![new_parameter("p", ParameterKind::Path)];
+        let reference = format!("{}{}", ARTIFACT_PREFIX, "ab".repeat(32));
+        let args = BTreeMap::from([("p".to_string(), json!(reference))]);
+        let binding = bind(&params, &args, Path::new("/")).unwrap();
+        assert_eq!(binding.inputs["p"], json!(reference));
+        assert_eq!(artifact_digest(&binding.inputs["p"]), Some(&reference[9..]));
+        let args = BTreeMap::from([("p".to_string(), json!("artifact:abc"))]);
+        assert_eq!(
+            fields(bind(&params, &args, Path::new("/")).unwrap_err()),
+            vec![".inputs.p"]
+        );
+        assert_eq!(artifact_digest(&json!("/tmp/x")), None);
+    }
+
+    #[test]
+    fn test_bind_errors() {
+        let params = vec![
+            new_parameter("n", ParameterKind::Number),
+            new_parameter("t", ParameterKind::Text),
+            new_parameter("p", ParameterKind::Path),
+        ];
+        let args = BTreeMap::from([
+            ("n".to_string(), json!("many")),
+            ("p".to_string(), json!("/bda/no/such/path")),
+            ("x".to_string(), json!(1)),
+        ]);
+        let e = bind(&params, &args, Path::new("/")).unwrap_err();
+        assert_eq!(
+            fields(e),
+            vec![".inputs.x", ".inputs.n", ".inputs.t", ".inputs.p"]
+        );
+    }
+
+    #[test]
+    fn test_bind_bounds() {
+        let number = |n: f64| {
+            Some(Value {
+                kind: Some(Kind::NumberValue(n)),
+            })
+        };
+        let bind_n = |p: &Parameter, n: JValue| {
+            let args = BTreeMap::from([("n".to_string(), n)]);
+            bind(&[p.clone()], &args, Path::new("/"))
+                .map(|b| b.inputs["n"].clone())
+                .map_err(|e| e.errors[0].message.clone())
+        };
+        let p = Parameter {
+            minimum: number(1.0),
+            maximum: number(10.0),
+            ..new_parameter("n", ParameterKind::Number)
+        };
+        assert_eq!(bind_n(&p, json!(1)), Ok(json!(1)));
+        assert_eq!(bind_n(&p, json!("10")), Ok(json!(10.0)));
+        assert_eq!(
+            bind_n(&p, json!(0.5)),
+            Err("0.5 is lower than the minimum 1".to_string())
+        );
+        assert_eq!(
+            bind_n(&p, json!("10.01")),
+            Err("10.01 is greater than the maximum 10".to_string())
+        );
+        let p = Parameter {
+            minimum: number(0.0),
+            ..new_parameter("n", ParameterKind::Number)
+        };
+        assert_eq!(bind_n(&p, json!(1e9)), Ok(json!(1e9)));
+        assert!(bind_n(&p, json!(-1)).is_err());
+        let p = Parameter {
+            maximum: Some(Value {
+                kind: Some(Kind::StringValue("ten".to_string())),
+            }),
+            ..new_parameter("n", ParameterKind::Number)
+        };
+        assert_eq!(
+            bind_n(&p, json!(1)),
+            Err("maximum must be a number".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bind_outputs() {
+        let dir = std::env::temp_dir();
+        let params = vec![
+            new_parameter("count", ParameterKind::Number),
+            new_parameter_with_default(
+                "ok",
+                ParameterKind::Boolean,
+                Value {
+                    kind: Some(Kind::BoolValue(false)),
+                },
+            ),
+        ];
+        let values = BTreeMap::from([
+            ("count".to_string(), json!("3")),
+            ("other".to_string(), json!("ignored")),
+        ]);
+        assert_eq!(
+            bind_outputs(&params, &values, &dir),
+            Ok(BTreeMap::from([
+                ("count".to_string(), json!(3.0)),
+                ("ok".to_string(), json!(false)),
+            ]))
+        );
+        let e = bind_outputs(&params, &BTreeMap::new(), &dir).unwrap_err();
+        assert_eq!(fields(e), vec![".outputs.count"]);
+    }
+
+    #[test]
+    fn test_binding_process() {
+        let binding = Binding {
+            inputs: BTreeMap::from([
+                ("name".to_string(), json!("world")),
+                ("count".to_string(), json!(2)),
+            ]),
+        };
+        let command = vec![
+            "echo".to_string(),
+            "hello $(inputs.name)".to_string(),
+            "--count=$(inputs.count)".to_string(),
+        ];
+        assert_eq!(
+            binding.command(&command).unwrap(),
+            vec!["echo", "hello world", "--count=2"]
+        );
+        assert!(binding.command(&["$(inputs.other)".to_string()]).is_err());
+        assert!(binding.command(&["$(inputs.name".to_string()]).is_err());
+        assert_eq!(
+            binding.env(),
+            vec![
+                ("BDA_INPUT_COUNT".to_string(), "2".to_string()),
+                ("BDA_INPUT_NAME".to_string(), "world".to_string()),
+            ]
+        );
+        assert_eq!(input_env_name("my-input"), "BDA_INPUT_MY_INPUT");
+    }
+}
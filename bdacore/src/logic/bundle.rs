@@ -0,0 +1,383 @@
+use super::{identifier, parse_resource_id, resource_id, BUNDLE_KIND};
+use crate::data::EntityID;
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Bundle, Resource};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::io::{Read, Write};
+
+/// Path of the manifest in a bundle archive.
+pub const MANIFEST_FILE: &str = "manifest.yaml";
+/// Kinds of the resources a bundle can package.
+pub const PACKAGED_KINDS: [&str; 3] = ["function", "runtime.container", "dag"];
+
+/// Describes the content of a bundle archive: the bundle and the YAML file of every resource,
+/// the bundle included, with the sha256 digest of the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub bundle: String,
+    pub semver: String,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub resource: String,
+    pub path: String,
+    pub digest: String,
+}
+
+/// Writes the bundle and its resources as a tar archive of YAML files, the manifest first.
+/// The resources must be exactly the ones listed by the bundle.
+pub fn export<W: Write>(
+    bundle: &Resource,
+    resources: &[Resource],
+    out: W,
+) -> Result<Manifest, Box<dyn Error>> {
+    let (bundle_id, b) = as_bundle(bundle)?;
+    let mut files = vec![(
+        bundle_id.clone(),
+        path(bundle),
+        serde_yaml::to_string(bundle)?,
+    )];
+    for r in resources {
+        files.push((id(r)?, path(r), serde_yaml::to_string(r)?));
+    }
+    check_listed(&bundle_id, b, files[1..].iter().map(|(id, _, _)| id))?;
+    let manifest = Manifest {
+        bundle: bundle_id,
+        semver: b.semver.clone(),
+        entries: files
+            .iter()
+            .map(|(id, path, content)| Entry {
+                resource: id.clone(),
+                path: path.clone(),
+                digest: digest(content.as_bytes()),
+            })
+            .collect(),
+    };
+    let mut archive = tar::Builder::new(out);
+    append(
+        &mut archive,
+        MANIFEST_FILE,
+        &serde_yaml::to_string(&manifest)?,
+    )?;
+    for (_, path, content) in files.iter() {
+        append(&mut archive, path, content)?;
+    }
+    archive.into_inner()?.flush()?;
+    Ok(manifest)
+}
+
+/// Reads a bundle archive, checking the digests and the identifiers of the resources against
+/// the manifest, and returns the bundle and its resources. When a namespace is given, the
+/// resources are moved to it, along with the references between them.
+pub fn import<R: Read>(
+    input: R,
+    namespace: Option<&str>,
+) -> Result<(Resource, Vec<Resource>), Box<dyn Error>> {
+    let mut files = BTreeMap::new();
+    for entry in tar::Archive::new(input).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        files.insert(path, content);
+    }
+    let manifest: Manifest = serde_yaml::from_str(
+        &files
+            .remove(MANIFEST_FILE)
+            .ok_or_else(|| format!("bundle archive without {}", MANIFEST_FILE))?,
+    )?;
+    let mut bundle = None;
+    let mut resources = Vec::new();
+    for e in manifest.entries.iter() {
+        let content = files
+            .remove(&e.path)
+            .ok_or_else(|| format!("bundle archive without {}", e.path))?;
+        if digest(content.as_bytes()) != e.digest {
+            Err(format!("digest mismatch for {}", e.path))?
+        }
+        let mut r: Resource = serde_yaml::from_str(&content)
+            .map_err(|err| format!("invalid resource {}: {}", e.path, err))?;
+        super::defaults(&mut r);
+        if id(&r)? != e.resource {
+            Err(format!("{} does not hold {}", e.path, e.resource))?
+        }
+        if e.resource == manifest.bundle {
+            bundle = Some(r);
+        } else {
+            resources.push(r);
+        }
+    }
+    if let Some(path) = files.keys().next() {
+        Err(format!("{} is not listed in the manifest", path))?
+    }
+    let mut bundle = bundle.ok_or_else(|| format!("bundle {} not found", manifest.bundle))?;
+    let (_, b) = as_bundle(&bundle)?;
+    if b.semver != manifest.semver {
+        Err(format!(
+            "bundle version {} does not match the manifest version {}",
+            b.semver, manifest.semver
+        ))?
+    }
+    check_listed(
+        &manifest.bundle,
+        b,
+        manifest
+            .entries
+            .iter()
+            .map(|e| &e.resource)
+            .filter(|id| **id != manifest.bundle),
+    )?;
+    if let Some(namespace) = namespace {
+        relocate(&mut bundle, &mut resources, namespace)?;
+    }
+    Ok((bundle, resources))
+}
+
+/// Moves the bundle and its resources to a namespace, rewriting the bundle list and the
+/// functions of the dag nodes referring to resources of the bundle.
+fn relocate(
+    bundle: &mut Resource,
+    resources: &mut [Resource],
+    namespace: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut moved = BTreeMap::new();
+    for r in resources.iter_mut() {
+        let old = id(r)?;
+        r.namespace = namespace.to_string();
+        super::defaults(r);
+        moved.insert(old, id(r)?);
+    }
+    let rename = |id: &mut String| {
+        if let Some(new) = moved.get(id.as_str()) {
+            *id = new.clone();
+        }
+    };
+    for r in resources.iter_mut() {
+        if let Some(ResourceKind::Dag(ref mut d)) = r.resource_kind {
+            d.nodes.iter_mut().for_each(|n| rename(&mut n.function));
+        }
+    }
+    bundle.namespace = namespace.to_string();
+    super::defaults(bundle);
+    if let Some(ResourceKind::Bundle(ref mut b)) = bundle.resource_kind {
+        b.resources.iter_mut().for_each(rename);
+    }
+    Ok(())
+}
+
+fn as_bundle(r: &Resource) -> Result<(String, &Bundle), Box<dyn Error>> {
+    match r.resource_kind {
+        Some(ResourceKind::Bundle(ref b)) => Ok((id(r)?, b)),
+        _ => Err(format!("resource {} is not a bundle", id(r)?))?,
+    }
+}
+
+fn check_listed<'a>(
+    bundle: &str,
+    b: &Bundle,
+    ids: impl Iterator<Item = &'a String>,
+) -> Result<(), Box<dyn Error>> {
+    let listed: BTreeSet<&String> = b.resources.iter().collect();
+    let mut found = BTreeSet::new();
+    for id in ids {
+        if !listed.contains(id) {
+            Err(format!("{} is not listed by bundle {}", id, bundle))?
+        }
+        if !found.insert(id) {
+            Err(format!("{} is packaged more than once", id))?
+        }
+    }
+    if let Some(id) = listed.difference(&found).next() {
+        Err(format!("{} listed by bundle {} is missing", id, bundle))?
+    }
+    Ok(())
+}
+
+fn append<W: Write>(
+    archive: &mut tar::Builder<W>,
+    path: &str,
+    content: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, path, content.as_bytes())?;
+    Ok(())
+}
+
+fn id(r: &Resource) -> Result<String, Box<dyn Error>> {
+    match resource_id(r)? {
+        EntityID::ResourceID(id) => Ok(id),
+    }
+}
+
+/// Path of a resource in the archive, like `resources/latest/function/build.yaml`. The
+/// namespace is left out since the resources of a bundle share the namespace of the bundle.
+fn path(r: &Resource) -> String {
+    let kind = super::resource_kind_to_string(r).unwrap_or_default();
+    if kind == BUNDLE_KIND {
+        format!("{}.yaml", BUNDLE_KIND)
+    } else {
+        format!("resources/{}/{}/{}.yaml", r.version, kind, r.name)
+    }
+}
+
+fn digest(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Checks the resource ids listed by a bundle: packaged kinds, in the namespace of the bundle,
+/// at a version rather than a range, and listed once.
+pub fn check_resources(namespace: &str, b: &Bundle) -> Vec<(usize, String)> {
+    let mut errors = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (i, id) in b.resources.iter().enumerate() {
+        match parse_resource_id(id) {
+            Ok((_, _, kind, _)) if !PACKAGED_KINDS.contains(&kind.as_str()) => errors.push((
+                i,
+                format!(
+                    "{:?} is not the id of a resource of kind {}",
+                    id,
+                    PACKAGED_KINDS.join(", ")
+                ),
+            )),
+            Ok((_, ns, _, _)) if ns != namespace => errors.push((
+                i,
+                format!(
+                    "{:?} is not in the namespace {:?} of the bundle",
+                    id, namespace
+                ),
+            )),
+            Ok((version, _, _, _)) if identifier::check(&version).is_err() => {
+                errors.push((i, format!("{:?} must name a version, not a range", id)))
+            }
+            Ok(_) if !seen.insert(id) => errors.push((i, format!("duplicated resource {:?}", id))),
+            Ok(_) => {}
+            Err(e) => errors.push((i, e)),
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+    use bdaproto::DagNode;
+
+    fn fixtures() -> (Resource, Vec<Resource>) {
+        let mut f = logic::new_resource_function("build");
+        if let Some(ResourceKind::Function(ref mut f)) = f.resource_kind {
+            f.base_command = vec!["make".to_string()];
+        }
+        let mut d = logic::new_resource_dag("pipeline");
+        if let Some(ResourceKind::Dag(ref mut d)) = d.resource_kind {
+            d.nodes = vec![
+                DagNode {
+                    name: "build".to_string(),
+                    function: "/latest/default/function/build".to_string(),
+                    inputs: None,
+                },
+                DagNode {
+                    name: "lint".to_string(),
+                    function: "/latest/default/function/lint".to_string(),
+                    inputs: None,
+                },
+            ];
+        }
+        let mut b = logic::new_resource_bundle("ci");
+        if let Some(ResourceKind::Bundle(ref mut b)) = b.resource_kind {
+            b.semver = "1.2.0".to_string();
+            b.resources = vec![
+                "/latest/default/function/build".to_string(),
+                "/latest/default/dag/pipeline".to_string(),
+            ];
+        }
+        (b, vec![f, d])
+    }
+
+    #[test]
+    fn test_export_import() {
+        let (b, rs) = fixtures();
+        let mut archive = Vec::new();
+        let manifest = export(&b, &rs, &mut archive).unwrap();
+        assert_eq!(manifest.bundle, "/latest/default/bundle/ci");
+        assert_eq!(manifest.semver, "1.2.0");
+        let paths: Vec<&str> = manifest.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "bundle.yaml",
+                "resources/latest/function/build.yaml",
+                "resources/latest/dag/pipeline.yaml"
+            ]
+        );
+        assert_eq!(import(&archive[..], None).unwrap(), (b, rs));
+
+        assert!(export(&fixtures().0, &fixtures().1[..1], Vec::new()).is_err());
+        assert!(export(&fixtures().1[0], &[], Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_import_namespace() {
+        let (b, rs) = fixtures();
+        let mut archive = Vec::new();
+        export(&b, &rs, &mut archive).unwrap();
+        let (b, rs) = import(&archive[..], Some("staging")).unwrap();
+        assert_eq!(b.namespace, "staging");
+        if let Some(ResourceKind::Bundle(b)) = b.resource_kind {
+            assert_eq!(
+                b.resources,
+                vec![
+                    "/latest/staging/function/build".to_string(),
+                    "/latest/staging/dag/pipeline".to_string(),
+                ]
+            );
+        }
+        assert!(rs.iter().all(|r| r.namespace == "staging"));
+        if let Some(ResourceKind::Dag(ref d)) = rs[1].resource_kind {
+            // only the functions packaged in the bundle move
+            assert_eq!(d.nodes[0].function, "/latest/staging/function/build");
+            assert_eq!(d.nodes[1].function, "/latest/default/function/lint");
+        }
+    }
+
+    #[test]
+    fn test_import_tampered() {
+        let (b, rs) = fixtures();
+        let mut archive = Vec::new();
+        export(&b, &rs, &mut archive).unwrap();
+        let text = String::from_utf8_lossy(&archive).replace("make", "mak3");
+        let e = import(text.as_bytes(), None).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "digest mismatch for resources/latest/function/build.yaml"
+        );
+    }
+
+    #[test]
+    fn test_check_resources() {
+        let (b, _) = fixtures();
+        let mut b = match b.resource_kind {
+            Some(ResourceKind::Bundle(b)) => b,
+            _ => unreachable!(),
+        };
+        assert_eq!(check_resources("default", &b), vec![]);
+        b.resources.push("/latest/default/secret/token".to_string());
+        b.resources.push("/latest/other/function/build".to_string());
+        b.resources
+            .push("/latest/default/function/build".to_string());
+        b.resources.push("/^1.2/default/dag/pipeline".to_string());
+        let indexes: Vec<usize> = check_resources("default", &b)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(indexes, vec![2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,355 @@
+use super::binding::{self, BindingErrors};
+use super::identifier;
+use super::validation::ValidationError;
+use bdaproto::{Config, Parameter};
+use pbjson_types::value::Kind;
+use pbjson_types::Value;
+use serde_json::Value as JValue;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Start of a reference to a config value, like `${config:deploy/region}`.
+pub const REFERENCE_START: &str = "${config:";
+const REFERENCE_END: char = '}';
+
+/// Value of a config referenced by a parameter default value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ConfigRef {
+    pub config: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part<'a> {
+    Text(&'a str),
+    Ref(ConfigRef),
+}
+
+/// Configs referenced by parameters, by name, one map per namespace in lookup order: the
+/// namespace of the function first, then the default namespace. Each key is looked up in turn,
+/// so a namespace only overrides the keys it sets.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Layers(pub Vec<BTreeMap<String, Config>>);
+
+impl Layers {
+    pub fn lookup(&self, r: &ConfigRef) -> Option<&Value> {
+        self.0
+            .iter()
+            .find_map(|layer| layer.get(&r.config)?.values.get(&r.key))
+    }
+}
+
+/// Checks a config key: between 1 and `identifier::MAX_LENGTH` characters among ascii letters,
+/// digits, `-`, `_` and `.`.
+pub fn check_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        Err("must not be empty".to_string())
+    } else if key.len() > identifier::MAX_LENGTH {
+        Err(format!(
+            "must not be longer than {} characters, got {}",
+            identifier::MAX_LENGTH,
+            key.len()
+        ))
+    } else if let Some(c) = key
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_' || *c == '.'))
+    {
+        Err(format!(
+            "contains invalid character {:?}, allowed are a-z, A-Z, 0-9, '-', '_' and '.'",
+            c
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Config references in a text.
+pub fn references(text: &str) -> Result<Vec<ConfigRef>, String> {
+    Ok(parse(text)?
+        .into_iter()
+        .filter_map(|part| match part {
+            Part::Ref(r) => Some(r),
+            Part::Text(_) => None,
+        })
+        .collect())
+}
+
+fn parse(text: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(REFERENCE_START) {
+        let end = rest[start..]
+            .find(REFERENCE_END)
+            .map(|end| start + end)
+            .ok_or_else(|| format!("unterminated config reference in {:?}", text))?;
+        let reference = &rest[start + REFERENCE_START.len()..end];
+        let (config, key) = reference.split_once('/').ok_or_else(|| {
+            format!(
+                "invalid config reference {:?}, expected {}<config>/<key>}}",
+                reference, REFERENCE_START
+            )
+        })?;
+        identifier::check(config)
+            .map_err(|e| format!("invalid config name {:?}: {}", config, e))?;
+        check_key(key).map_err(|e| format!("invalid config key {:?}: {}", key, e))?;
+        if start > 0 {
+            parts.push(Part::Text(&rest[..start]));
+        }
+        parts.push(Part::Ref(ConfigRef {
+            config: config.to_string(),
+            key: key.to_string(),
+        }));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+fn text_default(p: &Parameter) -> Option<&str> {
+    match p.default_value.as_ref()?.kind.as_ref()? {
+        Kind::StringValue(s) if s.contains(REFERENCE_START) => Some(s),
+        _ => None,
+    }
+}
+
+/// Whether the default value of a parameter references configs, in which case its kind is only
+/// checked once the references are resolved.
+pub fn has_references(p: &Parameter) -> bool {
+    text_default(p).is_some()
+}
+
+/// Names of the configs referenced by the default values of parameters.
+pub fn referenced(params: &[Parameter]) -> BTreeSet<String> {
+    params
+        .iter()
+        .filter_map(text_default)
+        .filter_map(|text| references(text).ok())
+        .flatten()
+        .map(|r| r.config)
+        .collect()
+}
+
+/// Replaces the config references in the default values of parameters by the config values.
+/// A default value made of a single reference takes the config value as is, keeping its type,
+/// while references inside a longer text are replaced by the text of their value. Returns every
+/// error found, with `.inputs.<name>` field paths like `binding::bind`.
+pub fn resolve(params: &[Parameter], layers: &Layers) -> Result<Vec<Parameter>, BindingErrors> {
+    let mut errors = Vec::new();
+    let mut resolved = Vec::new();
+    for p in params {
+        let mut p = p.clone();
+        match text_default(&p).map(|text| resolve_text(text, layers)) {
+            None => {}
+            Some(Ok(value)) => p.default_value = Some(value),
+            Some(Err(message)) => errors.push(ValidationError {
+                field: format!(".inputs.{}", p.name),
+                message,
+            }),
+        }
+        resolved.push(p);
+    }
+    if errors.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(BindingErrors { errors })
+    }
+}
+
+fn resolve_text(text: &str, layers: &Layers) -> Result<Value, String> {
+    let lookup = |r: &ConfigRef| {
+        layers.lookup(r).ok_or_else(|| {
+            format!(
+                "config value {}{}/{}}} not found",
+                REFERENCE_START, r.config, r.key
+            )
+        })
+    };
+    let parts = parse(text)?;
+    if let [Part::Ref(r)] = &parts[..] {
+        return lookup(r).cloned();
+    }
+    let mut out = String::new();
+    for part in parts.iter() {
+        match part {
+            Part::Text(s) => out.push_str(s),
+            Part::Ref(r) => out.push_str(&value_text(lookup(r)?)?),
+        }
+    }
+    Ok(Value {
+        kind: Some(Kind::StringValue(out)),
+    })
+}
+
+/// JSON object of the values of a config, for configs read as settings. Protobuf numbers are all
+/// floats, integral ones are written as integers so that they read as integer settings.
+pub fn values_json(c: &Config) -> Result<JValue, String> {
+    let mut object = serde_json::Map::new();
+    for (k, v) in c.values.iter() {
+        object.insert(k.clone(), value_json(v)?);
+    }
+    Ok(JValue::Object(object))
+}
+
+fn value_json(value: &Value) -> Result<JValue, String> {
+    match value.kind {
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() < 1e15 => {
+            Ok(JValue::from(n as i64))
+        }
+        Some(Kind::StructValue(ref s)) => {
+            let mut object = serde_json::Map::new();
+            for (k, v) in s.fields.iter() {
+                object.insert(k.clone(), value_json(v)?);
+            }
+            Ok(JValue::Object(object))
+        }
+        Some(Kind::ListValue(ref l)) => l
+            .values
+            .iter()
+            .map(value_json)
+            .collect::<Result<Vec<_>, _>>()
+            .map(JValue::Array),
+        _ => serde_json::to_value(value).map_err(|e| e.to_string()),
+    }
+}
+
+/// Text of a value, like `binding` gives inputs to processes. Protobuf numbers are all floats,
+/// integral ones are written without a fractional part.
+fn value_text(value: &Value) -> Result<String, String> {
+    match value.kind {
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() < 1e15 => {
+            Ok((n as i64).to_string())
+        }
+        _ => serde_json::to_value(value)
+            .map(|v| binding::text(&v))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic::new_parameter_with_default;
+    use bdaproto::parameter::ParameterKind;
+
+    fn string(s: &str) -> Value {
+        Value {
+            kind: Some(Kind::StringValue(s.to_string())),
+        }
+    }
+
+    fn number(n: f64) -> Value {
+        Value {
+            kind: Some(Kind::NumberValue(n)),
+        }
+    }
+
+    fn config(values: &[(&str, Value)]) -> Config {
+        Config {
+            values: values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_references() {
+        assert_eq!(
+            references("https://${config:deploy/host}:${config:net/port}/api"),
+            Ok(vec![
+                ConfigRef {
+                    config: "deploy".to_string(),
+                    key: "host".to_string(),
+                },
+                ConfigRef {
+                    config: "net".to_string(),
+                    key: "port".to_string(),
+                },
+            ])
+        );
+        assert_eq!(references("no reference"), Ok(vec![]));
+        assert!(references("${config:deploy/host").is_err());
+        assert!(references("${config:deploy}").is_err());
+        assert!(references("${config:Deploy/host}").is_err());
+        assert!(references("${config:deploy/a b}").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let params = vec![
+            new_parameter_with_default(
+                "region",
+                ParameterKind::Generic,
+                string("${config:deploy/region}"),
+            ),
+            new_parameter_with_default(
+                "replicas",
+                ParameterKind::Generic,
+                string("${config:deploy/replicas}"),
+            ),
+            new_parameter_with_default(
+                "url",
+                ParameterKind::Generic,
+                string("https://${config:deploy/host}:${config:deploy/port}"),
+            ),
+            new_parameter_with_default("plain", ParameterKind::Generic, string("text")),
+        ];
+        assert_eq!(referenced(&params), BTreeSet::from(["deploy".to_string()]));
+        let namespace = BTreeMap::from([(
+            "deploy".to_string(),
+            config(&[("region", string("eu-west-1")), ("port", number(8443.0))]),
+        )]);
+        let default = BTreeMap::from([(
+            "deploy".to_string(),
+            config(&[
+                ("region", string("us-east-1")),
+                ("replicas", number(3.0)),
+                ("host", string("example.com")),
+            ]),
+        )]);
+        let layers = Layers(vec![namespace, default.clone()]);
+        let resolved = resolve(&params, &layers).unwrap();
+        let defaults: Vec<Value> = resolved
+            .into_iter()
+            .map(|p| p.default_value.unwrap())
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                string("eu-west-1"),
+                number(3.0),
+                string("https://example.com:8443"),
+                string("text"),
+            ]
+        );
+
+        let errors = resolve(&params, &Layers(vec![BTreeMap::new(), default])).unwrap_err();
+        let fields: Vec<String> = errors.errors.into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec![".inputs.url"]);
+    }
+
+    #[test]
+    fn test_values_json() {
+        let c = config(&[
+            ("maxValueLength", number(64.0)),
+            ("ratio", number(0.5)),
+            (
+                "exclude",
+                Value {
+                    kind: Some(Kind::ListValue(pbjson_types::ListValue {
+                        values: vec![string(".attributes")],
+                    })),
+                },
+            ),
+        ]);
+        assert_eq!(
+            values_json(&c),
+            Ok(serde_json::json!({
+                "maxValueLength": 64,
+                "ratio": 0.5,
+                "exclude": [".attributes"]
+            }))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+const MINUTE_MILLIS: u64 = 60_000;
+const DAY_MINUTES: u64 = 24 * 60;
+/// Horizon of the search for the next time of a schedule, past which it never fires, like on
+/// the 30th of February.
+const HORIZON_DAYS: u64 = 5 * 366;
+
+/// Cron schedule of five fields: minute, hour, day of the month, month and day of the week,
+/// in UTC. Each field is `*` or a comma separated list of values, `a-b` ranges and steps like
+/// `*/15` or `1-10/2`. Days of the week go from 0 for sunday to 6, 7 is sunday as well. When both
+/// days are restricted, a time matches when either of them does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+pub fn parse(s: &str) -> Result<Schedule, String> {
+    let fields: Vec<&str> = s.split_whitespace().collect();
+    let (minutes, hours, days, months, weekdays) = match fields[..] {
+        [minutes, hours, days, months, weekdays] => (minutes, hours, days, months, weekdays),
+        _ => {
+            return Err(format!(
+                "expected 5 fields: minute hour day-of-month month day-of-week, got {:?}",
+                s
+            ))
+        }
+    };
+    let mut weekdays_mask = field("day of the week", weekdays, 0, 7)?;
+    if weekdays_mask & 1 << 7 != 0 {
+        weekdays_mask |= 1;
+    }
+    Ok(Schedule {
+        minutes: field("minute", minutes, 0, 59)?,
+        hours: field("hour", hours, 0, 23)?,
+        days: field("day of the month", days, 1, 31)?,
+        months: field("month", months, 1, 12)?,
+        weekdays: weekdays_mask,
+        any_day: days == "*",
+        any_weekday: weekdays == "*",
+    })
+}
+
+fn field(name: &str, s: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0;
+    for item in s.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, number(name, step)?),
+            None => (item, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (number(name, start)?, number(name, end)?),
+            None if step > 1 => (number(name, range)?, max),
+            None => {
+                let n = number(name, range)?;
+                (n, n)
+            }
+        };
+        if start < min || end > max || start > end || step == 0 {
+            return Err(format!(
+                "invalid {} {:?}, expected values between {} and {}",
+                name, item, min, max
+            ));
+        }
+        for v in (start..=end).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+fn number(name: &str, s: &str) -> Result<u32, String> {
+    s.parse()
+        .map_err(|_| format!("invalid {} {:?}, expected a number", name, s))
+}
+
+impl Schedule {
+    /// First time matching the schedule strictly after a time, both in milliseconds since the
+    /// unix epoch. Schedules fire at the start of a minute.
+    pub fn next_after(&self, millis: u64) -> Option<u64> {
+        let mut minute = millis / MINUTE_MILLIS + 1;
+        let limit = minute + HORIZON_DAYS * DAY_MINUTES;
+        while minute < limit {
+            let days = minute / DAY_MINUTES;
+            if !self.day_matches(days) {
+                minute = (days + 1) * DAY_MINUTES;
+                continue;
+            }
+            if !bit(self.hours, minute % DAY_MINUTES / 60) {
+                minute = (minute / 60 + 1) * 60;
+                continue;
+            }
+            if !bit(self.minutes, minute % 60) {
+                minute += 1;
+                continue;
+            }
+            return Some(minute * MINUTE_MILLIS);
+        }
+        None
+    }
+
+    fn day_matches(&self, days: u64) -> bool {
+        let (month, day) = month_day(days);
+        // the unix epoch is a thursday
+        let weekday = (days + 4) % 7;
+        let day = bit(self.days, day);
+        let weekday = bit(self.weekdays, weekday);
+        bit(self.months, month)
+            && match (self.any_day, self.any_weekday) {
+                (false, false) => day || weekday,
+                _ => day && weekday,
+            }
+    }
+}
+
+fn bit(mask: u64, v: u64) -> bool {
+    mask & 1 << v != 0
+}
+
+/// Month and day of the month of a day counted from the unix epoch.
+fn month_day(days: u64) -> (u64, u64) {
+    // shifts the epoch to the 1st of march 0000, so that leap days end the years
+    let z = days + 719_468;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (month, day)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    // 2023-11-14T22:13:20Z, a tuesday
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn next(s: &str, millis: u64) -> Option<u64> {
+        parse(s).unwrap().next_after(millis)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(parse("* * * * *").is_ok());
+        assert!(parse("*/15 0-6,22 1,15 */2 1-5").is_ok());
+        assert!(parse("* * * *").is_err());
+        assert!(parse("60 * * * *").is_err());
+        assert!(parse("* * 0 * *").is_err());
+        assert!(parse("*/0 * * * *").is_err());
+        assert!(parse("5-1 * * * *").is_err());
+        assert!(parse("a * * * *").is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 22:14
+        assert_eq!(next("* * * * *", NOW), Some(1_700_000_040_000));
+        // 22:15
+        assert_eq!(next("*/5 * * * *", NOW), Some(1_700_000_100_000));
+        // the next day at 06:30
+        assert_eq!(next("30 6 * * *", NOW), Some(1_700_029_800_000));
+        // thursday 16th at 00:00
+        assert_eq!(next("0 0 * * 4", NOW), Some(1_700_092_800_000));
+        // sunday 19th, 7 is sunday too
+        assert_eq!(next("0 0 * * 7", NOW), Some(1_700_352_000_000));
+        // either the 1st of december or a wednesday, the 15th comes first
+        assert_eq!(next("0 0 1 * 3", NOW), Some(1_700_006_400_000));
+        // the 29th of february 2024
+        assert_eq!(next("0 0 29 2 *", NOW), Some(1_709_164_800_000));
+        assert_eq!(next("0 0 30 2 *", NOW), None);
+        // strictly after
+        assert_eq!(
+            next("* * * * *", 1_700_000_040_000),
+            Some(1_700_000_100_000)
+        );
+    }
+}
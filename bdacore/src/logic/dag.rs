@@ -0,0 +1,174 @@
+use bdaproto::{Dag, DagNode};
+use serde_json::Value as JValue;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Upstream nodes of every node of the dag.
+pub fn dependencies(dag: &Dag) -> BTreeMap<String, BTreeSet<String>> {
+    let mut deps: BTreeMap<String, BTreeSet<String>> = dag
+        .nodes
+        .iter()
+        .map(|n| (n.name.clone(), BTreeSet::new()))
+        .collect();
+    for e in dag.edges.iter() {
+        if let Some(d) = deps.get_mut(&e.to) {
+            d.insert(e.from.clone());
+        }
+    }
+    deps
+}
+
+/// Node names ordered so every node comes after its upstream nodes, with ties broken by
+/// declaration order. Fails naming the nodes left in a cycle.
+pub fn topological_order(dag: &Dag) -> Result<Vec<String>, String> {
+    let mut deps = dependencies(dag);
+    let mut order = Vec::new();
+    loop {
+        let ready: Vec<String> = dag
+            .nodes
+            .iter()
+            .map(|n| &n.name)
+            .filter(|n| deps.get(*n).map_or(false, |d| d.is_empty()))
+            .cloned()
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        for n in ready.iter() {
+            deps.remove(n);
+        }
+        for d in deps.values_mut() {
+            for n in ready.iter() {
+                d.remove(n);
+            }
+        }
+        order.extend(ready);
+    }
+    if deps.is_empty() {
+        Ok(order)
+    } else {
+        let cycle: Vec<String> = deps.into_keys().collect();
+        Err(format!("nodes {} form a cycle", cycle.join(", ")))
+    }
+}
+
+/// Nodes depending, directly or not, on a node.
+pub fn descendants(dag: &Dag, node: &str) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    let mut stack = vec![node.to_string()];
+    while let Some(n) = stack.pop() {
+        for e in dag.edges.iter().filter(|e| e.from == n) {
+            if found.insert(e.to.clone()) {
+                stack.push(e.to.clone());
+            }
+        }
+    }
+    found
+}
+
+/// Arguments of a node: its constant inputs, overridden by the outputs of its upstream nodes.
+pub fn node_args(
+    dag: &Dag,
+    node: &DagNode,
+    outputs: &BTreeMap<String, BTreeMap<String, JValue>>,
+) -> Result<BTreeMap<String, JValue>, String> {
+    let mut args = match node.inputs.as_ref() {
+        None => BTreeMap::new(),
+        Some(inputs) => match serde_json::to_value(inputs).map_err(|e| e.to_string())? {
+            JValue::Object(m) => m.into_iter().collect(),
+            v => Err(format!(
+                "inputs of node {} must be an object, got {}",
+                node.name, v
+            ))?,
+        },
+    };
+    for e in dag.edges.iter().filter(|e| e.to == node.name) {
+        let value = outputs
+            .get(&e.from)
+            .and_then(|o| o.get(&e.output))
+            .ok_or_else(|| format!("node {} produced no output {:?}", e.from, e.output))?;
+        args.insert(e.input.clone(), value.clone());
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use bdaproto::DagEdge;
+    use serde_json::json;
+
+    fn node(name: &str) -> DagNode {
+        DagNode {
+            name: name.to_string(),
+            function: format!("/latest/default/function/{}", name),
+            inputs: None,
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> DagEdge {
+        DagEdge {
+            from: from.to_string(),
+            output: "out".to_string(),
+            to: to.to_string(),
+            input: "in".to_string(),
+        }
+    }
+
+    fn dag(nodes: &[&str], edges: &[(&str, &str)]) -> Dag {
+        Dag {
+            nodes: nodes.iter().map(|n| node(n)).collect(),
+            edges: edges.iter().map(|(f, t)| edge(f, t)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let d = dag(
+            &["test", "build", "fetch", "lint"],
+            &[("fetch", "build"), ("build", "test"), ("fetch", "lint")],
+        );
+        assert_eq!(
+            topological_order(&d),
+            Ok(vec![
+                "fetch".to_string(),
+                "build".to_string(),
+                "lint".to_string(),
+                "test".to_string()
+            ])
+        );
+        let d = dag(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("c", "b")]);
+        assert_eq!(
+            topological_order(&d),
+            Err("nodes b, c form a cycle".to_string())
+        );
+    }
+
+    #[test]
+    fn test_descendants() {
+        let d = dag(&["a", "b", "c", "d"], &[("a", "b"), ("b", "c"), ("d", "c")]);
+        assert_eq!(
+            descendants(&d, "a"),
+            BTreeSet::from(["b".to_string(), "c".to_string()])
+        );
+        assert!(descendants(&d, "c").is_empty());
+    }
+
+    #[test]
+    fn test_node_args() {
+        let mut d = dag(&["a", "b"], &[("a", "b")]);
+        d.nodes[1].inputs = Some(serde_json::from_value(json!({"in": 0, "k": "v"})).unwrap());
+        let outputs = BTreeMap::from([(
+            "a".to_string(),
+            BTreeMap::from([("out".to_string(), json!(1.0))]),
+        )]);
+        assert_eq!(
+            node_args(&d, &d.nodes[1], &outputs),
+            Ok(BTreeMap::from([
+                ("in".to_string(), json!(1.0)),
+                ("k".to_string(), json!("v")),
+            ]))
+        );
+        assert!(node_args(&d, &d.nodes[1], &BTreeMap::new()).is_err());
+    }
+}
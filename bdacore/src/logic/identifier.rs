@@ -0,0 +1,66 @@
+/// Maximum length of a version, namespace or name, in bytes.
+pub const MAX_LENGTH: usize = 63;
+
+/// Returns the canonical form of an identifier: trimmed and lowercased.
+pub fn normalize(s: &str) -> String {
+    s.trim().to_lowercase()
+}
+
+/// Checks that an identifier is canonical: between 1 and `MAX_LENGTH` characters among
+/// lowercase ascii letters, digits, `-`, `_` and `.`, starting with a letter or a digit.
+pub fn check(s: &str) -> Result<(), String> {
+    let first = s.chars().next().ok_or("must not be empty")?;
+    if s.len() > MAX_LENGTH {
+        Err(format!(
+            "must not be longer than {} characters, got {}",
+            MAX_LENGTH,
+            s.len()
+        ))
+    } else if !(first.is_ascii_lowercase() || first.is_ascii_digit()) {
+        Err(format!(
+            "must start with a letter or a digit, got {:?}",
+            first
+        ))
+    } else if let Some(c) = s.chars().find(|c| !is_allowed(*c)) {
+        Err(format!(
+            "contains invalid character {:?}, allowed are a-z, 0-9, '-', '_' and '.'",
+            c
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Normalizes an identifier and checks the result, naming the field on errors.
+pub fn canonical(field: &str, s: &str) -> Result<String, String> {
+    let s = normalize(s);
+    check(&s).map_err(|e| format!("invalid {} {:?}: {}", field, s, e))?;
+    Ok(s)
+}
+
+fn is_allowed(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_' || c == '.'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(
+            canonical("name", " MyFunction "),
+            Ok("myfunction".to_string())
+        );
+        assert_eq!(
+            canonical("version", "1.0.0-rc_1"),
+            Ok("1.0.0-rc_1".to_string())
+        );
+        assert!(canonical("name", "").is_err());
+        assert!(canonical("name", "a/b").is_err());
+        assert!(canonical("name", "a b").is_err());
+        assert!(canonical("name", "-a").is_err());
+        assert!(canonical("name", &"a".repeat(MAX_LENGTH)).is_ok());
+        assert!(canonical("name", &"a".repeat(MAX_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+/// Maximum length of a label key or value, in bytes.
+pub const MAX_LENGTH: usize = 63;
+
+/// Requirement is a term of a label selector, on the value of a single label.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Requirement {
+    Exists(String),
+    NotExists(String),
+    Equals(String, String),
+    NotEquals(String, String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+}
+
+/// Checks that a label key is between 1 and `MAX_LENGTH` characters among lowercase ascii letters,
+/// digits, `_` and `.`, starting with a letter and made of non-empty dot separated segments. Keys
+/// are field names in queries, like `.labels.env`, hence the narrower set than identifiers.
+pub fn check_key(key: &str) -> Result<(), String> {
+    let first = key.chars().next().ok_or("must not be empty")?;
+    if key.len() > MAX_LENGTH {
+        Err(format!(
+            "must not be longer than {} characters, got {}",
+            MAX_LENGTH,
+            key.len()
+        ))
+    } else if !first.is_ascii_lowercase() {
+        Err(format!("must start with a letter, got {:?}", first))
+    } else if let Some(c) = key
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_' || *c == '.'))
+    {
+        Err(format!(
+            "contains invalid character {:?}, allowed are a-z, 0-9, '_' and '.'",
+            c
+        ))
+    } else if key.split('.').any(|segment| segment.is_empty()) {
+        Err("must not have empty segments between dots".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that a label value is blank or at most `MAX_LENGTH` characters among ascii letters,
+/// digits, `-`, `_` and `.`, starting and ending with a letter or a digit.
+pub fn check_value(value: &str) -> Result<(), String> {
+    let alphanumeric = |c: Option<char>| c.map_or(false, |c| c.is_ascii_alphanumeric());
+    if value.is_empty() {
+        Ok(())
+    } else if value.len() > MAX_LENGTH {
+        Err(format!(
+            "must not be longer than {} characters, got {}",
+            MAX_LENGTH,
+            value.len()
+        ))
+    } else if !alphanumeric(value.chars().next()) || !alphanumeric(value.chars().last()) {
+        Err(format!(
+            "must start and end with a letter or a digit, got {:?}",
+            value
+        ))
+    } else if let Some(c) = value
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_' || *c == '.'))
+    {
+        Err(format!(
+            "contains invalid character {:?}, allowed are a-z, A-Z, 0-9, '-', '_' and '.'",
+            c
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses a label selector, comma separated requirements all of which must hold:
+/// - `key` and `!key` for a label set or not;
+/// - `key=value`, `key==value` and `key!=value`;
+/// - `key in (v1,v2)` and `key notin (v1,v2)` for a value among a set or not.
+///
+/// Negations, `!key`, `key!=value` and `notin`, hold for resources without the label. A blank
+/// selector has no requirements.
+pub fn parse(selector: &str) -> Result<Vec<Requirement>, String> {
+    split(selector)?
+        .into_iter()
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            parse_requirement(term).map_err(|e| format!("invalid label selector {:?}: {}", term, e))
+        })
+        .collect()
+}
+
+/// Returns the BQL expression holding for the resources whose labels satisfy all the
+/// requirements, none when there are no requirements.
+pub fn bql(requirements: &[Requirement]) -> Option<String> {
+    let field = |key: &str| format!(".labels.{}", key);
+    // the index negates a relation on a field among the resources defining that field, the
+    // conjunction with `all` negates it among all the resources, the ones without the label too
+    let not = |relation: String| format!("!( all&&{} )", relation);
+    let list = |values: &[String]| {
+        values
+            .iter()
+            .map(|v| format!("'{}'", v))
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let terms: Vec<String> = requirements
+        .iter()
+        .map(|r| match r {
+            Requirement::Exists(k) => field(k),
+            Requirement::NotExists(k) => not(field(k)),
+            Requirement::Equals(k, v) => format!("{}=='{}'", field(k), v),
+            Requirement::NotEquals(k, v) => not(format!("{}=='{}'", field(k), v)),
+            Requirement::In(k, vs) => format!("{}@any[{}]", field(k), list(vs)),
+            Requirement::NotIn(k, vs) => not(format!("{}@any[{}]", field(k), list(vs))),
+        })
+        .collect();
+    match terms.len() {
+        0 => None,
+        1 => Some(terms[0].clone()),
+        _ => Some(
+            terms
+                .iter()
+                .map(|t| format!("( {} )", t))
+                .collect::<Vec<String>>()
+                .join("&&"),
+        ),
+    }
+}
+
+/// Applies label changes, `key=value` to set a label and `key-` to remove it. Changing the value
+/// of a label already set is an error unless `overwrite` is set, removing a label not set is not.
+pub fn update(
+    labels: &mut HashMap<String, String>,
+    changes: &[String],
+    overwrite: bool,
+) -> Result<(), String> {
+    for change in changes.iter() {
+        let change = change.trim();
+        if let Some(key) = change.strip_suffix('-') {
+            check_key(key).map_err(|e| format!("invalid label key {:?}: {}", key, e))?;
+            labels.remove(key);
+            continue;
+        }
+        let (key, value) = change.split_once('=').ok_or_else(|| {
+            format!(
+                "invalid label change {:?}, expected key=value or key-",
+                change
+            )
+        })?;
+        let (key, value) = (key.trim(), value.trim());
+        check_key(key).map_err(|e| format!("invalid label key {:?}: {}", key, e))?;
+        check_value(value).map_err(|e| format!("invalid label value {:?}: {}", value, e))?;
+        match labels.get(key) {
+            Some(current) if current != value && !overwrite => Err(format!(
+                "label {} is already set to {:?}, overwrite to change it",
+                key, current
+            ))?,
+            _ => labels.insert(key.to_string(), value.to_string()),
+        };
+    }
+    Ok(())
+}
+
+/// Splits a selector on the commas outside of parentheses.
+fn split(selector: &str) -> Result<Vec<&str>, String> {
+    let mut terms = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => Err(format!("unbalanced ')' in label selector {:?}", selector))?,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                terms.push(selector[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        Err(format!("unclosed '(' in label selector {:?}", selector))?
+    }
+    terms.push(selector[start..].trim());
+    Ok(terms)
+}
+
+fn parse_requirement(term: &str) -> Result<Requirement, String> {
+    let key = |k: &str| {
+        let k = k.trim();
+        check_key(k)
+            .map(|_| k.to_string())
+            .map_err(|e| format!("key {:?} {}", k, e))
+    };
+    let value = |v: &str| {
+        let v = v.trim();
+        check_value(v)
+            .map(|_| v.to_string())
+            .map_err(|e| format!("value {:?} {}", v, e))
+    };
+    if let Some(k) = term.strip_prefix('!') {
+        return Ok(Requirement::NotExists(key(k)?));
+    }
+    if let Some((k, v)) = term.split_once("!=") {
+        return Ok(Requirement::NotEquals(key(k)?, value(v)?));
+    }
+    if let Some((k, v)) = term.split_once("==").or_else(|| term.split_once('=')) {
+        return Ok(Requirement::Equals(key(k)?, value(v)?));
+    }
+    let (head, values) = match term.split_once('(') {
+        None => return Ok(Requirement::Exists(key(term)?)),
+        Some((head, rest)) => match rest.trim_end().strip_suffix(')') {
+            Some(values) => (head, values),
+            None => return Err("expected the values to end the term".to_string()),
+        },
+    };
+    let values = values
+        .split(',')
+        .map(value)
+        .collect::<Result<Vec<String>, String>>()?;
+    match head.split_whitespace().collect::<Vec<&str>>()[..] {
+        [k, "in"] => Ok(Requirement::In(key(k)?, values)),
+        [k, "notin"] => Ok(Requirement::NotIn(key(k)?, values)),
+        _ => Err("expected key in (values) or key notin (values)".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let s = |v: &str| v.to_string();
+        assert_eq!(
+            parse(
+                "env in (prod, staging),!deprecated, tier=web,team==ci,zone!=eu,os notin (windows)"
+            ),
+            Ok(vec![
+                Requirement::In(s("env"), vec![s("prod"), s("staging")]),
+                Requirement::NotExists(s("deprecated")),
+                Requirement::Equals(s("tier"), s("web")),
+                Requirement::Equals(s("team"), s("ci")),
+                Requirement::NotEquals(s("zone"), s("eu")),
+                Requirement::NotIn(s("os"), vec![s("windows")]),
+            ])
+        );
+        assert_eq!(parse(" "), Ok(vec![]));
+        assert_eq!(
+            parse("app.tier"),
+            Ok(vec![Requirement::Exists(s("app.tier"))])
+        );
+        assert!(parse("env in (prod").is_err());
+        assert!(parse("env in prod)").is_err());
+        assert!(parse("env within (prod)").is_err());
+        assert!(parse("Env=prod").is_err());
+        assert!(parse("env=-prod").is_err());
+        assert!(parse("app-name").is_err());
+    }
+
+    #[test]
+    fn test_bql() {
+        let bql = |selector: &str| bql(&parse(selector).unwrap());
+        assert_eq!(bql(""), None);
+        assert_eq!(bql("env=prod"), Some(".labels.env=='prod'".to_string()));
+        assert_eq!(
+            bql("env in (prod,staging),!deprecated"),
+            Some(
+                "( .labels.env@any['prod','staging'] )&&( !( all&&.labels.deprecated ) )"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            bql("env notin (dev),tier!=db"),
+            Some(
+                "( !( all&&.labels.env@any['dev'] ) )&&( !( all&&.labels.tier=='db' ) )"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut labels = HashMap::from([("env".to_string(), "dev".to_string())]);
+        let changes = |cs: &[&str]| cs.iter().map(|c| c.to_string()).collect::<Vec<String>>();
+        update(&mut labels, &changes(&["tier=web", "env=dev"]), false).unwrap();
+        assert!(update(&mut labels, &changes(&["env=prod"]), false).is_err());
+        update(
+            &mut labels,
+            &changes(&["env=prod", "tier-", "absent-"]),
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            labels,
+            HashMap::from([("env".to_string(), "prod".to_string())])
+        );
+        assert!(update(&mut labels, &changes(&["env"]), false).is_err());
+        assert!(update(&mut labels, &changes(&["env=a b"]), false).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use super::binding;
+use pbjson_types::Struct;
+use serde_json::Value as JValue;
+use std::collections::BTreeMap;
+
+/// Start of a reference to the payload of an event, like `${event.resource.name}`.
+pub const REFERENCE_START: &str = "${event";
+const REFERENCE_END: char = '}';
+/// Prefix of the URL paths of webhooks.
+pub const WEBHOOK_PREFIX: &str = "/hooks/";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part<'a> {
+    Text(&'a str),
+    Ref(Vec<&'a str>),
+}
+
+/// Checks the path of a webhook, relative to `WEBHOOK_PREFIX`: segments of ascii letters,
+/// digits, `-`, `_` and `.` separated by `/`, none of them empty, `.` or `..`.
+pub fn check_webhook_path(path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Err("must not be empty".to_string());
+    }
+    for segment in path.split('/') {
+        if segment.is_empty() || segment == "." || segment == ".." {
+            return Err(format!("invalid segment {:?} in {:?}", segment, path));
+        }
+        if let Some(c) = segment
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_' || *c == '.'))
+        {
+            return Err(format!(
+                "contains invalid character {:?}, allowed are a-z, A-Z, 0-9, '-', '_', '.' and '/'",
+                c
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the event references of a text.
+pub fn check_template(text: &str) -> Result<(), String> {
+    parse(text).map(|_| ())
+}
+
+fn parse(text: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(REFERENCE_START) {
+        let end = rest[start..]
+            .find(REFERENCE_END)
+            .map(|end| start + end)
+            .ok_or_else(|| format!("unterminated event reference in {:?}", text))?;
+        let path = match &rest[start + REFERENCE_START.len()..end] {
+            "" => vec![],
+            reference => match reference.strip_prefix('.') {
+                Some(path) if path.split('.').all(|s| !s.is_empty()) => path.split('.').collect(),
+                _ => {
+                    return Err(format!(
+                        "invalid event reference {:?}, expected {}}} or {}.<path>}}",
+                        &rest[start..=end],
+                        REFERENCE_START,
+                        REFERENCE_START
+                    ))
+                }
+            },
+        };
+        if start > 0 {
+            parts.push(Part::Text(&rest[..start]));
+        }
+        parts.push(Part::Ref(path));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+/// Value at a path of the payload, made of object keys and array indexes.
+fn lookup<'a>(payload: &'a JValue, path: &[&str]) -> Result<&'a JValue, String> {
+    path.iter()
+        .try_fold(payload, |v, segment| match v {
+            JValue::Object(o) => o.get(*segment),
+            JValue::Array(a) => segment.parse::<usize>().ok().and_then(|i| a.get(i)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            format!(
+                "{}.{}}} not found in the event",
+                REFERENCE_START,
+                path.join(".")
+            )
+        })
+}
+
+/// Replaces the event references in the inputs of a listener by the values of the payload. A
+/// string made of a single reference takes the value as is, keeping its type, while references
+/// inside a longer text are replaced by the text of their value.
+pub fn render(
+    inputs: Option<&Struct>,
+    payload: &JValue,
+) -> Result<BTreeMap<String, JValue>, String> {
+    let inputs = match inputs {
+        Some(inputs) => serde_json::to_value(inputs).map_err(|e| e.to_string())?,
+        None => return Ok(BTreeMap::new()),
+    };
+    match render_value(&inputs, payload)? {
+        JValue::Object(o) => Ok(o.into_iter().collect()),
+        _ => Err("listener inputs must be an object".to_string()),
+    }
+}
+
+fn render_value(v: &JValue, payload: &JValue) -> Result<JValue, String> {
+    match v {
+        JValue::String(s) => {
+            let parts = parse(s)?;
+            if let [Part::Ref(path)] = &parts[..] {
+                return lookup(payload, path).cloned();
+            }
+            let mut out = String::new();
+            for part in parts.iter() {
+                match part {
+                    Part::Text(s) => out.push_str(s),
+                    Part::Ref(path) => out.push_str(&binding::text(lookup(payload, path)?)),
+                }
+            }
+            Ok(JValue::String(out))
+        }
+        JValue::Array(a) => a
+            .iter()
+            .map(|v| render_value(v, payload))
+            .collect::<Result<_, _>>()
+            .map(JValue::Array),
+        JValue::Object(o) => o
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), render_value(v, payload)?)))
+            .collect::<Result<_, String>>()
+            .map(JValue::Object),
+        v => Ok(v.clone()),
+    }
+}
+
+/// Splits the inputs of a listener targeting a dag, named `<node>.<input>`, by node.
+pub fn node_inputs(
+    inputs: BTreeMap<String, JValue>,
+) -> Result<BTreeMap<String, BTreeMap<String, JValue>>, String> {
+    let mut nodes: BTreeMap<String, BTreeMap<String, JValue>> = BTreeMap::new();
+    for (name, value) in inputs {
+        let (node, input) = name
+            .split_once('.')
+            .ok_or_else(|| format!("invalid dag input {:?}, expected <node>.<input>", name))?;
+        nodes
+            .entry(node.to_string())
+            .or_default()
+            .insert(input.to_string(), value);
+    }
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_template() {
+        assert!(check_template("no reference").is_ok());
+        assert!(check_template("${event}").is_ok());
+        assert!(check_template("run ${event.resource.name} at ${event.time}").is_ok());
+        assert!(check_template("${event.resource").is_err());
+        assert!(check_template("${eventname}").is_err());
+        assert!(check_template("${event.a..b}").is_err());
+    }
+
+    #[test]
+    fn test_check_webhook_path() {
+        assert!(check_webhook_path("git/push").is_ok());
+        assert!(check_webhook_path("").is_err());
+        assert!(check_webhook_path("/git").is_err());
+        assert!(check_webhook_path("git/../x").is_err());
+        assert!(check_webhook_path("git push").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let inputs: Struct = serde_json::from_value(json!({
+            "name": "${event.resource.name}",
+            "event": "${event}",
+            "count": "${event.changes.1.size}",
+            "message": "${event.changes.0.path} changed at ${event.time}",
+            "list": ["${event.time}", true],
+        }))
+        .unwrap();
+        let payload = json!({
+            "time": 1700000040000u64,
+            "resource": {"name": "build"},
+            "changes": [{"path": "a.txt"}, {"path": "b.txt", "size": 3}],
+        });
+        let rendered = render(Some(&inputs), &payload).unwrap();
+        assert_eq!(rendered["name"], json!("build"));
+        assert_eq!(rendered["event"], payload);
+        assert_eq!(rendered["count"], json!(3));
+        assert_eq!(rendered["message"], json!("a.txt changed at 1700000040000"));
+        assert_eq!(rendered["list"], json!([1700000040000u64, true]));
+        assert_eq!(render(None, &payload), Ok(BTreeMap::new()));
+
+        let inputs: Struct = serde_json::from_value(json!({"x": "${event.missing}"})).unwrap();
+        assert_eq!(
+            render(Some(&inputs), &payload),
+            Err("${event.missing} not found in the event".to_string())
+        );
+    }
+
+    #[test]
+    fn test_node_inputs() {
+        let inputs = BTreeMap::from([
+            ("build.target".to_string(), json!("all")),
+            ("test.filter".to_string(), json!("unit")),
+        ]);
+        assert_eq!(
+            node_inputs(inputs),
+            Ok(BTreeMap::from([
+                (
+                    "build".to_string(),
+                    BTreeMap::from([("target".to_string(), json!("all"))])
+                ),
+                (
+                    "test".to_string(),
+                    BTreeMap::from([("filter".to_string(), json!("unit"))])
+                ),
+            ]))
+        );
+        assert!(node_inputs(BTreeMap::from([("x".to_string(), json!(1))])).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use super::{identifier, parse_resource_id, resource_kind_to_string, version, DEFAULT_VERSION};
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Resource, ResourceRef};
+
+/// Parses a resource id, like `/^1.2/default/function/build`, into a reference.
+pub fn parse(id: &str) -> Result<ResourceRef, String> {
+    let (version, namespace, kind, name) = parse_resource_id(id)?;
+    Ok(ResourceRef {
+        kind,
+        namespace: identifier::normalize(&namespace),
+        name: identifier::normalize(&name),
+        version: identifier::normalize(&version),
+    })
+}
+
+/// Returns the references of a resource: the declared ones and the resource ids of its kind, like
+/// the functions of a dag or the bundles of an applier. References are normalized, blank
+/// namespaces and versions defaulting to the namespace of the resource and `latest`, and listed
+/// once in order. Invalid resource ids, rejected by the validation, are left out.
+pub fn references(r: &Resource) -> Vec<ResourceRef> {
+    let mut ids: Vec<&str> = Vec::new();
+    match r.resource_kind {
+        Some(ResourceKind::Function(ref f)) => {
+            ids.extend(f.secrets.iter().map(|s| s.secret.as_str()))
+        }
+        Some(ResourceKind::Dag(ref d)) => ids.extend(d.nodes.iter().map(|n| n.function.as_str())),
+        Some(ResourceKind::Bundle(ref b)) => ids.extend(b.resources.iter().map(|id| id.as_str())),
+        Some(ResourceKind::Listener(ref l)) => ids.push(&l.target),
+        Some(ResourceKind::Deployer(ref d)) => ids.push(&d.function),
+        Some(ResourceKind::Applier(ref a)) => {
+            ids.push(&a.deployer);
+            ids.extend(a.stages.values().map(|id| id.as_str()));
+        }
+        Some(ResourceKind::Runtime(_))
+        | Some(ResourceKind::Secret(_))
+        | Some(ResourceKind::Config(_))
+        | None => {}
+    }
+    let mut refs: Vec<ResourceRef> = r
+        .references
+        .iter()
+        .map(|reference| {
+            let mut reference = ResourceRef {
+                kind: reference.kind.trim().to_lowercase(),
+                namespace: identifier::normalize(&reference.namespace),
+                name: identifier::normalize(&reference.name),
+                version: identifier::normalize(&reference.version),
+            };
+            if reference.namespace.is_empty() {
+                reference.namespace = r.namespace.clone();
+            }
+            if reference.version.is_empty() {
+                reference.version = DEFAULT_VERSION.to_string();
+            }
+            reference
+        })
+        .chain(ids.into_iter().filter_map(|id| parse(id).ok()))
+        .collect();
+    refs.sort_by(|a, b| key(a).cmp(&key(b)));
+    refs.dedup();
+    refs
+}
+
+/// Tells whether a reference designates a resource, the version of the reference being resolved
+/// among the versions the resource is stored at.
+pub fn designates(reference: &ResourceRef, r: &Resource, versions: &[String]) -> bool {
+    reference.kind == resource_kind_to_string(r).unwrap_or_default()
+        && reference.namespace == r.namespace
+        && reference.name == r.name
+        && version::resolve(&reference.version, versions.iter().map(|v| v.as_str())).as_ref()
+            == Some(&r.version)
+}
+
+fn key(r: &ResourceRef) -> (&str, &str, &str, &str) {
+    (&r.kind, &r.namespace, &r.name, &r.version)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+    use bdaproto::DagNode;
+
+    fn reference(id: &str) -> ResourceRef {
+        parse(id).unwrap()
+    }
+
+    #[test]
+    fn test_references() {
+        let mut d = logic::new_resource_dag("pipeline");
+        d.namespace = "ci".to_string();
+        if let Some(ResourceKind::Dag(ref mut d)) = d.resource_kind {
+            let node = |name: &str, function: &str| DagNode {
+                name: name.to_string(),
+                function: function.to_string(),
+                inputs: None,
+            };
+            d.nodes = vec![
+                node("build", "/^1.2/ci/function/build"),
+                node("test", "/latest/ci/function/test"),
+                node("again", "/^1.2/ci/function/build"),
+                node("broken", "/latest/ci/unknown/x"),
+            ];
+        }
+        d.references = vec![ResourceRef {
+            kind: "runtime.container".to_string(),
+            namespace: String::new(),
+            name: " Builder".to_string(),
+            version: String::new(),
+        }];
+        assert_eq!(
+            references(&d),
+            vec![
+                reference("/^1.2/ci/function/build"),
+                reference("/latest/ci/function/test"),
+                reference("/latest/ci/runtime.container/builder"),
+            ]
+        );
+        assert_eq!(references(&logic::new_resource_config("c")), vec![]);
+    }
+
+    #[test]
+    fn test_designates() {
+        let mut f = logic::new_resource_function("build");
+        f.version = "1.2.0".to_string();
+        let versions = vec!["1.2.0".to_string(), "1.3.0".to_string()];
+        let designated = |id: &str| designates(&reference(id), &f, &versions);
+        assert!(designated("/~1.2/default/function/build"));
+        assert!(designated("/1.2.0/default/function/build"));
+        assert!(!designated("/^1.2/default/function/build"));
+        assert!(!designated("/~1.2/default/dag/build"));
+        assert!(!designated("/~1.2/other/function/build"));
+    }
+}
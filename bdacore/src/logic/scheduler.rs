@@ -0,0 +1,187 @@
+use crate::data::EntityID;
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Function, Resource, Runtime};
+use std::error::Error;
+
+/// Runtime able to run a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub id: String,
+    /// Capabilities of the runtime not required by the function.
+    pub extra: Vec<String>,
+    /// Whether the runtime lives in the namespace of the function.
+    pub same_namespace: bool,
+}
+
+/// Runtime unable to run a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub id: String,
+    /// Capabilities required by the function the runtime lacks.
+    pub missing: Vec<String>,
+}
+
+/// BQL selecting the runtimes whose capabilities are a superset of the function ones.
+pub fn capabilities_bql(f: &Function) -> String {
+    if f.runtime_capabilities.is_empty() {
+        return ".runtime".to_string();
+    }
+    let capabilities: Vec<String> = f
+        .runtime_capabilities
+        .iter()
+        .map(|c| format!("'{}'", c.replace('\'', "\\'")))
+        .collect();
+    format!(".runtime.capabilities in all [{}]", capabilities.join(","))
+}
+
+/// Capabilities required by the function the runtime lacks.
+pub fn missing(f: &Function, rt: &Runtime) -> Vec<String> {
+    f.runtime_capabilities
+        .iter()
+        .filter(|c| !rt.capabilities.contains(c))
+        .cloned()
+        .collect()
+}
+
+/// Ranks the runtimes able to run a function, usually the result of `capabilities_bql`.
+/// Runtimes in the namespace of the function come first, then the ones with fewer capabilities
+/// the function does not need, so specialized runtimes are kept for functions requiring them.
+/// Resources that are not runtimes, or lack a capability, are discarded.
+pub fn rank(
+    function: &Resource,
+    f: &Function,
+    runtimes: &[Resource],
+) -> Result<Vec<Candidate>, Box<dyn Error>> {
+    let mut candidates = Vec::new();
+    for r in runtimes {
+        if let Some(ResourceKind::Runtime(ref rt)) = r.resource_kind {
+            if missing(f, rt).is_empty() {
+                candidates.push(Candidate {
+                    id: id(r)?,
+                    extra: rt
+                        .capabilities
+                        .iter()
+                        .filter(|c| !f.runtime_capabilities.contains(c))
+                        .cloned()
+                        .collect(),
+                    same_namespace: r.namespace == function.namespace,
+                })
+            }
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.same_namespace
+            .cmp(&a.same_namespace)
+            .then(a.extra.len().cmp(&b.extra.len()))
+            .then(a.id.cmp(&b.id))
+    });
+    Ok(candidates)
+}
+
+/// Explains why the runtimes cannot run a function, closest runtimes first.
+pub fn explain(f: &Function, runtimes: &[Resource]) -> Result<Vec<Mismatch>, Box<dyn Error>> {
+    let mut mismatches = Vec::new();
+    for r in runtimes {
+        if let Some(ResourceKind::Runtime(ref rt)) = r.resource_kind {
+            let missing = missing(f, rt);
+            if !missing.is_empty() {
+                mismatches.push(Mismatch {
+                    id: id(r)?,
+                    missing,
+                })
+            }
+        }
+    }
+    mismatches.sort_by(|a, b| a.missing.len().cmp(&b.missing.len()).then(a.id.cmp(&b.id)));
+    Ok(mismatches)
+}
+
+fn id(r: &Resource) -> Result<String, Box<dyn Error>> {
+    match super::resource_id(r)? {
+        EntityID::ResourceID(id) => Ok(id),
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+
+    fn runtime(namespace: &str, name: &str, capabilities: &[&str]) -> Resource {
+        let mut r = logic::new_resource_runtime_container(name);
+        r.namespace = namespace.to_string();
+        if let Some(ResourceKind::Runtime(ref mut rt)) = r.resource_kind {
+            rt.capabilities = capabilities.iter().map(|c| c.to_string()).collect();
+        }
+        r
+    }
+
+    fn function(capabilities: &[&str]) -> (Resource, Function) {
+        let mut r = logic::new_resource_function("f");
+        let f = Function {
+            runtime_capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            ..logic::new_function(&[])
+        };
+        r.resource_kind = Some(ResourceKind::Function(f.clone()));
+        (r, f)
+    }
+
+    #[test]
+    fn test_capabilities_bql() {
+        let (_, f) = function(&["git", "linux"]);
+        assert_eq!(
+            capabilities_bql(&f),
+            ".runtime.capabilities in all ['git','linux']"
+        );
+        assert!(bdaindex::bql::from_str(&capabilities_bql(&f)).is_ok());
+        let (_, f) = function(&[]);
+        assert_eq!(capabilities_bql(&f), ".runtime");
+    }
+
+    #[test]
+    fn test_rank() {
+        let (r, f) = function(&["git"]);
+        let runtimes = vec![
+            runtime("other", "a", &["git"]),
+            runtime("default", "b", &["git", "linux", "docker"]),
+            runtime("default", "c", &["git", "linux"]),
+            runtime("default", "d", &["linux"]),
+        ];
+        let ids: Vec<String> = rank(&r, &f, &runtimes)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "/latest/default/runtime.container/c",
+                "/latest/default/runtime.container/b",
+                "/latest/other/runtime.container/a",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let (_, f) = function(&["git", "linux", "gpu"]);
+        let runtimes = vec![
+            runtime("default", "a", &["git"]),
+            runtime("default", "b", &["git", "linux"]),
+            runtime("default", "c", &["git", "linux", "gpu"]),
+        ];
+        assert_eq!(
+            explain(&f, &runtimes).unwrap(),
+            vec![
+                Mismatch {
+                    id: "/latest/default/runtime.container/b".to_string(),
+                    missing: vec!["gpu".to_string()],
+                },
+                Mismatch {
+                    id: "/latest/default/runtime.container/a".to_string(),
+                    missing: vec!["linux".to_string(), "gpu".to_string()],
+                },
+            ]
+        );
+    }
+}
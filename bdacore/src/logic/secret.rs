@@ -0,0 +1,226 @@
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Resource, Secret};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Value shown instead of the values of secrets. Putting it back keeps the stored value.
+pub const REDACTED: &str = "<redacted>";
+/// Prefix of the values encrypted by a `SecretKey`.
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM key encrypting the values of secrets. Key files hold the base64 encoding of 32
+/// random bytes.
+pub struct SecretKey {
+    cipher: Aes256Gcm,
+}
+
+impl SecretKey {
+    pub fn new(key: &[u8]) -> Result<Self, String> {
+        if key.len() != KEY_LEN {
+            return Err(format!(
+                "secret keys are {} bytes long, got {}",
+                KEY_LEN,
+                key.len()
+            ));
+        }
+        Ok(SecretKey {
+            cipher: Aes256Gcm::new(Key::from_slice(key)),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("could not read secret key {}: {}", path.display(), e))?;
+        let key = base64::decode(content.trim())
+            .map_err(|e| format!("invalid secret key {}: {}", path.display(), e))?;
+        Ok(SecretKey::new(&key)?)
+    }
+
+    /// Same as `load`, first writing a new random key, readable only by its owner, when the
+    /// file does not exist.
+    pub fn load_or_create(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut key = [0u8; KEY_LEN];
+            getrandom::getrandom(&mut key)?;
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            options
+                .open(path)?
+                .write_all(base64::encode(key).as_bytes())?;
+        }
+        Self::load(path)
+    }
+
+    /// Encrypts a value with a random nonce, returning `enc:v1:` followed by the base64 encoding
+    /// of the nonce and the ciphertext.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce).map_err(|e| e.to_string())?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend(
+            self.cipher
+                .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+                .map_err(|_| "could not encrypt the secret value".to_string())?,
+        );
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, base64::encode(sealed)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, String> {
+        let sealed = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .and_then(|v| base64::decode(v).ok())
+            .filter(|sealed| sealed.len() > NONCE_LEN)
+            .ok_or("the secret value is not encrypted")?;
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                "could not decrypt the secret value, was it encrypted with another key?"
+            })?;
+        String::from_utf8(plaintext).map_err(|e| e.to_string())
+    }
+
+    /// Encrypts the plaintext values of a secret resource, other resources are left untouched.
+    /// Values matching the stored secret keep their ciphertext, so putting the same secret twice
+    /// changes nothing, and `<redacted>` values keep the stored value.
+    pub fn seal(&self, r: &mut Resource, stored: Option<&Resource>) -> Result<(), String> {
+        let secret = match r.resource_kind {
+            Some(ResourceKind::Secret(ref mut secret)) => secret,
+            _ => return Ok(()),
+        };
+        let stored = match stored.and_then(|s| s.resource_kind.as_ref()) {
+            Some(ResourceKind::Secret(stored)) => Some(stored),
+            _ => None,
+        };
+        for (key, value) in secret.data.iter_mut() {
+            let old = stored.and_then(|s| s.data.get(key));
+            *value = match old {
+                Some(old) if value == REDACTED => old.clone(),
+                None if value == REDACTED => {
+                    return Err(format!(
+                        "value of key {:?} is redacted and none is stored",
+                        key
+                    ))
+                }
+                Some(old) if self.decrypt(old).as_deref() == Ok(value.as_str()) => old.clone(),
+                _ => self.encrypt(value)?,
+            };
+        }
+        Ok(())
+    }
+
+    /// Decrypts the values of a secret, by key.
+    pub fn open(&self, secret: &Secret) -> Result<BTreeMap<String, String>, String> {
+        secret
+            .data
+            .iter()
+            .map(|(key, value)| {
+                self.decrypt(value)
+                    .map(|v| (key.clone(), v))
+                    .map_err(|e| format!("key {:?}: {}", key, e))
+            })
+            .collect()
+    }
+}
+
+/// Replaces the values of a secret resource with `<redacted>`, other resources are left
+/// untouched.
+pub fn redact(r: &mut Resource) {
+    if let Some(ResourceKind::Secret(ref mut secret)) = r.resource_kind {
+        for value in secret.data.values_mut() {
+            *value = REDACTED.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+
+    fn secret(values: &[(&str, &str)]) -> Resource {
+        let mut r = logic::new_resource_secret("registry");
+        if let Some(ResourceKind::Secret(ref mut s)) = r.resource_kind {
+            s.data = values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+        }
+        r
+    }
+
+    fn data(r: &Resource) -> &Secret {
+        match r.resource_kind {
+            Some(ResourceKind::Secret(ref s)) => s,
+            _ => panic!("not a secret"),
+        }
+    }
+
+    #[test]
+    fn test_seal_open() {
+        let key = SecretKey::new(&[7u8; KEY_LEN]).unwrap();
+        let mut sealed = secret(&[("token", "s3cr3t"), ("user", "bda")]);
+        key.seal(&mut sealed, None).unwrap();
+        assert!(data(&sealed)
+            .data
+            .values()
+            .all(|v| v.starts_with(ENCRYPTED_PREFIX) && !v.contains("s3cr3t")));
+        let opened = key.open(data(&sealed)).unwrap();
+        assert_eq!(opened["token"], "s3cr3t");
+        assert_eq!(opened["user"], "bda");
+
+        // unchanged and redacted values keep their ciphertext, changed ones are encrypted again
+        let mut again = secret(&[("token", "s3cr3t"), ("user", REDACTED)]);
+        key.seal(&mut again, Some(&sealed)).unwrap();
+        assert_eq!(again, sealed);
+        let mut changed = secret(&[("token", "other"), ("user", "bda")]);
+        key.seal(&mut changed, Some(&sealed)).unwrap();
+        assert_ne!(data(&changed).data["token"], data(&sealed).data["token"]);
+        assert_eq!(data(&changed).data["user"], data(&sealed).data["user"]);
+        assert_eq!(key.open(data(&changed)).unwrap()["token"], "other");
+
+        let mut missing = secret(&[("password", REDACTED)]);
+        assert!(key.seal(&mut missing, Some(&sealed)).is_err());
+        let other = SecretKey::new(&[8u8; KEY_LEN]).unwrap();
+        assert!(other.open(data(&sealed)).is_err());
+        assert!(key.decrypt("s3cr3t").is_err());
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut r = secret(&[("token", "s3cr3t")]);
+        redact(&mut r);
+        assert_eq!(data(&r).data["token"], REDACTED);
+        let f = logic::new_resource_function("f");
+        let mut redacted = f.clone();
+        redact(&mut redacted);
+        assert_eq!(redacted, f);
+    }
+
+    #[test]
+    fn test_load_or_create() {
+        let dir = std::env::temp_dir().join(format!("bda-secret-{}", std::process::id()));
+        let path = dir.join("secret.key");
+        let _ = fs::remove_dir_all(&dir);
+        let sealed = SecretKey::load_or_create(&path)
+            .unwrap()
+            .encrypt("s3cr3t")
+            .unwrap();
+        let key = SecretKey::load_or_create(&path).unwrap();
+        assert_eq!(key.decrypt(&sealed).unwrap(), "s3cr3t");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
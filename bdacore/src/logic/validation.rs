@@ -0,0 +1,1008 @@
+use super::{binding, bundle, config, cron, dag, identifier, label, listener};
+use bdaproto::parameter::ParameterKind;
+use bdaproto::resource::ResourceKind;
+use bdaproto::runtime::RuntimeKind;
+use bdaproto::trigger::TriggerKind;
+use bdaproto::{
+    Applier, Bundle, Config, Container, Dag, Deployer, Function, Listener, Parameter, Resource,
+    ResourceRef, Runtime, Secret, SecretRef,
+};
+use pbjson_types::{value::Kind, Value};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::path::{Component, Path};
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub field: String,
+    pub message: String,
+}
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationErrors {
+    pub errors: Vec<ValidationError>,
+}
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let errors: Vec<String> = self.errors.iter().map(|e| e.to_string()).collect();
+        write!(f, "invalid resource: {}", errors.join("; "))
+    }
+}
+impl Error for ValidationErrors {}
+
+struct Errors(Vec<ValidationError>);
+impl Errors {
+    fn push(&mut self, field: &str, message: String) {
+        self.0.push(ValidationError {
+            field: field.to_string(),
+            message,
+        })
+    }
+}
+
+/// Validates a resource normalized by `logic::defaults`, returning every error found.
+pub fn validate(r: &Resource) -> Result<(), ValidationErrors> {
+    let mut errors = Errors(Vec::new());
+    validate_identifier(".version", &r.version, &mut errors);
+    validate_identifier(".namespace", &r.namespace, &mut errors);
+    validate_identifier(".name", &r.name, &mut errors);
+    validate_references(".references", &r.references, &mut errors);
+    validate_labels(".labels", &r.labels, &mut errors);
+    match &r.resource_kind {
+        None => errors.push(
+            ".",
+            "resource kind not specified, expected one of: function, runtime, dag, secret, config, \
+             bundle, listener, deployer, applier"
+                .to_string(),
+        ),
+        Some(ResourceKind::Function(f)) => validate_function(".function", f, &mut errors),
+        Some(ResourceKind::Runtime(rt)) => validate_runtime(".runtime", rt, &mut errors),
+        Some(ResourceKind::Dag(d)) => validate_dag(".dag", d, &mut errors),
+        Some(ResourceKind::Secret(s)) => validate_secret(".secret", s, &mut errors),
+        Some(ResourceKind::Config(c)) => validate_config(".config", c, &mut errors),
+        Some(ResourceKind::Bundle(b)) => validate_bundle(".bundle", &r.namespace, b, &mut errors),
+        Some(ResourceKind::Listener(l)) => validate_listener(".listener", l, &mut errors),
+        Some(ResourceKind::Deployer(d)) => validate_deployer(".deployer", d, &mut errors),
+        Some(ResourceKind::Applier(a)) => validate_applier(".applier", a, &mut errors),
+    }
+    if errors.0.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationErrors { errors: errors.0 })
+    }
+}
+
+fn validate_identifier(field: &str, v: &str, errors: &mut Errors) {
+    if let Err(e) = identifier::check(v) {
+        errors.push(field, e);
+    }
+}
+
+fn validate_references(field: &str, refs: &[ResourceRef], errors: &mut Errors) {
+    let or = |v: &str, default: &str| match v.trim() {
+        "" => default.to_string(),
+        v => v.to_string(),
+    };
+    for (i, r) in refs.iter().enumerate() {
+        let id = format!(
+            "/{}/{}/{}/{}",
+            or(&r.version, super::DEFAULT_VERSION),
+            or(&r.namespace, super::DEFAULT_NAMESPACE),
+            r.kind.trim().to_lowercase(),
+            r.name
+        );
+        if let Err(e) = super::parse_resource_id(&id) {
+            errors.push(&format!("{}[{}]", field, i), e);
+        }
+    }
+}
+
+fn validate_labels(field: &str, labels: &HashMap<String, String>, errors: &mut Errors) {
+    let mut keys: Vec<&String> = labels.keys().collect();
+    keys.sort();
+    for key in keys {
+        let field = format!("{}.{}", field, key);
+        if let Err(e) = label::check_key(key) {
+            errors.push(&field, format!("invalid key: {}", e));
+        }
+        if let Err(e) = label::check_value(&labels[key]) {
+            errors.push(&field, format!("invalid value: {}", e));
+        }
+    }
+}
+
+fn validate_function(field: &str, f: &Function, errors: &mut Errors) {
+    validate_parameters(&format!("{}.inputs", field), &f.inputs, errors);
+    validate_parameters(&format!("{}.outputs", field), &f.outputs, errors);
+    validate_capabilities(
+        &format!("{}.runtimeCapabilities", field),
+        &f.runtime_capabilities,
+        errors,
+    );
+    validate_secret_refs(&format!("{}.secrets", field), &f.secrets, errors);
+}
+
+fn validate_secret_refs(field: &str, refs: &[SecretRef], errors: &mut Errors) {
+    let mut targets = HashSet::new();
+    for (i, s) in refs.iter().enumerate() {
+        let field = format!("{}[{}]", field, i);
+        match super::parse_resource_id(&s.secret) {
+            Ok((_, _, kind, _)) if kind == super::SECRET_KIND => {}
+            Ok(_) => errors.push(
+                &format!("{}.secret", field),
+                format!("{:?} is not the id of a secret", s.secret),
+            ),
+            Err(e) => errors.push(&format!("{}.secret", field), e),
+        }
+        if s.key.is_empty() {
+            errors.push(&format!("{}.key", field), "must not be empty".to_string());
+        }
+        match (s.env.is_empty(), s.path.is_empty()) {
+            (false, true) if !is_env_name(&s.env) => errors.push(
+                &format!("{}.env", field),
+                format!("{:?} is not a valid environment variable name", s.env),
+            ),
+            (true, false)
+                if Path::new(&s.path)
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir)) =>
+            {
+                errors.push(
+                    &format!("{}.path", field),
+                    format!(
+                        "{:?} must be a relative path inside the working directory",
+                        s.path
+                    ),
+                )
+            }
+            (false, true) | (true, false) => {
+                if !targets.insert((s.env.as_str(), s.path.as_str())) {
+                    let target = if s.path.is_empty() { &s.env } else { &s.path };
+                    errors.push(&field, format!("{:?} is already injected", target));
+                }
+            }
+            _ => errors.push(&field, "must set exactly one of env and path".to_string()),
+        }
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn validate_secret(field: &str, s: &Secret, errors: &mut Errors) {
+    for key in s.data.keys() {
+        if key.trim().is_empty() {
+            errors.push(
+                &format!("{}.data", field),
+                "keys must not be empty".to_string(),
+            );
+        }
+    }
+}
+
+fn validate_config(field: &str, c: &Config, errors: &mut Errors) {
+    let mut keys: Vec<&String> = c.values.keys().collect();
+    keys.sort();
+    for key in keys {
+        if let Err(e) = config::check_key(key) {
+            errors.push(&format!("{}.values.{}", field, key), e);
+        }
+    }
+}
+
+fn validate_bundle(field: &str, namespace: &str, b: &Bundle, errors: &mut Errors) {
+    if let Err(e) = semver::Version::parse(&b.semver) {
+        errors.push(
+            &format!("{}.semver", field),
+            format!("{:?} is not a semantic version: {}", b.semver, e),
+        );
+    }
+    if b.resources.is_empty() {
+        errors.push(
+            &format!("{}.resources", field),
+            "must list at least one resource".to_string(),
+        );
+    }
+    for (i, e) in bundle::check_resources(namespace, b) {
+        errors.push(&format!("{}.resources[{}]", field, i), e);
+    }
+}
+
+fn validate_listener(field: &str, l: &Listener, errors: &mut Errors) {
+    match super::parse_resource_id(&l.target) {
+        Ok((_, _, kind, _)) if kind == super::FUNCTION_KIND || kind == super::DAG_KIND => {}
+        Ok(_) => errors.push(
+            &format!("{}.target", field),
+            format!("{:?} is not the id of a function or a dag", l.target),
+        ),
+        Err(e) => errors.push(&format!("{}.target", field), e),
+    }
+    if l.triggers.is_empty() {
+        errors.push(
+            &format!("{}.triggers", field),
+            "must declare at least one trigger".to_string(),
+        );
+    }
+    for (i, t) in l.triggers.iter().enumerate() {
+        let field = format!("{}.triggers[{}]", field, i);
+        match t.trigger_kind {
+            None => errors.push(
+                &field,
+                "trigger kind not specified, expected one of: cron, resourceChange, pathWatch, \
+                 webhook"
+                    .to_string(),
+            ),
+            Some(TriggerKind::Cron(ref c)) => {
+                if let Err(e) = cron::parse(&c.schedule) {
+                    errors.push(&format!("{}.cron.schedule", field), e);
+                }
+            }
+            Some(TriggerKind::ResourceChange(ref r)) => {
+                if !r.bql.is_empty() {
+                    if let Err(e) = bdaindex::bql::from_str(&r.bql) {
+                        errors.push(&format!("{}.resourceChange.bql", field), e);
+                    }
+                }
+            }
+            Some(TriggerKind::PathWatch(ref p)) => {
+                if p.path.is_empty() {
+                    errors.push(
+                        &format!("{}.pathWatch.path", field),
+                        "must not be empty".to_string(),
+                    );
+                }
+            }
+            Some(TriggerKind::Webhook(ref w)) => {
+                if let Err(e) = listener::check_webhook_path(&w.path) {
+                    errors.push(&format!("{}.webhook.path", field), e);
+                }
+            }
+        }
+    }
+    let inputs = l.inputs.as_ref().map(serde_json::to_value);
+    if let Some(Ok(serde_json::Value::Object(inputs))) = inputs {
+        for (name, value) in inputs.iter() {
+            if let Err(e) = check_templates(value) {
+                errors.push(&format!("{}.inputs.{}", field, name), e);
+            }
+        }
+    }
+}
+
+fn check_templates(v: &serde_json::Value) -> Result<(), String> {
+    match v {
+        serde_json::Value::String(s) => listener::check_template(s),
+        serde_json::Value::Array(a) => a.iter().try_for_each(check_templates),
+        serde_json::Value::Object(o) => o.values().try_for_each(check_templates),
+        _ => Ok(()),
+    }
+}
+
+fn validate_deployer(field: &str, d: &Deployer, errors: &mut Errors) {
+    validate_reference(
+        &format!("{}.function", field),
+        &d.function,
+        super::FUNCTION_KIND,
+        errors,
+    );
+    if d.stages.is_empty() {
+        errors.push(
+            &format!("{}.stages", field),
+            "must declare at least one stage".to_string(),
+        );
+    }
+    let mut names = HashSet::new();
+    for (i, stage) in d.stages.iter().enumerate() {
+        let field = format!("{}.stages[{}]", field, i);
+        validate_identifier(&format!("{}.name", field), &stage.name, errors);
+        if !names.insert(stage.name.as_str()) {
+            errors.push(
+                &format!("{}.name", field),
+                format!("duplicate stage {}", stage.name),
+            );
+        }
+        let inputs = stage.inputs.as_ref().map(serde_json::to_value);
+        if let Some(Ok(serde_json::Value::Object(inputs))) = inputs {
+            for name in inputs
+                .keys()
+                .filter(|n| super::DEPLOY_INPUTS.contains(&n.as_str()))
+            {
+                errors.push(
+                    &format!("{}.inputs.{}", field, name),
+                    "is given by the deployment".to_string(),
+                );
+            }
+        }
+    }
+}
+
+fn validate_applier(field: &str, a: &Applier, errors: &mut Errors) {
+    validate_reference(
+        &format!("{}.deployer", field),
+        &a.deployer,
+        super::DEPLOYER_KIND,
+        errors,
+    );
+    if a.stages.is_empty() {
+        errors.push(
+            &format!("{}.stages", field),
+            "must want a bundle in at least one stage".to_string(),
+        );
+    }
+    let mut stages: Vec<_> = a.stages.iter().collect();
+    stages.sort();
+    for (stage, bundle) in stages {
+        validate_reference(
+            &format!("{}.stages.{}", field, stage),
+            bundle,
+            super::BUNDLE_KIND,
+            errors,
+        );
+    }
+}
+
+fn validate_reference(field: &str, id: &str, kind: &str, errors: &mut Errors) {
+    match super::parse_resource_id(id) {
+        Ok((_, _, k, _)) if k == kind => {}
+        Ok(_) => errors.push(field, format!("{:?} is not the id of a {}", id, kind)),
+        Err(e) => errors.push(field, e),
+    }
+}
+
+fn validate_parameters(field: &str, ps: &[Parameter], errors: &mut Errors) {
+    let mut names = HashSet::new();
+    for (i, p) in ps.iter().enumerate() {
+        let field = format!("{}[{}]", field, i);
+        if p.name.is_empty() {
+            errors.push(&format!("{}.name", field), "must not be empty".to_string());
+        } else if !names.insert(p.name.as_str()) {
+            errors.push(
+                &format!("{}.name", field),
+                format!("duplicated parameter name {:?}", p.name),
+            );
+        }
+        validate_default_value(&field, p, errors);
+        validate_bounds(&field, p, errors);
+    }
+}
+
+/// Checks that only `NUMBER` parameters have bounds, and that their default value is within them.
+fn validate_bounds(field: &str, p: &Parameter, errors: &mut Errors) {
+    let mut bound = |name: &str, value: &Option<Value>| {
+        binding::number_bound(name, value).unwrap_or_else(|e| {
+            errors.push(&format!("{}.{}", field, name), e);
+            None
+        })
+    };
+    let (min, max) = (bound("minimum", &p.minimum), bound("maximum", &p.maximum));
+    if min.is_none() && max.is_none() {
+        return;
+    }
+    if p.parameter_kind != ParameterKind::Number as i32 {
+        return errors.push(
+            field,
+            "minimum and maximum only bound NUMBER parameters".to_string(),
+        );
+    }
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            errors.push(
+                &format!("{}.minimum", field),
+                format!("{} is greater than the maximum {}", min, max),
+            );
+        }
+    }
+    if let Some(Kind::NumberValue(n)) = p.default_value.as_ref().and_then(|v| v.kind.as_ref()) {
+        if min.map_or(false, |min| *n < min) || max.map_or(false, |max| *n > max) {
+            errors.push(
+                &format!("{}.defaultValue", field),
+                format!("{} is out of the bounds of the parameter", n),
+            );
+        }
+    }
+}
+
+fn validate_default_value(field: &str, p: &Parameter, errors: &mut Errors) {
+    let kind = match ParameterKind::from_i32(p.parameter_kind) {
+        Some(kind) => kind,
+        None => {
+            return errors.push(
+                &format!("{}.parameterKind", field),
+                format!("unknown parameter kind {}", p.parameter_kind),
+            )
+        }
+    };
+    let value = match p.default_value.as_ref().and_then(|v| v.kind.as_ref()) {
+        None | Some(Kind::NullValue(_)) => return,
+        Some(value) => value,
+    };
+    let field = format!("{}.defaultValue", field);
+    if let Kind::StringValue(s) = value {
+        // config values are only known, and checked against the kind, when inputs are bound
+        if config::has_references(p) {
+            if let Err(e) = config::references(s) {
+                errors.push(&field, e);
+            }
+            return;
+        }
+    }
+    match (kind, value) {
+        (ParameterKind::Generic, _)
+        | (ParameterKind::Number, Kind::NumberValue(_))
+        | (ParameterKind::Boolean, Kind::BoolValue(_))
+        | (ParameterKind::Text, Kind::StringValue(_))
+        | (ParameterKind::Json, Kind::StructValue(_))
+        | (ParameterKind::Json, Kind::ListValue(_))
+        | (ParameterKind::Json, Kind::NumberValue(_))
+        | (ParameterKind::Json, Kind::BoolValue(_)) => {}
+        (ParameterKind::Json, Kind::StringValue(s)) => {
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(s) {
+                errors.push(&field, format!("is not a valid JSON document: {}", e))
+            }
+        }
+        (ParameterKind::Url, Kind::StringValue(s)) => {
+            if let Err(e) = Url::parse(s) {
+                errors.push(&field, format!("is not a valid URL: {}", e))
+            }
+        }
+        (ParameterKind::Path, Kind::StringValue(s)) if !s.is_empty() => {}
+        (kind, value) => errors.push(
+            &field,
+            format!(
+                "expected {} for parameter kind {:?} but got {}",
+                expected_value(kind),
+                kind,
+                describe_value(value)
+            ),
+        ),
+    }
+}
+
+fn expected_value(kind: ParameterKind) -> &'static str {
+    match kind {
+        ParameterKind::Generic | ParameterKind::Json => "any value",
+        ParameterKind::Number => "a number",
+        ParameterKind::Boolean => "a boolean",
+        ParameterKind::Text => "a text",
+        ParameterKind::Url => "a text with an URL",
+        ParameterKind::Path => "a non empty text with a path",
+    }
+}
+
+fn describe_value(value: &Kind) -> String {
+    match value {
+        Kind::NullValue(_) => "null".to_string(),
+        Kind::NumberValue(n) => format!("the number {}", n),
+        Kind::StringValue(s) => format!("the text {:?}", s),
+        Kind::BoolValue(b) => format!("the boolean {}", b),
+        Kind::StructValue(_) => "an object".to_string(),
+        Kind::ListValue(_) => "a list".to_string(),
+    }
+}
+
+fn validate_capabilities(field: &str, capabilities: &[String], errors: &mut Errors) {
+    for (i, c) in capabilities.iter().enumerate() {
+        if c.trim().is_empty() {
+            errors.push(
+                &format!("{}[{}]", field, i),
+                "must not be empty".to_string(),
+            );
+        }
+    }
+}
+
+fn validate_runtime(field: &str, rt: &Runtime, errors: &mut Errors) {
+    validate_capabilities(&format!("{}.capabilities", field), &rt.capabilities, errors);
+    match &rt.runtime_kind {
+        None => errors.push(
+            field,
+            "runtime kind not specified, expected one of: container".to_string(),
+        ),
+        Some(RuntimeKind::Container(c)) => {
+            validate_container(&format!("{}.container", field), c, errors)
+        }
+    }
+}
+
+fn validate_container(field: &str, c: &Container, errors: &mut Errors) {
+    let field = format!("{}.dockerfile", field);
+    let path = Path::new(&c.dockerfile);
+    if c.dockerfile.is_empty() {
+        errors.push(&field, "must not be empty".to_string());
+    } else if path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        errors.push(
+            &field,
+            format!(
+                "{:?} must be a relative path inside the build context",
+                c.dockerfile
+            ),
+        );
+    }
+}
+
+fn validate_dag(field: &str, d: &Dag, errors: &mut Errors) {
+    let mut names = HashSet::new();
+    for (i, n) in d.nodes.iter().enumerate() {
+        let field = format!("{}.nodes[{}]", field, i);
+        if let Err(e) = identifier::check(&n.name) {
+            errors.push(&format!("{}.name", field), e);
+        } else if !names.insert(n.name.as_str()) {
+            errors.push(
+                &format!("{}.name", field),
+                format!("duplicated node name {:?}", n.name),
+            );
+        }
+        match super::parse_resource_id(&n.function) {
+            Ok((_, _, kind, _)) if kind == super::FUNCTION_KIND => {}
+            Ok(_) => errors.push(
+                &format!("{}.function", field),
+                format!("{:?} is not the id of a function", n.function),
+            ),
+            Err(e) => errors.push(&format!("{}.function", field), e),
+        }
+    }
+    let mut inputs = HashSet::new();
+    for (i, e) in d.edges.iter().enumerate() {
+        let field = format!("{}.edges[{}]", field, i);
+        for (name, node) in [("from", &e.from), ("to", &e.to)] {
+            if !names.contains(node.as_str()) {
+                errors.push(
+                    &format!("{}.{}", field, name),
+                    format!("unknown node {:?}", node),
+                );
+            }
+        }
+        for (name, parameter) in [("output", &e.output), ("input", &e.input)] {
+            if parameter.is_empty() {
+                errors.push(
+                    &format!("{}.{}", field, name),
+                    "must not be empty".to_string(),
+                );
+            }
+        }
+        if !inputs.insert((e.to.as_str(), e.input.as_str())) {
+            errors.push(
+                &format!("{}.input", field),
+                format!("input {:?} of node {:?} is already wired", e.input, e.to),
+            );
+        }
+    }
+    if let Err(e) = dag::topological_order(d) {
+        errors.push(&format!("{}.edges", field), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic;
+    use bdaproto::{deployer, trigger, DagEdge, DagNode, Trigger};
+    use pbjson_types::Value;
+
+    fn fields(r: &Resource) -> Vec<String> {
+        match validate(r) {
+            Ok(()) => vec![],
+            Err(e) => e.errors.into_iter().map(|e| e.field).collect(),
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value {
+            kind: Some(Kind::StringValue(s.to_string())),
+        }
+    }
+
+    fn number(n: f64) -> Value {
+        Value {
+            kind: Some(Kind::NumberValue(n)),
+        }
+    }
+
+    #[test]
+    fn test_validate_valid_resources() {
+        let mut f = logic::new_resource_function("function");
+        if let Some(ResourceKind::Function(ref mut f)) = f.resource_kind {
+            f.base_command = vec!["echo".to_string()];
+            f.inputs = vec![
+                logic::new_parameter_with_default("n", ParameterKind::Number, number(6.0)),
+                logic::new_parameter_with_default(
+                    "u",
+                    ParameterKind::Url,
+                    string("https://github.com/gracig/bda"),
+                ),
+                logic::new_parameter_with_default("j", ParameterKind::Json, string(r#"{"a":1}"#)),
+            ];
+        }
+        assert_eq!(fields(&f), Vec::<String>::new());
+        let rt = logic::new_resource_runtime_container("container");
+        assert_eq!(fields(&rt), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_reports_all_errors() {
+        let mut r = logic::new_resource_function("a/b");
+        r.namespace = String::new();
+        if let Some(ResourceKind::Function(ref mut f)) = r.resource_kind {
+            f.inputs = vec![
+                logic::new_parameter_with_default("n", ParameterKind::Number, string("six")),
+                logic::new_parameter_with_default("n", ParameterKind::Url, string("no url")),
+            ];
+        }
+        assert_eq!(
+            fields(&r),
+            vec![
+                ".namespace",
+                ".name",
+                ".function.inputs[0].defaultValue",
+                ".function.inputs[1].name",
+                ".function.inputs[1].defaultValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        let bounded = |kind, default, minimum: Option<f64>, maximum: Option<f64>| Parameter {
+            minimum: minimum.map(number),
+            maximum: maximum.map(number),
+            ..logic::new_parameter_with_default("p", kind, number(default))
+        };
+        let mut r = logic::new_resource_function("function");
+        if let Some(ResourceKind::Function(ref mut f)) = r.resource_kind {
+            f.base_command = vec!["echo".to_string()];
+            f.inputs = vec![
+                bounded(ParameterKind::Number, 1.0, Some(1.0), Some(10.0)),
+                bounded(ParameterKind::Number, 5.0, Some(10.0), Some(1.0)),
+                bounded(ParameterKind::Number, 20.0, None, Some(10.0)),
+                bounded(ParameterKind::Json, 5.0, Some(1.0), None),
+                Parameter {
+                    minimum: Some(string("one")),
+                    ..bounded(ParameterKind::Number, 5.0, None, None)
+                },
+            ];
+            for (i, p) in f.inputs.iter_mut().enumerate() {
+                p.name = format!("p{}", i);
+            }
+        }
+        assert_eq!(
+            fields(&r),
+            vec![
+                ".function.inputs[1].minimum",
+                ".function.inputs[1].defaultValue",
+                ".function.inputs[2].defaultValue",
+                ".function.inputs[3]",
+                ".function.inputs[4].minimum",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_kinds() {
+        let mut r = logic::new_resource_function("name");
+        r.resource_kind = None;
+        assert_eq!(fields(&r), vec!["."]);
+        let mut r = logic::new_resource_runtime_container("name");
+        if let Some(ResourceKind::Runtime(ref mut rt)) = r.resource_kind {
+            rt.runtime_kind = Some(RuntimeKind::Container(Container {
+                dockerfile: "../Dockerfile".to_string(),
+            }));
+        }
+        assert_eq!(fields(&r), vec![".runtime.container.dockerfile"]);
+        if let Some(ResourceKind::Runtime(ref mut rt)) = r.resource_kind {
+            rt.runtime_kind = Some(RuntimeKind::Container(Container {
+                dockerfile: String::new(),
+            }));
+        }
+        assert_eq!(fields(&r), vec![".runtime.container.dockerfile"]);
+        if let Some(ResourceKind::Runtime(ref mut rt)) = r.resource_kind {
+            rt.runtime_kind = None;
+        }
+        assert_eq!(fields(&r), vec![".runtime"]);
+    }
+
+    #[test]
+    fn test_validate_dag() {
+        let node = |name: &str, function: &str| DagNode {
+            name: name.to_string(),
+            function: function.to_string(),
+            inputs: None,
+        };
+        let edge = |from: &str, output: &str, to: &str| DagEdge {
+            from: from.to_string(),
+            output: output.to_string(),
+            to: to.to_string(),
+            input: "in".to_string(),
+        };
+        let mut r = logic::new_resource_dag("pipeline");
+        if let Some(ResourceKind::Dag(ref mut d)) = r.resource_kind {
+            d.nodes = vec![
+                node("build", "/latest/default/function/build"),
+                node("test", "/latest/default/function/test"),
+            ];
+            d.edges = vec![edge("build", "out", "test")];
+        }
+        assert_eq!(fields(&r), Vec::<String>::new());
+        if let Some(ResourceKind::Dag(ref mut d)) = r.resource_kind {
+            d.nodes.push(node("build", "/latest/default/dag/build"));
+            d.nodes.push(node("Bad Name", "build"));
+            d.edges.push(edge("test", "", "build"));
+            d.edges.push(edge("build", "out", "deploy"));
+            d.edges.push(edge("build", "out", "test"));
+        }
+        assert_eq!(
+            fields(&r),
+            vec![
+                ".dag.nodes[2].name",
+                ".dag.nodes[2].function",
+                ".dag.nodes[3].name",
+                ".dag.nodes[3].function",
+                ".dag.edges[1].output",
+                ".dag.edges[2].to",
+                ".dag.edges[3].input",
+                ".dag.edges",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_secrets() {
+        let secret_ref = |secret: &str, key: &str, env: &str, path: &str| SecretRef {
+            secret: secret.to_string(),
+            key: key.to_string(),
+            env: env.to_string(),
+            path: path.to_string(),
+        };
+        let mut r = logic::new_resource_function("deploy");
+        if let Some(ResourceKind::Function(ref mut f)) = r.resource_kind {
+            f.base_command = vec!["deploy".to_string()];
+            f.secrets = vec![
+                secret_ref("/latest/default/secret/registry", "token", "TOKEN", ""),
+                secret_ref("/latest/default/secret/ssh", "key", "", "keys/id_rsa"),
+            ];
+        }
+        assert_eq!(fields(&r), Vec::<String>::new());
+        if let Some(ResourceKind::Function(ref mut f)) = r.resource_kind {
+            f.secrets
+                .push(secret_ref("/latest/default/function/f", "", "1A", ""));
+            f.secrets
+                .push(secret_ref("/latest/default/secret/ssh", "key", "K", "k"));
+            f.secrets
+                .push(secret_ref("/latest/default/secret/ssh", "key", "", "../k"));
+            f.secrets
+                .push(secret_ref("/latest/default/secret/ssh", "key", "TOKEN", ""));
+        }
+        assert_eq!(
+            fields(&r),
+            vec![
+                ".function.secrets[2].secret",
+                ".function.secrets[2].key",
+                ".function.secrets[2].env",
+                ".function.secrets[3]",
+                ".function.secrets[4].path",
+                ".function.secrets[5]",
+            ]
+        );
+
+        let mut s = logic::new_resource_secret("registry");
+        if let Some(ResourceKind::Secret(ref mut s)) = s.resource_kind {
+            s.data.insert("token".to_string(), "s3cr3t".to_string());
+        }
+        assert_eq!(fields(&s), Vec::<String>::new());
+        if let Some(ResourceKind::Secret(ref mut s)) = s.resource_kind {
+            s.data.insert(" ".to_string(), "s3cr3t".to_string());
+        }
+        assert_eq!(fields(&s), vec![".secret.data"]);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let mut c = logic::new_resource_config("deploy");
+        if let Some(ResourceKind::Config(ref mut c)) = c.resource_kind {
+            c.values.insert(
+                "region".to_string(),
+                Value {
+                    kind: Some(Kind::StringValue("eu-west-1".to_string())),
+                },
+            );
+        }
+        assert_eq!(fields(&c), Vec::<String>::new());
+        if let Some(ResourceKind::Config(ref mut c)) = c.resource_kind {
+            c.values.insert("a/b".to_string(), Value { kind: None });
+        }
+        assert_eq!(fields(&c), vec![".config.values.a/b"]);
+
+        let mut f = logic::new_resource_function("deploy");
+        if let Some(ResourceKind::Function(ref mut f)) = f.resource_kind {
+            f.base_command = vec!["deploy".to_string()];
+            f.inputs = vec![
+                logic::new_parameter_with_default(
+                    "replicas",
+                    ParameterKind::Number,
+                    string("${config:deploy/replicas}"),
+                ),
+                logic::new_parameter_with_default(
+                    "url",
+                    ParameterKind::Url,
+                    string("https://${config:deploy/host}/api"),
+                ),
+            ];
+        }
+        assert_eq!(fields(&f), Vec::<String>::new());
+        if let Some(ResourceKind::Function(ref mut f)) = f.resource_kind {
+            f.inputs.push(logic::new_parameter_with_default(
+                "region",
+                ParameterKind::Text,
+                string("${config:deploy}"),
+            ));
+        }
+        assert_eq!(fields(&f), vec![".function.inputs[2].defaultValue"]);
+    }
+
+    #[test]
+    fn test_validate_bundle() {
+        let mut b = logic::new_resource_bundle("ci");
+        assert_eq!(fields(&b), vec![".bundle.semver", ".bundle.resources"]);
+        if let Some(ResourceKind::Bundle(ref mut b)) = b.resource_kind {
+            b.semver = "1.2.0-rc.1".to_string();
+            b.resources = vec![
+                "/latest/default/function/build".to_string(),
+                "/latest/default/config/deploy".to_string(),
+            ];
+        }
+        assert_eq!(fields(&b), vec![".bundle.resources[1]"]);
+    }
+
+    #[test]
+    fn test_validate_listener() {
+        let trigger = |kind: TriggerKind| Trigger {
+            trigger_kind: Some(kind),
+        };
+        let mut l = logic::new_resource_listener("nightly");
+        assert_eq!(fields(&l), vec![".listener.target", ".listener.triggers"]);
+        if let Some(ResourceKind::Listener(ref mut l)) = l.resource_kind {
+            l.target = "/latest/default/dag/pipeline".to_string();
+            l.triggers = vec![
+                trigger(TriggerKind::Cron(trigger::Cron {
+                    schedule: "0 2 * * *".to_string(),
+                })),
+                trigger(TriggerKind::ResourceChange(trigger::ResourceChange {
+                    bql: ".function".to_string(),
+                })),
+                trigger(TriggerKind::PathWatch(trigger::PathWatch {
+                    path: "/data/in".to_string(),
+                })),
+                trigger(TriggerKind::Webhook(trigger::Webhook {
+                    path: "git/push".to_string(),
+                })),
+            ];
+            l.inputs = Some(
+                serde_json::from_value(serde_json::json!({"build.ref": "${event.body.ref}"}))
+                    .unwrap(),
+            );
+        }
+        assert_eq!(fields(&l), Vec::<String>::new());
+        if let Some(ResourceKind::Listener(ref mut l)) = l.resource_kind {
+            l.target = "/latest/default/config/deploy".to_string();
+            l.triggers.push(trigger(TriggerKind::Cron(trigger::Cron {
+                schedule: "0 25 * * *".to_string(),
+            })));
+            l.triggers
+                .push(trigger(TriggerKind::Webhook(trigger::Webhook {
+                    path: "../x".to_string(),
+                })));
+            l.triggers.push(Trigger { trigger_kind: None });
+            l.inputs =
+                Some(serde_json::from_value(serde_json::json!({"ref": "${event.body"})).unwrap());
+        }
+        assert_eq!(
+            fields(&l),
+            vec![
+                ".listener.target",
+                ".listener.triggers[4].cron.schedule",
+                ".listener.triggers[5].webhook.path",
+                ".listener.triggers[6]",
+                ".listener.inputs.ref",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_deployer() {
+        let stage = |name: &str, inputs: serde_json::Value| deployer::Stage {
+            name: name.to_string(),
+            approval: name == "prod",
+            inputs: Some(serde_json::from_value(inputs).unwrap()),
+        };
+        let mut d = logic::new_resource_deployer("web");
+        assert_eq!(fields(&d), vec![".deployer.function", ".deployer.stages"]);
+        if let Some(ResourceKind::Deployer(ref mut d)) = d.resource_kind {
+            d.function = "/latest/default/function/deploy".to_string();
+            d.stages = vec![
+                stage("staging", serde_json::json!({"replicas": 1})),
+                stage("prod", serde_json::json!({"replicas": 3})),
+            ];
+        }
+        assert_eq!(fields(&d), Vec::<String>::new());
+        if let Some(ResourceKind::Deployer(ref mut d)) = d.resource_kind {
+            d.function = "/latest/default/dag/deploy".to_string();
+            d.stages
+                .push(stage("prod", serde_json::json!({"semver": "1.0.0"})));
+        }
+        assert_eq!(
+            fields(&d),
+            vec![
+                ".deployer.function",
+                ".deployer.stages[2].name",
+                ".deployer.stages[2].inputs.semver",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_applier() {
+        let mut a = logic::new_resource_applier("web");
+        assert_eq!(fields(&a), vec![".applier.deployer", ".applier.stages"]);
+        if let Some(ResourceKind::Applier(ref mut a)) = a.resource_kind {
+            a.deployer = "/latest/default/deployer/web".to_string();
+            a.stages
+                .insert("prod".to_string(), "/1.2.0/default/bundle/web".to_string());
+        }
+        assert_eq!(fields(&a), Vec::<String>::new());
+        if let Some(ResourceKind::Applier(ref mut a)) = a.resource_kind {
+            a.stages
+                .insert("dev".to_string(), "/latest/default/dag/web".to_string());
+        }
+        assert_eq!(fields(&a), vec![".applier.stages.dev"]);
+    }
+
+    #[test]
+    fn test_validate_references() {
+        let reference = |kind: &str, namespace: &str, name: &str, version: &str| ResourceRef {
+            kind: kind.to_string(),
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        let mut c = logic::new_resource_config("deploy");
+        c.references = vec![
+            reference("runtime.container", "", "builder", ""),
+            reference("Function", "ci", "build", "^1.2"),
+        ];
+        assert_eq!(fields(&c), Vec::<String>::new());
+        c.references.push(reference("unknown", "", "x", ""));
+        c.references.push(reference("function", "", "", ""));
+        c.references.push(reference("function", "", "build", "^x"));
+        assert_eq!(
+            fields(&c),
+            vec![".references[2]", ".references[3]", ".references[4]"]
+        );
+    }
+
+    #[test]
+    fn test_validate_labels() {
+        let mut c = logic::new_resource_config("deploy");
+        c.labels = HashMap::from([
+            ("env".to_string(), "prod".to_string()),
+            ("app.tier".to_string(), "".to_string()),
+        ]);
+        assert_eq!(fields(&c), Vec::<String>::new());
+        c.labels.insert("Team".to_string(), "ci".to_string());
+        c.labels.insert("zone".to_string(), "eu west".to_string());
+        assert_eq!(fields(&c), vec![".labels.Team", ".labels.zone"]);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{identifier, resource_kind_to_string, DEFAULT_VERSION};
+use bdaproto::Resource;
+use semver::{Version, VersionReq};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Tells whether a version selector names a single version, as opposed to blank or `latest`,
+/// resolving to the highest release, and to ranges like `^1.2` or `>=1.0, <2.0`.
+pub fn is_exact(selector: &str) -> bool {
+    let s = identifier::normalize(selector);
+    !(s.is_empty()
+        || s == DEFAULT_VERSION
+        || (Version::parse(&s).is_err() && VersionReq::parse(&s).is_ok()))
+}
+
+/// Checks that a version selector is either a valid version identifier or a semver range.
+pub fn check_selector(selector: &str) -> Result<(), String> {
+    let s = identifier::normalize(selector);
+    match identifier::check(&s) {
+        Ok(()) => Ok(()),
+        Err(e) => VersionReq::parse(&s)
+            .map(|_| ())
+            .map_err(|_| format!("{}, and is not a semver range", e)),
+    }
+}
+
+/// Resolves a version selector among the versions a resource is stored at:
+/// - blank and `latest` resolve to the highest semver version without prerelease, or to the
+///   `latest` version for resources stored before versions were semver;
+/// - a stored version resolves to itself, and a full semver version matches only itself;
+/// - a range resolves to the highest semver version it matches, partial versions like `1.2`
+///   being read as `^1.2`.
+pub fn resolve<'a, I>(selector: &str, versions: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let s = identifier::normalize(selector);
+    let versions: Vec<&str> = versions.into_iter().collect();
+    let highest = |matching: &dyn Fn(&Version) -> bool| {
+        versions
+            .iter()
+            .filter_map(|v| Version::parse(v).ok().map(|parsed| (parsed, *v)))
+            .filter(|(parsed, _)| matching(parsed))
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, v)| v.to_string())
+    };
+    if s.is_empty() || s == DEFAULT_VERSION {
+        return highest(&|v| v.pre.is_empty()).or_else(|| {
+            versions
+                .iter()
+                .find(|v| **v == DEFAULT_VERSION)
+                .map(|v| v.to_string())
+        });
+    }
+    if versions.contains(&s.as_str()) {
+        return Some(s);
+    }
+    if Version::parse(&s).is_ok() {
+        return None;
+    }
+    let req = VersionReq::parse(&s).ok()?;
+    highest(&|v| req.matches(v))
+}
+
+/// Keeps, for every resource name, the resources at the version the selector resolves to among
+/// the versions found in `resources`. The order of the resources is kept.
+pub fn select(resources: Vec<Resource>, selector: &str) -> Vec<Resource> {
+    let mut versions: BTreeMap<(String, String, String), Vec<String>> = BTreeMap::new();
+    for r in resources.iter() {
+        versions.entry(key(r)).or_default().push(r.version.clone());
+    }
+    let resolved: BTreeMap<_, _> = versions
+        .into_iter()
+        .filter_map(|(k, vs)| resolve(selector, vs.iter().map(|v| v.as_str())).map(|v| (k, v)))
+        .collect();
+    resources
+        .into_iter()
+        .filter(|r| resolved.get(&key(r)) == Some(&r.version))
+        .collect()
+}
+
+/// Sorts versions, semver versions first in semver order, then the others alphabetically.
+pub fn sort(versions: &mut [String]) {
+    versions.sort_by(|a, b| match (Version::parse(a), Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    })
+}
+
+fn key(r: &Resource) -> (String, String, String) {
+    (
+        r.namespace.clone(),
+        resource_kind_to_string(r).unwrap_or_default(),
+        r.name.clone(),
+    )
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::logic;
+
+    const VERSIONS: [&str; 6] = ["1.0.0", "1.2.0", "1.10.1", "2.0.0-rc.1", "latest", "dev"];
+
+    #[test]
+    fn test_resolve() {
+        let resolve = |s: &str| resolve(s, VERSIONS);
+        assert_eq!(resolve(""), Some("1.10.1".to_string()));
+        assert_eq!(resolve("latest"), Some("1.10.1".to_string()));
+        assert_eq!(resolve("dev"), Some("dev".to_string()));
+        assert_eq!(resolve("1.2.0"), Some("1.2.0".to_string()));
+        assert_eq!(resolve("1.3.0"), None);
+        assert_eq!(resolve("^1.2"), Some("1.10.1".to_string()));
+        assert_eq!(resolve("~1.2"), Some("1.2.0".to_string()));
+        assert_eq!(resolve("1"), Some("1.10.1".to_string()));
+        assert_eq!(resolve(">=1.0, <1.5"), Some("1.2.0".to_string()));
+        assert_eq!(resolve("^2.0.0-rc.0"), Some("2.0.0-rc.1".to_string()));
+        assert_eq!(resolve("^3"), None);
+        assert_eq!(resolve("other"), None);
+        assert_eq!(
+            super::resolve("latest", ["latest", "dev"]),
+            Some("latest".to_string())
+        );
+        assert_eq!(super::resolve("latest", ["dev"]), None);
+    }
+
+    #[test]
+    fn test_is_exact() {
+        assert!(is_exact("1.2.0"));
+        assert!(is_exact("dev"));
+        assert!(!is_exact(""));
+        assert!(!is_exact("Latest"));
+        assert!(!is_exact("^1.2"));
+        assert!(!is_exact("1.2"));
+        assert!(check_selector("^1.2").is_ok());
+        assert!(check_selector(">=1.0, <2.0").is_ok());
+        assert!(check_selector("dev").is_ok());
+        assert!(check_selector("^dev").is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let at = |name: &str, version: &str| {
+            let mut r = logic::new_resource_function(name);
+            r.version = version.to_string();
+            r
+        };
+        let mut dag = logic::new_resource_dag("build");
+        dag.version = "1.0.0".to_string();
+        let resources = vec![
+            at("build", "1.0.0"),
+            at("build", "1.1.0"),
+            dag.clone(),
+            at("test", "latest"),
+            at("deploy", "0.1.0"),
+        ];
+        assert_eq!(
+            select(resources.clone(), ""),
+            vec![
+                at("build", "1.1.0"),
+                dag.clone(),
+                at("test", "latest"),
+                at("deploy", "0.1.0")
+            ]
+        );
+        assert_eq!(select(resources, "~1.0"), vec![at("build", "1.0.0"), dag]);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut versions: Vec<String> = VERSIONS.iter().map(|v| v.to_string()).collect();
+        sort(&mut versions);
+        assert_eq!(
+            versions,
+            vec!["1.0.0", "1.2.0", "1.10.1", "2.0.0-rc.1", "dev", "latest"]
+        );
+    }
+}
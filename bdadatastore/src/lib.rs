@@ -1,24 +1,39 @@
 use bdacore::data::datastore::mem::MemDatastore;
-use bdacore::data::query::Query;
-use bdacore::data::{self, EntityKind};
+use bdacore::data::query::{self, Query};
+use bdacore::data::{self, EntityID, EntityKind, Referenced};
+use bdacore::logic::reference;
+use bdacore::logic::secret::SecretKey;
+use bdacore::logic::validation::ValidationErrors;
 use bdacore::{self, logic};
 use bdaindex::backend::llrb::LLRBBackend;
 use bdaindex::backend::lmdb::LMDBBackend;
 use bdaindex::backend::Backend;
+use bdaindex::policy::IndexPolicy;
 use bdaproto::bda_server::Bda;
+use bdaproto::resource_revision::Operation;
 use bdaproto::{
-    self, DelResourceRequest, DelResourceResponse, DelResourcesRequest, GetKindsRequest,
-    GetKindsResponse, GetNamespacesRequest, GetNamespacesResponse, GetResourceRequest,
-    GetResourcesRequest, GetResourcesResponse, GetVersionsRequest, GetVersionsResponse,
-    PutResourceRequest, PutResourceResponse, Resource,
+    self, DelResourceRequest, DelResourceResponse, DelResourcesRequest, DeletePolicy,
+    GetKindsRequest, GetKindsResponse, GetNamespacesRequest, GetNamespacesResponse,
+    GetResourceHistoryResponse, GetResourceRequest, GetResourcesRequest, GetResourcesResponse,
+    GetSecretRequest, GetVersionsRequest, GetVersionsResponse, InstalledResource,
+    ListInstalledRequest, ListInstalledResponse, PutResourceRequest, PutResourceResponse, Resource,
+    ResourceEvent, ResourceRevision, ResourceVersions, Secret, WatchResourcesRequest,
 };
+use pbjson_types::Timestamp;
+use std::collections::BTreeSet;
+use std::error::Error;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{async_trait, Response, Status};
 
+/// Number of resource events buffered for each watcher, a watcher lagging further behind is
+/// disconnected.
+const WATCH_BUFFER: usize = 256;
+
 fn _lmdb() -> impl Backend {
     let s = shellexpand::tilde("~/.bda/index").to_string();
     let path = Path::new(&s);
@@ -30,22 +45,123 @@ fn _llrb() -> impl Backend {
 }
 
 pub struct BDADatastoreService {
-    data: Arc<bdacore::data::Data>,
+    data: bdacore::data::Data,
+    events: broadcast::Sender<ResourceEvent>,
 }
 impl BDADatastoreService {
     pub fn new_mem() -> BDADatastoreService {
+        Self::new_mem_with_index_policy(IndexPolicy::default())
+    }
+    pub fn new_mem_with_index_policy(policy: IndexPolicy) -> BDADatastoreService {
+        Self::new_mem_with_index_policy_config(policy, None)
+    }
+    /// The index policy is read from the config resource `config`, when given, each time it is
+    /// put, and is `policy` until then and once the config is deleted.
+    pub fn new_mem_with_index_policy_config(
+        policy: IndexPolicy,
+        config: Option<EntityID>,
+    ) -> BDADatastoreService {
+        let datastore = MemDatastore::with_index_policy(Arc::new(_lmdb()), policy);
+        let datastore = match config {
+            Some(id) => datastore.with_policy_config(id),
+            None => datastore,
+        };
         BDADatastoreService {
-            data: Arc::new(data::new(Arc::new(MemDatastore::new(Arc::new(_lmdb()))))),
+            data: data::new(Arc::new(datastore)),
+            events: broadcast::channel(WATCH_BUFFER).0,
         }
     }
+
+    /// Encrypts the values of secrets with the key, without which secrets cannot be put.
+    pub fn with_secret_key(mut self, secret_key: Arc<SecretKey>) -> Self {
+        self.data = self.data.with_secret_key(secret_key);
+        self
+    }
+
+    /// Reads an index policy from a YAML or JSON file.
+    pub fn load_index_policy(path: &Path) -> Result<IndexPolicy, Box<dyn Error>> {
+        let s = fs::read_to_string(path)?;
+        serde_yaml::from_str(&s)
+            .map_err(|e| format!("invalid index policy {}: {}", path.display(), e).into())
+    }
+
+    /// Moves resources stored with non canonical identifiers to their canonical ID.
+    pub fn migrate_identifiers(&self) -> Result<data::IdentifierMigration, Box<dyn Error>> {
+        self.data.migrate_identifiers()
+    }
+
+    /// Keeps the resources referencing the resource `references`, and the ones referenced by the
+    /// resource `referencedBy`, when the request gives them.
+    fn related(
+        &self,
+        mut rs: Vec<Resource>,
+        request: &GetResourcesRequest,
+    ) -> Result<Vec<Resource>, Status> {
+        for (id, referencing) in [(&request.references, true), (&request.referenced_by, false)] {
+            if id.is_empty() {
+                continue;
+            }
+            let x = reference::parse(id).map_err(Status::invalid_argument)?;
+            let target = self
+                .data
+                .get_resource_at(&x.version, &x.namespace, &x.kind, &x.name)
+                .map_err(|e| Status::internal(e.to_string()))?
+                .ok_or_else(|| Status::not_found(format!("entity not found: {}", id)))?;
+            let related = if referencing {
+                self.data.referencing(&target)
+            } else {
+                self.data.referenced(&target)
+            }
+            .map_err(|e| Status::internal(e.to_string()))?;
+            let ids: BTreeSet<EntityID> = related
+                .iter()
+                .filter_map(|r| logic::resource_id(r).ok())
+                .collect();
+            rs.retain(|r| logic::resource_id(r).map_or(false, |id| ids.contains(&id)));
+        }
+        Ok(rs)
+    }
+
+    /// Deletes resources with a delete policy, sending the deletes to the watchers and returning
+    /// their number.
+    fn delete(&self, ids: &[EntityID], policy: i32) -> Result<i32, Status> {
+        let policy = DeletePolicy::from_i32(policy)
+            .ok_or_else(|| Status::invalid_argument(format!("invalid delete policy {}", policy)))?;
+        let ops =
+            self.data
+                .delete(ids, policy)
+                .map_err(|e| match e.downcast_ref::<Referenced>() {
+                    Some(e) => Status::failed_precondition(e.to_string()),
+                    None => Status::internal(e.to_string()),
+                })?;
+        for op in ops.iter() {
+            self.notify(op);
+        }
+        Ok(ops.len() as i32)
+    }
+
+    /// Sends a write to the watchers, if any.
+    fn notify(&self, op: &data::Op) {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let _ = self.events.send(op_to_event(op, millis));
+    }
 }
 
 #[async_trait]
 impl Bda for BDADatastoreService {
     async fn get_versions(
         &self,
-        _request: tonic::Request<GetVersionsRequest>,
+        request: tonic::Request<GetVersionsRequest>,
     ) -> Result<tonic::Response<GetVersionsResponse>, Status> {
+        let query = Query::from_get_versions_request(request.get_ref())
+            .map_err(tonic::Status::invalid_argument)?;
+        let resources = self
+            .data
+            .versions(&query)
+            .map_err(|e| Status::internal(e.to_string()))?;
         self.data
             .values_as_string(&EntityKind::Resource, ".version")
             .and_then(|mut iter| {
@@ -56,6 +172,16 @@ impl Bda for BDADatastoreService {
                             Ok(acc)
                         })
                     })?,
+                    resources: resources
+                        .into_iter()
+                        .map(|v| ResourceVersions {
+                            namespace: v.namespace,
+                            kind: v.kind,
+                            name: v.name,
+                            versions: v.versions,
+                            latest: v.latest.unwrap_or_default(),
+                        })
+                        .collect(),
                 }))
             })
             .map_err(|e| Status::internal(e.to_string()))
@@ -97,10 +223,11 @@ impl Bda for BDADatastoreService {
             .map_err(|e| tonic::Status::internal(e.to_string()))
             .and_then(|ref query| {
                 self.data
-                    .resources(&query)
-                    .and_then(|rs| Ok(Response::new(GetResourcesResponse { resources: rs })))
+                    .resources_at(&query, &request.get_ref().version)
                     .map_err(|e| tonic::Status::internal(e.to_string()))
             })
+            .and_then(|rs| self.related(rs, request.get_ref()))
+            .map(|rs| Response::new(GetResourcesResponse { resources: rs }))
     }
 
     type StreamResourcesStream = ReceiverStream<Result<Resource, Status>>;
@@ -113,10 +240,10 @@ impl Bda for BDADatastoreService {
             .map_err(|e| tonic::Status::internal(e.to_string()))
             .and_then(|ref query| {
                 self.data
-                    .resources(&query)
-                    .and_then(|rs| Ok(rs))
+                    .resources_at(&query, &request.get_ref().version)
                     .map_err(|e| tonic::Status::internal(e.to_string()))
-            })?;
+            })
+            .and_then(|rs| self.related(rs, request.get_ref()))?;
         tokio::spawn(async move {
             for item in items {
                 if let Err(e) = tx.send(Ok(item)).await {
@@ -132,70 +259,42 @@ impl Bda for BDADatastoreService {
         &self,
         request: tonic::Request<DelResourcesRequest>,
     ) -> Result<tonic::Response<DelResourceResponse>, tonic::Status> {
-        Query::from_del_resources_request(request.get_ref())
+        let ids = Query::from_del_resources_request(request.get_ref())
             .map_err(|e| tonic::Status::internal(e.to_string()))
             .and_then(|ref query| {
                 self.data
-                    .search(&query)
+                    .resources_at(&query, &request.get_ref().version)
                     .map_err(|e| tonic::Status::internal(e.to_string()))
-            })
-            .and_then(|mut iter| {
-                iter.try_fold(0 as i32, |acc, ri| {
-                    ri.and_then(|ref id| {
-                        self.data.del(id).and_then(|op| {
-                            if let Some(bdacore::data::Op::Delete { .. }) = op {
-                                Ok(acc + 1)
-                            } else {
-                                Ok(acc)
-                            }
-                        })
-                    })
-                })
-                .map_err(|e| tonic::Status::internal(e.to_string()))
-            })
-            .and_then(|updates| Ok(Response::new(DelResourceResponse { updates })))
+            })?
+            .iter()
+            .map(logic::resource_id)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(tonic::Status::internal)?;
+        self.delete(&ids, request.get_ref().policy)
+            .map(|updates| Response::new(DelResourceResponse { updates }))
     }
 
     async fn get_resource(
         &self,
         request: tonic::Request<GetResourceRequest>,
     ) -> Result<tonic::Response<Resource>, tonic::Status> {
-        logic::resource_id_from_get_request(request.get_ref())
-            .map_err(|e| tonic::Status::internal(e.to_string()))
-            .and_then(|ref id| {
-                self.data
-                    .get_resource(id)
-                    .map_err(|e| tonic::Status::internal(e.to_string()))
-            })
-            .and_then(|r| {
-                r.ok_or(Err(tonic::Status::internal(format!(
-                    "entity not found: {:?}",
-                    request.get_ref()
-                )))?)
-            })
-            .and_then(|r| Ok(Response::new(r)))
-            .map_err(|e| tonic::Status::internal(e.to_string()))
+        let r = request.get_ref();
+        logic::check_get_request(r).map_err(tonic::Status::invalid_argument)?;
+        self.data
+            .get_resource_at(&r.version, &r.namespace, &r.kind, &r.name)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .ok_or_else(|| tonic::Status::not_found(format!("entity not found: {:?}", r)))
+            .map(Response::new)
     }
 
     async fn del_resource(
         &self,
         request: tonic::Request<DelResourceRequest>,
     ) -> Result<tonic::Response<DelResourceResponse>, tonic::Status> {
-        logic::resource_id_from_del_request(request.get_ref())
-            .map_err(|e| tonic::Status::internal(e.to_string()))
-            .and_then(|id| Ok(self.data.del(&id)))
-            .and_then(|rop| {
-                rop.map_err(|e| tonic::Status::internal(e.to_string()))
-                    .and_then(|op| {
-                        if let Some(bdacore::data::Op::Delete { .. }) = op {
-                            Ok(1)
-                        } else {
-                            Ok(0)
-                        }
-                    })
-            })
-            .and_then(|updates| Ok(Response::new(DelResourceResponse { updates })))
-            .map_err(|e| tonic::Status::internal(e.to_string()))
+        let id = logic::resource_id_from_del_request(request.get_ref())
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        self.delete(&[id], request.get_ref().policy)
+            .map(|updates| Response::new(DelResourceResponse { updates }))
     }
 
     async fn put_resource(
@@ -211,13 +310,172 @@ impl Bda for BDADatastoreService {
             .and_then(|r| {
                 self.data
                     .put_resource(r)
-                    .map_err(|e| tonic::Status::internal(e.to_string()))
+                    .map_err(|e| match e.downcast_ref::<ValidationErrors>() {
+                        Some(e) => tonic::Status::invalid_argument(e.to_string()),
+                        None => tonic::Status::internal(e.to_string()),
+                    })
                     .and_then(|x| match x {
-                        Some(bdacore::data::Op::Create { .. }) => Ok(1),
-                        Some(bdacore::data::Op::Update { .. }) => Ok(1),
+                        Some(ref op @ bdacore::data::Op::Create { .. })
+                        | Some(ref op @ bdacore::data::Op::Update { .. }) => {
+                            self.notify(op);
+                            Ok(1)
+                        }
                         _ => Ok(0),
                     })
                     .and_then(|updates| Ok(Response::new(PutResourceResponse { updates })))
             })
     }
+
+    async fn get_resource_history(
+        &self,
+        request: tonic::Request<GetResourceRequest>,
+    ) -> Result<tonic::Response<GetResourceHistoryResponse>, tonic::Status> {
+        let r = request.get_ref();
+        logic::check_get_request(r).map_err(tonic::Status::invalid_argument)?;
+        // deleted resources keep their history at the version named by the request
+        let version = self
+            .data
+            .resolve_version(&r.version, &r.namespace, &r.kind, &r.name)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .unwrap_or_else(|| r.version.clone());
+        let id = logic::resource_id_builder(&version, &r.namespace, &r.kind, &r.name)
+            .map_err(|_| tonic::Status::not_found(format!("entity not found: {:?}", r)))?;
+        let history = self
+            .data
+            .history(&id)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if history.is_empty() {
+            return Err(tonic::Status::not_found(format!(
+                "entity not found: {:?}",
+                request.get_ref()
+            )));
+        }
+        Ok(Response::new(GetResourceHistoryResponse {
+            revisions: history.into_iter().map(revision_to_proto).collect(),
+        }))
+    }
+
+    async fn list_installed(
+        &self,
+        request: tonic::Request<ListInstalledRequest>,
+    ) -> Result<tonic::Response<ListInstalledResponse>, tonic::Status> {
+        self.data
+            .installed(&request.get_ref().namespaces)
+            .map_err(|e| tonic::Status::internal(e.to_string()))
+            .map(|installed| {
+                Response::new(ListInstalledResponse {
+                    resources: installed
+                        .into_iter()
+                        .map(|i| InstalledResource {
+                            resource: i.resource,
+                            bundle: i.bundle,
+                            semver: i.semver,
+                        })
+                        .collect(),
+                })
+            })
+    }
+
+    type WatchResourcesStream = ReceiverStream<Result<ResourceEvent, Status>>;
+    async fn watch_resources(
+        &self,
+        request: tonic::Request<WatchResourcesRequest>,
+    ) -> Result<tonic::Response<Self::WatchResourcesStream>, tonic::Status> {
+        let filter = match request.get_ref().bql.as_str() {
+            "" => None,
+            bql => Some(bdaindex::bql::from_str(bql).map_err(tonic::Status::invalid_argument)?),
+        };
+        let mut events = self.events.subscribe();
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        let status =
+                            Status::data_loss(format!("{} resource events missed", missed));
+                        let _ = tx.send(Err(status)).await;
+                        return;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                let matched = match (&filter, &event.resource) {
+                    (None, _) => Ok(true),
+                    (Some(ast), Some(r)) => query::matches(ast, r).map_err(|e| e.to_string()),
+                    (Some(_), None) => Ok(false),
+                };
+                let sent = match matched {
+                    Ok(false) => continue,
+                    Ok(true) => tx.send(Ok(event)).await,
+                    Err(e) => tx.send(Err(Status::internal(e))).await,
+                };
+                if sent.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn get_secret(
+        &self,
+        request: tonic::Request<GetSecretRequest>,
+    ) -> Result<tonic::Response<Secret>, tonic::Status> {
+        logic::resource_id_from_get_secret_request(request.get_ref())
+            .map_err(tonic::Status::invalid_argument)
+            .and_then(|ref id| {
+                self.data
+                    .get_secret(id)
+                    .map_err(|e| tonic::Status::internal(e.to_string()))
+            })
+            .and_then(|s| {
+                s.ok_or_else(|| {
+                    tonic::Status::not_found(format!("secret not found: {:?}", request.get_ref()))
+                })
+            })
+            .map(Response::new)
+    }
+}
+
+/// Event sent to the watchers for a write, with the values of secrets redacted. Deletes carry the
+/// deleted resource.
+pub fn op_to_event(op: &data::Op, millis: u64) -> ResourceEvent {
+    let (operation, data::Entity::Resource(_, resource)) = match op {
+        data::Op::Create { new } => (Operation::Create, new),
+        data::Op::Update { new, .. } => (Operation::Update, new),
+        data::Op::Delete { old, .. } => (Operation::Delete, old),
+    };
+    let mut resource = resource.clone();
+    logic::secret::redact(&mut resource);
+    ResourceEvent {
+        operation: operation as i32,
+        resource: Some(resource),
+        time: Some(timestamp(millis)),
+    }
+}
+
+fn timestamp(millis: u64) -> Timestamp {
+    Timestamp {
+        seconds: (millis / 1000) as i64,
+        nanos: ((millis % 1000) * 1_000_000) as i32,
+    }
+}
+
+pub fn revision_to_proto(r: data::Revision) -> ResourceRevision {
+    let (operation, resource) = match r.op {
+        data::Op::Create {
+            new: data::Entity::Resource(_, new),
+        } => (Operation::Create, Some(new)),
+        data::Op::Update {
+            new: data::Entity::Resource(_, new),
+            ..
+        } => (Operation::Update, Some(new)),
+        data::Op::Delete { .. } => (Operation::Delete, None),
+    };
+    ResourceRevision {
+        revision: r.revision,
+        time: Some(timestamp(r.time)),
+        operation: operation as i32,
+        resource,
+    }
 }
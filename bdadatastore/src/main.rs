@@ -1,20 +1,38 @@
+use bdacore::data::EntityID;
+use bdacore::logic::{self, secret::SecretKey};
 use bdadatastore::BDADatastoreService;
 use bdaproto::bda_server::BdaServer;
 use clap::{ArgEnum, Parser};
-use std::{error::Error, net::SocketAddr};
+use std::{error::Error, net::SocketAddr, path::PathBuf, sync::Arc};
 use tonic::transport::Server;
 
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
     let ref cfg = Config::parse();
     println!("{:?}", cfg);
+    let secret_key = shellexpand::tilde(&cfg.secret_key.to_string_lossy()).to_string();
+    let secret_key = Arc::new(SecretKey::load_or_create(&PathBuf::from(secret_key))?);
     Some(cfg.backend)
         .map(|b| match b {
-            DatastoreType::Mem => BDADatastoreService::new_mem(),
+            DatastoreType::Mem => cfg
+                .index_policy
+                .as_ref()
+                .map(|path| BDADatastoreService::load_index_policy(path))
+                .transpose()
+                .and_then(|policy| {
+                    Ok(BDADatastoreService::new_mem_with_index_policy_config(
+                        policy.unwrap_or_default(),
+                        index_policy_config(&cfg.index_policy_config)?,
+                    ))
+                }),
             DatastoreType::Etcd => todo!(),
             DatastoreType::Redis => todo!(),
             DatastoreType::File => todo!(),
         })
+        .transpose()?
+        .map(|bsvc| bsvc.with_secret_key(secret_key))
+        .map(|bsvc| migrate_identifiers(&bsvc).map(|_| bsvc))
+        .transpose()?
         .and_then(|bsvc| Some(BdaServer::new(bsvc)))
         .and_then(|svc| Some(Server::builder().add_service(svc).serve(cfg.address)))
         .ok_or("could not build server")?
@@ -22,6 +40,35 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Resource id of the config the index policy is read from, which must name a config.
+fn index_policy_config(id: &Option<String>) -> Result<Option<EntityID>, Box<dyn Error>> {
+    let id = match id {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let (version, namespace, kind, name) = logic::parse_resource_id(id)?;
+    if kind != logic::CONFIG_KIND {
+        Err(format!("index policy resource {} is not a config", id))?
+    }
+    Ok(Some(logic::resource_id_builder(
+        &version, &namespace, &kind, &name,
+    )?))
+}
+
+fn migrate_identifiers(bsvc: &BDADatastoreService) -> Result<(), Box<dyn Error>> {
+    let migration = bsvc.migrate_identifiers()?;
+    for (old, new) in migration.renamed.iter() {
+        println!("migrated {:?} to {:?}", old, new);
+    }
+    for (old, new) in migration.conflicts.iter() {
+        eprintln!("could not migrate {:?}: {:?} already exists", old, new);
+    }
+    for (id, e) in migration.invalid.iter() {
+        eprintln!("could not migrate {:?}: {}", id, e);
+    }
+    Ok(())
+}
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Config {
@@ -29,6 +76,16 @@ struct Config {
     address: SocketAddr,
     #[clap(arg_enum, short, long, default_value = "mem")]
     backend: DatastoreType,
+    /// YAML or JSON file selecting the fields to index
+    #[clap(short, long)]
+    index_policy: Option<PathBuf>,
+    /// Config resource, like /latest/default/config/index-policy, whose values replace the index
+    /// policy each time it is put, the resources being reindexed
+    #[clap(long)]
+    index_policy_config: Option<String>,
+    /// File holding the key encrypting the values of secrets, created when missing
+    #[clap(long, default_value = "~/.bda/secret.key")]
+    secret_key: PathBuf,
 }
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
 enum DatastoreType {
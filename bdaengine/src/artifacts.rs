@@ -0,0 +1,382 @@
+use crate::executor::{self, RunRecord};
+use bdacore::logic::binding::{self, ARTIFACT_PREFIX};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// File or directory of the store, named by the sha256 digest of its content. The content of a
+/// directory is its tree, the JSON list of its entries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    pub digest: String,
+    /// Size of the content, the tree of a directory.
+    pub size: u64,
+    pub directory: bool,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub name: String,
+    pub digest: String,
+    pub directory: bool,
+}
+
+/// Content-addressed store of the files produced and consumed by runs. Objects are stored as
+/// `objects/<first 2 digits>/<digest>`, next to their description `<digest>.json`.
+pub struct ArtifactStore {
+    dir: PathBuf,
+}
+
+impl ArtifactStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        ArtifactStore { dir: dir.into() }
+    }
+
+    fn object_path(&self, digest: &str) -> PathBuf {
+        self.dir
+            .join("objects")
+            .join(&digest[..2.min(digest.len())])
+            .join(digest)
+    }
+
+    fn meta_path(&self, digest: &str) -> PathBuf {
+        self.object_path(digest).with_extension("json")
+    }
+
+    /// Stores a file, or a directory with everything below it.
+    pub fn put(&self, path: &Path) -> io::Result<Artifact> {
+        if !fs::metadata(path)?.is_dir() {
+            return self.put_file(path);
+        }
+        let mut names: Vec<PathBuf> = fs::read_dir(path)?
+            .map(|e| e.map(|e| e.path()))
+            .collect::<io::Result<_>>()?;
+        names.sort();
+        let mut tree = Vec::new();
+        for entry in names {
+            let artifact = self.put(&entry)?;
+            tree.push(TreeEntry {
+                name: entry
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                digest: artifact.digest,
+                directory: artifact.directory,
+            });
+        }
+        let content = serde_json::to_vec(&tree)?;
+        let tmp = self.tmp_path()?;
+        fs::write(&tmp, &content)?;
+        self.commit(&tmp, &format!("{:x}", Sha256::digest(&content)), true)
+    }
+
+    /// Copies a file to the store, computing its digest on the way.
+    fn put_file(&self, path: &Path) -> io::Result<Artifact> {
+        let tmp = self.tmp_path()?;
+        let mut source = fs::File::open(path)?;
+        let mut target = fs::File::create(&tmp)?;
+        let mut hasher = Sha256::new();
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = source.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            target.write_all(&buf[..n])?;
+        }
+        target.sync_all()?;
+        self.commit(&tmp, &format!("{:x}", hasher.finalize()), false)
+    }
+
+    fn tmp_path(&self) -> io::Result<PathBuf> {
+        let dir = self.dir.join("tmp");
+        fs::create_dir_all(&dir)?;
+        Ok(dir.join(executor::new_run_id()))
+    }
+
+    /// Moves a written object to its place, unless the store already holds it.
+    fn commit(&self, tmp: &Path, digest: &str, directory: bool) -> io::Result<Artifact> {
+        if let Some(artifact) = self.get(digest) {
+            fs::remove_file(tmp)?;
+            return Ok(artifact);
+        }
+        let path = self.object_path(digest);
+        fs::create_dir_all(path.parent().unwrap_or(&self.dir))?;
+        let artifact = Artifact {
+            digest: digest.to_string(),
+            size: fs::metadata(tmp)?.len(),
+            directory,
+            created_at: executor::now(),
+        };
+        fs::rename(tmp, &path)?;
+        fs::write(
+            self.meta_path(digest),
+            serde_json::to_vec_pretty(&artifact)?,
+        )?;
+        Ok(artifact)
+    }
+
+    pub fn get(&self, digest: &str) -> Option<Artifact> {
+        if !is_digest(digest) || !self.object_path(digest).exists() {
+            return None;
+        }
+        let content = fs::read(self.meta_path(digest)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Entries of a directory.
+    pub fn tree(&self, digest: &str) -> io::Result<Vec<TreeEntry>> {
+        let content = fs::read(self.object_path(digest))?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    /// Files of an artifact with their path relative to it, the path of a file being empty,
+    /// and the object holding their content. Directories have no object, so empty ones are
+    /// listed too.
+    pub fn files(&self, digest: &str) -> io::Result<Vec<(PathBuf, Option<PathBuf>)>> {
+        let artifact = self.get(digest).ok_or_else(|| not_found(digest))?;
+        let mut files = Vec::new();
+        self.walk(
+            &artifact.digest,
+            artifact.directory,
+            PathBuf::new(),
+            &mut files,
+        )?;
+        Ok(files)
+    }
+
+    fn walk(
+        &self,
+        digest: &str,
+        directory: bool,
+        path: PathBuf,
+        files: &mut Vec<(PathBuf, Option<PathBuf>)>,
+    ) -> io::Result<()> {
+        if !directory {
+            files.push((path, Some(self.object_path(digest))));
+            return Ok(());
+        }
+        files.push((path.clone(), None));
+        for entry in self.tree(digest)? {
+            self.walk(
+                &entry.digest,
+                entry.directory,
+                path.join(&entry.name),
+                files,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Copies an artifact to a path, which must not exist.
+    pub fn materialize(&self, digest: &str, target: &Path) -> io::Result<Artifact> {
+        let artifact = self.get(digest).ok_or_else(|| not_found(digest))?;
+        for (path, object) in self.files(digest)? {
+            let path = if path.as_os_str().is_empty() {
+                target.to_path_buf()
+            } else {
+                target.join(path)
+            };
+            match object {
+                None => fs::create_dir_all(&path)?,
+                Some(object) => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(object, &path)?;
+                }
+            }
+        }
+        Ok(artifact)
+    }
+
+    /// Artifacts, most recent first.
+    pub fn list(&self) -> io::Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+        let prefixes = match fs::read_dir(self.dir.join("objects")) {
+            Ok(prefixes) => prefixes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(artifacts),
+            Err(e) => return Err(e),
+        };
+        for prefix in prefixes {
+            for entry in fs::read_dir(prefix?.path())? {
+                let path = entry?.path();
+                if path.extension().map_or(false, |ext| ext == "json") {
+                    let content = fs::read(&path)?;
+                    artifacts.push(serde_json::from_slice(&content)?);
+                }
+            }
+        }
+        artifacts
+            .sort_by(|a: &Artifact, b| (b.created_at, &a.digest).cmp(&(a.created_at, &b.digest)));
+        Ok(artifacts)
+    }
+
+    /// Removes the artifacts older than `min_age` milliseconds that are neither referenced
+    /// nor below a referenced directory, returning their digests. The age keeps the artifacts
+    /// of the runs in progress.
+    pub fn gc(&self, referenced: &BTreeSet<String>, min_age: u64) -> io::Result<Vec<String>> {
+        let mut kept = BTreeSet::new();
+        let mut pending: Vec<String> = referenced.iter().cloned().collect();
+        while let Some(digest) = pending.pop() {
+            if !kept.insert(digest.clone()) {
+                continue;
+            }
+            if let Some(artifact) = self.get(&digest) {
+                if artifact.directory {
+                    pending.extend(self.tree(&digest)?.into_iter().map(|e| e.digest));
+                }
+            }
+        }
+        let now = executor::now();
+        let mut removed = Vec::new();
+        for artifact in self.list()? {
+            if kept.contains(&artifact.digest) || artifact.created_at + min_age > now {
+                continue;
+            }
+            fs::remove_file(self.object_path(&artifact.digest))?;
+            fs::remove_file(self.meta_path(&artifact.digest))?;
+            removed.push(artifact.digest);
+        }
+        Ok(removed)
+    }
+}
+
+fn not_found(digest: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("artifact not found: {}", digest),
+    )
+}
+
+pub fn is_digest(digest: &str) -> bool {
+    digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Value of a `PATH` parameter referring to an artifact.
+pub fn reference(digest: &str) -> JValue {
+    JValue::String(format!("{}{}", ARTIFACT_PREFIX, digest))
+}
+
+/// Digests of the artifacts a run consumed and produced.
+pub fn references(record: &RunRecord) -> BTreeSet<String> {
+    let mut digests = input_references(&record.inputs);
+    digests.extend(record.artifacts.values().cloned());
+    digests
+}
+
+pub fn input_references(inputs: &BTreeMap<String, JValue>) -> BTreeSet<String> {
+    inputs
+        .values()
+        .filter_map(binding::artifact_digest)
+        .map(|d| d.to_string())
+        .collect()
+}
+
+/// Outputs of a run, the stored ones replaced by their artifact reference, for the runs
+/// consuming them.
+pub fn output_references(record: &RunRecord) -> BTreeMap<String, JValue> {
+    let mut outputs = record.outputs.clone();
+    for (name, digest) in record.artifacts.iter() {
+        outputs.insert(name.clone(), reference(digest));
+    }
+    outputs
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_put_materialize() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let store = ArtifactStore::new(tmp.path().join("store"));
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::create_dir_all(src.join("empty")).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(src.join("sub").join("b.txt"), "b").unwrap();
+        let file = store.put(&src.join("a.txt")).unwrap();
+        assert!(!file.directory);
+        assert_eq!(file.digest, crate::cache::digest(b"a"));
+        let dir = store.put(&src).unwrap();
+        assert!(dir.directory);
+        assert_eq!(store.put(&src).unwrap(), dir);
+        // a.txt, b.txt and the trees of src, sub and empty
+        assert_eq!(store.list().unwrap().len(), 5);
+
+        let target = tmp.path().join("copy");
+        store.materialize(&dir.digest, &target).unwrap();
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "a");
+        assert_eq!(
+            fs::read_to_string(target.join("sub").join("b.txt")).unwrap(),
+            "b"
+        );
+        assert!(target.join("empty").is_dir());
+        assert_eq!(store.put(&target).unwrap().digest, dir.digest);
+        let missing = "0".repeat(64);
+        assert!(store.get(&missing).is_none());
+        assert!(store.materialize(&missing, &target).is_err());
+    }
+
+    #[test]
+    fn test_gc() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let store = ArtifactStore::new(tmp.path().join("store"));
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(tmp.path().join("b.txt"), "b").unwrap();
+        let dir = store.put(&src).unwrap();
+        let b = store.put(&tmp.path().join("b.txt")).unwrap();
+        let referenced = BTreeSet::from([dir.digest.clone()]);
+        assert!(store.gc(&referenced, 60_000).unwrap().is_empty());
+        assert_eq!(store.gc(&referenced, 0).unwrap(), vec![b.digest.clone()]);
+        assert!(store.get(&b.digest).is_none());
+        // the file below the referenced directory is kept
+        assert_eq!(store.list().unwrap().len(), 2);
+        assert_eq!(store.gc(&BTreeSet::new(), 0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_references() {
+        let digest = "ab".repeat(32);
+        let mut record: RunRecord = serde_json::from_value(serde_json::json!({
+            "id": "r1",
+            "function": "f",
+            "inputs": {"src": format!("artifact:{}", digest), "n": 1},
+            "command": [],
+            "workDir": "/tmp/r1",
+            "startedAt": 1,
+            "finishedAt": 2,
+            "exitCode": 0,
+            "stdout": "",
+            "stderr": "",
+            "error": null,
+            "cancelled": false,
+            "outputs": {"report": "/tmp/r1/report", "n": 2},
+        }))
+        .unwrap();
+        record
+            .artifacts
+            .insert("report".to_string(), "cd".repeat(32));
+        assert_eq!(
+            references(&record),
+            BTreeSet::from([digest, "cd".repeat(32)])
+        );
+        let outputs = output_references(&record);
+        assert_eq!(outputs["report"], reference(&"cd".repeat(32)));
+        assert_eq!(outputs["n"], serde_json::json!(2));
+    }
+}
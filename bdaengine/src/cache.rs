@@ -0,0 +1,293 @@
+use crate::container::ContainerTarget;
+use crate::executor::{self, RunRecord};
+use bdacore::logic::binding::{Binding, ARTIFACT_PREFIX};
+use bdaproto::parameter::ParameterKind;
+use bdaproto::{Function, Resource};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JValue};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Resource attribute opting a function out of caching when it is `false`.
+pub const CACHE_ATTRIBUTE: &str = "cache";
+
+/// Successful run whose outputs are reused by the runs with the same key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    pub key: String,
+    pub function: String,
+    /// Run the outputs come from.
+    pub run: String,
+    pub created_at: u64,
+    pub last_used: u64,
+    pub hits: u64,
+    pub record: RunRecord,
+}
+
+/// Whether the runs of a function resource may be cached.
+pub fn enabled(resource: &Resource) -> bool {
+    let attributes = serde_json::to_value(&resource.attributes).unwrap_or_default();
+    attributes.get(CACHE_ATTRIBUTE) != Some(&JValue::Bool(false))
+}
+
+/// Key of a run, the digest of the function resource, which covers its `baseCommand` and
+/// revision, of the runtime, and of the bound inputs with the content of `PATH` inputs.
+pub fn cache_key(
+    function_id: &str,
+    resource: &Resource,
+    function: &Function,
+    target: Option<&ContainerTarget>,
+    binding: &Binding,
+) -> Result<String, String> {
+    let mut contents = serde_json::Map::new();
+    for p in function.inputs.iter() {
+        if p.parameter_kind != ParameterKind::Path as i32 {
+            continue;
+        }
+        if let Some(JValue::String(path)) = binding.inputs.get(&p.name) {
+            // artifacts are named by their content already
+            if path.starts_with(ARTIFACT_PREFIX) {
+                continue;
+            }
+            let digest = digest_path(Path::new(path))
+                .map_err(|e| format!("could not read {}: {}", path, e))?;
+            contents.insert(p.name.clone(), JValue::String(digest));
+        }
+    }
+    let material = json!({
+        "function": function_id,
+        "resource": serde_json::to_value(resource).map_err(|e| e.to_string())?,
+        "baseCommand": function.base_command,
+        "runtime": target.map(|t| json!({"id": t.runtime, "container": t.container})),
+        "inputs": binding.inputs,
+        "contents": contents,
+    });
+    Ok(digest(material.to_string().as_bytes()))
+}
+
+pub fn digest(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Digest of the content of a file, or of the names and contents of the files below a
+/// directory.
+pub fn digest_path(path: &Path) -> io::Result<String> {
+    if !fs::metadata(path)?.is_dir() {
+        return Ok(digest(&fs::read(path)?));
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+    let mut hasher = Sha256::new();
+    for entry in entries {
+        let name = entry.file_name().unwrap_or_default().to_string_lossy();
+        hasher.update(format!("{}\0{}\n", name, digest_path(&entry)?));
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Cached runs stored as `<key>.json` in a directory.
+pub struct RunCache {
+    dir: PathBuf,
+}
+
+impl RunCache {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        RunCache { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    pub fn get(&self, key: &str) -> Option<CacheEntry> {
+        let content = fs::read(self.path(key)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Entry of a key whose `PATH` outputs still exist, counting the hit.
+    pub fn lookup(&self, key: &str, function: &Function) -> Option<CacheEntry> {
+        let mut entry = self.get(key)?;
+        let missing = function.outputs.iter().any(|p| {
+            p.parameter_kind == ParameterKind::Path as i32
+                && match entry.record.outputs.get(&p.name) {
+                    Some(JValue::String(path)) => !Path::new(path).exists(),
+                    _ => false,
+                }
+        });
+        if missing {
+            return None;
+        }
+        entry.hits += 1;
+        entry.last_used = executor::now();
+        if let Err(e) = self.save(&entry) {
+            eprintln!("{}", e);
+        }
+        Some(entry)
+    }
+
+    /// Caches a successful run.
+    pub fn put(&self, key: &str, function: &str, record: &RunRecord) -> Result<(), String> {
+        let now = executor::now();
+        self.save(&CacheEntry {
+            key: key.to_string(),
+            function: function.to_string(),
+            run: record.id.clone(),
+            created_at: now,
+            last_used: now,
+            hits: 0,
+            record: record.clone(),
+        })
+    }
+
+    fn save(&self, entry: &CacheEntry) -> Result<(), String> {
+        let tmp = self.dir.join(format!("{}.json.tmp", entry.key));
+        let content = serde_json::to_vec_pretty(entry).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&tmp, content))
+            .and_then(|_| fs::rename(&tmp, self.path(&entry.key)))
+            .map_err(|e| format!("could not cache run {}: {}", entry.run, e))
+    }
+
+    /// Entries, most recently used first.
+    pub fn list(&self) -> Result<Vec<CacheEntry>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => Err(format!("could not read {}: {}", self.dir.display(), e))?,
+        };
+        let mut cached = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().map_or(false, |ext| ext == "json") {
+                let content = fs::read(&path).map_err(|e| e.to_string())?;
+                let entry: CacheEntry = serde_json::from_slice(&content)
+                    .map_err(|e| format!("invalid cache entry {}: {}", path.display(), e))?;
+                cached.push(entry);
+            }
+        }
+        cached.sort_by(|a, b| b.last_used.cmp(&a.last_used).then(a.key.cmp(&b.key)));
+        Ok(cached)
+    }
+
+    /// Removes the entries matching a predicate, returning their keys.
+    pub fn evict<F: Fn(&CacheEntry) -> bool>(&self, matches: F) -> Result<Vec<String>, String> {
+        let mut evicted = Vec::new();
+        for entry in self.list()?.into_iter().filter(|e| matches(e)) {
+            fs::remove_file(self.path(&entry.key)).map_err(|e| e.to_string())?;
+            evicted.push(entry.key);
+        }
+        Ok(evicted)
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use bdacore::logic::{self, new_parameter};
+    use tempdir::TempDir;
+
+    fn function() -> Function {
+        Function {
+            inputs: vec![
+                new_parameter("src", ParameterKind::Path),
+                new_parameter("level", ParameterKind::Number),
+            ],
+            outputs: vec![new_parameter("report", ParameterKind::Path)],
+            ..logic::new_function(&["make"])
+        }
+    }
+
+    fn resource(f: &Function) -> Resource {
+        Resource {
+            resource_kind: Some(bdaproto::resource::ResourceKind::Function(f.clone())),
+            ..logic::new_resource_function("build")
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("main.c"), "int main;").unwrap();
+        let f = function();
+        let r = resource(&f);
+        let binding = Binding {
+            inputs: [
+                ("src".to_string(), json!(src.display().to_string())),
+                ("level".to_string(), json!(2.0)),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let key = |r: &Resource, b: &Binding| cache_key("f", r, &f, None, b).unwrap();
+        let k = key(&r, &binding);
+        assert_eq!(k, key(&r, &binding));
+        fs::write(src.join("main.c"), "int main();").unwrap();
+        let changed_content = key(&r, &binding);
+        assert_ne!(k, changed_content);
+        let mut other = binding.clone();
+        other.inputs.insert("level".to_string(), json!(3.0));
+        assert_ne!(changed_content, key(&r, &other));
+        let mut revised = r.clone();
+        revised.description = "builds".to_string();
+        assert_ne!(changed_content, key(&revised, &binding));
+        fs::remove_dir_all(&src).unwrap();
+        assert!(cache_key("f", &r, &f, None, &binding).is_err());
+    }
+
+    #[test]
+    fn test_enabled() {
+        let mut r = resource(&function());
+        assert!(enabled(&r));
+        r.attributes = Some(serde_json::from_value(json!({"cache": false})).unwrap());
+        assert!(!enabled(&r));
+        r.attributes = Some(serde_json::from_value(json!({"cache": true})).unwrap());
+        assert!(enabled(&r));
+    }
+
+    #[test]
+    fn test_run_cache() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let cache = RunCache::new(tmp.path().join("cache"));
+        let report = tmp.path().join("report.txt");
+        fs::write(&report, "ok").unwrap();
+        let record = RunRecord {
+            id: "r1".to_string(),
+            function: "f".to_string(),
+            inputs: Default::default(),
+            command: vec!["make".to_string()],
+            work_dir: tmp.path().into(),
+            started_at: 1,
+            finished_at: 2,
+            exit_code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+            cancelled: false,
+            timed_out: false,
+            outputs: [("report".to_string(), json!(report.display().to_string()))]
+                .into_iter()
+                .collect(),
+            artifacts: Default::default(),
+        };
+        assert!(cache.list().unwrap().is_empty());
+        assert_eq!(cache.lookup("k1", &function()), None);
+        cache.put("k1", "f", &record).unwrap();
+        cache.put("k2", "g", &record).unwrap();
+        let entry = cache.lookup("k1", &function()).unwrap();
+        assert_eq!((entry.run.as_str(), entry.hits), ("r1", 1));
+        assert_eq!(cache.list().unwrap().len(), 2);
+        assert_eq!(cache.evict(|e| e.function == "g").unwrap(), vec!["k2"]);
+        fs::remove_file(&report).unwrap();
+        assert_eq!(cache.lookup("k1", &function()), None);
+        assert_eq!(cache.evict(|_| true).unwrap(), vec!["k1"]);
+        assert!(cache.list().unwrap().is_empty());
+    }
+}
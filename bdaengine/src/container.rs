@@ -0,0 +1,280 @@
+use bdacore::data::EntityID;
+use bdacore::logic;
+use bdaproto::resource::ResourceKind;
+use bdaproto::runtime::RuntimeKind;
+use bdaproto::{Container, Resource};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Mutex,
+};
+use tokio::process::Command;
+use tonic::async_trait;
+
+/// Directory where the run working directory is mounted inside the container.
+pub const CONTAINER_WORK_DIR: &str = "/bda/work";
+/// Directory where `PATH` inputs are mounted inside the container, one entry per input.
+pub const CONTAINER_INPUTS_DIR: &str = "/bda/inputs";
+
+/// Container runtime a function runs in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainerTarget {
+    /// Resource id of the runtime, naming the image built for it.
+    pub runtime: String,
+    pub container: Container,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mount {
+    pub source: PathBuf,
+    pub target: String,
+    pub read_only: bool,
+}
+
+/// Container to run, independently of the tool running it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerSpec {
+    pub name: String,
+    pub image: String,
+    pub work_dir: String,
+    pub mounts: Vec<Mount>,
+    pub env: Vec<(String, String)>,
+    /// Variables passed by name, with the value they have in the environment of the host
+    /// command, so that secret values never show in the command.
+    pub inherited_env: Vec<String>,
+    pub command: Vec<String>,
+}
+
+/// Builds images from dockerfiles and runs containers from them.
+#[async_trait]
+pub trait ContainerDriver: Send + Sync {
+    /// Builds an image from a dockerfile, relative to the build context.
+    async fn build(&self, context: &Path, dockerfile: &str, image: &str) -> Result<(), String>;
+    /// Host command running the container in the foreground, exiting with the status of the
+    /// container command. The executor runs it like any other process.
+    fn run_command(&self, spec: &ContainerSpec) -> Vec<String>;
+    /// Removes a container, succeeding when it is already gone.
+    async fn remove_container(&self, name: &str) -> Result<(), String>;
+    async fn remove_image(&self, image: &str) -> Result<(), String>;
+}
+
+/// Image built for a container runtime, like `bda/default/builder:latest`.
+pub fn image_name(runtime: &str) -> Result<String, String> {
+    let (version, namespace, _, name) = logic::parse_resource_id(runtime)?;
+    Ok(format!("bda/{}/{}:{}", namespace, name, version))
+}
+
+pub fn container_name(run_id: &str) -> String {
+    format!(
+        "bda-{}",
+        run_id.replace(|c: char| !c.is_ascii_alphanumeric(), "-")
+    )
+}
+
+/// Target of a container runtime resource.
+pub fn container_target(runtime: &Resource) -> Result<ContainerTarget, Box<dyn Error>> {
+    let id = match logic::resource_id(runtime)? {
+        EntityID::ResourceID(id) => id,
+    };
+    match runtime.resource_kind {
+        Some(ResourceKind::Runtime(ref rt)) => match rt.runtime_kind {
+            Some(RuntimeKind::Container(ref c)) => Ok(ContainerTarget {
+                runtime: id,
+                container: c.clone(),
+            }),
+            None => Err(format!("runtime {} has no runtime kind", id))?,
+        },
+        _ => Err(format!("resource {} is not a runtime", id))?,
+    }
+}
+
+/// Driver using an OCI command line tool with the docker interface, like podman or docker.
+pub struct CliDriver {
+    program: String,
+}
+
+impl CliDriver {
+    pub fn new(program: &str) -> Self {
+        CliDriver {
+            program: program.to_string(),
+        }
+    }
+
+    /// Uses podman when it is on the `PATH`, or docker otherwise.
+    pub fn detect() -> Result<Self, String> {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        ["podman", "docker"]
+            .iter()
+            .find(|program| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+            .map(|program| CliDriver::new(program))
+            .ok_or_else(|| "neither podman nor docker was found on the PATH".to_string())
+    }
+
+    async fn exec(&self, args: &[&str]) -> Result<(), String> {
+        let output = Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|e| format!("could not start {}: {}", self.program, e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} {} failed: {}",
+                self.program,
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl ContainerDriver for CliDriver {
+    async fn build(&self, context: &Path, dockerfile: &str, image: &str) -> Result<(), String> {
+        let dockerfile = context.join(dockerfile);
+        let (dockerfile, context) = (dockerfile.to_string_lossy(), context.to_string_lossy());
+        self.exec(&["build", "-t", image, "-f", &dockerfile, &context])
+            .await
+    }
+
+    fn run_command(&self, spec: &ContainerSpec) -> Vec<String> {
+        let mut command = vec![
+            self.program.clone(),
+            "run".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            spec.name.clone(),
+            "-w".to_string(),
+            spec.work_dir.clone(),
+        ];
+        for m in spec.mounts.iter() {
+            let mode = if m.read_only { ":ro" } else { "" };
+            command.push("-v".to_string());
+            command.push(format!("{}:{}{}", m.source.display(), m.target, mode));
+        }
+        for (name, value) in spec.env.iter() {
+            command.push("-e".to_string());
+            command.push(format!("{}={}", name, value));
+        }
+        for name in spec.inherited_env.iter() {
+            command.push("-e".to_string());
+            command.push(name.clone());
+        }
+        command.push(spec.image.clone());
+        command.extend(spec.command.iter().cloned());
+        command
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<(), String> {
+        match self.exec(&["rm", "-f", name]).await {
+            Err(e) if e.to_lowercase().contains("no such container") => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn remove_image(&self, image: &str) -> Result<(), String> {
+        self.exec(&["rmi", image]).await
+    }
+}
+
+/// Driver running the container command on the host, for tests. Mounts are emulated by
+/// replacing their targets with their sources in the command and the environment. Calls are
+/// recorded in `calls`.
+#[derive(Default)]
+pub struct FakeDriver {
+    pub calls: Mutex<Vec<String>>,
+}
+
+impl FakeDriver {
+    fn record(&self, call: String) {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(call)
+        }
+    }
+}
+
+#[async_trait]
+impl ContainerDriver for FakeDriver {
+    async fn build(&self, context: &Path, dockerfile: &str, image: &str) -> Result<(), String> {
+        self.record(format!("build {} {}", image, dockerfile));
+        if context.join(dockerfile).is_file() {
+            Ok(())
+        } else {
+            Err(format!("dockerfile {} not found", dockerfile))
+        }
+    }
+
+    fn run_command(&self, spec: &ContainerSpec) -> Vec<String> {
+        self.record(format!("run {} {}", spec.name, spec.image));
+        let unmount = |s: &str| {
+            spec.mounts.iter().fold(s.to_string(), |s, m| {
+                s.replace(&m.target, &m.source.to_string_lossy())
+            })
+        };
+        let mut command = vec!["env".to_string()];
+        for (name, value) in spec.env.iter() {
+            command.push(format!("{}={}", name, unmount(value)));
+        }
+        command.extend(spec.command.iter().map(|word| unmount(word)));
+        command
+    }
+
+    async fn remove_container(&self, name: &str) -> Result<(), String> {
+        self.record(format!("rm {}", name));
+        Ok(())
+    }
+
+    async fn remove_image(&self, image: &str) -> Result<(), String> {
+        self.record(format!("rmi {}", image));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(
+            image_name("/latest/default/runtime.container/builder"),
+            Ok("bda/default/builder:latest".to_string())
+        );
+        assert!(image_name("builder").is_err());
+        assert_eq!(container_name("17f.a-1"), "bda-17f-a-1");
+    }
+
+    #[test]
+    fn test_cli_run_command() {
+        let spec = ContainerSpec {
+            name: "bda-r1".to_string(),
+            image: "bda/default/builder:latest".to_string(),
+            work_dir: CONTAINER_WORK_DIR.to_string(),
+            mounts: vec![
+                Mount {
+                    source: "/runs/r1".into(),
+                    target: CONTAINER_WORK_DIR.to_string(),
+                    read_only: false,
+                },
+                Mount {
+                    source: "/data/in.csv".into(),
+                    target: "/bda/inputs/data".to_string(),
+                    read_only: true,
+                },
+            ],
+            env: vec![("BDA_RUN_ID".to_string(), "r1".to_string())],
+            inherited_env: vec!["TOKEN".to_string()],
+            command: vec!["make".to_string(), "all".to_string()],
+        };
+        assert_eq!(
+            CliDriver::new("podman").run_command(&spec).join(" "),
+            "podman run --rm --name bda-r1 -w /bda/work -v /runs/r1:/bda/work \
+             -v /data/in.csv:/bda/inputs/data:ro -e BDA_RUN_ID=r1 -e TOKEN \
+             bda/default/builder:latest make all"
+        );
+    }
+}
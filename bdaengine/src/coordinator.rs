@@ -0,0 +1,390 @@
+use crate::container::ContainerTarget;
+use crate::executor::{self, RunRecord};
+use crate::queue::{self, Job, RunQueue};
+use crate::service::{args_to_struct, struct_to_args};
+use bdaproto::coordinator_server::Coordinator;
+use bdaproto::execution::Status as RunStatus;
+use bdaproto::{
+    CompleteRunRequest, CompleteRunResponse, Execution, HeartbeatRequest, HeartbeatResponse,
+    LeaseRunRequest, LeaseRunResponse, RegisterWorkerRequest, RegisterWorkerResponse, RunLease,
+};
+use pbjson_types::Timestamp;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tonic::{async_trait, Request, Response, Status};
+
+/// Workers missing that many heartbeats in a row are dead.
+pub const MISSED_HEARTBEATS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerInfo {
+    pub id: String,
+    pub name: String,
+    pub capabilities: Vec<String>,
+    /// Last time the worker called the engine, in milliseconds since the unix epoch.
+    pub last_seen: u64,
+}
+
+/// Remote workers registered with the engine, leasing runs from the queue. Every call of a
+/// worker counts as a heartbeat. The runs leased by dead workers are reassigned.
+pub struct Workers {
+    queue: Arc<RunQueue>,
+    heartbeat: Duration,
+    workers: Mutex<BTreeMap<String, WorkerInfo>>,
+}
+
+impl Workers {
+    pub fn new(queue: Arc<RunQueue>, heartbeat: Duration) -> Self {
+        Workers {
+            queue,
+            heartbeat,
+            workers: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn heartbeat(&self) -> Duration {
+        self.heartbeat
+    }
+
+    pub fn register(
+        &self,
+        name: &str,
+        capabilities: &[String],
+        now: u64,
+    ) -> Result<String, String> {
+        let id = format!("{}.{}", name, executor::new_run_id());
+        let worker = WorkerInfo {
+            id: id.clone(),
+            name: name.to_string(),
+            capabilities: capabilities.to_vec(),
+            last_seen: now,
+        };
+        self.workers
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(id.clone(), worker);
+        Ok(id)
+    }
+
+    /// Records a call of a worker, failing when it is not registered, like after it was
+    /// declared dead. Returns the worker.
+    pub fn touch(&self, id: &str, now: u64) -> Result<WorkerInfo, String> {
+        let mut workers = self.workers.lock().map_err(|e| e.to_string())?;
+        let worker = workers
+            .get_mut(id)
+            .ok_or_else(|| format!("worker not registered: {}", id))?;
+        worker.last_seen = now;
+        Ok(worker.clone())
+    }
+
+    /// Leases the next queued run matching the capabilities of a worker.
+    pub fn lease(&self, id: &str, now: u64) -> Result<Option<Job>, String> {
+        let worker = self.touch(id, now)?;
+        self.queue.lease(&worker.id, &worker.capabilities)
+    }
+
+    /// Forgets the workers not seen for `MISSED_HEARTBEATS` heartbeats, reassigning their runs.
+    /// Returns the dead workers.
+    pub fn reap(&self, now: u64) -> Result<Vec<String>, String> {
+        let timeout = self.heartbeat.as_millis() as u64 * MISSED_HEARTBEATS as u64;
+        let dead: Vec<String> = {
+            let mut workers = self.workers.lock().map_err(|e| e.to_string())?;
+            let dead = workers
+                .values()
+                .filter(|w| now.saturating_sub(w.last_seen) > timeout)
+                .map(|w| w.id.clone())
+                .collect();
+            workers.retain(|_, w| now.saturating_sub(w.last_seen) <= timeout);
+            dead
+        };
+        for id in dead.iter() {
+            self.queue.reassign(id)?;
+        }
+        Ok(dead)
+    }
+
+    /// Reaps dead workers every heartbeat, until the engine stops.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            tokio::time::sleep(self.heartbeat).await;
+            match self.reap(executor::now()) {
+                Ok(dead) => {
+                    for id in dead {
+                        eprintln!("worker {} is dead, its runs are reassigned", id);
+                    }
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    }
+}
+
+/// Lease given to a worker for the running attempt of a job.
+pub fn job_to_lease(job: &Job) -> Result<RunLease, String> {
+    let (runtime, container) = match job.target {
+        Some(ref t) => (t.runtime.clone(), Some(t.container.clone())),
+        None => (String::new(), None),
+    };
+    Ok(RunLease {
+        run: job.id.clone(),
+        attempt: job.attempts,
+        function: job.function_id.clone(),
+        definition: Some(job.function.clone()),
+        inputs: Some(args_to_struct(&job.inputs)?),
+        runtime,
+        container,
+    })
+}
+
+/// Container target of a lease, if it runs in a container runtime.
+pub fn lease_target(lease: &RunLease) -> Option<ContainerTarget> {
+    match lease.container {
+        Some(ref container) if !lease.runtime.is_empty() => Some(ContainerTarget {
+            runtime: lease.runtime.clone(),
+            container: container.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Run record of an attempt a worker completed. The logs stay in the working directory of the
+/// worker.
+pub fn execution_to_record(lease: &RunLease, e: &Execution) -> Result<RunRecord, String> {
+    let status = RunStatus::from_i32(e.status).unwrap_or(RunStatus::Failed);
+    let error = match status {
+        RunStatus::Succeeded => None,
+        _ if !e.error.is_empty() => Some(e.error.clone()),
+        _ => Some(format!("run ended as {:?}", status)),
+    };
+    Ok(RunRecord {
+        id: queue::attempt_run_id(&lease.run, lease.attempt),
+        function: lease.function.clone(),
+        inputs: struct_to_args(e.inputs.clone())?,
+        command: Vec::new(),
+        work_dir: e.work_dir.clone().into(),
+        started_at: millis(&e.started_at),
+        finished_at: millis(&e.finished_at),
+        exit_code: Some(e.exit_code),
+        stdout: String::new(),
+        stderr: String::new(),
+        error,
+        cancelled: status == RunStatus::Cancelled,
+        timed_out: false,
+        outputs: struct_to_args(e.outputs.clone())?,
+        artifacts: e.artifacts.clone().into_iter().collect(),
+    })
+}
+
+fn millis(t: &Option<Timestamp>) -> u64 {
+    t.as_ref().map_or(0, |t| {
+        t.seconds.max(0) as u64 * 1000 + t.nanos.max(0) as u64 / 1_000_000
+    })
+}
+
+/// Coordinator service through which remote workers lease the runs of the queue.
+pub struct BDACoordinatorService {
+    workers: Arc<Workers>,
+    queue: Arc<RunQueue>,
+}
+
+impl BDACoordinatorService {
+    pub fn new(workers: Arc<Workers>, queue: Arc<RunQueue>) -> Self {
+        BDACoordinatorService { workers, queue }
+    }
+}
+
+#[async_trait]
+impl Coordinator for BDACoordinatorService {
+    async fn register_worker(
+        &self,
+        request: Request<RegisterWorkerRequest>,
+    ) -> Result<Response<RegisterWorkerResponse>, Status> {
+        let request = request.into_inner();
+        if request.name.is_empty() {
+            return Err(Status::invalid_argument("worker name is required"));
+        }
+        let worker_id = self
+            .workers
+            .register(&request.name, &request.capabilities, executor::now())
+            .map_err(Status::internal)?;
+        Ok(Response::new(RegisterWorkerResponse {
+            worker_id,
+            heartbeat_seconds: self.workers.heartbeat().as_secs().max(1) as u32,
+        }))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<HeartbeatResponse>, Status> {
+        let id = &request.get_ref().worker_id;
+        self.workers
+            .touch(id, executor::now())
+            .map_err(Status::not_found)?;
+        Ok(Response::new(HeartbeatResponse {
+            cancel: self.queue.cancelled(id),
+        }))
+    }
+
+    async fn lease_run(
+        &self,
+        request: Request<LeaseRunRequest>,
+    ) -> Result<Response<LeaseRunResponse>, Status> {
+        let worker = self
+            .workers
+            .touch(&request.get_ref().worker_id, executor::now())
+            .map_err(Status::not_found)?;
+        let lease = match self.queue.lease(&worker.id, &worker.capabilities) {
+            Ok(Some(job)) => Some(job_to_lease(&job).map_err(Status::internal)?),
+            Ok(None) => None,
+            Err(e) => Err(Status::internal(e))?,
+        };
+        Ok(Response::new(LeaseRunResponse { lease }))
+    }
+
+    async fn complete_run(
+        &self,
+        request: Request<CompleteRunRequest>,
+    ) -> Result<Response<CompleteRunResponse>, Status> {
+        let request = request.into_inner();
+        self.workers
+            .touch(&request.worker_id, executor::now())
+            .map_err(Status::not_found)?;
+        let job = self
+            .queue
+            .get(&request.run)
+            .ok_or_else(|| Status::not_found(format!("run not found: {}", request.run)))?;
+        let mut lease = job_to_lease(&job).map_err(Status::internal)?;
+        lease.attempt = request.attempt;
+        let record = execution_to_record(&lease, &request.execution.unwrap_or_default())
+            .map_err(Status::invalid_argument)?;
+        self.queue
+            .complete(
+                &request.worker_id,
+                &request.run,
+                request.attempt,
+                Ok(record),
+            )
+            .map_err(Status::failed_precondition)?;
+        Ok(Response::new(CompleteRunResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::executor::LocalExecutor;
+    use crate::queue::JobState;
+    use bdacore::logic::binding::Binding;
+    use bdaproto::Function;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    fn setup(tmp: &TempDir) -> (Arc<RunQueue>, Workers) {
+        let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+        let queue = Arc::new(RunQueue::open(tmp.path().join("queue"), executor, 0).unwrap());
+        let workers = Workers::new(queue.clone(), Duration::from_secs(1));
+        (queue, workers)
+    }
+
+    fn submit(queue: &RunQueue, capabilities: &[&str]) -> String {
+        let f = Function {
+            base_command: vec!["true".to_string()],
+            runtime_capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        let binding = Binding {
+            inputs: BTreeMap::from([("n".to_string(), json!(1.0))]),
+        };
+        queue.submit(Job::new("f", &f, None, &binding)).unwrap().id
+    }
+
+    fn succeeded(lease: &RunLease) -> Execution {
+        Execution {
+            status: RunStatus::Succeeded as i32,
+            inputs: lease.inputs.clone(),
+            work_dir: "/runs/r".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lease_by_capabilities() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let (queue, workers) = setup(&tmp);
+        let linux = workers.register("w1", &["linux".to_string()], 0).unwrap();
+        let gpu = workers
+            .register("w2", &["linux".to_string(), "gpu".to_string()], 0)
+            .unwrap();
+        let id = submit(&queue, &["gpu"]);
+        assert_eq!(workers.lease(&linux, 0).unwrap(), None);
+        let job = workers.lease(&gpu, 0).unwrap().unwrap();
+        assert_eq!((job.id.as_str(), job.attempts), (id.as_str(), 1));
+        assert_eq!(job.worker.as_deref(), Some(gpu.as_str()));
+        assert_eq!(workers.lease(&gpu, 0).unwrap(), None);
+        assert!(workers.lease("unknown", 0).is_err());
+
+        let lease = job_to_lease(&job).unwrap();
+        let record = execution_to_record(&lease, &succeeded(&lease)).unwrap();
+        assert!(queue.complete(&linux, &id, 1, Ok(record.clone())).is_err());
+        let job = queue.complete(&gpu, &id, 1, Ok(record)).unwrap();
+        assert_eq!(job.state, JobState::Succeeded);
+        assert_eq!(job.inputs["n"], json!(1.0));
+        assert_eq!(job.work_dir, std::path::PathBuf::from("/runs/r"));
+    }
+
+    #[test]
+    fn test_reap_reassigns() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let (queue, workers) = setup(&tmp);
+        let dead = workers.register("w1", &[], 0).unwrap();
+        let alive = workers.register("w2", &[], 0).unwrap();
+        let id = submit(&queue, &[]);
+        let lease = job_to_lease(&workers.lease(&dead, 0).unwrap().unwrap()).unwrap();
+        assert_eq!(workers.reap(3000).unwrap(), Vec::<String>::new());
+        workers.touch(&alive, 3000).unwrap();
+        assert_eq!(workers.reap(3001).unwrap(), vec![dead.clone()]);
+        assert!(workers.touch(&dead, 3001).is_err());
+        let job = queue.get(&id).unwrap();
+        assert_eq!(job.state, JobState::Queued);
+        assert_eq!(job.reassigned, 1);
+        assert_eq!(
+            job.error,
+            Some(format!("attempt 1 lost with worker {}", dead))
+        );
+        // the dead worker lost its lease, the attempt of the other worker settles the job
+        let record = execution_to_record(&lease, &succeeded(&lease)).unwrap();
+        assert!(queue.complete(&dead, &id, 1, Ok(record)).is_err());
+        let job = workers.lease(&alive, 3001).unwrap().unwrap();
+        assert_eq!((job.id.as_str(), job.attempts), (id.as_str(), 2));
+        let lease = job_to_lease(&job).unwrap();
+        let failed = Execution {
+            status: RunStatus::Failed as i32,
+            exit_code: 2,
+            ..Default::default()
+        };
+        let record = execution_to_record(&lease, &failed).unwrap();
+        assert_eq!(record.id, format!("{}.2", id));
+        assert_eq!(record.error, Some("run ended as Failed".to_string()));
+        // the lost attempt is not a retry, and the run policy allows none
+        let job = queue.complete(&alive, &id, 2, Ok(record)).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+    }
+
+    #[test]
+    fn test_cancel_leased() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let (queue, workers) = setup(&tmp);
+        let worker = workers.register("w1", &[], 0).unwrap();
+        let id = submit(&queue, &[]);
+        workers.lease(&worker, 0).unwrap().unwrap();
+        assert!(queue.cancelled(&worker).is_empty());
+        queue.cancel(&id).unwrap();
+        assert_eq!(queue.cancelled(&worker), vec![id.clone()]);
+        assert_eq!(workers.reap(10_000).unwrap(), vec![worker]);
+        assert_eq!(queue.get(&id).unwrap().state, JobState::Cancelled);
+    }
+}
@@ -0,0 +1,398 @@
+use crate::artifacts;
+use crate::executor::{self, LocalExecutor, RunRecord};
+use bdacore::logic::{binding, dag as graph};
+use bdaproto::dag::FailurePolicy;
+use bdaproto::{Dag, Function};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use std::{collections::BTreeMap, error::Error, path::PathBuf, sync::Arc};
+use tokio::{
+    fs,
+    sync::{mpsc, watch},
+};
+
+pub const DAG_RUN_FILE: &str = "dag.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// Not run because an upstream node did not succeed, or the dag failed fast.
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRun {
+    pub status: NodeStatus,
+    pub run: Option<RunRecord>,
+    pub error: Option<String>,
+}
+
+/// Result of running a dag, also stored as `dag.json` in the dag run working directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DagRunRecord {
+    pub id: String,
+    pub dag: String,
+    pub work_dir: PathBuf,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub nodes: BTreeMap<String, NodeRun>,
+}
+impl DagRunRecord {
+    pub fn succeeded(&self) -> bool {
+        self.nodes
+            .values()
+            .all(|n| n.status == NodeStatus::Succeeded)
+    }
+}
+
+/// Runs the nodes of a dag with a local executor, as soon as their upstream nodes succeed.
+/// Each node runs in its own working directory, named after the dag run and the node.
+pub struct DagRunner {
+    executor: Arc<LocalExecutor>,
+}
+
+impl DagRunner {
+    pub fn new(executor: Arc<LocalExecutor>) -> Self {
+        DagRunner { executor }
+    }
+
+    /// Runs the dag, with `functions` holding the function of every node by resource id.
+    /// At most `parallelism` nodes run at once. When a node fails, the `failurePolicy` either
+    /// cancels the running nodes and skips the others, or only skips the nodes depending on it.
+    pub async fn run(
+        &self,
+        dag_id: &str,
+        dag: &Dag,
+        functions: &BTreeMap<String, Function>,
+    ) -> Result<DagRunRecord, Box<dyn Error>> {
+        let order = graph::topological_order(dag)?;
+        let deps = graph::dependencies(dag);
+        let parallelism = dag.parallelism.max(1) as usize;
+        let fail_fast = dag.failure_policy == FailurePolicy::FailFast as i32;
+        let id = executor::new_run_id();
+        let mut record = DagRunRecord {
+            id: id.clone(),
+            dag: dag_id.to_string(),
+            work_dir: self.executor.work_dir(&id),
+            started_at: executor::now(),
+            finished_at: 0,
+            nodes: order
+                .iter()
+                .map(|n| {
+                    let run = NodeRun {
+                        status: NodeStatus::Pending,
+                        run: None,
+                        error: None,
+                    };
+                    (n.clone(), run)
+                })
+                .collect(),
+        };
+        let base_dir = std::env::current_dir()?;
+        let (done, mut finished) = mpsc::unbounded_channel();
+        let mut running: BTreeMap<String, watch::Sender<bool>> = BTreeMap::new();
+        let mut outputs: BTreeMap<String, BTreeMap<String, JValue>> = BTreeMap::new();
+        let mut stopped = false;
+        loop {
+            for name in order.iter() {
+                if stopped || running.len() >= parallelism {
+                    break;
+                }
+                if record.nodes[name].status != NodeStatus::Pending {
+                    continue;
+                }
+                let upstream: Vec<NodeStatus> =
+                    deps[name].iter().map(|d| record.nodes[d].status).collect();
+                if upstream.iter().any(|s| {
+                    matches!(
+                        s,
+                        NodeStatus::Failed | NodeStatus::Cancelled | NodeStatus::Skipped
+                    )
+                }) {
+                    set_status(&mut record, name, NodeStatus::Skipped);
+                    continue;
+                }
+                if !upstream.iter().all(|s| *s == NodeStatus::Succeeded) {
+                    continue;
+                }
+                let node = dag.nodes.iter().find(|n| &n.name == name).unwrap();
+                let prepared = functions
+                    .get(&node.function)
+                    .ok_or_else(|| format!("function {} not found", node.function))
+                    .and_then(|f| {
+                        let args = graph::node_args(dag, node, &outputs)?;
+                        let b = binding::bind(&f.inputs, &args, &base_dir)
+                            .map_err(|e| e.to_string())?;
+                        Ok((f.clone(), b))
+                    });
+                let (function, binding) = match prepared {
+                    Ok(prepared) => prepared,
+                    Err(e) => {
+                        let run = record.nodes.get_mut(name).unwrap();
+                        run.status = NodeStatus::Failed;
+                        run.error = Some(e);
+                        stopped = fail_fast;
+                        continue;
+                    }
+                };
+                set_status(&mut record, name, NodeStatus::Running);
+                let (cancel, cancelled) = watch::channel(false);
+                running.insert(name.clone(), cancel);
+                let runner = self.executor.clone();
+                let done = done.clone();
+                let run_id = format!("{}.{}", id, name);
+                let (name, function_id) = (name.clone(), node.function.clone());
+                tokio::spawn(async move {
+                    let result = runner
+                        .run_cancellable(&run_id, &function_id, &function, &binding, cancelled)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = done.send((name, result));
+                });
+            }
+            if running.is_empty() {
+                break;
+            }
+            let (name, result) = match finished.recv().await {
+                Some(finished) => finished,
+                None => break,
+            };
+            running.remove(&name);
+            let run = record.nodes.get_mut(&name).unwrap();
+            match result {
+                Ok(r) => {
+                    run.status = if r.cancelled {
+                        NodeStatus::Cancelled
+                    } else if r.succeeded() {
+                        NodeStatus::Succeeded
+                    } else {
+                        NodeStatus::Failed
+                    };
+                    // stored outputs reach the next nodes through the artifact store
+                    outputs.insert(name, artifacts::output_references(&r));
+                    run.run = Some(r);
+                }
+                Err(e) => {
+                    run.status = NodeStatus::Failed;
+                    run.error = Some(e);
+                }
+            }
+            if fail_fast && run.status != NodeStatus::Succeeded && !stopped {
+                stopped = true;
+                for cancel in running.values() {
+                    let _ = cancel.send(true);
+                }
+            }
+        }
+        for run in record.nodes.values_mut() {
+            if run.status == NodeStatus::Pending {
+                run.status = NodeStatus::Skipped;
+            }
+        }
+        record.finished_at = executor::now();
+        fs::create_dir_all(&record.work_dir).await?;
+        fs::write(
+            record.work_dir.join(DAG_RUN_FILE),
+            serde_json::to_vec_pretty(&record)?,
+        )
+        .await?;
+        Ok(record)
+    }
+}
+
+fn set_status(record: &mut DagRunRecord, node: &str, status: NodeStatus) {
+    if let Some(run) = record.nodes.get_mut(node) {
+        run.status = status;
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::artifacts::ArtifactStore;
+    use bdacore::logic::{new_function, new_parameter};
+    use bdaproto::parameter::ParameterKind;
+    use bdaproto::{DagEdge, DagNode};
+    use tempdir::TempDir;
+
+    fn function(script: &str, inputs: &[&str], outputs: &[&str]) -> Function {
+        let text = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| new_parameter(name, ParameterKind::Text))
+                .collect()
+        };
+        Function {
+            inputs: text(inputs),
+            outputs: text(outputs),
+            ..new_function(&["sh", "-c", script])
+        }
+    }
+
+    fn dag(nodes: &[(&str, &str)], edges: &[(&str, &str)], policy: FailurePolicy) -> Dag {
+        Dag {
+            nodes: nodes
+                .iter()
+                .map(|(name, function)| DagNode {
+                    name: name.to_string(),
+                    function: format!("/latest/default/function/{}", function),
+                    inputs: None,
+                })
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(from, to)| DagEdge {
+                    from: from.to_string(),
+                    output: "message".to_string(),
+                    to: to.to_string(),
+                    input: "message".to_string(),
+                })
+                .collect(),
+            parallelism: 2,
+            failure_policy: policy as i32,
+        }
+    }
+
+    fn functions() -> BTreeMap<String, Function> {
+        BTreeMap::from([
+            (
+                "/latest/default/function/hello".to_string(),
+                function("echo hello > $BDA_OUTPUTS_DIR/message", &[], &["message"]),
+            ),
+            (
+                "/latest/default/function/shout".to_string(),
+                function(
+                    "echo \"$BDA_INPUT_MESSAGE!\" > $BDA_OUTPUTS_DIR/message",
+                    &["message"],
+                    &["message"],
+                ),
+            ),
+            (
+                "/latest/default/function/fail".to_string(),
+                function("exit 1", &[], &[]),
+            ),
+            (
+                "/latest/default/function/sleep".to_string(),
+                function("sleep 30", &[], &[]),
+            ),
+        ])
+    }
+
+    fn statuses(record: &DagRunRecord) -> Vec<(&str, NodeStatus)> {
+        record
+            .nodes
+            .iter()
+            .map(|(name, run)| (name.as_str(), run.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_wires_outputs() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let runner = DagRunner::new(Arc::new(LocalExecutor::new(tmp.path())));
+        let d = dag(
+            &[("a", "hello"), ("b", "shout"), ("c", "shout")],
+            &[("a", "b"), ("b", "c")],
+            FailurePolicy::FailFast,
+        );
+        let record = runner.run("d", &d, &functions()).await.unwrap();
+        assert!(record.succeeded(), "{:?}", record);
+        let c = record.nodes["c"].run.as_ref().unwrap();
+        assert_eq!(c.outputs["message"], JValue::String("hello!!".to_string()));
+        assert!(record.work_dir.join(DAG_RUN_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_artifacts() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let store = Arc::new(ArtifactStore::new(tmp.path().join("artifacts")));
+        let executor = LocalExecutor::new(tmp.path().join("runs")).with_artifacts(store.clone());
+        let runner = DagRunner::new(Arc::new(executor));
+        let path = |mut f: Function| {
+            for p in f.inputs.iter_mut().chain(f.outputs.iter_mut()) {
+                p.parameter_kind = ParameterKind::Path as i32;
+            }
+            f
+        };
+        let functions = BTreeMap::from([
+            (
+                "/latest/default/function/hello".to_string(),
+                path(function(
+                    "echo hello > $BDA_OUTPUTS_DIR/message",
+                    &[],
+                    &["message"],
+                )),
+            ),
+            (
+                "/latest/default/function/shout".to_string(),
+                path(function(
+                    "echo \"$(cat $BDA_INPUT_MESSAGE)!\" > $BDA_OUTPUTS_DIR/message",
+                    &["message"],
+                    &["message"],
+                )),
+            ),
+        ]);
+        let d = dag(
+            &[("a", "hello"), ("b", "shout")],
+            &[("a", "b")],
+            FailurePolicy::FailFast,
+        );
+        let record = runner.run("d", &d, &functions).await.unwrap();
+        assert!(record.succeeded(), "{:?}", record);
+        let a = record.nodes["a"].run.as_ref().unwrap();
+        let b = record.nodes["b"].run.as_ref().unwrap();
+        let digest = &a.artifacts["message"];
+        assert_eq!(b.inputs["message"], artifacts::reference(digest));
+        let copy = b.work_dir.join(executor::ARTIFACTS_DIR).join("message");
+        assert_eq!(std::fs::read_to_string(copy).unwrap(), "hello\n");
+        let shouted = store.get(&b.artifacts["message"]).unwrap();
+        assert_eq!(shouted.digest, crate::cache::digest(b"hello!\n"));
+    }
+
+    #[tokio::test]
+    async fn test_run_continue() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let runner = DagRunner::new(Arc::new(LocalExecutor::new(tmp.path())));
+        let d = dag(
+            &[("a", "fail"), ("b", "shout"), ("c", "hello")],
+            &[("a", "b")],
+            FailurePolicy::Continue,
+        );
+        let record = runner.run("d", &d, &functions()).await.unwrap();
+        assert_eq!(
+            statuses(&record),
+            vec![
+                ("a", NodeStatus::Failed),
+                ("b", NodeStatus::Skipped),
+                ("c", NodeStatus::Succeeded)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_fail_fast() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let runner = DagRunner::new(Arc::new(LocalExecutor::new(tmp.path())));
+        let d = dag(
+            &[("a", "sleep"), ("b", "fail"), ("c", "hello")],
+            &[],
+            FailurePolicy::FailFast,
+        );
+        let record = runner.run("d", &d, &functions()).await.unwrap();
+        assert_eq!(
+            statuses(&record),
+            vec![
+                ("a", NodeStatus::Cancelled),
+                ("b", NodeStatus::Failed),
+                ("c", NodeStatus::Skipped)
+            ]
+        );
+    }
+}
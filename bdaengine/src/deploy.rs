@@ -0,0 +1,772 @@
+use crate::executor;
+use crate::queue::{JobState, RunQueue};
+use crate::service::{self, args_to_struct, struct_to_args};
+use bdacore::data::EntityID;
+use bdacore::logic;
+use bdaproto::bda_client::BdaClient;
+use bdaproto::deployer::Stage;
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Applier, Deployer, GetResourceRequest, GetResourcesRequest, SubmitRunRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tonic::{transport::Channel, Status};
+
+/// Interval between two checks of the deploy runs.
+pub const POLL: Duration = Duration::from_secs(1);
+/// Polls between two reconciliations of the appliers.
+pub const RECONCILE_POLLS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeploymentState {
+    AwaitingApproval,
+    /// The run of the deploy function is starting or running.
+    Deploying,
+    Deployed,
+    Failed,
+}
+
+/// Bundle deployed to a stage of a deployer. Deployments are stored as `<id>.json` in the
+/// deployment directory, and updated on every state change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    pub id: String,
+    pub deployer: String,
+    pub stage: String,
+    pub bundle: String,
+    pub semver: String,
+    pub state: DeploymentState,
+    /// Run of the deploy function, once started.
+    pub run: Option<String>,
+    pub rollback: bool,
+    pub created_at: u64,
+    pub finished_at: u64,
+    pub error: Option<String>,
+}
+
+impl Deployment {
+    /// Whether it deploys the same bundle at the same semantic version, whatever the version of
+    /// the resource the bundle was fetched from.
+    pub fn deploys(&self, bundle: &str, semver: &str) -> bool {
+        self.semver == semver && bundle_name(&self.bundle) == bundle_name(bundle)
+    }
+
+    fn in_progress(&self) -> bool {
+        matches!(
+            self.state,
+            DeploymentState::AwaitingApproval | DeploymentState::Deploying
+        )
+    }
+}
+
+/// Namespace and name of a bundle resource id.
+fn bundle_name(id: &str) -> (String, String) {
+    match logic::parse_resource_id(id) {
+        Ok((_, namespace, _, name)) => (namespace, name),
+        Err(_) => (String::new(), id.to_string()),
+    }
+}
+
+pub struct DeploymentStore {
+    dir: PathBuf,
+}
+
+impl DeploymentStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        DeploymentStore { dir: dir.into() }
+    }
+
+    pub fn save(&self, d: &Deployment) -> Result<(), String> {
+        let path = self.dir.join(format!("{}.json", d.id));
+        let tmp = self.dir.join(format!("{}.json.tmp", d.id));
+        let content = serde_json::to_vec_pretty(d).map_err(|e| e.to_string())?;
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| format!("could not save deployment {}: {}", d.id, e))
+    }
+
+    /// Loads every deployment of the store, oldest first, creating its directory when missing.
+    pub fn load(&self) -> Result<Vec<Deployment>, String> {
+        let read = |e: std::io::Error| format!("could not read {}: {}", self.dir.display(), e);
+        std::fs::create_dir_all(&self.dir).map_err(read)?;
+        let mut deployments: Vec<Deployment> = Vec::new();
+        for entry in std::fs::read_dir(&self.dir).map_err(read)? {
+            let path = entry.map_err(read)?.path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            let content = std::fs::read(&path).map_err(read)?;
+            let d = serde_json::from_slice(&content)
+                .map_err(|e| format!("invalid deployment {}: {}", path.display(), e))?;
+            deployments.push(d);
+        }
+        deployments.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(deployments)
+    }
+}
+
+/// Durable history of the deployments, enforcing the promotion rules of the deployers: a bundle
+/// is deployed to the stages in order, one deployment at a time per stage, and waits for an
+/// approval in the stages with an approval gate. The bundle deployed in a stage is the one of
+/// its last successful deployment.
+pub struct Deployments {
+    store: DeploymentStore,
+    deployments: Mutex<Vec<Deployment>>,
+}
+
+impl Deployments {
+    pub fn open<P: Into<PathBuf>>(dir: P) -> Result<Self, String> {
+        let store = DeploymentStore::new(dir);
+        let deployments = Mutex::new(store.load()?);
+        Ok(Deployments { store, deployments })
+    }
+
+    pub fn get(&self, id: &str) -> Option<Deployment> {
+        let deployments = self.deployments.lock().ok()?;
+        deployments.iter().find(|d| d.id == id).cloned()
+    }
+
+    /// Deployments of a deployer, or of all of them, most recent first. With `current`, only
+    /// the deployment in place in each stage.
+    pub fn list(&self, deployer: Option<&str>, current: bool) -> Vec<Deployment> {
+        let deployments = match self.deployments.lock() {
+            Ok(deployments) => deployments,
+            Err(_) => return vec![],
+        };
+        deployments
+            .iter()
+            .rev()
+            .filter(|d| deployer.map_or(true, |id| d.deployer == id))
+            .filter(|d| {
+                !current
+                    || find_current(&deployments, &d.deployer, &d.stage)
+                        .map_or(false, |c| c.id == d.id)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Records the deployment of a bundle to a stage, the stage after the last one the bundle
+    /// is deployed to by default. It awaits an approval when the stage has an approval gate, or
+    /// is deploying and waits for its run to start.
+    pub fn promote(
+        &self,
+        deployer_id: &str,
+        deployer: &Deployer,
+        stage: Option<&str>,
+        bundle: &str,
+        semver: &str,
+        now: u64,
+    ) -> Result<Deployment, String> {
+        let mut deployments = self.deployments.lock().map_err(|e| e.to_string())?;
+        let stage = match stage {
+            Some(stage) => stage.to_string(),
+            None => next_stage(&deployments, deployer_id, deployer, bundle, semver)?,
+        };
+        let i = stage_index(deployer, &stage)?;
+        check_idle(&deployments, deployer_id, &stage)?;
+        if let Some(current) = find_current(&deployments, deployer_id, &stage) {
+            if current.deploys(bundle, semver) {
+                return Err(format!(
+                    "{} {} is already deployed to {}",
+                    bundle, semver, stage
+                ));
+            }
+        }
+        if i > 0 {
+            let previous = &deployer.stages[i - 1].name;
+            match find_current(&deployments, deployer_id, previous) {
+                Some(d) if d.deploys(bundle, semver) => {}
+                _ => {
+                    return Err(format!(
+                        "{} {} must be deployed to {} before {}",
+                        bundle, semver, previous, stage
+                    ))
+                }
+            }
+        }
+        let state = if deployer.stages[i].approval {
+            DeploymentState::AwaitingApproval
+        } else {
+            DeploymentState::Deploying
+        };
+        let d = new_deployment(deployer_id, &stage, bundle, semver, state, now);
+        self.store.save(&d)?;
+        deployments.push(d.clone());
+        Ok(d)
+    }
+
+    /// Deploys back the bundle of a stage deployed before the current one. Rollbacks skip the
+    /// approval gate and the promotion order.
+    pub fn rollback(
+        &self,
+        deployer_id: &str,
+        deployer: &Deployer,
+        stage: &str,
+        now: u64,
+    ) -> Result<Deployment, String> {
+        let mut deployments = self.deployments.lock().map_err(|e| e.to_string())?;
+        stage_index(deployer, stage)?;
+        check_idle(&deployments, deployer_id, stage)?;
+        let current = find_current(&deployments, deployer_id, stage)
+            .ok_or_else(|| format!("nothing is deployed to {}", stage))?;
+        let previous = deployments
+            .iter()
+            .rev()
+            .filter(|d| d.deployer == deployer_id && d.stage == stage)
+            .filter(|d| d.state == DeploymentState::Deployed)
+            .find(|d| !d.deploys(&current.bundle, &current.semver))
+            .ok_or_else(|| format!("no previous deployment of {} to roll back to", stage))?;
+        let mut d = new_deployment(
+            deployer_id,
+            stage,
+            &previous.bundle,
+            &previous.semver,
+            DeploymentState::Deploying,
+            now,
+        );
+        d.rollback = true;
+        self.store.save(&d)?;
+        deployments.push(d.clone());
+        Ok(d)
+    }
+
+    pub fn approve(&self, id: &str) -> Result<Deployment, String> {
+        self.update(id, |d| match d.state {
+            DeploymentState::AwaitingApproval => {
+                d.state = DeploymentState::Deploying;
+                Ok(())
+            }
+            state => Err(format!(
+                "deployment {} is not awaiting approval: {:?}",
+                id, state
+            )),
+        })
+    }
+
+    /// Records the run of the deploy function of a deploying deployment.
+    pub fn started(&self, id: &str, run: &str) -> Result<Deployment, String> {
+        self.update(id, |d| {
+            d.run = Some(run.to_string());
+            Ok(())
+        })
+    }
+
+    pub fn finish(&self, id: &str, error: Option<String>, now: u64) -> Result<Deployment, String> {
+        self.update(id, |d| {
+            d.state = match error {
+                None => DeploymentState::Deployed,
+                Some(_) => DeploymentState::Failed,
+            };
+            d.error = error;
+            d.finished_at = now;
+            Ok(())
+        })
+    }
+
+    /// Finishes the deployments whose run finished, given the state of a run by id.
+    pub fn settle<F>(&self, run_state: F, now: u64) -> Result<Vec<Deployment>, String>
+    where
+        F: Fn(&str) -> Option<JobState>,
+    {
+        let running: Vec<(String, String)> = match self.deployments.lock() {
+            Ok(deployments) => deployments
+                .iter()
+                .filter(|d| d.state == DeploymentState::Deploying)
+                .filter_map(|d| Some((d.id.clone(), d.run.clone()?)))
+                .collect(),
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut settled = Vec::new();
+        for (id, run) in running {
+            let error = match run_state(&run) {
+                Some(JobState::Succeeded) => None,
+                Some(state) if state.finished() => {
+                    Some(format!("deploy run {} ended {:?}", run, state))
+                }
+                Some(_) => continue,
+                None => Some(format!("deploy run {} not found", run)),
+            };
+            settled.push(self.finish(&id, error, now)?);
+        }
+        Ok(settled)
+    }
+
+    fn update<F>(&self, id: &str, f: F) -> Result<Deployment, String>
+    where
+        F: FnOnce(&mut Deployment) -> Result<(), String>,
+    {
+        let mut deployments = self.deployments.lock().map_err(|e| e.to_string())?;
+        let d = deployments
+            .iter_mut()
+            .find(|d| d.id == id)
+            .ok_or_else(|| format!("deployment not found: {}", id))?;
+        f(d)?;
+        self.store.save(d)?;
+        Ok(d.clone())
+    }
+
+    /// Settles the deploy runs every `POLL`, and reconciles every applier of the datastore
+    /// every `RECONCILE_POLLS`, until the engine stops.
+    pub async fn run(self: Arc<Self>, datastore: BdaClient<Channel>, queue: Arc<RunQueue>) {
+        let mut polls: u64 = 0;
+        loop {
+            tokio::time::sleep(POLL).await;
+            let settled = self.settle(|run| queue.get(run).map(|j| j.state), executor::now());
+            if let Err(e) = settled {
+                eprintln!("{}", e);
+            }
+            polls += 1;
+            if polls % RECONCILE_POLLS != 0 {
+                continue;
+            }
+            let request = GetResourcesRequest {
+                namespaces: "all".to_string(),
+                kinds: logic::APPLIER_KIND.to_string(),
+                ..Default::default()
+            };
+            let appliers = match datastore.clone().get_resources(request).await {
+                Ok(response) => response.into_inner().resources,
+                Err(e) => {
+                    eprintln!("cannot load the appliers: {}", e);
+                    continue;
+                }
+            };
+            for r in appliers {
+                let id = match logic::resource_id(&r) {
+                    Ok(EntityID::ResourceID(id)) => id,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        continue;
+                    }
+                };
+                let reconciled = reconcile(&mut datastore.clone(), &queue, &self, &id).await;
+                if let Err(e) = reconciled {
+                    eprintln!("applier {}: {}", id, e.message());
+                }
+            }
+        }
+    }
+}
+
+fn new_deployment(
+    deployer: &str,
+    stage: &str,
+    bundle: &str,
+    semver: &str,
+    state: DeploymentState,
+    now: u64,
+) -> Deployment {
+    Deployment {
+        id: executor::new_run_id(),
+        deployer: deployer.to_string(),
+        stage: stage.to_string(),
+        bundle: bundle.to_string(),
+        semver: semver.to_string(),
+        state,
+        run: None,
+        rollback: false,
+        created_at: now,
+        finished_at: 0,
+        error: None,
+    }
+}
+
+/// Last successful deployment of a stage.
+fn find_current<'a>(
+    deployments: &'a [Deployment],
+    deployer: &str,
+    stage: &str,
+) -> Option<&'a Deployment> {
+    deployments.iter().rev().find(|d| {
+        d.deployer == deployer && d.stage == stage && d.state == DeploymentState::Deployed
+    })
+}
+
+fn check_idle(deployments: &[Deployment], deployer: &str, stage: &str) -> Result<(), String> {
+    match deployments
+        .iter()
+        .find(|d| d.deployer == deployer && d.stage == stage && d.in_progress())
+    {
+        Some(d) => Err(format!(
+            "deployment {} to {} is in progress: {:?}",
+            d.id, stage, d.state
+        )),
+        None => Ok(()),
+    }
+}
+
+fn stage_index(deployer: &Deployer, stage: &str) -> Result<usize, String> {
+    deployer
+        .stages
+        .iter()
+        .position(|s| s.name == stage)
+        .ok_or_else(|| format!("stage {} not found", stage))
+}
+
+/// First stage the bundle is not deployed to.
+fn next_stage(
+    deployments: &[Deployment],
+    deployer_id: &str,
+    deployer: &Deployer,
+    bundle: &str,
+    semver: &str,
+) -> Result<String, String> {
+    deployer
+        .stages
+        .iter()
+        .find(|s| {
+            find_current(deployments, deployer_id, &s.name)
+                .map_or(true, |d| !d.deploys(bundle, semver))
+        })
+        .map(|s| s.name.clone())
+        .ok_or_else(|| format!("{} {} is deployed to every stage", bundle, semver))
+}
+
+/// Promotes a bundle fetched from the datastore, starting the deploy run unless the stage
+/// awaits an approval.
+pub async fn promote(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    deployments: &Deployments,
+    deployer_id: &str,
+    bundle: &str,
+    stage: Option<&str>,
+) -> Result<Deployment, Status> {
+    let deployer = fetch_deployer(datastore, deployer_id).await?;
+    let semver = fetch_semver(datastore, bundle).await?;
+    let d = deployments
+        .promote(
+            deployer_id,
+            &deployer,
+            stage,
+            bundle,
+            &semver,
+            executor::now(),
+        )
+        .map_err(Status::failed_precondition)?;
+    match d.state {
+        DeploymentState::Deploying => start(datastore, queue, deployments, &deployer, d).await,
+        _ => Ok(d),
+    }
+}
+
+pub async fn approve(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    deployments: &Deployments,
+    id: &str,
+) -> Result<Deployment, Status> {
+    let d = deployments
+        .get(id)
+        .ok_or_else(|| Status::not_found(format!("deployment not found: {}", id)))?;
+    let deployer = fetch_deployer(datastore, &d.deployer).await?;
+    let d = deployments
+        .approve(id)
+        .map_err(Status::failed_precondition)?;
+    start(datastore, queue, deployments, &deployer, d).await
+}
+
+pub async fn rollback(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    deployments: &Deployments,
+    deployer_id: &str,
+    stage: &str,
+) -> Result<Deployment, Status> {
+    let deployer = fetch_deployer(datastore, deployer_id).await?;
+    let d = deployments
+        .rollback(deployer_id, &deployer, stage, executor::now())
+        .map_err(Status::failed_precondition)?;
+    start(datastore, queue, deployments, &deployer, d).await
+}
+
+/// Promotes the bundles an applier wants in the stages of its deployer, unless the last
+/// deployment of the stage is of the wanted bundle, whatever its outcome: a failed deployment
+/// is promoted again by hand. Stages waiting for a deployment in progress or for the previous
+/// stage are left for a later reconciliation.
+pub async fn reconcile(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    deployments: &Deployments,
+    applier_id: &str,
+) -> Result<Vec<Deployment>, Status> {
+    let applier = fetch_applier(datastore, applier_id).await?;
+    let deployer = fetch_deployer(datastore, &applier.deployer).await?;
+    let mut promoted = Vec::new();
+    for stage in deployer.stages.iter() {
+        let bundle = match applier.stages.get(&stage.name) {
+            Some(bundle) => bundle,
+            None => continue,
+        };
+        let semver = fetch_semver(datastore, bundle).await?;
+        let listed = deployments.list(Some(&applier.deployer), false);
+        let last = listed.iter().find(|d| d.stage == stage.name);
+        if last.map_or(false, |d| d.deploys(bundle, &semver)) {
+            continue;
+        }
+        let now = executor::now();
+        let d = deployments.promote(
+            &applier.deployer,
+            &deployer,
+            Some(&stage.name),
+            bundle,
+            &semver,
+            now,
+        );
+        match d {
+            Ok(d) if d.state == DeploymentState::Deploying => {
+                promoted.push(start(datastore, queue, deployments, &deployer, d).await?)
+            }
+            Ok(d) => promoted.push(d),
+            // blocked until the previous stage or the deployment in progress is done
+            Err(_) => {}
+        }
+    }
+    Ok(promoted)
+}
+
+/// Submits the run of the deploy function, with the inputs of the stage and the deployed
+/// bundle. A run that cannot be submitted fails the deployment.
+async fn start(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    deployments: &Deployments,
+    deployer: &Deployer,
+    d: Deployment,
+) -> Result<Deployment, Status> {
+    let submitted = match deploy_request(deployer, &d) {
+        Ok(request) => service::submit(datastore, queue, request)
+            .await
+            .map_err(|e| e.message().to_string()),
+        Err(e) => Err(e),
+    };
+    let d = match submitted {
+        Ok(job) => deployments.started(&d.id, &job.id),
+        Err(e) => deployments.finish(&d.id, Some(e), executor::now()),
+    };
+    d.map_err(Status::internal)
+}
+
+fn deploy_request(deployer: &Deployer, d: &Deployment) -> Result<SubmitRunRequest, String> {
+    let stage: &Stage = deployer
+        .stages
+        .iter()
+        .find(|s| s.name == d.stage)
+        .ok_or_else(|| format!("stage {} not found", d.stage))?;
+    let mut inputs = struct_to_args(stage.inputs.clone())?;
+    for (name, value) in logic::DEPLOY_INPUTS
+        .iter()
+        .zip([&d.bundle, &d.semver, &d.stage])
+    {
+        inputs.insert(name.to_string(), JValue::String(value.clone()));
+    }
+    let (version, namespace, _, name) = logic::parse_resource_id(&deployer.function)?;
+    Ok(SubmitRunRequest {
+        version,
+        namespace,
+        name,
+        inputs: Some(args_to_struct(&inputs)?),
+        runtime: String::new(),
+    })
+}
+
+async fn fetch_kind(datastore: &mut BdaClient<Channel>, id: &str) -> Result<ResourceKind, Status> {
+    let (version, namespace, kind, name) =
+        logic::parse_resource_id(id).map_err(Status::invalid_argument)?;
+    let request = GetResourceRequest {
+        version,
+        namespace,
+        kind,
+        name,
+    };
+    let resource = datastore.get_resource(request).await?.into_inner();
+    resource
+        .resource_kind
+        .ok_or_else(|| Status::not_found(format!("resource {} has no kind", id)))
+}
+
+async fn fetch_deployer(datastore: &mut BdaClient<Channel>, id: &str) -> Result<Deployer, Status> {
+    match fetch_kind(datastore, id).await? {
+        ResourceKind::Deployer(d) => Ok(d),
+        _ => Err(Status::invalid_argument(format!(
+            "resource {} is not a deployer",
+            id
+        ))),
+    }
+}
+
+async fn fetch_applier(datastore: &mut BdaClient<Channel>, id: &str) -> Result<Applier, Status> {
+    match fetch_kind(datastore, id).await? {
+        ResourceKind::Applier(a) => Ok(a),
+        _ => Err(Status::invalid_argument(format!(
+            "resource {} is not an applier",
+            id
+        ))),
+    }
+}
+
+async fn fetch_semver(datastore: &mut BdaClient<Channel>, id: &str) -> Result<String, Status> {
+    match fetch_kind(datastore, id).await? {
+        ResourceKind::Bundle(b) => Ok(b.semver),
+        _ => Err(Status::invalid_argument(format!(
+            "resource {} is not a bundle",
+            id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use tempdir::TempDir;
+
+    const DEPLOYER: &str = "/latest/default/deployer/web";
+    const BUNDLE: &str = "/latest/default/bundle/web";
+
+    fn deployer() -> Deployer {
+        let stage = |name: &str| Stage {
+            name: name.to_string(),
+            approval: name == "prod",
+            inputs: None,
+        };
+        Deployer {
+            function: "/latest/default/function/deploy".to_string(),
+            stages: vec![stage("dev"), stage("staging"), stage("prod")],
+        }
+    }
+
+    fn deploy(
+        deployments: &Deployments,
+        stage: Option<&str>,
+        semver: &str,
+        now: u64,
+    ) -> Deployment {
+        let d = deployments
+            .promote(DEPLOYER, &deployer(), stage, BUNDLE, semver, now)
+            .unwrap();
+        deployments
+            .started(&d.id, &format!("run-{}", d.id))
+            .unwrap();
+        deployments.finish(&d.id, None, now).unwrap()
+    }
+
+    #[test]
+    fn test_promote() {
+        let tmp = TempDir::new("deploy").unwrap();
+        let deployments = Deployments::open(tmp.path()).unwrap();
+        let d = deployer();
+        let promote = |stage: Option<&str>, semver: &str, now: u64| {
+            deployments.promote(DEPLOYER, &d, stage, BUNDLE, semver, now)
+        };
+        // stages are deployed in order
+        assert!(promote(Some("staging"), "1.0.0", 1).is_err());
+        assert!(promote(Some("qa"), "1.0.0", 1).is_err());
+        let dev = promote(None, "1.0.0", 1).unwrap();
+        assert_eq!(dev.stage, "dev");
+        assert_eq!(dev.state, DeploymentState::Deploying);
+        // one deployment at a time per stage
+        assert!(promote(Some("dev"), "1.1.0", 2).is_err());
+        deployments.finish(&dev.id, None, 2).unwrap();
+        assert!(promote(Some("dev"), "1.0.0", 3).is_err());
+        assert_eq!(deploy(&deployments, None, "1.0.0", 3).stage, "staging");
+        // the approval gate of prod
+        let prod = promote(None, "1.0.0", 4).unwrap();
+        assert_eq!(prod.stage, "prod");
+        assert_eq!(prod.state, DeploymentState::AwaitingApproval);
+        let approved = deployments.approve(&prod.id).unwrap();
+        assert_eq!(approved.state, DeploymentState::Deploying);
+        assert!(deployments.approve(&prod.id).is_err());
+        deployments.finish(&prod.id, None, 5).unwrap();
+        assert!(promote(None, "1.0.0", 6).is_err());
+        // the bundle fetched at another resource version is the same release
+        let fixed = "/1.0.0/default/bundle/web";
+        assert!(deployments
+            .promote(DEPLOYER, &d, Some("dev"), fixed, "1.0.0", 6)
+            .is_err());
+
+        let current = deployments.list(Some(DEPLOYER), true);
+        let stages: Vec<&str> = current.iter().map(|d| d.stage.as_str()).collect();
+        assert_eq!(stages, vec!["prod", "staging", "dev"]);
+        // reloaded from the store
+        let reopened = Deployments::open(tmp.path()).unwrap();
+        assert_eq!(reopened.list(None, false), deployments.list(None, false));
+    }
+
+    #[test]
+    fn test_rollback() {
+        let tmp = TempDir::new("deploy").unwrap();
+        let deployments = Deployments::open(tmp.path()).unwrap();
+        let d = deployer();
+        assert!(deployments.rollback(DEPLOYER, &d, "dev", 1).is_err());
+        deploy(&deployments, Some("dev"), "1.0.0", 1);
+        assert!(deployments.rollback(DEPLOYER, &d, "dev", 2).is_err());
+        deploy(&deployments, Some("dev"), "1.1.0", 2);
+        let rollback = deployments.rollback(DEPLOYER, &d, "dev", 3).unwrap();
+        assert!(rollback.rollback);
+        assert_eq!(rollback.semver, "1.0.0");
+        assert_eq!(rollback.state, DeploymentState::Deploying);
+        deployments.finish(&rollback.id, None, 4).unwrap();
+        let current = deployments.list(Some(DEPLOYER), true);
+        assert_eq!(current[0].semver, "1.0.0");
+    }
+
+    #[test]
+    fn test_settle() {
+        let tmp = TempDir::new("deploy").unwrap();
+        let deployments = Deployments::open(tmp.path()).unwrap();
+        let d = deployer();
+        let dev = deployments
+            .promote(DEPLOYER, &d, None, BUNDLE, "1.0.0", 1)
+            .unwrap();
+        deployments.started(&dev.id, "run-1").unwrap();
+        let running = |_: &str| Some(JobState::Running);
+        assert!(deployments.settle(running, 2).unwrap().is_empty());
+        let failed = |_: &str| Some(JobState::Failed);
+        let settled = deployments.settle(failed, 3).unwrap();
+        assert_eq!(settled[0].state, DeploymentState::Failed);
+        assert_eq!(settled[0].finished_at, 3);
+        // a failed deployment is not current, and another one may start
+        assert!(deployments.list(None, true).is_empty());
+        let dev = deployments
+            .promote(DEPLOYER, &d, None, BUNDLE, "1.0.0", 4)
+            .unwrap();
+        deployments.started(&dev.id, "run-2").unwrap();
+        let succeeded = |_: &str| Some(JobState::Succeeded);
+        let settled = deployments.settle(succeeded, 5).unwrap();
+        assert_eq!(settled[0].state, DeploymentState::Deployed);
+    }
+
+    #[test]
+    fn test_deploy_request() {
+        let mut d = deployer();
+        d.stages[0].inputs =
+            serde_json::from_value(serde_json::json!({"cluster": "eu-1"})).unwrap();
+        let deployment = new_deployment(
+            DEPLOYER,
+            "dev",
+            BUNDLE,
+            "1.0.0",
+            DeploymentState::Deploying,
+            1,
+        );
+        let request = deploy_request(&d, &deployment).unwrap();
+        assert_eq!(request.name, "deploy");
+        assert_eq!(
+            struct_to_args(request.inputs).unwrap(),
+            serde_json::from_value(serde_json::json!({
+                "cluster": "eu-1",
+                "bundle": BUNDLE,
+                "semver": "1.0.0",
+                "stage": "dev",
+            }))
+            .unwrap()
+        );
+    }
+}
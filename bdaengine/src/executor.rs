@@ -0,0 +1,1030 @@
+use crate::artifacts::ArtifactStore;
+use crate::container::{
+    self, ContainerDriver, ContainerSpec, ContainerTarget, Mount, CONTAINER_INPUTS_DIR,
+    CONTAINER_WORK_DIR,
+};
+use crate::logs::{LogLimits, LogWriter, MAX_LINE};
+use crate::secrets::{self, Injected, SecretSource};
+use bdacore::logic::binding::{
+    self, Binding, INPUTS_FILE, INPUTS_FILE_ENV, OUTPUTS_DIR, OUTPUTS_DIR_ENV, OUTPUTS_FILE,
+    OUTPUTS_FILE_ENV,
+};
+use bdaproto::parameter::ParameterKind;
+use bdaproto::Function;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+    process::{ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    fs,
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    process::{Child, Command},
+    sync::{watch, Mutex},
+};
+
+pub const RUN_FILE: &str = "run.json";
+/// Directory, in the run working directory, where the artifacts given as inputs are copied.
+pub const ARTIFACTS_DIR: &str = "artifacts";
+pub const STDOUT_FILE: &str = "stdout.log";
+pub const STDERR_FILE: &str = "stderr.log";
+/// Longest end of the standard output and error kept in run records, the whole outputs being in
+/// the log files of the run.
+pub const MAX_OUTPUT: usize = 64 * 1024;
+/// Delay between SIGTERM and SIGKILL when the run policy of a function sets none.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Result of running a function, also stored as `run.json` in the run working directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRecord {
+    pub id: String,
+    pub function: String,
+    pub inputs: BTreeMap<String, JValue>,
+    pub command: Vec<String>,
+    pub work_dir: PathBuf,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub exit_code: Option<i32>,
+    /// End of the standard output, at most `MAX_OUTPUT` bytes after a line telling how many bytes
+    /// were dropped before.
+    pub stdout: String,
+    /// End of the standard error, like `stdout`.
+    pub stderr: String,
+    pub error: Option<String>,
+    pub cancelled: bool,
+    #[serde(default)]
+    pub timed_out: bool,
+    #[serde(default)]
+    pub outputs: BTreeMap<String, JValue>,
+    /// Digests of the `PATH` outputs copied to the artifact store, by output name.
+    #[serde(default)]
+    pub artifacts: BTreeMap<String, String>,
+}
+impl RunRecord {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none() && self.exit_code == Some(0)
+    }
+}
+
+/// Runs functions as local subprocesses, each one in its own directory below `work_root`.
+/// Functions may also run in container runtimes, when a container driver is configured.
+pub struct LocalExecutor {
+    work_root: PathBuf,
+    containers: Option<Containers>,
+    logs: LogLimits,
+    artifacts: Option<Arc<ArtifactStore>>,
+    secrets: Option<Arc<dyn SecretSource>>,
+}
+
+/// Container driver used to run functions in container runtimes.
+pub struct Containers {
+    pub driver: Arc<dyn ContainerDriver>,
+    /// Directory the dockerfiles of the runtimes are relative to.
+    pub context: PathBuf,
+    /// Keeps the images built for the runs instead of removing them afterwards.
+    pub keep_images: bool,
+}
+
+pub fn new<P: Into<PathBuf>>(work_root: P) -> LocalExecutor {
+    LocalExecutor::new(work_root)
+}
+
+impl LocalExecutor {
+    pub fn new<P: Into<PathBuf>>(work_root: P) -> Self {
+        LocalExecutor {
+            work_root: work_root.into(),
+            containers: None,
+            logs: LogLimits::default(),
+            artifacts: None,
+            secrets: None,
+        }
+    }
+
+    pub fn with_containers(mut self, containers: Containers) -> Self {
+        self.containers = Some(containers);
+        self
+    }
+
+    pub fn with_log_limits(mut self, logs: LogLimits) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    /// Materializes the artifacts given as `PATH` inputs and stores the `PATH` outputs.
+    pub fn with_artifacts(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Resolves the secrets referenced by functions.
+    pub fn with_secrets(mut self, secrets: Arc<dyn SecretSource>) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Working directory of a run.
+    pub fn work_dir(&self, id: &str) -> PathBuf {
+        self.work_root.join(id)
+    }
+
+    /// Binds the arguments to the function inputs, resolving relative paths against the current
+    /// directory, and runs its `baseCommand`.
+    pub async fn run(
+        &self,
+        function_id: &str,
+        function: &Function,
+        args: &BTreeMap<String, JValue>,
+    ) -> Result<RunRecord, Box<dyn Error>> {
+        let binding = binding::bind(&function.inputs, args, &std::env::current_dir()?)?;
+        let (_cancel, cancelled) = watch::channel(false);
+        self.run_cancellable(&new_run_id(), function_id, function, &binding, cancelled)
+            .await
+    }
+
+    /// Runs the `baseCommand` of a function with bound inputs, stopping the process when `true` is
+    /// sent through the `cancel` channel or when the run policy timeout expires. Stopping sends
+    /// SIGTERM to the process group, then SIGKILL after the run policy grace period. The process
+    /// starts with a cleared environment holding only `PATH`, `HOME` (the working directory) and
+    /// the variables of `run_env`. Standard output and error are written to the working directory
+    /// while the process runs. The outputs of a successful run are collected as described by
+    /// `collect_outputs`, failing the run when they do not match the function outputs. With an
+    /// artifact store, the artifacts given as `PATH` inputs are copied to the working directory
+    /// first, and the `PATH` outputs are stored afterwards. The secrets referenced by the function
+    /// are given as variables or as files, removed when the run ends.
+    pub async fn run_cancellable(
+        &self,
+        id: &str,
+        function_id: &str,
+        function: &Function,
+        binding: &Binding,
+        cancel: watch::Receiver<bool>,
+    ) -> Result<RunRecord, Box<dyn Error>> {
+        let materialized = self.materialize(id, function, binding).await?;
+        let work_dir = self.prepare(id, &materialized).await?;
+        let base_command = materialized.command(&function.base_command)?;
+        let mut command = process(&base_command, function_id)?;
+        let injected = self.inject(function, &work_dir).await?;
+        command
+            .env_clear()
+            .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+            .env("HOME", &work_dir)
+            .envs(run_env(id, &work_dir, &materialized))
+            .envs(injected.env.iter().cloned());
+        let result = self
+            .execute(
+                id,
+                function_id,
+                function,
+                binding,
+                base_command,
+                command,
+                cancel,
+            )
+            .await;
+        injected.remove();
+        result
+    }
+
+    /// Same as `run_cancellable`, running the function in a container built from the runtime
+    /// dockerfile. The working directory is mounted at `/bda/work` and `PATH` inputs at
+    /// `/bda/inputs/<name>`, so the paths given to the function are the ones inside the
+    /// container. `PATH` outputs written to `outputs.json` must be relative to the working
+    /// directory. The container, and unless `keep_images` is set the image, are removed
+    /// afterwards, also when the run fails to start. Secret variables are passed to the container
+    /// by name only.
+    pub async fn run_in_container(
+        &self,
+        id: &str,
+        function_id: &str,
+        function: &Function,
+        target: &ContainerTarget,
+        binding: &Binding,
+        cancel: watch::Receiver<bool>,
+    ) -> Result<RunRecord, Box<dyn Error>> {
+        let containers = self
+            .containers
+            .as_ref()
+            .ok_or("no container driver is configured")?;
+        let image = container::image_name(&target.runtime)?;
+        let work_dir = self.work_dir(id);
+        let materialized = self.materialize(id, function, binding).await?;
+        let (inside, mut mounts) = container_binding(function, &materialized);
+        mounts.push(Mount {
+            source: work_dir.clone(),
+            target: CONTAINER_WORK_DIR.to_string(),
+            read_only: false,
+        });
+        self.prepare(id, &inside).await?;
+        containers
+            .driver
+            .build(&containers.context, &target.container.dockerfile, &image)
+            .await?;
+        let name = container::container_name(id);
+        let result = async {
+            let base_command = inside.command(&function.base_command)?;
+            let injected = self.inject(function, &work_dir).await?;
+            let spec = ContainerSpec {
+                name: name.clone(),
+                image: image.clone(),
+                work_dir: CONTAINER_WORK_DIR.to_string(),
+                mounts,
+                env: run_env(id, Path::new(CONTAINER_WORK_DIR), &inside),
+                inherited_env: injected.env.iter().map(|(name, _)| name.clone()).collect(),
+                command: base_command,
+            };
+            let host_command = containers.driver.run_command(&spec);
+            let result = match process(&host_command, function_id) {
+                Ok(mut command) => {
+                    command.envs(injected.env.iter().cloned());
+                    self.execute(
+                        id,
+                        function_id,
+                        function,
+                        binding,
+                        host_command,
+                        command,
+                        cancel,
+                    )
+                    .await
+                    .map_err(|e| e.to_string())
+                }
+                Err(e) => Err(e),
+            };
+            injected.remove();
+            result
+        }
+        .await;
+        let mut cleanup = vec![containers.driver.remove_container(&name).await];
+        if !containers.keep_images {
+            cleanup.push(containers.driver.remove_image(&image).await);
+        }
+        let mut record = result?;
+        if let Some(Err(e)) = cleanup.into_iter().find(|r| r.is_err()) {
+            record.error = record.error.or(Some(format!("cleanup failed: {}", e)));
+            save(&record).await?;
+        }
+        Ok(record)
+    }
+
+    /// Injects the secrets referenced by a function, failing when there are some and no secret
+    /// source.
+    async fn inject(&self, function: &Function, work_dir: &Path) -> Result<Injected, String> {
+        if function.secrets.is_empty() {
+            return Ok(Injected::default());
+        }
+        let source = self
+            .secrets
+            .as_ref()
+            .ok_or("the function references secrets but no secret source is configured")?;
+        secrets::inject(source.as_ref(), &function.secrets, work_dir).await
+    }
+
+    /// Binding with the artifacts given as `PATH` inputs copied to the `artifacts` directory of
+    /// the run, named after their input.
+    async fn materialize(
+        &self,
+        id: &str,
+        function: &Function,
+        binding: &Binding,
+    ) -> Result<Binding, Box<dyn Error>> {
+        let mut materialized = binding.clone();
+        for p in function.inputs.iter() {
+            if p.parameter_kind != ParameterKind::Path as i32 {
+                continue;
+            }
+            let digest = match binding
+                .inputs
+                .get(&p.name)
+                .and_then(binding::artifact_digest)
+            {
+                Some(digest) => digest.to_string(),
+                None => continue,
+            };
+            let store = self
+                .artifacts
+                .clone()
+                .ok_or("no artifact store is configured")?;
+            let target = self.work_dir(id).join(ARTIFACTS_DIR).join(&p.name);
+            let path = target.clone();
+            tokio::task::spawn_blocking(move || store.materialize(&digest, &path))
+                .await?
+                .map_err(|e| format!("could not materialize input {}: {}", p.name, e))?;
+            materialized
+                .inputs
+                .insert(p.name.clone(), JValue::String(target.display().to_string()));
+        }
+        Ok(materialized)
+    }
+
+    /// Copies the `PATH` outputs of a run to the artifact store, when there is one.
+    async fn store_outputs(
+        &self,
+        function: &Function,
+        record: &mut RunRecord,
+    ) -> Result<(), Box<dyn Error>> {
+        let store = match self.artifacts {
+            Some(ref store) => store,
+            None => return Ok(()),
+        };
+        for p in function.outputs.iter() {
+            if p.parameter_kind != ParameterKind::Path as i32 {
+                continue;
+            }
+            let value = match record.outputs.get(&p.name) {
+                Some(value) => value,
+                None => continue,
+            };
+            let digest = match (binding::artifact_digest(value), value.as_str()) {
+                (Some(digest), _) => digest.to_string(),
+                (None, Some(path)) => {
+                    let (store, path) = (store.clone(), PathBuf::from(path));
+                    tokio::task::spawn_blocking(move || store.put(&path))
+                        .await?
+                        .map_err(|e| format!("could not store output {}: {}", p.name, e))?
+                        .digest
+                }
+                (None, None) => continue,
+            };
+            record.artifacts.insert(p.name.clone(), digest);
+        }
+        Ok(())
+    }
+
+    /// Creates the working directory of a run, with the inputs file and the outputs directory.
+    async fn prepare(&self, id: &str, binding: &Binding) -> Result<PathBuf, Box<dyn Error>> {
+        let work_dir = self.work_dir(id);
+        fs::create_dir_all(work_dir.join(OUTPUTS_DIR)).await?;
+        fs::write(work_dir.join(INPUTS_FILE), binding.to_json()?).await?;
+        Ok(work_dir)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn execute(
+        &self,
+        id: &str,
+        function_id: &str,
+        function: &Function,
+        binding: &Binding,
+        argv: Vec<String>,
+        mut command: Command,
+        mut cancel: watch::Receiver<bool>,
+    ) -> Result<RunRecord, Box<dyn Error>> {
+        let work_dir = self.work_dir(id);
+        command
+            .current_dir(&work_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        own_process_group(&mut command);
+        let mut record = RunRecord {
+            id: id.to_string(),
+            function: function_id.to_string(),
+            inputs: binding.inputs.clone(),
+            command: argv,
+            work_dir: work_dir.clone(),
+            started_at: now(),
+            finished_at: 0,
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+            cancelled: false,
+            timed_out: false,
+            outputs: BTreeMap::new(),
+            artifacts: BTreeMap::new(),
+        };
+        let policy = function.run_policy.clone().unwrap_or_default();
+        let timeout = match policy.timeout_seconds {
+            0 => None,
+            t => Some(Duration::from_secs(t as u64)),
+        };
+        let grace = match policy.grace_period_seconds {
+            0 => DEFAULT_GRACE_PERIOD,
+            g => Duration::from_secs(g as u64),
+        };
+        // created before the process is spawned, so that failing to create it leaves no process
+        let log = Arc::new(Mutex::new(LogWriter::create(&work_dir, self.logs).await?));
+        match command.spawn() {
+            Ok(mut child) => {
+                let stdout = tokio::spawn(capture(
+                    child.stdout.take(),
+                    work_dir.join(STDOUT_FILE),
+                    "stdout",
+                    self.logs,
+                    log.clone(),
+                ));
+                let stderr = tokio::spawn(capture(
+                    child.stderr.take(),
+                    work_dir.join(STDERR_FILE),
+                    "stderr",
+                    self.logs,
+                    log,
+                ));
+                let status = tokio::select! {
+                    status = child.wait() => status?,
+                    _ = cancelled(&mut cancel) => {
+                        record.cancelled = true;
+                        terminate(&mut child, grace).await?
+                    }
+                    _ = expired(timeout) => {
+                        record.timed_out = true;
+                        terminate(&mut child, grace).await?
+                    }
+                };
+                record.exit_code = status.code();
+                record.stdout = stdout.await??;
+                record.stderr = stderr.await??;
+                if record.cancelled {
+                    record.error = Some("run cancelled".to_string());
+                } else if record.timed_out {
+                    record.error = Some(format!("run timed out after {}s", policy.timeout_seconds));
+                } else if record.exit_code.is_none() {
+                    record.error = Some("process terminated by a signal".to_string());
+                } else if record.succeeded() {
+                    match collect_outputs(function, &work_dir, &record.stdout).await {
+                        Ok(outputs) => record.outputs = outputs,
+                        Err(e) => record.error = Some(e.to_string()),
+                    }
+                    if record.error.is_none() {
+                        if let Err(e) = self.store_outputs(function, &mut record).await {
+                            record.error = Some(e.to_string());
+                        }
+                    }
+                }
+            }
+            Err(e) => record.error = Some(format!("could not start {}: {}", record.command[0], e)),
+        }
+        record.finished_at = now();
+        save(&record).await?;
+        Ok(record)
+    }
+}
+
+fn process(argv: &[String], function_id: &str) -> Result<Command, String> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| format!("function {} has no baseCommand", function_id))?;
+    let mut command = Command::new(program);
+    command.args(args);
+    Ok(command)
+}
+
+/// Variables given to every run: `BDA_RUN_ID`, `BDA_WORK_DIR`, `BDA_INPUTS_FILE`,
+/// `BDA_OUTPUTS_FILE`, `BDA_OUTPUTS_DIR` and the input variables described by `Binding`.
+pub fn run_env(id: &str, work_dir: &Path, binding: &Binding) -> Vec<(String, String)> {
+    let path = |p: &str| work_dir.join(p).display().to_string();
+    let mut env = vec![
+        ("BDA_RUN_ID".to_string(), id.to_string()),
+        ("BDA_WORK_DIR".to_string(), work_dir.display().to_string()),
+        (INPUTS_FILE_ENV.to_string(), path(INPUTS_FILE)),
+        (OUTPUTS_FILE_ENV.to_string(), path(OUTPUTS_FILE)),
+        (OUTPUTS_DIR_ENV.to_string(), path(OUTPUTS_DIR)),
+    ];
+    env.extend(binding.env());
+    env
+}
+
+/// Binding seen from inside a container, with every `PATH` input replaced by its mount point.
+fn container_binding(function: &Function, binding: &Binding) -> (Binding, Vec<Mount>) {
+    let mut inside = binding.clone();
+    let mut mounts = Vec::new();
+    for p in function.inputs.iter() {
+        if p.parameter_kind != ParameterKind::Path as i32 {
+            continue;
+        }
+        if let Some(JValue::String(source)) = inside.inputs.get(&p.name) {
+            let target = format!("{}/{}", CONTAINER_INPUTS_DIR, p.name);
+            mounts.push(Mount {
+                source: source.into(),
+                target: target.clone(),
+                read_only: true,
+            });
+            inside.inputs.insert(p.name.clone(), JValue::String(target));
+        }
+    }
+    (inside, mounts)
+}
+
+/// Collects the values produced by a run and checks them against the function outputs. Values
+/// come from, by precedence, the `outputs.json` object, the files of the `outputs` directory,
+/// named after each output, and the standard output when it is a JSON object. A file of the
+/// `outputs` directory holds the value text, or is itself the value of a `PATH` output. A standard
+/// output longer than `MAX_OUTPUT` is truncated, hence not read as an object.
+pub async fn collect_outputs(
+    function: &Function,
+    work_dir: &Path,
+    stdout: &str,
+) -> Result<BTreeMap<String, JValue>, Box<dyn Error>> {
+    let mut values = BTreeMap::new();
+    if let Ok(JValue::Object(fields)) = serde_json::from_str(stdout.trim()) {
+        values.extend(fields);
+    }
+    for p in function.outputs.iter() {
+        let path = work_dir.join(OUTPUTS_DIR).join(&p.name);
+        if !fs::metadata(&path).await.map_or(false, |m| m.is_file()) {
+            continue;
+        }
+        let value = if p.parameter_kind == ParameterKind::Path as i32 {
+            path.display().to_string()
+        } else {
+            fs::read_to_string(&path)
+                .await?
+                .trim_end_matches('\n')
+                .to_string()
+        };
+        values.insert(p.name.clone(), JValue::String(value));
+    }
+    match fs::read(work_dir.join(OUTPUTS_FILE)).await {
+        Ok(content) => match serde_json::from_slice(&content)? {
+            JValue::Object(fields) => values.extend(fields),
+            v => Err(format!(
+                "{} must hold a JSON object, got {}",
+                OUTPUTS_FILE, v
+            ))?,
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => Err(e)?,
+    }
+    Ok(binding::bind_outputs(&function.outputs, &values, work_dir)?)
+}
+
+/// Copies a process output to a file and its lines to the run log as it is produced, returning
+/// the end of the output as described by `RunRecord::stdout`. The file stops growing at the size
+/// the log limits allow the run log, ending with a line telling the output was truncated.
+async fn capture<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    path: PathBuf,
+    stream: &str,
+    limits: LogLimits,
+    log: Arc<Mutex<LogWriter>>,
+) -> io::Result<String> {
+    let mut file = fs::File::create(&path).await?;
+    let max_size = limits.max_bytes * limits.max_files as u64;
+    let (mut size, mut total) = (0u64, 0u64);
+    let mut tail = Vec::new();
+    let mut line = Vec::new();
+    if let Some(mut reader) = reader {
+        let mut buf = [0u8; 8192];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            let room = max_size.saturating_sub(size) as usize;
+            if room > 0 {
+                file.write_all(&buf[..n.min(room)]).await?;
+                if n > room {
+                    file.write_all(b"\n[output truncated, the run log keeps its end]\n")
+                        .await?;
+                }
+                file.flush().await?;
+                size += n as u64;
+            }
+            total += n as u64;
+            tail.extend_from_slice(&buf[..n]);
+            if tail.len() > 2 * MAX_OUTPUT {
+                tail.drain(..tail.len() - MAX_OUTPUT);
+            }
+            for b in buf[..n].iter() {
+                if *b == b'\n' {
+                    log.lock().await.write(stream, &line).await?;
+                    line.clear();
+                } else {
+                    line.push(*b);
+                    if line.len() >= MAX_LINE {
+                        log.lock().await.write(stream, &line).await?;
+                        line.clear();
+                    }
+                }
+            }
+        }
+    }
+    if !line.is_empty() {
+        log.lock().await.write(stream, &line).await?;
+    }
+    let kept = &tail[tail.len().saturating_sub(MAX_OUTPUT)..];
+    let text = String::from_utf8_lossy(kept).to_string();
+    Ok(match total - kept.len() as u64 {
+        0 => text,
+        dropped => format!("[{} bytes truncated, see the run log]\n{}", dropped, text),
+    })
+}
+
+/// Starts the process in its own group, so cancelling also kills the processes it spawned.
+#[cfg(unix)]
+fn own_process_group(command: &mut Command) {
+    unsafe {
+        command.pre_exec(|| {
+            libc::setpgid(0, 0);
+            Ok(())
+        });
+    }
+}
+#[cfg(not(unix))]
+fn own_process_group(_command: &mut Command) {}
+
+/// Asks the process group to stop with SIGTERM, and kills it when it is still running after
+/// the grace period.
+async fn terminate(child: &mut Child, grace: Duration) -> io::Result<ExitStatus> {
+    signal(child, Signal::Term)?;
+    match tokio::time::timeout(grace, child.wait()).await {
+        Ok(status) => status,
+        Err(_) => {
+            signal(child, Signal::Kill)?;
+            child.wait().await
+        }
+    }
+}
+
+enum Signal {
+    Term,
+    Kill,
+}
+
+#[cfg(unix)]
+fn signal(child: &mut Child, signal: Signal) -> io::Result<()> {
+    let sig = match signal {
+        Signal::Term => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+    };
+    match child.id() {
+        Some(pid) if unsafe { libc::kill(-(pid as i32), sig) } == 0 => Ok(()),
+        _ => child.start_kill(),
+    }
+}
+#[cfg(not(unix))]
+fn signal(child: &mut Child, _signal: Signal) -> io::Result<()> {
+    child.start_kill()
+}
+
+async fn expired(timeout: Option<Duration>) {
+    match timeout {
+        Some(timeout) => tokio::time::sleep(timeout).await,
+        None => std::future::pending::<()>().await,
+    }
+}
+
+async fn cancelled(cancel: &mut watch::Receiver<bool>) {
+    while !*cancel.borrow() {
+        if cancel.changed().await.is_err() {
+            // the sender is gone, so the run can no longer be cancelled
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+async fn save(record: &RunRecord) -> Result<(), Box<dyn Error>> {
+    let dir = Path::new(&record.work_dir);
+    fs::write(dir.join(RUN_FILE), serde_json::to_vec_pretty(record)?).await?;
+    Ok(())
+}
+
+/// Records of the runs below a working root, skipping the directories without one.
+pub async fn read_records(work_root: &Path) -> Result<Vec<RunRecord>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    let mut entries = match fs::read_dir(work_root).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(records),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let path = entry.path().join(RUN_FILE);
+        match fs::read(&path).await {
+            Ok(content) => records.push(
+                serde_json::from_slice(&content)
+                    .map_err(|e| format!("invalid run record {}: {}", path.display(), e))?,
+            ),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(records)
+}
+
+pub fn new_run_id() -> String {
+    format!(
+        "{:x}-{:x}-{:x}",
+        now(),
+        std::process::id(),
+        RUN_COUNTER.fetch_add(1, Ordering::SeqCst)
+    )
+}
+
+/// Milliseconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use crate::container::FakeDriver;
+    use crate::secrets::StaticSecrets;
+    use bdacore::logic::{new_function, new_parameter, new_parameter_with_default};
+    use bdaproto::parameter::ParameterKind;
+    use bdaproto::{Container, RunPolicy, SecretRef};
+    use pbjson_types::{value::Kind, Value};
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    #[tokio::test]
+    async fn test_run() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path());
+        let mut f = new_function(&[
+            "sh",
+            "-c",
+            "echo $BDA_INPUT_NAME $(inputs.name); pwd; echo oops >&2; exit 3",
+        ]);
+        f.inputs = vec![new_parameter_with_default(
+            "name",
+            ParameterKind::Generic,
+            Value {
+                kind: Some(Kind::StringValue("world".to_string())),
+            },
+        )];
+        let record = executor
+            .run("/latest/default/function/f", &f, &BTreeMap::new())
+            .await
+            .unwrap();
+        assert_eq!(record.exit_code, Some(3));
+        assert!(!record.succeeded());
+        assert_eq!(
+            record.stdout,
+            format!("world world\n{}\n", record.work_dir.display())
+        );
+        assert_eq!(record.stderr, "oops\n");
+        let saved: RunRecord =
+            serde_json::from_slice(&std::fs::read(record.work_dir.join(RUN_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(saved, record);
+        let inputs: JValue =
+            serde_json::from_slice(&std::fs::read(record.work_dir.join(INPUTS_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(inputs, json!({"name": "world"}));
+        let log = crate::logs::read(&record.work_dir, 0).await.unwrap();
+        let stdout: Vec<&str> = log
+            .iter()
+            .filter(|l| l.stream == "stdout")
+            .map(|l| l.line.as_str())
+            .collect();
+        let work_dir = record.work_dir.display().to_string();
+        assert_eq!(stdout, vec!["world world", work_dir.as_str()]);
+        assert_eq!(log.len(), 3);
+        assert!(log.iter().all(|l| l.time >= record.started_at));
+    }
+
+    #[tokio::test]
+    async fn test_run_outputs() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path());
+        let mut f = new_function(&[
+            "sh",
+            "-c",
+            r#"echo '{"count": "2", "ignored": true}'
+                echo '{"ok": true}' > $BDA_OUTPUTS_FILE
+                echo report > $BDA_OUTPUTS_DIR/report
+                echo hello > $BDA_OUTPUTS_DIR/message"#,
+        ]);
+        f.outputs = vec![
+            new_parameter("count", ParameterKind::Number),
+            new_parameter("ok", ParameterKind::Boolean),
+            new_parameter("report", ParameterKind::Path),
+            new_parameter("message", ParameterKind::Text),
+        ];
+        let record = executor.run("f", &f, &BTreeMap::new()).await.unwrap();
+        assert!(record.succeeded(), "{:?}", record.error);
+        assert_eq!(
+            record.outputs,
+            BTreeMap::from([
+                ("count".to_string(), json!(2.0)),
+                ("ok".to_string(), json!(true)),
+                (
+                    "report".to_string(),
+                    json!(record.work_dir.join("outputs/report").display().to_string())
+                ),
+                ("message".to_string(), json!("hello")),
+            ])
+        );
+        f.outputs
+            .push(new_parameter("missing", ParameterKind::Text));
+        let record = executor.run("f", &f, &BTreeMap::new()).await.unwrap();
+        assert!(!record.succeeded());
+        assert_eq!(record.exit_code, Some(0));
+        assert!(record.error.unwrap().contains(".outputs.missing"));
+    }
+
+    #[tokio::test]
+    async fn test_run_in_container() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        std::fs::write(tmp.path().join("Dockerfile"), "FROM alpine\n").unwrap();
+        std::fs::write(tmp.path().join("data.txt"), "content\n").unwrap();
+        let driver = Arc::new(FakeDriver::default());
+        let executor = LocalExecutor::new(tmp.path().join("runs")).with_containers(Containers {
+            driver: driver.clone(),
+            context: tmp.path().into(),
+            keep_images: false,
+        });
+        let mut f = new_function(&[
+            "sh",
+            "-c",
+            "cat $(inputs.data) > $BDA_OUTPUTS_DIR/copy; echo $BDA_WORK_DIR",
+        ]);
+        f.inputs = vec![new_parameter("data", ParameterKind::Path)];
+        f.outputs = vec![new_parameter("copy", ParameterKind::Generic)];
+        let args = BTreeMap::from([("data".to_string(), json!("data.txt"))]);
+        let binding = binding::bind(&f.inputs, &args, tmp.path()).unwrap();
+        let mut target = ContainerTarget {
+            runtime: "/latest/default/runtime.container/builder".to_string(),
+            container: Container {
+                dockerfile: "Dockerfile".to_string(),
+            },
+        };
+        let (_cancel, cancelled) = watch::channel(false);
+        let record = executor
+            .run_in_container("r1", "f", &f, &target, &binding, cancelled.clone())
+            .await
+            .unwrap();
+        assert!(record.succeeded(), "{:?}", record.error);
+        assert_eq!(record.outputs["copy"], json!("content"));
+        assert_eq!(record.stdout, format!("{}\n", record.work_dir.display()));
+        let inputs: JValue =
+            serde_json::from_slice(&std::fs::read(record.work_dir.join(INPUTS_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(inputs, json!({"data": "/bda/inputs/data"}));
+        assert_eq!(
+            *driver.calls.lock().unwrap(),
+            vec![
+                "build bda/default/builder:latest Dockerfile",
+                "run bda-r1 bda/default/builder:latest",
+                "rm bda-r1",
+                "rmi bda/default/builder:latest",
+            ]
+        );
+        target.container.dockerfile = "Missing".to_string();
+        assert!(executor
+            .run_in_container("r2", "f", &f, &target, &binding, cancelled.clone())
+            .await
+            .is_err());
+
+        target.container.dockerfile = "Dockerfile".to_string();
+        f.secrets = vec![SecretRef {
+            secret: "/latest/default/secret/deploy".to_string(),
+            key: "token".to_string(),
+            env: "TOKEN".to_string(),
+            path: String::new(),
+        }];
+        driver.calls.lock().unwrap().clear();
+        assert!(executor
+            .run_in_container("r3", "f", &f, &target, &binding, cancelled)
+            .await
+            .is_err());
+        assert_eq!(
+            *driver.calls.lock().unwrap(),
+            vec![
+                "build bda/default/builder:latest Dockerfile",
+                "rm bda-r3",
+                "rmi bda/default/builder:latest",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_secrets() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let secret_ref = |key: &str, env: &str, path: &str| SecretRef {
+            secret: "/latest/default/secret/deploy".to_string(),
+            key: key.to_string(),
+            env: env.to_string(),
+            path: path.to_string(),
+        };
+        let mut f = new_function(&["sh", "-c", r#"echo "$TOKEN $(cat keys/id)" | tr a-z A-Z"#]);
+        f.secrets = vec![
+            secret_ref("token", "TOKEN", ""),
+            secret_ref("key", "", "keys/id"),
+        ];
+        let executor = LocalExecutor::new(tmp.path());
+        assert!(executor
+            .run("/latest/default/function/f", &f, &BTreeMap::new())
+            .await
+            .is_err());
+
+        let values = BTreeMap::from([
+            ("token".to_string(), "s3cr3t".to_string()),
+            ("key".to_string(), "key".to_string()),
+        ]);
+        let secrets = StaticSecrets(BTreeMap::from([(
+            "/latest/default/secret/deploy".to_string(),
+            values,
+        )]));
+        let executor = LocalExecutor::new(tmp.path()).with_secrets(Arc::new(secrets));
+        let record = executor
+            .run("/latest/default/function/f", &f, &BTreeMap::new())
+            .await
+            .unwrap();
+        assert!(record.succeeded(), "{:?}", record);
+        assert_eq!(record.stdout, "S3CR3T KEY\n");
+        assert!(!record.work_dir.join("keys/id").exists());
+        let saved = std::fs::read_to_string(record.work_dir.join(RUN_FILE)).unwrap();
+        assert!(!saved.contains("s3cr3t"));
+    }
+
+    #[tokio::test]
+    async fn test_run_missing_program() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path());
+        let f = new_function(&["bda-no-such-program"]);
+        let record = executor.run("f", &f, &BTreeMap::new()).await.unwrap();
+        assert_eq!(record.exit_code, None);
+        assert!(record.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_run_timeout() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path());
+        let mut f = new_function(&[
+            "sh",
+            "-c",
+            "trap 'echo stopping' TERM; echo started; sleep 30 & wait",
+        ]);
+        f.run_policy = Some(RunPolicy {
+            timeout_seconds: 1,
+            grace_period_seconds: 1,
+            ..Default::default()
+        });
+        let record = executor.run("f", &f, &BTreeMap::new()).await.unwrap();
+        assert!(record.timed_out);
+        assert!(!record.cancelled);
+        assert_eq!(record.error, Some("run timed out after 1s".to_string()));
+        assert_eq!(record.stdout, "started\nstopping\n");
+        assert!(record.finished_at - record.started_at < 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_run_cancelled() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path());
+        let f = new_function(&["sh", "-c", "echo started; sleep 30"]);
+        let binding = Binding::default();
+        let (cancel, cancelled) = watch::channel(false);
+        let run = executor.run_cancellable("r1", "f", &f, &binding, cancelled);
+        let cancel = async {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            cancel.send(true).unwrap();
+        };
+        let (record, _) = tokio::join!(run, cancel);
+        let record = record.unwrap();
+        assert!(record.cancelled);
+        assert!(!record.succeeded());
+        assert_eq!(record.stdout, "started\n");
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("r1").join(STDOUT_FILE)).unwrap(),
+            "started\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_truncated_output() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = LocalExecutor::new(tmp.path()).with_log_limits(LogLimits {
+            max_bytes: 1000,
+            max_files: 2,
+        });
+        let f = new_function(&["sh", "-c", "yes 0123456789 | head -n 10000"]);
+        let record = executor.run("f", &f, &BTreeMap::new()).await.unwrap();
+        assert!(record.succeeded());
+        let (marker, end) = record.stdout.split_once('\n').unwrap();
+        assert_eq!(
+            marker,
+            format!(
+                "[{} bytes truncated, see the run log]",
+                110_000 - MAX_OUTPUT
+            )
+        );
+        assert_eq!(end.len(), MAX_OUTPUT);
+        assert!(end.ends_with("0123456789\n"));
+        let saved = std::fs::read_to_string(record.work_dir.join(STDOUT_FILE)).unwrap();
+        assert!(saved.starts_with("0123456789\n"));
+        assert!(saved.ends_with("\n[output truncated, the run log keeps its end]\n"));
+        assert_eq!(saved.len(), 2000 + 47);
+    }
+}
@@ -0,0 +1,201 @@
+pub mod artifacts;
+pub mod cache;
+pub mod container;
+pub mod coordinator;
+pub mod dag;
+pub mod deploy;
+pub mod executor;
+pub mod listener;
+pub mod logs;
+pub mod queue;
+pub mod secrets;
+pub mod service;
+pub mod worker;
+
+use bdacore::logic::{self, config};
+use bdaproto::bda_client::BdaClient;
+use bdaproto::resource::ResourceKind;
+use bdaproto::{Dag, Function, GetResourceRequest, Resource};
+use serde_json::Value as JValue;
+use std::collections::BTreeMap;
+use std::error::Error;
+use tonic::transport::Channel;
+use tonic::Code;
+
+pub async fn connect(endpoint: &str) -> Result<BdaClient<Channel>, Box<dyn Error>> {
+    Ok(BdaClient::connect(endpoint.to_string()).await?)
+}
+
+/// Fetches a function from the datastore, returning its resource ID and definition.
+pub async fn fetch_function(
+    client: &mut BdaClient<Channel>,
+    version: &str,
+    namespace: &str,
+    name: &str,
+) -> Result<(String, Function), Box<dyn Error>> {
+    let (id, _, f) = fetch_function_resource(client, version, namespace, name).await?;
+    Ok((id, f))
+}
+
+/// Same as `fetch_function`, also returning the whole resource. The config references in the
+/// default values of the function inputs are resolved, the resource is left untouched.
+pub async fn fetch_function_resource(
+    client: &mut BdaClient<Channel>,
+    version: &str,
+    namespace: &str,
+    name: &str,
+) -> Result<(String, Resource, Function), Box<dyn Error>> {
+    let (id, resource) = fetch_resource(client, version, namespace, "function", name).await?;
+    match resource.resource_kind {
+        Some(ResourceKind::Function(ref f)) => {
+            let mut f = f.clone();
+            let layers = fetch_config_layers(client, &resource.namespace, &f).await?;
+            f.inputs = config::resolve(&f.inputs, &layers)?;
+            Ok((id, resource, f))
+        }
+        _ => Err(format!("resource {} is not a function", id))?,
+    }
+}
+
+/// Fetches the configs referenced by the default values of the function inputs, at the latest
+/// version, from the namespace of the function then from the default namespace.
+pub async fn fetch_config_layers(
+    client: &mut BdaClient<Channel>,
+    namespace: &str,
+    function: &Function,
+) -> Result<config::Layers, Box<dyn Error>> {
+    let names = config::referenced(&function.inputs);
+    let mut namespaces = vec![namespace];
+    if namespace != logic::DEFAULT_NAMESPACE {
+        namespaces.push(logic::DEFAULT_NAMESPACE);
+    }
+    let mut layers = config::Layers::default();
+    for namespace in namespaces {
+        let mut layer = BTreeMap::new();
+        for name in names.iter() {
+            let request = GetResourceRequest {
+                version: logic::DEFAULT_VERSION.to_string(),
+                namespace: namespace.to_string(),
+                kind: logic::CONFIG_KIND.to_string(),
+                name: name.to_string(),
+            };
+            match client.get_resource(request).await {
+                Ok(response) => {
+                    if let Some(ResourceKind::Config(c)) = response.into_inner().resource_kind {
+                        layer.insert(name.clone(), c);
+                    }
+                }
+                Err(status) if status.code() == Code::NotFound => {}
+                Err(status) => return Err(Box::new(status)),
+            }
+        }
+        layers.0.push(layer);
+    }
+    Ok(layers)
+}
+
+/// Fetches a dag from the datastore, returning its resource ID and definition.
+pub async fn fetch_dag(
+    client: &mut BdaClient<Channel>,
+    version: &str,
+    namespace: &str,
+    name: &str,
+) -> Result<(String, Dag), Box<dyn Error>> {
+    let (id, resource) = fetch_resource(client, version, namespace, "dag", name).await?;
+    match resource.resource_kind {
+        Some(ResourceKind::Dag(d)) => Ok((id, d)),
+        _ => Err(format!("resource {} is not a dag", id))?,
+    }
+}
+
+/// Fetches the functions referenced by the nodes of a dag, by resource ID. The nodes are pinned
+/// to the version their function resolves to, so that runs record the version they ran.
+pub async fn fetch_dag_functions(
+    client: &mut BdaClient<Channel>,
+    dag: &mut Dag,
+) -> Result<BTreeMap<String, Function>, Box<dyn Error>> {
+    let mut functions = BTreeMap::new();
+    let mut pinned: BTreeMap<String, String> = BTreeMap::new();
+    for node in dag.nodes.iter_mut() {
+        if let Some(id) = pinned.get(&node.function) {
+            node.function = id.clone();
+            continue;
+        }
+        let (version, namespace, _, name) = logic::parse_resource_id(&node.function)?;
+        let (id, f) = fetch_function(client, &version, &namespace, &name).await?;
+        pinned.insert(node.function.clone(), id.clone());
+        node.function = id.clone();
+        functions.insert(id, f);
+    }
+    Ok(functions)
+}
+
+/// Fetches a container runtime from the datastore by resource ID.
+pub async fn fetch_container_target(
+    client: &mut BdaClient<Channel>,
+    runtime: &str,
+) -> Result<container::ContainerTarget, Box<dyn Error>> {
+    let (version, namespace, kind, name) = logic::parse_resource_id(runtime)?;
+    let (_, resource) = fetch_resource(client, &version, &namespace, &kind, &name).await?;
+    container::container_target(&resource)
+}
+
+async fn fetch_resource(
+    client: &mut BdaClient<Channel>,
+    version: &str,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<(String, Resource), Box<dyn Error>> {
+    let request = GetResourceRequest {
+        version: version.to_string(),
+        namespace: namespace.to_string(),
+        kind: kind.to_string(),
+        name: name.to_string(),
+    };
+    let resource = client.get_resource(request).await?.into_inner();
+    let id = match logic::resource_id(&resource)? {
+        bdacore::data::EntityID::ResourceID(id) => id,
+    };
+    Ok((id, resource))
+}
+
+/// Parses `name=value` arguments. Values are read as JSON when possible, or as text.
+pub fn parse_args(args: &[String]) -> Result<BTreeMap<String, JValue>, String> {
+    args.iter()
+        .map(|arg| {
+            arg.split_once('=')
+                .map(|(k, v)| {
+                    (
+                        k.to_string(),
+                        serde_json::from_str(v).unwrap_or_else(|_| JValue::String(v.to_string())),
+                    )
+                })
+                .ok_or_else(|| format!("invalid argument {:?}, expected name=value", arg))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_args() {
+        let args = vec![
+            "a=1".to_string(),
+            "b=text".to_string(),
+            "c=[true]".to_string(),
+        ];
+        assert_eq!(
+            parse_args(&args),
+            Ok(BTreeMap::from([
+                ("a".to_string(), json!(1)),
+                ("b".to_string(), json!("text")),
+                ("c".to_string(), json!([true])),
+            ]))
+        );
+        assert!(parse_args(&["novalue".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,685 @@
+use crate::dag::DagRunner;
+use crate::executor::{self, LocalExecutor};
+use crate::queue::RunQueue;
+use crate::service::{self, args_to_struct};
+use bdacore::data::query::{self, Query};
+use bdacore::data::EntityID;
+use bdacore::logic::{self, cron, listener};
+use bdaproto::bda_client::BdaClient;
+use bdaproto::resource::ResourceKind;
+use bdaproto::resource_revision::Operation;
+use bdaproto::trigger::TriggerKind;
+use bdaproto::{
+    GetResourcesRequest, Listener, Resource, ResourceEvent, SubmitRunRequest, WatchResourcesRequest,
+};
+use hyper::body::HttpBody;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{json, Value as JValue};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+use tonic::{transport::Channel, Status, Streaming};
+
+/// Interval between two checks of the cron and path watch triggers.
+pub const TICK: Duration = Duration::from_secs(1);
+/// Ticks between two reloads of the listeners from the datastore.
+pub const RELOAD_TICKS: u64 = 30;
+/// Largest webhook body read, in bytes. Longer bodies are answered with 413.
+pub const MAX_WEBHOOK_BODY: usize = 1 << 20;
+
+/// Listener fired by a trigger, with its inputs rendered from the payload of the event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Firing {
+    pub listener: String,
+    pub target: String,
+    pub payload: JValue,
+    pub inputs: Result<BTreeMap<String, JValue>, String>,
+}
+
+/// Modification time and size of the files under a watched path.
+type Snapshot = BTreeMap<PathBuf, (u128, u64)>;
+
+struct Watched {
+    listener: Listener,
+    /// Cron triggers with their schedule and next time.
+    crons: Vec<(String, cron::Schedule, Option<u64>)>,
+    changes: Vec<Query>,
+    paths: Vec<(String, Snapshot)>,
+    webhooks: Vec<String>,
+}
+
+/// Listeners of the datastore with the state of their triggers. Times are in milliseconds since
+/// the unix epoch and given by the caller.
+#[derive(Default)]
+pub struct Listeners {
+    watched: Mutex<BTreeMap<String, Watched>>,
+}
+
+impl Listeners {
+    pub fn new() -> Self {
+        Listeners::default()
+    }
+
+    /// Replaces the listeners, keeping the state of the ones left unchanged. The crons of new
+    /// listeners fire after `now` and their paths are compared to their content at `now`.
+    pub fn load(&self, resources: &[Resource], now: u64) {
+        let mut watched = self.watched.lock().unwrap();
+        let mut loaded = BTreeMap::new();
+        for r in resources {
+            let l = match r.resource_kind {
+                Some(ResourceKind::Listener(ref l)) => l,
+                _ => continue,
+            };
+            let id = match logic::resource_id(r) {
+                Ok(EntityID::ResourceID(id)) => id,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    continue;
+                }
+            };
+            let w = match watched.remove(&id) {
+                Some(w) if &w.listener == l => w,
+                _ => watch(&id, l, now),
+            };
+            loaded.insert(id, w);
+        }
+        *watched = loaded;
+    }
+
+    /// Fires the crons due at `now`, once however many times were missed, and the path watches
+    /// whose files changed since the previous tick.
+    pub fn tick(&self, now: u64) -> Vec<Firing> {
+        let mut watched = self.watched.lock().unwrap();
+        let mut firings = Vec::new();
+        for (id, w) in watched.iter_mut() {
+            for (schedule, parsed, next) in w.crons.iter_mut() {
+                match *next {
+                    Some(time) if time <= now => {
+                        *next = parsed.next_after(now);
+                        let payload =
+                            json!({"trigger": "cron", "schedule": schedule, "time": time});
+                        firings.push(fire(id, &w.listener, payload));
+                    }
+                    _ => {}
+                }
+            }
+            for (path, before) in w.paths.iter_mut() {
+                let after = snapshot(Path::new(path));
+                let changes = diff(before, &after);
+                *before = after;
+                if !changes.is_empty() {
+                    let payload = json!({
+                        "trigger": "pathWatch",
+                        "path": path,
+                        "changes": changes,
+                        "time": now,
+                    });
+                    firings.push(fire(id, &w.listener, payload));
+                }
+            }
+        }
+        firings
+    }
+
+    /// Fires the listeners with a resource change trigger matching the changed resource.
+    pub fn resource_changed(&self, event: &ResourceEvent) -> Vec<Firing> {
+        let resource = match event.resource {
+            Some(ref r) => r,
+            None => return vec![],
+        };
+        let operation = match Operation::from_i32(event.operation) {
+            Some(Operation::Create) => "create",
+            Some(Operation::Update) => "update",
+            Some(Operation::Delete) => "delete",
+            None => return vec![],
+        };
+        let time = event
+            .time
+            .as_ref()
+            .map_or(0, |t| t.seconds as u64 * 1000 + t.nanos as u64 / 1_000_000);
+        let watched = self.watched.lock().unwrap();
+        let mut firings = Vec::new();
+        for (id, w) in watched.iter() {
+            let matched = w.changes.iter().any(|q| {
+                query::matches(&q.ast, resource).unwrap_or_else(|e| {
+                    eprintln!("listener {}: {}", id, e);
+                    false
+                })
+            });
+            if matched {
+                let payload = json!({
+                    "trigger": "resourceChange",
+                    "operation": operation,
+                    "resource": resource,
+                    "time": time,
+                });
+                firings.push(fire(id, &w.listener, payload));
+            }
+        }
+        firings
+    }
+
+    /// Fires the listeners with a webhook on a path, relative to `listener::WEBHOOK_PREFIX`. The
+    /// body is in the payload as JSON when it parses, or as text.
+    pub fn webhook(&self, path: &str, body: &[u8], now: u64) -> Vec<Firing> {
+        let body = serde_json::from_slice(body)
+            .unwrap_or_else(|_| JValue::String(String::from_utf8_lossy(body).to_string()));
+        let watched = self.watched.lock().unwrap();
+        watched
+            .iter()
+            .filter(|(_, w)| w.webhooks.iter().any(|p| p == path))
+            .map(|(id, w)| {
+                let payload =
+                    json!({"trigger": "webhook", "path": path, "body": body, "time": now});
+                fire(id, &w.listener, payload)
+            })
+            .collect()
+    }
+
+    /// Runs the listeners until the engine stops: ticks, reloads the listeners from the
+    /// datastore every `RELOAD_TICKS`, follows the resource changes and receives the webhook
+    /// firings. Each firing starts its target in its own task.
+    pub async fn run(self: Arc<Self>, runner: Arc<Runner>, mut webhooks: mpsc::Receiver<Firing>) {
+        let mut interval = tokio::time::interval(TICK);
+        let mut events = None;
+        let mut ticks: u64 = 0;
+        loop {
+            let firings = tokio::select! {
+                _ = interval.tick() => {
+                    if ticks % RELOAD_TICKS == 0 {
+                        match runner.load().await {
+                            Ok(resources) => self.load(&resources, executor::now()),
+                            Err(e) => eprintln!("cannot load the listeners: {}", e),
+                        }
+                        if events.is_none() {
+                            events = runner
+                                .watch()
+                                .await
+                                .map_err(|e| eprintln!("cannot watch the resources: {}", e))
+                                .ok();
+                        }
+                    }
+                    ticks += 1;
+                    self.tick(executor::now())
+                }
+                event = next_event(&mut events) => match event {
+                    Ok(Some(event)) => self.resource_changed(&event),
+                    Ok(None) => {
+                        // watched again at the next reload
+                        events = None;
+                        vec![]
+                    }
+                    Err(e) => {
+                        eprintln!("resource watch stopped: {}", e);
+                        events = None;
+                        vec![]
+                    }
+                },
+                Some(firing) = webhooks.recv() => vec![firing],
+            };
+            for firing in firings {
+                let runner = runner.clone();
+                tokio::spawn(async move {
+                    match runner.fire(&firing).await {
+                        Ok(started) => {
+                            eprintln!("listener {} started {}", firing.listener, started)
+                        }
+                        Err(e) => eprintln!("listener {} failed: {}", firing.listener, e),
+                    }
+                });
+            }
+        }
+    }
+
+    /// Serves the webhooks over HTTP, sending their firings to `run`. A POST to a webhook path
+    /// answers 202 with the ids of the fired listeners, 404 when no listener has that path, or 413
+    /// when the body is longer than `MAX_WEBHOOK_BODY`.
+    pub async fn serve_webhooks(
+        self: Arc<Self>,
+        address: SocketAddr,
+        firings: mpsc::Sender<Firing>,
+    ) -> Result<(), hyper::Error> {
+        let make_service = make_service_fn(move |_| {
+            let listeners = self.clone();
+            let firings = firings.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle_webhook(listeners.clone(), firings.clone(), request)
+                }))
+            }
+        });
+        Server::bind(&address).serve(make_service).await
+    }
+}
+
+async fn handle_webhook(
+    listeners: Arc<Listeners>,
+    firings: mpsc::Sender<Firing>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let path = match request.uri().path().strip_prefix(listener::WEBHOOK_PREFIX) {
+        Some(path) => path.to_string(),
+        None => return Ok(reply(StatusCode::NOT_FOUND, json!("not found"))),
+    };
+    if request.method() != Method::POST {
+        return Ok(reply(StatusCode::METHOD_NOT_ALLOWED, json!("use POST")));
+    }
+    let body = match read_body(request).await {
+        Ok(body) => body,
+        Err(response) => return Ok(response),
+    };
+    let fired = listeners.webhook(&path, &body, executor::now());
+    if fired.is_empty() {
+        let message = format!("no listener on {}{}", listener::WEBHOOK_PREFIX, path);
+        return Ok(reply(StatusCode::NOT_FOUND, json!(message)));
+    }
+    let ids: Vec<String> = fired.iter().map(|f| f.listener.clone()).collect();
+    for firing in fired {
+        if firings.send(firing).await.is_err() {
+            return Ok(reply(StatusCode::SERVICE_UNAVAILABLE, json!("stopping")));
+        }
+    }
+    Ok(reply(StatusCode::ACCEPTED, json!(ids)))
+}
+
+/// Reads the body of a request, up to `MAX_WEBHOOK_BODY` bytes, checking the declared length
+/// first so that an oversized body is not read at all.
+async fn read_body(request: Request<Body>) -> Result<Vec<u8>, Response<Body>> {
+    let too_large = || {
+        let message = format!("body is longer than {} bytes", MAX_WEBHOOK_BODY);
+        reply(StatusCode::PAYLOAD_TOO_LARGE, json!(message))
+    };
+    let length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if length.map_or(false, |l| l > MAX_WEBHOOK_BODY as u64) {
+        return Err(too_large());
+    }
+    let mut body = request.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| reply(StatusCode::BAD_REQUEST, json!(e.to_string())))?;
+        if bytes.len() + chunk.len() > MAX_WEBHOOK_BODY {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn reply(status: StatusCode, body: JValue) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response
+}
+
+async fn next_event(
+    events: &mut Option<Streaming<ResourceEvent>>,
+) -> Result<Option<ResourceEvent>, Status> {
+    match events {
+        Some(events) => events.message().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn watch(id: &str, l: &Listener, now: u64) -> Watched {
+    let mut w = Watched {
+        listener: l.clone(),
+        crons: vec![],
+        changes: vec![],
+        paths: vec![],
+        webhooks: vec![],
+    };
+    for t in l.triggers.iter() {
+        let parsed = match t.trigger_kind {
+            Some(TriggerKind::Cron(ref c)) => cron::parse(&c.schedule).map(|s| {
+                let next = s.next_after(now);
+                w.crons.push((c.schedule.clone(), s, next))
+            }),
+            Some(TriggerKind::ResourceChange(ref r)) => {
+                Query::from_bql(&r.bql).map(|q| w.changes.push(q))
+            }
+            Some(TriggerKind::PathWatch(ref p)) => {
+                w.paths.push((p.path.clone(), snapshot(Path::new(&p.path))));
+                Ok(())
+            }
+            Some(TriggerKind::Webhook(ref h)) => {
+                listener::check_webhook_path(&h.path).map(|_| w.webhooks.push(h.path.clone()))
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = parsed {
+            eprintln!("listener {}: {}", id, e);
+        }
+    }
+    w
+}
+
+fn fire(id: &str, l: &Listener, payload: JValue) -> Firing {
+    Firing {
+        listener: id.to_string(),
+        target: l.target.clone(),
+        inputs: listener::render(l.inputs.as_ref(), &payload),
+        payload,
+    }
+}
+
+/// Files under a path, or the path itself when it is a file. A missing path has no files.
+fn snapshot(path: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|e| e.ok()).map(|e| e.path()));
+            }
+        } else {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos());
+            files.insert(path, (modified, metadata.len()));
+        }
+    }
+    files
+}
+
+fn diff(before: &Snapshot, after: &Snapshot) -> Vec<JValue> {
+    let mut changes = Vec::new();
+    for (path, file) in after.iter() {
+        let change = match before.get(path) {
+            None => "created",
+            Some(f) if f != file => "modified",
+            Some(_) => continue,
+        };
+        changes.push(json!({"path": path, "change": change}));
+    }
+    for path in before.keys().filter(|p| !after.contains_key(*p)) {
+        changes.push(json!({"path": path, "change": "removed"}));
+    }
+    changes
+}
+
+/// Starts the targets of fired listeners: functions are submitted to the run queue and dags
+/// run with a local executor, when one is given.
+pub struct Runner {
+    datastore: BdaClient<Channel>,
+    queue: Arc<RunQueue>,
+    dags: Option<Arc<LocalExecutor>>,
+}
+
+impl Runner {
+    pub fn new(datastore: BdaClient<Channel>, queue: Arc<RunQueue>) -> Self {
+        Runner {
+            datastore,
+            queue,
+            dags: None,
+        }
+    }
+
+    pub fn with_dags(mut self, executor: Arc<LocalExecutor>) -> Self {
+        self.dags = Some(executor);
+        self
+    }
+
+    /// Latest version of the listeners of all namespaces.
+    pub async fn load(&self) -> Result<Vec<Resource>, Status> {
+        let request = GetResourcesRequest {
+            namespaces: "all".to_string(),
+            kinds: logic::LISTENER_KIND.to_string(),
+            ..Default::default()
+        };
+        let response = self.datastore.clone().get_resources(request).await?;
+        Ok(response.into_inner().resources)
+    }
+
+    pub async fn watch(&self) -> Result<Streaming<ResourceEvent>, Status> {
+        let request = WatchResourcesRequest::default();
+        let response = self.datastore.clone().watch_resources(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Starts the target of a firing, returning the id of the run or of the dag run. Dag runs
+    /// finish before it returns. The inputs of a dag are named `<node>.<input>` and override the
+    /// constant inputs of the nodes.
+    pub async fn fire(&self, firing: &Firing) -> Result<String, String> {
+        let inputs = firing.inputs.clone()?;
+        let (version, namespace, kind, name) = logic::parse_resource_id(&firing.target)?;
+        match kind.as_str() {
+            logic::FUNCTION_KIND => {
+                let request = SubmitRunRequest {
+                    version,
+                    namespace,
+                    name,
+                    inputs: Some(args_to_struct(&inputs)?),
+                    runtime: String::new(),
+                };
+                let job = service::submit(&mut self.datastore.clone(), &self.queue, request)
+                    .await
+                    .map_err(|e| e.message().to_string())?;
+                Ok(format!("run {}", job.id))
+            }
+            logic::DAG_KIND => {
+                let executor = self.dags.clone().ok_or("dags are not run by this engine")?;
+                let mut datastore = self.datastore.clone();
+                let (id, mut dag) = crate::fetch_dag(&mut datastore, &version, &namespace, &name)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let mut overrides = listener::node_inputs(inputs)?;
+                for node in dag.nodes.iter_mut() {
+                    if let Some(values) = overrides.remove(&node.name) {
+                        let mut node_inputs = service::struct_to_args(node.inputs.take())?;
+                        node_inputs.extend(values);
+                        node.inputs = Some(args_to_struct(&node_inputs)?);
+                    }
+                }
+                if let Some(node) = overrides.keys().next() {
+                    return Err(format!("node {} not found in dag {}", node, id));
+                }
+                let functions = crate::fetch_dag_functions(&mut datastore, &mut dag)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let record = DagRunner::new(executor)
+                    .run(&id, &dag, &functions)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(format!("dag run {}", record.id))
+            }
+            kind => Err(format!("cannot fire a {}", kind)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use bdaproto::trigger::{Cron, PathWatch, ResourceChange, Webhook};
+    use bdaproto::Trigger;
+    use pbjson_types::Timestamp;
+    use tempdir::TempDir;
+
+    // 2023-11-14T22:13:20Z
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn new_listener(name: &str, trigger: TriggerKind) -> Resource {
+        let mut r = logic::new_resource_listener(name);
+        if let Some(ResourceKind::Listener(ref mut l)) = r.resource_kind {
+            l.target = "/latest/default/function/build".to_string();
+            l.triggers = vec![Trigger {
+                trigger_kind: Some(trigger),
+            }];
+            l.inputs = serde_json::from_value(json!({"event": "${event}"})).unwrap();
+        }
+        r
+    }
+
+    fn cron(schedule: &str) -> TriggerKind {
+        TriggerKind::Cron(Cron {
+            schedule: schedule.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_tick_cron() {
+        let listeners = Listeners::new();
+        listeners.load(&[new_listener("hourly", cron("0 * * * *"))], NOW);
+        assert!(listeners.tick(NOW + 60_000).is_empty());
+        // 23:00
+        let firings = listeners.tick(1_700_002_800_000);
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].listener, "/latest/default/listener/hourly");
+        assert_eq!(firings[0].target, "/latest/default/function/build");
+        assert_eq!(firings[0].payload["time"], json!(1_700_002_800_000u64));
+        assert_eq!(
+            firings[0].inputs,
+            Ok(BTreeMap::from([(
+                "event".to_string(),
+                firings[0].payload.clone()
+            )]))
+        );
+        assert!(listeners.tick(1_700_002_860_000).is_empty());
+        // missed times fire once, and the next time is after the tick
+        let firings = listeners.tick(1_700_002_800_000 + 5 * 3_600_000);
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].payload["time"], json!(1_700_006_400_000u64));
+        assert!(listeners.tick(1_700_002_800_000 + 5 * 3_600_000).is_empty());
+    }
+
+    #[test]
+    fn test_load() {
+        let listeners = Listeners::new();
+        let hourly = [new_listener("hourly", cron("0 * * * *"))];
+        listeners.load(&hourly, NOW);
+        // an unchanged listener keeps its next time, a changed one starts over
+        listeners.load(&hourly, 1_700_002_800_000);
+        assert_eq!(listeners.tick(1_700_002_800_000).len(), 1);
+        listeners.load(
+            &[new_listener("hourly", cron("30 * * * *"))],
+            1_700_002_800_000,
+        );
+        assert!(listeners.tick(1_700_002_800_000).is_empty());
+        assert_eq!(listeners.tick(1_700_004_600_000).len(), 1);
+        listeners.load(&[], NOW);
+        assert!(listeners.tick(1_700_006_400_000).is_empty());
+    }
+
+    #[test]
+    fn test_tick_path_watch() {
+        let dir = TempDir::new("listener").unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let listeners = Listeners::new();
+        let watch = TriggerKind::PathWatch(PathWatch { path: path.clone() });
+        listeners.load(&[new_listener("inbox", watch)], NOW);
+        assert!(listeners.tick(NOW).is_empty());
+        let file = dir.path().join("a.csv");
+        fs::write(&file, "a").unwrap();
+        let firings = listeners.tick(NOW + 1000);
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].payload["path"], json!(path));
+        assert_eq!(
+            firings[0].payload["changes"],
+            json!([{"path": file, "change": "created"}])
+        );
+        assert!(listeners.tick(NOW + 2000).is_empty());
+        fs::write(&file, "abc").unwrap();
+        let firings = listeners.tick(NOW + 3000);
+        assert_eq!(
+            firings[0].payload["changes"],
+            json!([{"path": file, "change": "modified"}])
+        );
+        fs::remove_file(&file).unwrap();
+        let firings = listeners.tick(NOW + 4000);
+        assert_eq!(
+            firings[0].payload["changes"],
+            json!([{"path": file, "change": "removed"}])
+        );
+    }
+
+    #[test]
+    fn test_resource_changed() {
+        let listeners = Listeners::new();
+        let change = TriggerKind::ResourceChange(ResourceChange {
+            bql: ".function&&.name=='build'".to_string(),
+        });
+        listeners.load(&[new_listener("rebuild", change)], NOW);
+        let event = |r: Resource| ResourceEvent {
+            operation: Operation::Update as i32,
+            resource: Some(r),
+            time: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+        };
+        let build = logic::new_resource_function("build");
+        let firings = listeners.resource_changed(&event(build.clone()));
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].payload["operation"], json!("update"));
+        assert_eq!(firings[0].payload["resource"]["name"], json!("build"));
+        assert_eq!(firings[0].payload["time"], json!(NOW));
+        let other = logic::new_resource_function("test");
+        assert!(listeners.resource_changed(&event(other)).is_empty());
+    }
+
+    #[test]
+    fn test_webhook() {
+        let listeners = Listeners::new();
+        let hook = TriggerKind::Webhook(Webhook {
+            path: "git/push".to_string(),
+        });
+        let mut resources = vec![new_listener("push", hook)];
+        resources.push(new_listener("hourly", cron("0 * * * *")));
+        listeners.load(&resources, NOW);
+        let firings = listeners.webhook("git/push", br#"{"ref": "main"}"#, NOW);
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].listener, "/latest/default/listener/push");
+        assert_eq!(firings[0].payload["body"], json!({"ref": "main"}));
+        let firings = listeners.webhook("git/push", b"not json", NOW);
+        assert_eq!(firings[0].payload["body"], json!("not json"));
+        assert!(listeners.webhook("git/pull", b"", NOW).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_webhook() {
+        let listeners = Arc::new(Listeners::new());
+        let hook = TriggerKind::Webhook(Webhook {
+            path: "git/push".to_string(),
+        });
+        listeners.load(&[new_listener("push", hook)], NOW);
+        let (sender, mut firings) = mpsc::channel(1);
+        let post = |length: Option<usize>, body: Vec<u8>| {
+            let mut request = Request::builder()
+                .method(Method::POST)
+                .uri(format!("{}git/push", listener::WEBHOOK_PREFIX));
+            if let Some(length) = length {
+                request = request.header(header::CONTENT_LENGTH, length);
+            }
+            let request = request.body(Body::from(body)).unwrap();
+            handle_webhook(listeners.clone(), sender.clone(), request)
+        };
+        let accepted = post(None, br#"{"ref": "main"}"#.to_vec()).await.unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        let firing = firings.recv().await.unwrap();
+        assert_eq!(firing.payload["body"], json!({"ref": "main"}));
+
+        let declared = post(Some(MAX_WEBHOOK_BODY + 1), vec![]).await.unwrap();
+        assert_eq!(declared.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let streamed = post(None, vec![b' '; MAX_WEBHOOK_BODY + 1]).await.unwrap();
+        assert_eq!(streamed.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(firings.try_recv().is_err());
+    }
+}
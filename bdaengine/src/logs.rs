@@ -0,0 +1,247 @@
+use crate::executor::{self, STDERR_FILE, STDOUT_FILE};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{fs, io::AsyncWriteExt};
+
+/// Log of a run, one JSON line per line of output, in its work directory. Rotated logs are
+/// `log.<n>.jsonl`, the higher `n` the older.
+pub const LOG_FILE: &str = "log.jsonl";
+
+/// Longest line kept whole, longer ones are split.
+pub const MAX_LINE: usize = 64 * 1024;
+
+/// Interval between the reads of a followed log.
+pub const FOLLOW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Size caps of the log of a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogLimits {
+    /// Size of a log file past which it is rotated.
+    pub max_bytes: u64,
+    /// Number of files kept, the current one included.
+    pub max_files: usize,
+}
+
+impl Default for LogLimits {
+    fn default() -> Self {
+        LogLimits {
+            max_bytes: 10 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+/// Line of output of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    /// Position of the line in the log, from 0.
+    pub offset: u64,
+    /// Milliseconds since the unix epoch, 0 when unknown.
+    pub time: u64,
+    /// `stdout` or `stderr`.
+    pub stream: String,
+    pub line: String,
+}
+
+/// Writes the log of a run, numbering the lines of both streams in the order they come.
+pub struct LogWriter {
+    dir: PathBuf,
+    limits: LogLimits,
+    file: fs::File,
+    size: u64,
+    offset: u64,
+}
+
+impl LogWriter {
+    pub async fn create(dir: &Path, limits: LogLimits) -> io::Result<Self> {
+        Ok(LogWriter {
+            dir: dir.to_path_buf(),
+            limits,
+            file: fs::File::create(dir.join(LOG_FILE)).await?,
+            size: 0,
+            offset: 0,
+        })
+    }
+
+    pub async fn write(&mut self, stream: &str, line: &[u8]) -> io::Result<()> {
+        let line = LogLine {
+            offset: self.offset,
+            time: executor::now(),
+            stream: stream.to_string(),
+            line: String::from_utf8_lossy(line).to_string(),
+        };
+        let mut content = serde_json::to_vec(&line)?;
+        content.push(b'\n');
+        if self.size > 0 && self.size + content.len() as u64 > self.limits.max_bytes {
+            self.rotate().await?;
+        }
+        self.file.write_all(&content).await?;
+        self.file.flush().await?;
+        self.size += content.len() as u64;
+        self.offset += 1;
+        Ok(())
+    }
+
+    /// Shifts the rotated files, dropping the oldest, and starts a new file.
+    async fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..self.limits.max_files).rev() {
+            match fs::rename(log_path(&self.dir, n - 1), log_path(&self.dir, n)).await {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        self.file = fs::File::create(self.dir.join(LOG_FILE)).await?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn log_path(dir: &Path, n: usize) -> PathBuf {
+    match n {
+        0 => dir.join(LOG_FILE),
+        n => dir.join(format!("log.{}.jsonl", n)),
+    }
+}
+
+/// Lines of the log of a run from an offset, oldest first. Lines dropped by the rotation are
+/// skipped. The runs from before logs were kept have their `stdout.log` then `stderr.log`
+/// read instead, without times.
+pub async fn read(dir: &Path, from: u64) -> io::Result<Vec<LogLine>> {
+    let mut rotated = Vec::new();
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let n = name
+            .strip_prefix("log.")
+            .and_then(|n| n.strip_suffix(".jsonl"))
+            .and_then(|n| n.parse::<usize>().ok());
+        if let Some(n) = n {
+            rotated.push(n);
+        }
+    }
+    rotated.sort_unstable_by(|a, b| b.cmp(a));
+    rotated.push(0);
+    let mut lines = Vec::new();
+    let mut found = false;
+    for n in rotated {
+        let content = match fs::read_to_string(log_path(dir, n)).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        found = true;
+        for l in content.lines() {
+            // the last line may be partly written
+            let line: LogLine = match serde_json::from_str(l) {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.offset >= from {
+                lines.push(line);
+            }
+        }
+    }
+    if !found {
+        return read_outputs(dir, from).await;
+    }
+    Ok(lines)
+}
+
+async fn read_outputs(dir: &Path, from: u64) -> io::Result<Vec<LogLine>> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for (stream, file) in [("stdout", STDOUT_FILE), ("stderr", STDERR_FILE)] {
+        let content = fs::read_to_string(dir.join(file)).await.unwrap_or_default();
+        for line in content.lines() {
+            if offset >= from {
+                lines.push(LogLine {
+                    offset,
+                    time: 0,
+                    stream: stream.to_string(),
+                    line: line.to_string(),
+                });
+            }
+            offset += 1;
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let limits = LogLimits {
+            max_bytes: 200,
+            max_files: 3,
+        };
+        let mut log = LogWriter::create(tmp.path(), limits).await.unwrap();
+        for i in 0..20 {
+            let stream = if i % 2 == 0 { "stdout" } else { "stderr" };
+            log.write(stream, format!("line {}", i).as_bytes())
+                .await
+                .unwrap();
+        }
+        assert!(tmp.path().join("log.2.jsonl").exists());
+        assert!(!tmp.path().join("log.3.jsonl").exists());
+        let lines = read(tmp.path(), 0).await.unwrap();
+        let last = lines.last().unwrap();
+        assert_eq!((last.offset, last.line.as_str()), (19, "line 19"));
+        assert_eq!(last.stream, "stderr");
+        // the oldest lines were dropped, the others are in order
+        assert!(lines[0].offset > 0);
+        assert!(lines.windows(2).all(|w| w[1].offset == w[0].offset + 1));
+        let tail = read(tmp.path(), 18).await.unwrap();
+        assert_eq!(tail.len(), 2);
+        assert!(read(tmp.path(), 20).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_partial() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let mut log = LogWriter::create(tmp.path(), LogLimits::default())
+            .await
+            .unwrap();
+        log.write("stdout", b"done").await.unwrap();
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(tmp.path().join(LOG_FILE))
+            .await
+            .unwrap();
+        file.write_all(b"{\"offset\":1,\"ti").await.unwrap();
+        let lines = read(tmp.path(), 0).await.unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].line, "done");
+    }
+
+    #[tokio::test]
+    async fn test_read_outputs() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        assert!(read(&tmp.path().join("none"), 0).await.unwrap().is_empty());
+        fs::write(tmp.path().join(STDOUT_FILE), "a\nb\n")
+            .await
+            .unwrap();
+        fs::write(tmp.path().join(STDERR_FILE), "c\n")
+            .await
+            .unwrap();
+        let lines = read(tmp.path(), 1).await.unwrap();
+        let lines: Vec<(u64, &str, &str)> = lines
+            .iter()
+            .map(|l| (l.offset, l.stream.as_str(), l.line.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(1, "stdout", "b"), (2, "stderr", "c")]);
+    }
+}
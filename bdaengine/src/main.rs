@@ -1,3 +1,400 @@
-fn main() {
-    println!("Hello, world!");
+use bdacore::logic::binding;
+use bdacore::logic::secret::SecretKey;
+use bdaengine::artifacts::{self, ArtifactStore};
+use bdaengine::cache::RunCache;
+use bdaengine::container::{CliDriver, ContainerDriver};
+use bdaengine::coordinator::{BDACoordinatorService, Workers};
+use bdaengine::dag::DagRunner;
+use bdaengine::deploy::Deployments;
+use bdaengine::executor::{self, Containers, LocalExecutor};
+use bdaengine::listener::{Listeners, Runner};
+use bdaengine::logs::LogLimits;
+use bdaengine::queue::{JobStore, RunQueue};
+use bdaengine::secrets::DatastoreSecrets;
+use bdaengine::service::BDAEngineService;
+use bdaengine::worker::Worker;
+use bdaproto::bda_client::BdaClient;
+use bdaproto::coordinator_client::CoordinatorClient;
+use bdaproto::coordinator_server::CoordinatorServer;
+use bdaproto::engine_server::EngineServer;
+use clap::{Parser, Subcommand};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch};
+use tonic::transport::{Channel, Server};
+use url::Url;
+
+#[tokio::main]
+pub async fn main() -> Result<(), Box<dyn Error>> {
+    let ref cfg = Config::parse();
+    let work_dir = shellexpand::tilde(&cfg.work_dir.to_string_lossy()).to_string();
+    let cache_dir = shellexpand::tilde(&cfg.cache_dir.to_string_lossy()).to_string();
+    let queue_dir = shellexpand::tilde(&cfg.queue_dir.to_string_lossy()).to_string();
+    let artifact_dir = shellexpand::tilde(&cfg.artifact_dir.to_string_lossy()).to_string();
+    let deploy_dir = shellexpand::tilde(&cfg.deploy_dir.to_string_lossy()).to_string();
+    let store = Arc::new(ArtifactStore::new(artifact_dir));
+    match cfg.command {
+        Command::Serve {
+            address,
+            workers,
+            heartbeat_seconds,
+            cache,
+            webhook_address,
+            ref capability,
+        } => {
+            let client = bdaengine::connect(cfg.endpoint.as_str()).await?;
+            let executor = Arc::new(local_executor(cfg, work_dir, store.clone())?);
+            let mut queue = RunQueue::open(queue_dir, executor.clone(), workers)?
+                .with_capabilities(capability.clone());
+            if cache {
+                queue = queue.with_cache(RunCache::new(cache_dir));
+            }
+            let queue = Arc::new(queue);
+            tokio::spawn(queue.clone().run());
+            let heartbeat = Duration::from_secs(heartbeat_seconds.max(1));
+            let remote = Arc::new(Workers::new(queue.clone(), heartbeat));
+            tokio::spawn(remote.clone().run());
+            let coordinator = BDACoordinatorService::new(remote, queue.clone());
+            let runner = Runner::new(client.clone(), queue.clone()).with_dags(executor);
+            let listeners = Arc::new(Listeners::new());
+            let (firings, webhooks) = mpsc::channel(64);
+            tokio::spawn(listeners.clone().run(Arc::new(runner), webhooks));
+            tokio::spawn(async move {
+                if let Err(e) = listeners.serve_webhooks(webhook_address, firings).await {
+                    eprintln!("webhooks stopped: {}", e);
+                }
+            });
+            let deployments = Arc::new(Deployments::open(deploy_dir)?);
+            tokio::spawn(deployments.clone().run(client.clone(), queue.clone()));
+            let service = BDAEngineService::new(client, queue)
+                .with_artifacts(store)
+                .with_deployments(deployments);
+            Server::builder()
+                .add_service(EngineServer::new(service))
+                .add_service(CoordinatorServer::new(coordinator))
+                .serve(address)
+                .await?;
+        }
+        Command::Cache { ref command } => {
+            let cache = RunCache::new(cache_dir);
+            match command {
+                CacheCommand::Ls { function } => {
+                    for e in cache.list()? {
+                        if function.as_ref().map_or(true, |f| f == &e.function) {
+                            println!("{}\t{}\t{}\t{} hits", e.key, e.function, e.run, e.hits);
+                        }
+                    }
+                }
+                CacheCommand::Evict {
+                    keys,
+                    function,
+                    all,
+                } => {
+                    if keys.is_empty() && function.is_none() && !all {
+                        Err("give keys, --function or --all to evict cached runs")?;
+                    }
+                    let evicted = cache.evict(|e| {
+                        *all || keys.contains(&e.key) || function.as_ref() == Some(&e.function)
+                    })?;
+                    eprintln!("{} cached runs evicted", evicted.len());
+                }
+            }
+        }
+        Command::Artifacts { ref command } => match command {
+            ArtifactsCommand::Gc { min_age_seconds } => {
+                let mut referenced = BTreeSet::new();
+                for record in executor::read_records(Path::new(&work_dir)).await? {
+                    referenced.extend(artifacts::references(&record));
+                }
+                for job in JobStore::new(queue_dir).load()? {
+                    referenced.extend(artifacts::input_references(&job.inputs));
+                    if let Some(ref record) = job.last_run {
+                        referenced.extend(artifacts::references(record));
+                    }
+                }
+                for entry in RunCache::new(cache_dir).list()? {
+                    referenced.extend(artifacts::references(&entry.record));
+                }
+                let removed = store.gc(&referenced, *min_age_seconds * 1000)?;
+                eprintln!("{} artifacts removed", removed.len());
+            }
+        },
+        Command::Worker {
+            ref coordinator,
+            ref name,
+            ref capability,
+            slots,
+        } => {
+            let client = CoordinatorClient::connect(coordinator.to_string()).await?;
+            let name = name
+                .clone()
+                .unwrap_or_else(|| format!("worker-{}", std::process::id()));
+            let executor = Arc::new(local_executor(cfg, work_dir, store)?);
+            Worker::new(client, executor, &name, capability, slots)
+                .run()
+                .await?;
+        }
+        Command::Run {
+            ref name,
+            ref namespace,
+            ref version,
+            ref arg,
+            ref runtime,
+        } => {
+            let mut client = bdaengine::connect(cfg.endpoint.as_str()).await?;
+            let (id, function) =
+                bdaengine::fetch_function(&mut client, version, namespace, name).await?;
+            let args = bdaengine::parse_args(arg)?;
+            let runner = local_executor(cfg, work_dir, store)?;
+            let record = match runtime {
+                None => runner.run(&id, &function, &args).await?,
+                Some(runtime) => {
+                    let runtime =
+                        format!("/{}/{}/runtime.container/{}", version, namespace, runtime);
+                    let target = bdaengine::fetch_container_target(&mut client, &runtime).await?;
+                    let binding =
+                        binding::bind(&function.inputs, &args, &std::env::current_dir()?)?;
+                    let (_cancel, cancelled) = watch::channel(false);
+                    let run_id = executor::new_run_id();
+                    runner
+                        .run_in_container(&run_id, &id, &function, &target, &binding, cancelled)
+                        .await?
+                }
+            };
+            print!("{}", record.stdout);
+            eprint!("{}", record.stderr);
+            if let Some(ref e) = record.error {
+                eprintln!("{}", e);
+            }
+            if !record.outputs.is_empty() {
+                eprintln!("outputs: {}", serde_json::to_string(&record.outputs)?);
+            }
+            eprintln!(
+                "run {} finished in {}",
+                record.id,
+                record.work_dir.display()
+            );
+            if !record.succeeded() {
+                std::process::exit(record.exit_code.filter(|c| *c != 0).unwrap_or(1))
+            }
+        }
+        Command::RunDag {
+            ref name,
+            ref namespace,
+            ref version,
+            parallelism,
+        } => {
+            let mut client = bdaengine::connect(cfg.endpoint.as_str()).await?;
+            let (id, mut dag) = bdaengine::fetch_dag(&mut client, version, namespace, name).await?;
+            if let Some(parallelism) = parallelism {
+                dag.parallelism = parallelism;
+            }
+            let functions = bdaengine::fetch_dag_functions(&mut client, &mut dag).await?;
+            let record = DagRunner::new(Arc::new(local_executor(cfg, work_dir, store)?))
+                .run(&id, &dag, &functions)
+                .await?;
+            for (node, run) in record.nodes.iter() {
+                match run.error {
+                    Some(ref e) => println!("{}\t{:?}\t{}", node, run.status, e),
+                    None => println!("{}\t{:?}", node, run.status),
+                }
+            }
+            eprintln!(
+                "dag run {} finished in {}",
+                record.id,
+                record.work_dir.display()
+            );
+            if !record.succeeded() {
+                std::process::exit(1)
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Executor running functions locally, and in container runtimes when a tool is available.
+/// Secrets are fetched from the datastore when its secret key is readable.
+fn local_executor(
+    cfg: &Config,
+    work_dir: String,
+    store: Arc<ArtifactStore>,
+) -> Result<LocalExecutor, Box<dyn Error>> {
+    let mut executor = LocalExecutor::new(work_dir)
+        .with_artifacts(store)
+        .with_log_limits(LogLimits {
+            max_bytes: cfg.log_max_bytes,
+            max_files: cfg.log_max_files.max(1),
+        });
+    let secret_key = shellexpand::tilde(&cfg.secret_key.to_string_lossy()).to_string();
+    let secret_key = Path::new(&secret_key);
+    if secret_key.exists() {
+        let key = Arc::new(SecretKey::load(secret_key)?);
+        let channel = Channel::from_shared(cfg.endpoint.to_string())?.connect_lazy()?;
+        let secrets = DatastoreSecrets::new(BdaClient::new(channel), key);
+        executor = executor.with_secrets(Arc::new(secrets));
+    }
+    let driver: Arc<dyn ContainerDriver> = match cfg.container_tool.as_str() {
+        "none" => return Ok(executor),
+        "auto" => match CliDriver::detect() {
+            Ok(driver) => Arc::new(driver),
+            Err(_) => return Ok(executor),
+        },
+        program => Arc::new(CliDriver::new(program)),
+    };
+    Ok(executor.with_containers(Containers {
+        driver,
+        context: cfg.build_context.clone(),
+        keep_images: cfg.keep_images,
+    }))
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Config {
+    #[clap(subcommand)]
+    command: Command,
+    #[clap(short, long, default_value = "http://127.0.0.1:7000")]
+    endpoint: Url,
+    #[clap(short, long, default_value = "~/.bda/runs")]
+    work_dir: PathBuf,
+    /// Directory where the cached runs are stored
+    #[clap(long, default_value = "~/.bda/cache")]
+    cache_dir: PathBuf,
+    /// Directory where the artifacts produced and consumed by runs are stored
+    #[clap(long, default_value = "~/.bda/artifacts")]
+    artifact_dir: PathBuf,
+    /// Directory where the deployments of bundles are recorded
+    #[clap(long, default_value = "~/.bda/deployments")]
+    deploy_dir: PathBuf,
+    /// Directory where the run queue is stored
+    #[clap(short, long, default_value = "~/.bda/queue")]
+    queue_dir: PathBuf,
+    /// Container tool running container runtimes: auto, none, podman, docker or a path
+    #[clap(long, default_value = "auto")]
+    container_tool: String,
+    /// Directory the dockerfiles of container runtimes are relative to
+    #[clap(long, default_value = ".")]
+    build_context: PathBuf,
+    /// Keeps the images built for container runs
+    #[clap(long)]
+    keep_images: bool,
+    /// Size of a run log file past which it is rotated
+    #[clap(long, default_value = "10485760")]
+    log_max_bytes: u64,
+    /// Number of log files kept per run, the oldest lines are dropped past it
+    #[clap(long, default_value = "5")]
+    log_max_files: usize,
+    /// Key of the datastore encrypting the secrets, needed to run functions referencing secrets
+    #[clap(long, default_value = "~/.bda/secret.key")]
+    secret_key: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Lists the cached runs, most recently used first
+    Ls {
+        /// Resource id of a function to list the runs of
+        #[clap(short, long)]
+        function: Option<String>,
+    },
+    /// Evicts cached runs by key, by function or all of them
+    Evict {
+        keys: Vec<String>,
+        /// Resource id of a function to evict the runs of
+        #[clap(short, long)]
+        function: Option<String>,
+        #[clap(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ArtifactsCommand {
+    /// Removes the artifacts no run, queued run or cached run refers to
+    Gc {
+        /// Keeps the artifacts younger than that, which runs in progress may still refer to
+        #[clap(long, default_value = "3600")]
+        min_age_seconds: u64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serves the engine gRPC API
+    Serve {
+        #[clap(short, long, default_value = "127.0.0.1:7001")]
+        address: SocketAddr,
+        /// Maximum number of runs at once in the engine process, 0 leaves them to workers
+        #[clap(long, default_value = "4")]
+        workers: usize,
+        /// Interval between the heartbeats of workers, which are dead after missing 3
+        #[clap(long, default_value = "5")]
+        heartbeat_seconds: u64,
+        /// Reuses the outputs of previous runs with the same function, runtime and inputs
+        #[clap(long)]
+        cache: bool,
+        /// Address of the HTTP server receiving the webhooks of listeners, under /hooks/
+        #[clap(long, default_value = "127.0.0.1:7002")]
+        webhook_address: SocketAddr,
+        /// Capability of the engine process, the runs of functions needing others are left to
+        /// workers
+        #[clap(short, long)]
+        capability: Vec<String>,
+    },
+    /// Inspects and evicts the cached runs
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+    /// Collects the unreferenced artifacts
+    Artifacts {
+        #[clap(subcommand)]
+        command: ArtifactsCommand,
+    },
+    /// Runs the runs leased from an engine, as a remote worker
+    Worker {
+        /// Engine to lease runs from
+        #[clap(long, default_value = "http://127.0.0.1:7001")]
+        coordinator: Url,
+        /// Name of the worker, worker-<pid> by default
+        #[clap(long)]
+        name: Option<String>,
+        /// Capability of the worker, matched against the runtimeCapabilities of functions
+        #[clap(short, long)]
+        capability: Vec<String>,
+        /// Maximum number of runs at once
+        #[clap(long, default_value = "4")]
+        slots: usize,
+    },
+    /// Runs a function as a local process
+    Run {
+        name: String,
+        #[clap(short, long, default_value = "default")]
+        namespace: String,
+        #[clap(short, long, default_value = "latest")]
+        version: String,
+        /// Input as name=value, where value is read as JSON when possible
+        #[clap(short, long)]
+        arg: Vec<String>,
+        /// Name of a container runtime to run the function in
+        #[clap(short, long)]
+        runtime: Option<String>,
+    },
+    /// Runs the nodes of a dag as local processes
+    RunDag {
+        name: String,
+        #[clap(short, long, default_value = "default")]
+        namespace: String,
+        #[clap(short, long, default_value = "latest")]
+        version: String,
+        /// Overrides the parallelism of the dag
+        #[clap(short, long)]
+        parallelism: Option<u32>,
+    },
 }
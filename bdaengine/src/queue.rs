@@ -0,0 +1,758 @@
+use crate::cache::RunCache;
+use crate::container::ContainerTarget;
+use crate::executor::{self, LocalExecutor, RunRecord, RUN_FILE};
+use bdacore::logic::binding::Binding;
+use bdaproto::{Function, RunPolicy};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{watch, Notify};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobState {
+    /// Waiting for a worker, or for the backoff delay of a retry.
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// Running when the engine stopped, with no retry left.
+    Lost,
+}
+impl JobState {
+    pub fn finished(&self) -> bool {
+        !matches!(self, JobState::Queued | JobState::Running)
+    }
+}
+
+/// Run of a function submitted to the queue, made of one attempt per try. Jobs are stored as
+/// `<id>.json` in the queue directory, and updated on every state change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub id: String,
+    pub function_id: String,
+    pub function: Function,
+    pub target: Option<ContainerTarget>,
+    pub inputs: BTreeMap<String, JValue>,
+    pub state: JobState,
+    /// Attempts started so far.
+    pub attempts: u32,
+    pub created_at: u64,
+    /// Start of the last attempt.
+    pub started_at: u64,
+    /// Earliest start of the next attempt.
+    pub not_before: u64,
+    /// Working directory of the last attempt.
+    pub work_dir: PathBuf,
+    /// Remote worker holding the lease of the running attempt, if any.
+    #[serde(default)]
+    pub worker: Option<String>,
+    /// Remote worker the last attempt was leased to, none when it ran in the engine.
+    #[serde(default)]
+    pub ran_on: Option<String>,
+    /// Attempts reassigned because their worker died, which do not count as retries.
+    #[serde(default)]
+    pub reassigned: u32,
+    /// Key under which the outputs of the job are cached, unless caching is off.
+    #[serde(default)]
+    pub cache_key: Option<String>,
+    /// Run whose cached outputs the job reused instead of running.
+    #[serde(default)]
+    pub cached_from: Option<String>,
+    pub cancel_requested: bool,
+    pub error: Option<String>,
+    /// Record of the last finished attempt.
+    pub last_run: Option<RunRecord>,
+}
+
+impl Job {
+    pub fn new(
+        function_id: &str,
+        function: &Function,
+        target: Option<ContainerTarget>,
+        binding: &Binding,
+    ) -> Self {
+        let now = executor::now();
+        Job {
+            id: executor::new_run_id(),
+            function_id: function_id.to_string(),
+            function: function.clone(),
+            target,
+            inputs: binding.inputs.clone(),
+            state: JobState::Queued,
+            attempts: 0,
+            created_at: now,
+            started_at: 0,
+            not_before: now,
+            work_dir: PathBuf::new(),
+            worker: None,
+            ran_on: None,
+            reassigned: 0,
+            cache_key: None,
+            cached_from: None,
+            cancel_requested: false,
+            error: None,
+            last_run: None,
+        }
+    }
+
+    pub fn run_id(&self, attempt: u32) -> String {
+        attempt_run_id(&self.id, attempt)
+    }
+
+    fn policy(&self) -> RunPolicy {
+        self.function.run_policy.clone().unwrap_or_default()
+    }
+
+    /// Milliseconds to wait after a failed attempt, doubling with every attempt.
+    fn backoff(&self) -> u64 {
+        let exponent = self.attempts.saturating_sub(1).min(16);
+        self.policy().backoff_seconds as u64 * 1000 * (1 << exponent)
+    }
+
+    fn start(&mut self, worker: Option<&str>, now: u64) {
+        self.state = JobState::Running;
+        self.attempts += 1;
+        self.started_at = now;
+        self.worker = worker.map(|w| w.to_string());
+        self.ran_on = self.worker.clone();
+    }
+
+    /// Queues again an attempt whose worker died, or cancels it when it was asked to.
+    fn reassign(&mut self, reason: String, now: u64) {
+        self.worker = None;
+        self.error = Some(reason);
+        if self.cancel_requested {
+            self.state = JobState::Cancelled;
+        } else {
+            self.state = JobState::Queued;
+            self.reassigned += 1;
+            self.not_before = now;
+        }
+    }
+
+    /// Applies the outcome of the last attempt, queueing a retry when the attempt failed and
+    /// the run policy allows more.
+    fn settle(&mut self, result: Result<RunRecord, String>, now: u64) {
+        match result {
+            Ok(record) => {
+                self.state = if record.cancelled {
+                    JobState::Cancelled
+                } else if record.succeeded() {
+                    JobState::Succeeded
+                } else {
+                    JobState::Failed
+                };
+                self.error = record.error.clone();
+                self.work_dir = record.work_dir.clone();
+                self.last_run = Some(record);
+            }
+            Err(e) => {
+                self.state = JobState::Failed;
+                self.error = Some(e);
+            }
+        }
+        if self.state == JobState::Failed && self.cancel_requested {
+            self.state = JobState::Cancelled;
+        }
+        self.worker = None;
+        let retries = self.attempts - self.reassigned.min(self.attempts);
+        if self.state == JobState::Failed && retries <= self.policy().retries {
+            self.state = JobState::Queued;
+            self.not_before = now + self.backoff();
+        }
+    }
+}
+
+/// Run id of an attempt of a job, counted from 1. The first attempt runs with the job id.
+pub fn attempt_run_id(job: &str, attempt: u32) -> String {
+    if attempt <= 1 {
+        job.to_string()
+    } else {
+        format!("{}.{}", job, attempt)
+    }
+}
+
+/// Jobs stored as JSON files, replaced atomically on every save.
+pub struct JobStore {
+    dir: PathBuf,
+}
+
+impl JobStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        JobStore { dir: dir.into() }
+    }
+
+    pub fn save(&self, job: &Job) -> Result<(), String> {
+        let path = self.dir.join(format!("{}.json", job.id));
+        let tmp = self.dir.join(format!("{}.json.tmp", job.id));
+        let content = serde_json::to_vec_pretty(job).map_err(|e| e.to_string())?;
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| format!("could not save job {}: {}", job.id, e))
+    }
+
+    /// Loads every job of the store, creating its directory when missing.
+    pub fn load(&self) -> Result<Vec<Job>, String> {
+        let read = |e: std::io::Error| format!("could not read {}: {}", self.dir.display(), e);
+        std::fs::create_dir_all(&self.dir).map_err(read)?;
+        let mut jobs = Vec::new();
+        for entry in std::fs::read_dir(&self.dir).map_err(read)? {
+            let path = entry.map_err(read)?.path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            let content = std::fs::read(&path).map_err(read)?;
+            let job = serde_json::from_slice(&content)
+                .map_err(|e| format!("invalid job {}: {}", path.display(), e))?;
+            jobs.push(job);
+        }
+        Ok(jobs)
+    }
+}
+
+/// Durable queue of function runs. Queued jobs are dispatched to at most `workers` concurrent
+/// attempts of the local executor, and leased to remote workers, following the run policy of
+/// their function for timeouts, retries and backoff. A job is saved as running before its
+/// attempt starts, so a job is attempted again when the engine or the worker stops during an
+/// attempt: dispatch is at least once. Jobs only run where all the `runtimeCapabilities` of their
+/// function are available, locally or on a remote worker.
+pub struct RunQueue {
+    executor: Arc<LocalExecutor>,
+    store: JobStore,
+    workers: usize,
+    capabilities: Vec<String>,
+    jobs: Mutex<BTreeMap<String, Job>>,
+    cancels: Mutex<BTreeMap<String, watch::Sender<bool>>>,
+    wake: Notify,
+    cache: Option<RunCache>,
+}
+
+impl RunQueue {
+    /// Opens the queue stored in `dir`, recovering the jobs a previous engine left running.
+    /// Jobs leased to remote workers are reassigned, as the workers register again. A job run
+    /// locally whose attempt saved its run record takes the outcome of that record. Otherwise
+    /// the attempt is lost and the job is queued again while its run policy allows retries,
+    /// or marked lost. With no local `workers`, jobs only run on remote workers.
+    pub fn open<P: Into<PathBuf>>(
+        dir: P,
+        executor: Arc<LocalExecutor>,
+        workers: usize,
+    ) -> Result<Self, String> {
+        let store = JobStore::new(dir);
+        let now = executor::now();
+        let mut jobs = BTreeMap::new();
+        for mut job in store.load()? {
+            if job.state == JobState::Running && job.worker.is_some() {
+                job.reassign("the engine stopped during the attempt".to_string(), now);
+                store.save(&job)?;
+            } else if job.state == JobState::Running {
+                let run_file = job.work_dir.join(RUN_FILE);
+                recover(&mut job, &run_file, now);
+                store.save(&job)?;
+            }
+            jobs.insert(job.id.clone(), job);
+        }
+        Ok(RunQueue {
+            executor,
+            store,
+            workers,
+            capabilities: Vec::new(),
+            jobs: Mutex::new(jobs),
+            cancels: Mutex::new(BTreeMap::new()),
+            wake: Notify::new(),
+            cache: None,
+        })
+    }
+
+    /// Capabilities of the local executor. Jobs needing others are left to remote workers.
+    pub fn with_capabilities(mut self, capabilities: Vec<String>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Reuses the outputs of the successful runs with the same cache key.
+    pub fn with_cache(mut self, cache: RunCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn caching(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// Queues a job, or settles it at once with the outputs of a cached run.
+    pub fn submit(&self, mut job: Job) -> Result<Job, String> {
+        job.work_dir = self.executor.work_dir(&job.id);
+        let cached = match (self.cache.as_ref(), job.cache_key.as_ref()) {
+            (Some(cache), Some(key)) => cache.lookup(key, &job.function),
+            _ => None,
+        };
+        if let Some(entry) = cached {
+            job.state = JobState::Succeeded;
+            job.cached_from = Some(entry.run);
+            job.work_dir = entry.record.work_dir.clone();
+            job.last_run = Some(entry.record);
+        }
+        self.store.save(&job)?;
+        self.jobs
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(job.id.clone(), job.clone());
+        self.wake.notify_one();
+        Ok(job)
+    }
+
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.jobs.lock().ok()?.get(id).cloned()
+    }
+
+    /// Jobs by creation time.
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = match self.jobs.lock() {
+            Ok(jobs) => jobs.values().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+        jobs.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        jobs
+    }
+
+    /// Cancels a queued job, or stops the running attempt of a job. The attempt process gets
+    /// SIGTERM, then SIGKILL after the grace period of the run policy. Remote workers learn
+    /// about the cancellation through `cancelled`.
+    pub fn cancel(&self, id: &str) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        let job = jobs
+            .get_mut(id)
+            .ok_or_else(|| format!("run not found: {}", id))?;
+        match job.state {
+            JobState::Queued => {
+                job.state = JobState::Cancelled;
+                job.cancel_requested = true;
+                job.error = Some("run cancelled".to_string());
+            }
+            JobState::Running => {
+                job.cancel_requested = true;
+                if let Some(cancel) = self.cancels.lock().map_err(|e| e.to_string())?.get(id) {
+                    let _ = cancel.send(true);
+                }
+            }
+            _ => Err(format!("run {} already finished", id))?,
+        }
+        self.store.save(job)?;
+        Ok(job.clone())
+    }
+
+    /// Leases the oldest due job a remote worker has the capabilities for, starting its next
+    /// attempt.
+    pub fn lease(&self, worker: &str, capabilities: &[String]) -> Result<Option<Job>, String> {
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        let now = executor::now();
+        let job = jobs
+            .values_mut()
+            .filter(|j| j.state == JobState::Queued && j.not_before <= now)
+            .filter(|j| capable(j, capabilities))
+            .min_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        match job {
+            None => Ok(None),
+            Some(job) => {
+                job.start(Some(worker), now);
+                job.work_dir = PathBuf::new();
+                self.store.save(job)?;
+                Ok(Some(job.clone()))
+            }
+        }
+    }
+
+    /// Settles the attempt a remote worker leased, failing when the worker no longer holds
+    /// the lease, like after the attempt was reassigned.
+    pub fn complete(
+        &self,
+        worker: &str,
+        id: &str,
+        attempt: u32,
+        result: Result<RunRecord, String>,
+    ) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        let job = jobs
+            .get_mut(id)
+            .filter(|j| j.state == JobState::Running && j.attempts == attempt)
+            .filter(|j| j.worker.as_deref() == Some(worker))
+            .ok_or_else(|| format!("worker {} holds no lease on run {}", worker, id))?;
+        job.settle(result, executor::now());
+        self.settled(job)?;
+        self.wake.notify_one();
+        Ok(job.clone())
+    }
+
+    /// Ids of the jobs leased to a remote worker whose cancellation was requested.
+    pub fn cancelled(&self, worker: &str) -> Vec<String> {
+        match self.jobs.lock() {
+            Ok(jobs) => jobs
+                .values()
+                .filter(|j| j.state == JobState::Running && j.cancel_requested)
+                .filter(|j| j.worker.as_deref() == Some(worker))
+                .map(|j| j.id.clone())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Queues again the jobs leased to a dead remote worker, returning their ids.
+    pub fn reassign(&self, worker: &str) -> Result<Vec<String>, String> {
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        let now = executor::now();
+        let mut reassigned = Vec::new();
+        for job in jobs.values_mut() {
+            if job.state != JobState::Running || job.worker.as_deref() != Some(worker) {
+                continue;
+            }
+            let reason = format!("attempt {} lost with worker {}", job.attempts, worker);
+            job.reassign(reason, now);
+            self.store.save(job)?;
+            reassigned.push(job.id.clone());
+        }
+        self.wake.notify_one();
+        Ok(reassigned)
+    }
+
+    /// Saves a job after an attempt, caching its outputs when it succeeded.
+    fn settled(&self, job: &Job) -> Result<(), String> {
+        self.store.save(job)?;
+        if let (Some(cache), Some(key), Some(record), JobState::Succeeded) = (
+            self.cache.as_ref(),
+            job.cache_key.as_ref(),
+            job.last_run.as_ref(),
+            job.state,
+        ) {
+            cache.put(key, &job.function_id, record)?;
+        }
+        Ok(())
+    }
+
+    /// Dispatches the queued jobs as workers become available, until the engine stops.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            let next = self.dispatch();
+            let delay = next.map_or(Duration::from_secs(3600), |at| {
+                Duration::from_millis(at.saturating_sub(executor::now()))
+            });
+            tokio::select! {
+                _ = self.wake.notified() => {}
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+    }
+
+    /// Starts the due jobs the local executor has the capabilities for, oldest first, while
+    /// workers are available. Returns when the next of these queued jobs is due, if any.
+    fn dispatch(self: &Arc<Self>) -> Option<u64> {
+        let mut jobs = match self.jobs.lock() {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                eprintln!("{}", e);
+                return None;
+            }
+        };
+        let now = executor::now();
+        let mut running = jobs
+            .values()
+            .filter(|j| j.state == JobState::Running && j.worker.is_none())
+            .count();
+        let mut queued: Vec<&mut Job> = jobs
+            .values_mut()
+            .filter(|j| j.state == JobState::Queued && capable(j, &self.capabilities))
+            .collect();
+        queued.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        let mut next = None;
+        for job in queued {
+            if job.not_before > now {
+                next = Some(next.map_or(job.not_before, |n: u64| n.min(job.not_before)));
+                continue;
+            }
+            if running >= self.workers {
+                // a finishing attempt wakes the dispatcher up
+                continue;
+            }
+            job.start(None, now);
+            job.work_dir = self.executor.work_dir(&job.run_id(job.attempts));
+            if let Err(e) = self.store.save(job) {
+                eprintln!("{}", e);
+            }
+            let (cancel, cancelled) = watch::channel(false);
+            if let Ok(mut cancels) = self.cancels.lock() {
+                cancels.insert(job.id.clone(), cancel);
+            }
+            running += 1;
+            tokio::spawn(self.clone().attempt(job.clone(), cancelled));
+        }
+        next
+    }
+
+    async fn attempt(self: Arc<Self>, job: Job, cancelled: watch::Receiver<bool>) {
+        let run_id = job.run_id(job.attempts);
+        let binding = Binding {
+            inputs: job.inputs.clone(),
+        };
+        let result = match job.target {
+            None => {
+                self.executor
+                    .run_cancellable(
+                        &run_id,
+                        &job.function_id,
+                        &job.function,
+                        &binding,
+                        cancelled,
+                    )
+                    .await
+            }
+            Some(ref target) => {
+                self.executor
+                    .run_in_container(
+                        &run_id,
+                        &job.function_id,
+                        &job.function,
+                        target,
+                        &binding,
+                        cancelled,
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| e.to_string());
+        if let Ok(mut cancels) = self.cancels.lock() {
+            cancels.remove(&job.id);
+        }
+        if let Ok(mut jobs) = self.jobs.lock() {
+            if let Some(job) = jobs.get_mut(&job.id) {
+                job.settle(result, executor::now());
+                if let Err(e) = self.settled(job) {
+                    eprintln!("{}", e);
+                }
+            }
+        }
+        self.wake.notify_one();
+    }
+}
+
+/// Settles a job found running when the queue opens, from the run record of its last attempt.
+/// Whether the function of a job only needs some of the `capabilities`.
+fn capable(job: &Job, capabilities: &[String]) -> bool {
+    job.function
+        .runtime_capabilities
+        .iter()
+        .all(|c| capabilities.contains(c))
+}
+
+fn recover(job: &mut Job, run_file: &Path, now: u64) {
+    let record = std::fs::read(run_file)
+        .ok()
+        .and_then(|content| serde_json::from_slice::<RunRecord>(&content).ok());
+    match record {
+        Some(record) => job.settle(Ok(record), now),
+        None => {
+            let lost = format!("attempt {} lost when the engine stopped", job.attempts);
+            job.settle(Err(lost), now);
+            if job.state == JobState::Failed {
+                job.state = JobState::Lost;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use bdacore::logic;
+    use tempdir::TempDir;
+
+    fn function(script: &str, retries: u32) -> Function {
+        Function {
+            run_policy: Some(RunPolicy {
+                retries,
+                grace_period_seconds: 1,
+                ..Default::default()
+            }),
+            ..logic::new_function(&["sh", "-c", script])
+        }
+    }
+
+    fn open(tmp: &TempDir, workers: usize) -> Arc<RunQueue> {
+        let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+        Arc::new(RunQueue::open(tmp.path().join("queue"), executor, workers).unwrap())
+    }
+
+    fn submit(queue: &RunQueue, f: &Function) -> String {
+        let job = Job::new("/latest/default/function/f", f, None, &Binding::default());
+        queue.submit(job).unwrap().id
+    }
+
+    async fn wait(queue: &RunQueue, id: &str) -> Job {
+        loop {
+            let job = queue.get(id).unwrap();
+            if job.state.finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let queue = open(&tmp, 2);
+        tokio::spawn(queue.clone().run());
+        // fails on the first two attempts, counted next to the attempt working directories
+        let flaky = function(
+            "n=$(cat ../count 2>/dev/null || echo 0); echo $((n+1)) > ../count; [ $n -ge 2 ]",
+            2,
+        );
+        let id = submit(&queue, &flaky);
+        let job = wait(&queue, &id).await;
+        assert_eq!(job.state, JobState::Succeeded, "{:?}", job.error);
+        assert_eq!(job.attempts, 3);
+        assert_eq!(
+            job.work_dir,
+            tmp.path().join("runs").join(format!("{}.3", id))
+        );
+        let id = submit(&queue, &function("exit 1", 1));
+        let job = wait(&queue, &id).await;
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.attempts, 2);
+        assert_eq!(job.last_run.unwrap().exit_code, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let queue = open(&tmp, 1);
+        tokio::spawn(queue.clone().run());
+        let running = submit(&queue, &function("sleep 30", 3));
+        let queued = submit(&queue, &function("true", 0));
+        while queue.get(&running).unwrap().state != JobState::Running {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(queue.get(&queued).unwrap().state, JobState::Queued);
+        assert_eq!(queue.cancel(&queued).unwrap().state, JobState::Cancelled);
+        queue.cancel(&running).unwrap();
+        let job = wait(&queue, &running).await;
+        assert_eq!(job.state, JobState::Cancelled);
+        assert_eq!(job.attempts, 1);
+        assert!(queue.cancel(&running).is_err());
+        assert!(queue.cancel("unknown").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recover() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let store = JobStore::new(tmp.path().join("queue"));
+        store.load().unwrap();
+        let mut jobs = Vec::new();
+        for retries in [0, 1] {
+            let mut job = Job::new("f", &function("true", retries), None, &Binding::default());
+            job.state = JobState::Running;
+            job.attempts = 1;
+            job.work_dir = tmp.path().join("runs").join(&job.id);
+            store.save(&job).unwrap();
+            jobs.push(job.id);
+        }
+        let queue = open(&tmp, 1);
+        let lost = queue.get(&jobs[0]).unwrap();
+        assert_eq!(lost.state, JobState::Lost);
+        assert_eq!(
+            lost.error,
+            Some("attempt 1 lost when the engine stopped".to_string())
+        );
+        assert_eq!(queue.get(&jobs[1]).unwrap().state, JobState::Queued);
+        tokio::spawn(queue.clone().run());
+        let job = wait(&queue, &jobs[1]).await;
+        assert_eq!(job.state, JobState::Succeeded);
+        assert_eq!(job.attempts, 2);
+        // the store holds the final states, for the next engine
+        let reopened = open(&tmp, 1);
+        assert_eq!(reopened.get(&jobs[0]).unwrap().state, JobState::Lost);
+        assert_eq!(reopened.get(&jobs[1]).unwrap().state, JobState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+        let queue = RunQueue::open(tmp.path().join("queue"), executor, 1)
+            .unwrap()
+            .with_cache(RunCache::new(tmp.path().join("cache")));
+        let queue = Arc::new(queue);
+        tokio::spawn(queue.clone().run());
+        let f = function("echo ran >> ../count", 0);
+        let submit = |key: Option<&str>| {
+            let mut job = Job::new("f", &f, None, &Binding::default());
+            job.cache_key = key.map(|k| k.to_string());
+            queue.submit(job).unwrap()
+        };
+        let first = submit(Some("k"));
+        assert_eq!(wait(&queue, &first.id).await.state, JobState::Succeeded);
+        let hit = submit(Some("k"));
+        assert_eq!(hit.state, JobState::Succeeded);
+        assert_eq!(hit.cached_from, Some(first.id.clone()));
+        assert_eq!(hit.work_dir, first.work_dir);
+        let uncached = submit(None);
+        assert_eq!(wait(&queue, &uncached.id).await.state, JobState::Succeeded);
+        let count = std::fs::read_to_string(tmp.path().join("runs/count")).unwrap();
+        assert_eq!(count, "ran\nran\n");
+    }
+
+    #[tokio::test]
+    async fn test_capabilities() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let queue = open(&tmp, 1);
+        tokio::spawn(queue.clone().run());
+        let mut gpu = function("true", 0);
+        gpu.runtime_capabilities = vec!["gpu".to_string()];
+        let gpu = submit(&queue, &gpu);
+        let plain = submit(&queue, &function("true", 0));
+        assert_eq!(wait(&queue, &plain).await.state, JobState::Succeeded);
+        // left for a worker with the capability
+        assert_eq!(queue.get(&gpu).unwrap().state, JobState::Queued);
+        assert!(queue.lease("w1", &[]).unwrap().is_none());
+        let leased = queue.lease("w1", &["gpu".to_string()]).unwrap().unwrap();
+        assert_eq!(leased.id, gpu);
+
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+        let queue = RunQueue::open(tmp.path().join("queue"), executor, 1)
+            .unwrap()
+            .with_capabilities(vec!["gpu".to_string()]);
+        let queue = Arc::new(queue);
+        tokio::spawn(queue.clone().run());
+        let mut f = function("true", 0);
+        f.runtime_capabilities = vec!["gpu".to_string()];
+        let id = submit(&queue, &f);
+        assert_eq!(wait(&queue, &id).await.state, JobState::Succeeded);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut f = function("true", 5);
+        f.run_policy.as_mut().unwrap().backoff_seconds = 2;
+        let mut job = Job::new("f", &f, None, &Binding::default());
+        let delays: Vec<u64> = (1..=4)
+            .map(|attempt| {
+                job.attempts = attempt;
+                job.settle(Err("failed".to_string()), 0);
+                assert_eq!(job.state, JobState::Queued);
+                job.not_before
+            })
+            .collect();
+        assert_eq!(delays, vec![2000, 4000, 8000, 16000]);
+        job.attempts = 6;
+        job.settle(Err("failed".to_string()), 0);
+        assert_eq!(job.state, JobState::Failed);
+    }
+}
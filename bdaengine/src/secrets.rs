@@ -0,0 +1,202 @@
+use bdacore::logic::{self, secret::SecretKey};
+use bdaproto::bda_client::BdaClient;
+use bdaproto::{GetSecretRequest, SecretRef};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{fs, io::AsyncWriteExt};
+use tonic::async_trait;
+use tonic::transport::Channel;
+
+/// Source of the plaintext values of the secrets referenced by functions.
+#[async_trait]
+pub trait SecretSource: Send + Sync {
+    /// Values of a secret, by key.
+    async fn values(&self, secret: &str) -> Result<BTreeMap<String, String>, String>;
+}
+
+/// Fetches secrets from the datastore, encrypted, and decrypts them with the key shared with it.
+pub struct DatastoreSecrets {
+    datastore: BdaClient<Channel>,
+    key: Arc<SecretKey>,
+}
+
+impl DatastoreSecrets {
+    pub fn new(datastore: BdaClient<Channel>, key: Arc<SecretKey>) -> Self {
+        DatastoreSecrets { datastore, key }
+    }
+}
+
+#[async_trait]
+impl SecretSource for DatastoreSecrets {
+    async fn values(&self, secret: &str) -> Result<BTreeMap<String, String>, String> {
+        let (version, namespace, _, name) = logic::parse_resource_id(secret)?;
+        let request = GetSecretRequest {
+            version,
+            namespace,
+            name,
+        };
+        let sealed = self
+            .datastore
+            .clone()
+            .get_secret(request)
+            .await
+            .map_err(|e| format!("could not fetch secret {}: {}", secret, e.message()))?
+            .into_inner();
+        self.key
+            .open(&sealed)
+            .map_err(|e| format!("secret {}: {}", secret, e))
+    }
+}
+
+/// Secrets given up front, by resource id, for tests.
+#[derive(Default)]
+pub struct StaticSecrets(pub BTreeMap<String, BTreeMap<String, String>>);
+
+#[async_trait]
+impl SecretSource for StaticSecrets {
+    async fn values(&self, secret: &str) -> Result<BTreeMap<String, String>, String> {
+        self.0
+            .get(secret)
+            .cloned()
+            .ok_or_else(|| format!("secret {} not found", secret))
+    }
+}
+
+/// Secret values injected into a run. Files are written below the working directory, readable
+/// only by their owner, and removed once the run ends.
+#[derive(Debug, Default, PartialEq)]
+pub struct Injected {
+    pub env: Vec<(String, String)>,
+    pub files: Vec<PathBuf>,
+}
+
+/// Resolves the secrets referenced by a function, fetching each secret once, and writes the
+/// ones injected as files. The files already written are removed when a reference fails.
+pub async fn inject(
+    source: &dyn SecretSource,
+    refs: &[SecretRef],
+    work_dir: &Path,
+) -> Result<Injected, String> {
+    let mut injected = Injected::default();
+    match inject_into(source, refs, work_dir, &mut injected).await {
+        Ok(()) => Ok(injected),
+        Err(e) => {
+            injected.remove();
+            Err(e)
+        }
+    }
+}
+
+async fn inject_into(
+    source: &dyn SecretSource,
+    refs: &[SecretRef],
+    work_dir: &Path,
+    injected: &mut Injected,
+) -> Result<(), String> {
+    let mut secrets = BTreeMap::new();
+    for r in refs {
+        if !secrets.contains_key(&r.secret) {
+            secrets.insert(r.secret.clone(), source.values(&r.secret).await?);
+        }
+        let value = secrets[&r.secret]
+            .get(&r.key)
+            .ok_or_else(|| format!("secret {} has no key {:?}", r.secret, r.key))?;
+        if r.path.is_empty() {
+            injected.env.push((r.env.clone(), value.clone()));
+            continue;
+        }
+        let path = work_dir.join(&r.path);
+        let written = write_file(&path, value).await;
+        injected.files.push(path);
+        written.map_err(|e| format!("could not write secret file {}: {}", r.path, e))?;
+    }
+    Ok(())
+}
+
+impl Injected {
+    pub fn remove(&self) {
+        for path in self.files.iter() {
+            if let Err(e) = std::fs::remove_file(path) {
+                eprintln!("could not remove secret file {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+async fn write_file(path: &Path, value: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(value.as_bytes()).await?;
+    file.flush().await
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use tempdir::TempDir;
+
+    fn secret_ref(key: &str, env: &str, path: &str) -> SecretRef {
+        SecretRef {
+            secret: "/latest/default/secret/registry".to_string(),
+            key: key.to_string(),
+            env: env.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inject() {
+        let tmp = TempDir::new("bdaengine").unwrap();
+        let values = [("token", "s3cr3t"), ("key", "ssh key")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let source = StaticSecrets(
+            vec![("/latest/default/secret/registry".to_string(), values)]
+                .into_iter()
+                .collect(),
+        );
+        let refs = vec![
+            secret_ref("token", "TOKEN", ""),
+            secret_ref("key", "", "keys/id_rsa"),
+        ];
+        let injected = inject(&source, &refs, tmp.path()).await.unwrap();
+        let file = tmp.path().join("keys/id_rsa");
+        assert_eq!(
+            injected,
+            Injected {
+                env: vec![("TOKEN".to_string(), "s3cr3t".to_string())],
+                files: vec![file.clone()],
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "ssh key");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        injected.remove();
+        assert!(!file.exists());
+
+        let missing = inject(&source, &[secret_ref("password", "P", "")], tmp.path()).await;
+        assert!(missing.is_err());
+
+        let refs = vec![
+            secret_ref("key", "", "keys/id_rsa"),
+            secret_ref("password", "", "keys/password"),
+        ];
+        let missing = inject(&source, &refs, tmp.path()).await;
+        assert!(missing.is_err());
+        assert!(!file.exists());
+    }
+}
@@ -0,0 +1,630 @@
+use crate::artifacts::{self, ArtifactStore};
+use crate::cache;
+use crate::deploy::{self, DeploymentState, Deployments};
+use crate::executor::RunRecord;
+use crate::logs::{self, LogLine};
+use crate::queue::{Job, JobState, RunQueue};
+use bdacore::logic::{self, binding};
+use bdaproto::bda_client::BdaClient;
+use bdaproto::deployment::Status as DeploymentStatus;
+use bdaproto::engine_server::Engine;
+use bdaproto::execution::Status as RunStatus;
+use bdaproto::{
+    ApproveDeploymentRequest, Artifact, ArtifactChunk, CancelRunRequest, Deployment, Execution,
+    GetArtifactRequest, GetRunRequest, ListArtifactsRequest, ListArtifactsResponse,
+    ListDeploymentsRequest, ListDeploymentsResponse, ListRunsRequest, ListRunsResponse,
+    PromoteRequest, ReconcileRequest, RollbackRequest, RunLog, StreamRunLogsRequest,
+    SubmitRunRequest,
+};
+use pbjson_types::{Struct, Timestamp};
+use serde_json::Value as JValue;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::{io::AsyncReadExt, sync::mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+use tonic::{async_trait, Request, Response, Status};
+
+/// Size of the chunks artifacts are streamed in.
+const ARTIFACT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Engine service running functions fetched from the datastore through a run queue.
+pub struct BDAEngineService {
+    datastore: BdaClient<Channel>,
+    queue: Arc<RunQueue>,
+    artifacts: Option<Arc<ArtifactStore>>,
+    deployments: Option<Arc<Deployments>>,
+}
+
+impl BDAEngineService {
+    pub fn new(datastore: BdaClient<Channel>, queue: Arc<RunQueue>) -> Self {
+        BDAEngineService {
+            datastore,
+            queue,
+            artifacts: None,
+            deployments: None,
+        }
+    }
+
+    /// Serves the artifacts of a store.
+    pub fn with_artifacts(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Promotes bundles through the stages of deployers, recording the deployments.
+    pub fn with_deployments(mut self, deployments: Arc<Deployments>) -> Self {
+        self.deployments = Some(deployments);
+        self
+    }
+
+    fn artifacts(&self) -> Result<Arc<ArtifactStore>, Status> {
+        self.artifacts
+            .clone()
+            .ok_or_else(|| Status::failed_precondition("no artifact store is configured"))
+    }
+
+    fn deployments(&self) -> Result<Arc<Deployments>, Status> {
+        self.deployments
+            .clone()
+            .ok_or_else(|| Status::failed_precondition("no deployment store is configured"))
+    }
+
+    fn job(&self, id: &str) -> Result<Job, Status> {
+        self.queue
+            .get(id)
+            .ok_or_else(|| Status::not_found(format!("run not found: {}", id)))
+    }
+}
+
+#[async_trait]
+impl Engine for BDAEngineService {
+    async fn submit_run(
+        &self,
+        request: Request<SubmitRunRequest>,
+    ) -> Result<Response<Execution>, Status> {
+        let job = submit(
+            &mut self.datastore.clone(),
+            &self.queue,
+            request.into_inner(),
+        )
+        .await?;
+        Ok(Response::new(job_to_execution(&job)))
+    }
+
+    async fn get_run(
+        &self,
+        request: Request<GetRunRequest>,
+    ) -> Result<Response<Execution>, Status> {
+        self.job(&request.get_ref().id)
+            .map(|job| Response::new(job_to_execution(&job)))
+    }
+
+    async fn list_runs(
+        &self,
+        request: Request<ListRunsRequest>,
+    ) -> Result<Response<ListRunsResponse>, Status> {
+        let function = &request.get_ref().function;
+        let runs = self
+            .queue
+            .list()
+            .iter()
+            .filter(|job| function.is_empty() || &job.function_id == function)
+            .map(job_to_execution)
+            .collect();
+        Ok(Response::new(ListRunsResponse { runs }))
+    }
+
+    async fn cancel_run(
+        &self,
+        request: Request<CancelRunRequest>,
+    ) -> Result<Response<Execution>, Status> {
+        let id = &request.get_ref().id;
+        self.job(id)?;
+        self.queue
+            .cancel(id)
+            .map(|job| Response::new(job_to_execution(&job)))
+            .map_err(Status::failed_precondition)
+    }
+
+    type StreamRunLogsStream = ReceiverStream<Result<RunLog, Status>>;
+    async fn stream_run_logs(
+        &self,
+        request: Request<StreamRunLogsRequest>,
+    ) -> Result<Response<Self::StreamRunLogsStream>, Status> {
+        let request = request.into_inner();
+        let job = self.job(&request.id)?;
+        if let Some(status) = remote_logs(&job) {
+            return Err(status);
+        }
+        // an offset in another attempt than the last one starts from its beginning
+        let mut next = match request.attempt {
+            0 => request.offset,
+            a if a == job.attempts => request.offset,
+            _ => 0,
+        };
+        let mut attempt = job.attempts;
+        let queue = self.queue.clone();
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            loop {
+                let job = match queue.get(&request.id) {
+                    Some(job) => job,
+                    None => return,
+                };
+                let finished = job.state.finished();
+                if job.attempts != attempt {
+                    attempt = job.attempts;
+                    next = 0;
+                }
+                if let Some(status) = remote_logs(&job) {
+                    let _ = tx.send(Err(status)).await;
+                    return;
+                }
+                // attempts leased before `ran_on` was recorded have no local working directory
+                if !job.work_dir.as_os_str().is_empty() {
+                    let lines = match logs::read(&job.work_dir, next).await {
+                        Ok(lines) => lines,
+                        Err(e) => {
+                            let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                            return;
+                        }
+                    };
+                    for line in lines {
+                        next = line.offset + 1;
+                        if tx.send(Ok(log_to_run_log(line, attempt))).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                if !request.follow || finished {
+                    return;
+                }
+                tokio::time::sleep(logs::FOLLOW_INTERVAL).await;
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn list_artifacts(
+        &self,
+        request: Request<ListArtifactsRequest>,
+    ) -> Result<Response<ListArtifactsResponse>, Status> {
+        let store = self.artifacts()?;
+        let run = &request.get_ref().run;
+        let artifacts = if run.is_empty() {
+            store
+                .list()
+                .map_err(|e| Status::internal(e.to_string()))?
+                .iter()
+                .map(|a| artifact_to_proto(a, ""))
+                .collect()
+        } else {
+            let job = self.job(run)?;
+            let produced = job.last_run.map(|r| r.artifacts).unwrap_or_default();
+            produced
+                .iter()
+                .filter_map(|(output, digest)| {
+                    store.get(digest).map(|a| artifact_to_proto(&a, output))
+                })
+                .collect()
+        };
+        Ok(Response::new(ListArtifactsResponse { artifacts }))
+    }
+
+    type GetArtifactStream = ReceiverStream<Result<ArtifactChunk, Status>>;
+    async fn get_artifact(
+        &self,
+        request: Request<GetArtifactRequest>,
+    ) -> Result<Response<Self::GetArtifactStream>, Status> {
+        let digest = &request.get_ref().digest;
+        let files = self
+            .artifacts()?
+            .files(digest)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Status::not_found(e.to_string()),
+                _ => Status::internal(e.to_string()),
+            })?;
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            for (path, object) in files {
+                let path = path.display().to_string();
+                let result = match object {
+                    None => {
+                        let chunk = ArtifactChunk {
+                            path,
+                            directory: true,
+                            content: Vec::new(),
+                        };
+                        send(&tx, chunk).await
+                    }
+                    Some(object) => send_file(&tx, path, &object).await,
+                };
+                if let Err(s) = result {
+                    let _ = tx.send(Err(s)).await;
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn promote(
+        &self,
+        request: Request<PromoteRequest>,
+    ) -> Result<Response<Deployment>, Status> {
+        let request = request.into_inner();
+        let stage = Some(request.stage.as_str()).filter(|s| !s.is_empty());
+        let d = deploy::promote(
+            &mut self.datastore.clone(),
+            &self.queue,
+            &*self.deployments()?,
+            &request.deployer,
+            &request.bundle,
+            stage,
+        )
+        .await?;
+        Ok(Response::new(deployment_to_proto(&d)))
+    }
+
+    async fn approve_deployment(
+        &self,
+        request: Request<ApproveDeploymentRequest>,
+    ) -> Result<Response<Deployment>, Status> {
+        let d = deploy::approve(
+            &mut self.datastore.clone(),
+            &self.queue,
+            &*self.deployments()?,
+            &request.get_ref().id,
+        )
+        .await?;
+        Ok(Response::new(deployment_to_proto(&d)))
+    }
+
+    async fn rollback(
+        &self,
+        request: Request<RollbackRequest>,
+    ) -> Result<Response<Deployment>, Status> {
+        let request = request.into_inner();
+        let d = deploy::rollback(
+            &mut self.datastore.clone(),
+            &self.queue,
+            &*self.deployments()?,
+            &request.deployer,
+            &request.stage,
+        )
+        .await?;
+        Ok(Response::new(deployment_to_proto(&d)))
+    }
+
+    async fn list_deployments(
+        &self,
+        request: Request<ListDeploymentsRequest>,
+    ) -> Result<Response<ListDeploymentsResponse>, Status> {
+        let request = request.get_ref();
+        let deployer = Some(request.deployer.as_str()).filter(|d| !d.is_empty());
+        let deployments = self
+            .deployments()?
+            .list(deployer, request.current)
+            .iter()
+            .map(deployment_to_proto)
+            .collect();
+        Ok(Response::new(ListDeploymentsResponse { deployments }))
+    }
+
+    async fn reconcile(
+        &self,
+        request: Request<ReconcileRequest>,
+    ) -> Result<Response<ListDeploymentsResponse>, Status> {
+        let deployments = deploy::reconcile(
+            &mut self.datastore.clone(),
+            &self.queue,
+            &*self.deployments()?,
+            &request.get_ref().applier,
+        )
+        .await?;
+        let deployments = deployments.iter().map(deployment_to_proto).collect();
+        Ok(Response::new(ListDeploymentsResponse { deployments }))
+    }
+}
+
+async fn send(
+    tx: &mpsc::Sender<Result<ArtifactChunk, Status>>,
+    chunk: ArtifactChunk,
+) -> Result<(), Status> {
+    tx.send(Ok(chunk))
+        .await
+        .map_err(|_| Status::cancelled("the client went away"))
+}
+
+/// Sends the content of a file in chunks, at least one so that empty files are created.
+async fn send_file(
+    tx: &mpsc::Sender<Result<ArtifactChunk, Status>>,
+    path: String,
+    object: &Path,
+) -> Result<(), Status> {
+    let internal = |e: std::io::Error| Status::internal(e.to_string());
+    let mut file = tokio::fs::File::open(object).await.map_err(internal)?;
+    let mut buf = vec![0u8; ARTIFACT_CHUNK_SIZE];
+    let mut sent = false;
+    loop {
+        let n = file.read(&mut buf).await.map_err(internal)?;
+        if n == 0 && sent {
+            return Ok(());
+        }
+        let chunk = ArtifactChunk {
+            path: path.clone(),
+            directory: false,
+            content: buf[..n].to_vec(),
+        };
+        send(tx, chunk).await?;
+        sent = true;
+        if n == 0 {
+            return Ok(());
+        }
+    }
+}
+
+pub fn artifact_to_proto(a: &artifacts::Artifact, output: &str) -> Artifact {
+    Artifact {
+        digest: a.digest.clone(),
+        size: a.size,
+        directory: a.directory,
+        created_at: Some(timestamp(a.created_at)),
+        output: output.to_string(),
+    }
+}
+
+/// Binds the inputs of a run to the function fetched from the datastore and queues the run.
+pub async fn submit(
+    datastore: &mut BdaClient<Channel>,
+    queue: &RunQueue,
+    request: SubmitRunRequest,
+) -> Result<Job, Status> {
+    let (function_id, resource, function) = crate::fetch_function_resource(
+        datastore,
+        or_default(&request.version, logic::DEFAULT_VERSION),
+        or_default(&request.namespace, logic::DEFAULT_NAMESPACE),
+        &request.name,
+    )
+    .await
+    .map_err(|e| match e.downcast_ref::<binding::BindingErrors>() {
+        Some(e) => Status::invalid_argument(e.to_string()),
+        None => Status::not_found(e.to_string()),
+    })?;
+    let target = match request.runtime.as_str() {
+        "" => None,
+        runtime => Some(
+            crate::fetch_container_target(datastore, runtime)
+                .await
+                .map_err(|e| Status::not_found(e.to_string()))?,
+        ),
+    };
+    let args = struct_to_args(request.inputs).map_err(Status::invalid_argument)?;
+    let base_dir = std::env::current_dir().map_err(|e| Status::internal(e.to_string()))?;
+    let binding = binding::bind(&function.inputs, &args, &base_dir)
+        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+    let mut job = Job::new(&function_id, &function, target, &binding);
+    if queue.caching() && cache::enabled(&resource) {
+        let key = cache::cache_key(
+            &function_id,
+            &resource,
+            &function,
+            job.target.as_ref(),
+            &binding,
+        )
+        .map_err(Status::invalid_argument)?;
+        job.cache_key = Some(key);
+    }
+    queue.submit(job).map_err(Status::internal)
+}
+
+fn or_default<'a>(v: &'a str, default: &'a str) -> &'a str {
+    if v.is_empty() {
+        default
+    } else {
+        v
+    }
+}
+
+pub fn log_to_run_log(line: LogLine, attempt: u32) -> RunLog {
+    RunLog {
+        stream: line.stream,
+        line: line.line,
+        offset: line.offset,
+        time: match line.time {
+            0 => None,
+            t => Some(timestamp(t)),
+        },
+        attempt,
+    }
+}
+
+/// Error telling where the logs of the last attempt of a job are, when a remote worker ran it in
+/// its own working directory.
+fn remote_logs(job: &Job) -> Option<Status> {
+    job.ran_on.as_ref().map(|worker| {
+        Status::failed_precondition(format!(
+            "the logs of attempt {} of run {} are on worker {}",
+            job.attempts, job.id, worker
+        ))
+    })
+}
+
+/// Deployment of a bundle to a stage, with its status.
+pub fn deployment_to_proto(d: &deploy::Deployment) -> Deployment {
+    let status = match d.state {
+        DeploymentState::AwaitingApproval => DeploymentStatus::AwaitingApproval,
+        DeploymentState::Deploying => DeploymentStatus::Deploying,
+        DeploymentState::Deployed => DeploymentStatus::Deployed,
+        DeploymentState::Failed => DeploymentStatus::Failed,
+    };
+    Deployment {
+        id: d.id.clone(),
+        deployer: d.deployer.clone(),
+        stage: d.stage.clone(),
+        bundle: d.bundle.clone(),
+        semver: d.semver.clone(),
+        status: status as i32,
+        run: d.run.clone().unwrap_or_default(),
+        rollback: d.rollback,
+        created_at: Some(timestamp(d.created_at)),
+        finished_at: Some(d.finished_at).filter(|t| *t > 0).map(timestamp),
+        error: d.error.clone().unwrap_or_default(),
+    }
+}
+
+/// Execution of a job, described by the record of its last finished attempt and its state.
+pub fn job_to_execution(job: &Job) -> Execution {
+    let mut e = Execution::default();
+    if let Some(ref record) = job.last_run {
+        record_to_execution(record, &mut e);
+    }
+    e.id = job.id.clone();
+    e.function = job.function_id.clone();
+    e.inputs = args_to_struct(&job.inputs).ok();
+    e.created_at = Some(timestamp(job.created_at));
+    e.attempts = job.attempts;
+    e.work_dir = job.work_dir.display().to_string();
+    e.error = job.error.clone().unwrap_or_default();
+    e.cached_from = job.cached_from.clone().unwrap_or_default();
+    let status = match job.state {
+        JobState::Queued => RunStatus::Pending,
+        JobState::Running => {
+            e.started_at = Some(timestamp(job.started_at));
+            e.finished_at = None;
+            RunStatus::Running
+        }
+        JobState::Succeeded => RunStatus::Succeeded,
+        JobState::Failed => RunStatus::Failed,
+        JobState::Cancelled => RunStatus::Cancelled,
+        JobState::Lost => RunStatus::Lost,
+    };
+    e.status = status as i32;
+    e
+}
+
+pub fn record_to_execution(record: &RunRecord, e: &mut Execution) {
+    let status = if record.cancelled {
+        RunStatus::Cancelled
+    } else if record.succeeded() {
+        RunStatus::Succeeded
+    } else {
+        RunStatus::Failed
+    };
+    e.status = status as i32;
+    e.started_at = Some(timestamp(record.started_at));
+    e.finished_at = Some(timestamp(record.finished_at));
+    e.exit_code = record.exit_code.unwrap_or_default();
+    e.work_dir = record.work_dir.display().to_string();
+    e.error = record.error.clone().unwrap_or_default();
+    e.outputs = args_to_struct(&record.outputs).ok();
+    e.artifacts = record.artifacts.clone().into_iter().collect();
+}
+
+pub fn timestamp(millis: u64) -> Timestamp {
+    Timestamp {
+        seconds: (millis / 1000) as i64,
+        nanos: ((millis % 1000) * 1_000_000) as i32,
+    }
+}
+
+pub fn struct_to_args(s: Option<Struct>) -> Result<BTreeMap<String, JValue>, String> {
+    match s {
+        None => Ok(BTreeMap::new()),
+        Some(s) => match serde_json::to_value(s).map_err(|e| e.to_string())? {
+            JValue::Object(m) => Ok(m.into_iter().collect()),
+            v => Err(format!("inputs must be an object, got {}", v)),
+        },
+    }
+}
+
+pub fn args_to_struct(args: &BTreeMap<String, JValue>) -> Result<Struct, String> {
+    serde_json::to_value(args)
+        .and_then(serde_json::from_value)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_args_struct_roundtrip() {
+        let args = BTreeMap::from([
+            ("a".to_string(), json!("x")),
+            ("b".to_string(), json!({"c": [1.0, true]})),
+        ]);
+        let s = args_to_struct(&args).unwrap();
+        assert_eq!(struct_to_args(Some(s)).unwrap(), args);
+        assert_eq!(struct_to_args(None).unwrap(), BTreeMap::new());
+    }
+
+    #[test]
+    fn test_remote_logs() {
+        let f = bdaproto::Function::default();
+        let mut job = Job::new("f", &f, None, &binding::Binding::default());
+        assert!(remote_logs(&job).is_none());
+        job.attempts = 2;
+        job.ran_on = Some("w1".to_string());
+        let status = remote_logs(&job).unwrap();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(
+            status.message(),
+            format!("the logs of attempt 2 of run {} are on worker w1", job.id)
+        );
+    }
+
+    #[test]
+    fn test_record_to_execution() {
+        let mut record = RunRecord {
+            id: "r".to_string(),
+            function: "f".to_string(),
+            inputs: BTreeMap::new(),
+            command: vec!["true".to_string()],
+            work_dir: "/tmp/r".into(),
+            started_at: 1500,
+            finished_at: 2000,
+            exit_code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+            cancelled: false,
+            timed_out: false,
+            outputs: BTreeMap::from([("n".to_string(), json!(1.0))]),
+            artifacts: BTreeMap::from([("p".to_string(), "ab".repeat(32))]),
+        };
+        let mut e = Execution::default();
+        record_to_execution(&record, &mut e);
+        assert_eq!(e.status, RunStatus::Succeeded as i32);
+        assert_eq!(e.artifacts["p"], "ab".repeat(32));
+        assert_eq!(struct_to_args(e.outputs.clone()).unwrap(), record.outputs);
+        assert_eq!(
+            e.started_at,
+            Some(Timestamp {
+                seconds: 1,
+                nanos: 500_000_000
+            })
+        );
+        record.cancelled = true;
+        record_to_execution(&record, &mut e);
+        assert_eq!(e.status, RunStatus::Cancelled as i32);
+    }
+
+    #[test]
+    fn test_job_to_execution() {
+        let function = bdaproto::Function::default();
+        let mut job = Job::new("f", &function, None, &binding::Binding::default());
+        job.state = JobState::Lost;
+        job.attempts = 2;
+        job.error = Some("attempt 2 lost when the engine stopped".to_string());
+        let e = job_to_execution(&job);
+        assert_eq!(e.id, job.id);
+        assert_eq!(e.status, RunStatus::Lost as i32);
+        assert_eq!(e.attempts, 2);
+        assert_eq!(e.error, "attempt 2 lost when the engine stopped");
+        assert_eq!(e.finished_at, None);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::coordinator;
+use crate::executor::{LocalExecutor, RunRecord};
+use crate::queue;
+use crate::service::{record_to_execution, struct_to_args};
+use bdacore::logic::binding::Binding;
+use bdaproto::coordinator_client::CoordinatorClient;
+use bdaproto::execution::Status as RunStatus;
+use bdaproto::{
+    CompleteRunRequest, Execution, HeartbeatRequest, LeaseRunRequest, RegisterWorkerRequest,
+    RunLease,
+};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use tokio::sync::{mpsc, watch};
+use tonic::{transport::Channel, Code, Status};
+
+/// Remote worker running the runs it leases from an engine with a local executor. The engine
+/// binds the inputs of the runs, so their `PATH` inputs must be reachable from the worker.
+pub struct Worker {
+    client: CoordinatorClient<Channel>,
+    executor: Arc<LocalExecutor>,
+    name: String,
+    capabilities: Vec<String>,
+    slots: usize,
+}
+
+impl Worker {
+    pub fn new(
+        client: CoordinatorClient<Channel>,
+        executor: Arc<LocalExecutor>,
+        name: &str,
+        capabilities: &[String],
+        slots: usize,
+    ) -> Self {
+        Worker {
+            client,
+            executor,
+            name: name.to_string(),
+            capabilities: capabilities.to_vec(),
+            slots: slots.max(1),
+        }
+    }
+
+    /// Registers with the engine, then leases runs while slots are free and sends heartbeats,
+    /// cancelling the runs the engine asks to. When the engine no longer knows the worker,
+    /// like after it restarted, the runs in progress are cancelled, as the engine reassigned
+    /// them, and the worker registers again. Only fails when the first registration fails.
+    pub async fn run(mut self) -> Result<(), Status> {
+        let (mut id, heartbeat) = self.register().await?;
+        let (done, mut finished) = mpsc::unbounded_channel();
+        let mut running: BTreeMap<String, watch::Sender<bool>> = BTreeMap::new();
+        let mut ticker = tokio::time::interval(heartbeat);
+        loop {
+            while running.len() < self.slots {
+                let request = LeaseRunRequest {
+                    worker_id: id.clone(),
+                };
+                let lease = match self.client.lease_run(request).await {
+                    Ok(response) => response.into_inner().lease,
+                    Err(s) if s.code() == Code::NotFound => {
+                        id = self.rejoin(&running).await?;
+                        continue;
+                    }
+                    Err(s) => {
+                        eprintln!("could not lease a run: {}", s.message());
+                        None
+                    }
+                };
+                let lease = match lease {
+                    Some(lease) => lease,
+                    None => break,
+                };
+                let (cancel, cancelled) = watch::channel(false);
+                running.insert(lease.run.clone(), cancel);
+                let (executor, done) = (self.executor.clone(), done.clone());
+                tokio::spawn(async move {
+                    let result = attempt(&executor, &lease, cancelled).await;
+                    let _ = done.send((lease, result));
+                });
+            }
+            tokio::select! {
+                Some((lease, result)) = finished.recv() => {
+                    running.remove(&lease.run);
+                    let request = CompleteRunRequest {
+                        worker_id: id.clone(),
+                        run: lease.run.clone(),
+                        attempt: lease.attempt,
+                        execution: Some(attempt_execution(&lease, result)),
+                    };
+                    if let Err(s) = self.client.complete_run(request).await {
+                        eprintln!("could not complete run {}: {}", lease.run, s.message());
+                    }
+                }
+                _ = ticker.tick() => {
+                    let request = HeartbeatRequest { worker_id: id.clone() };
+                    match self.client.heartbeat(request).await {
+                        Ok(response) => {
+                            for run in response.into_inner().cancel {
+                                if let Some(cancel) = running.get(&run) {
+                                    let _ = cancel.send(true);
+                                }
+                            }
+                        }
+                        Err(s) if s.code() == Code::NotFound => id = self.rejoin(&running).await?,
+                        Err(s) => eprintln!("could not send a heartbeat: {}", s.message()),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn register(&mut self) -> Result<(String, Duration), Status> {
+        let request = RegisterWorkerRequest {
+            name: self.name.clone(),
+            capabilities: self.capabilities.clone(),
+        };
+        let response = self.client.register_worker(request).await?.into_inner();
+        let heartbeat = Duration::from_secs(response.heartbeat_seconds.max(1) as u64);
+        Ok((response.worker_id, heartbeat))
+    }
+
+    async fn rejoin(
+        &mut self,
+        running: &BTreeMap<String, watch::Sender<bool>>,
+    ) -> Result<String, Status> {
+        for cancel in running.values() {
+            let _ = cancel.send(true);
+        }
+        Ok(self.register().await?.0)
+    }
+}
+
+async fn attempt(
+    executor: &LocalExecutor,
+    lease: &RunLease,
+    cancelled: watch::Receiver<bool>,
+) -> Result<RunRecord, String> {
+    let function = lease.definition.clone().unwrap_or_default();
+    let binding = Binding {
+        inputs: struct_to_args(lease.inputs.clone())?,
+    };
+    let run_id = queue::attempt_run_id(&lease.run, lease.attempt);
+    match coordinator::lease_target(lease) {
+        None => {
+            executor
+                .run_cancellable(&run_id, &lease.function, &function, &binding, cancelled)
+                .await
+        }
+        Some(ref target) => {
+            executor
+                .run_in_container(
+                    &run_id,
+                    &lease.function,
+                    &function,
+                    target,
+                    &binding,
+                    cancelled,
+                )
+                .await
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Outcome of a leased attempt, as sent to the engine.
+fn attempt_execution(lease: &RunLease, result: Result<RunRecord, String>) -> Execution {
+    let mut e = Execution {
+        id: lease.run.clone(),
+        function: lease.function.clone(),
+        inputs: lease.inputs.clone(),
+        attempts: lease.attempt,
+        ..Default::default()
+    };
+    match result {
+        Ok(ref record) => record_to_execution(record, &mut e),
+        Err(error) => {
+            e.status = RunStatus::Failed as i32;
+            e.error = error;
+        }
+    }
+    e
+}
@@ -0,0 +1,81 @@
+use bdaengine::artifacts::ArtifactStore;
+use bdaengine::executor::LocalExecutor;
+use bdaengine::queue::RunQueue;
+use bdaengine::service::BDAEngineService;
+use bdaproto::bda_client::BdaClient;
+use bdaproto::engine_client::EngineClient;
+use bdaproto::engine_server::EngineServer;
+use bdaproto::{GetArtifactRequest, ListArtifactsRequest};
+use std::{fs, sync::Arc};
+use tempdir::TempDir;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+use tonic::Code;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_artifact() {
+    let tmp = TempDir::new("bdaengine").unwrap();
+    let store = Arc::new(ArtifactStore::new(tmp.path().join("artifacts")));
+    let src = tmp.path().join("src");
+    fs::create_dir_all(src.join("empty")).unwrap();
+    fs::write(src.join("a.txt"), "a").unwrap();
+    fs::write(src.join("big.bin"), vec![7u8; 200_000]).unwrap();
+    let dir = store.put(&src).unwrap();
+
+    let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+    let queue = Arc::new(RunQueue::open(tmp.path().join("queue"), executor, 0).unwrap());
+    let datastore = BdaClient::new(
+        Channel::from_static("http://127.0.0.1:7000")
+            .connect_lazy()
+            .unwrap(),
+    );
+    let service = BDAEngineService::new(datastore, queue).with_artifacts(store);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(
+        Server::builder()
+            .add_service(EngineServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let mut client = EngineClient::connect(endpoint).await.unwrap();
+
+    let listed = client
+        .list_artifacts(ListArtifactsRequest::default())
+        .await
+        .unwrap()
+        .into_inner()
+        .artifacts;
+    // a.txt, big.bin and the trees of src and empty
+    assert_eq!(listed.len(), 4);
+
+    let mut stream = client
+        .get_artifact(GetArtifactRequest {
+            digest: dir.digest.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut dirs = Vec::new();
+    let mut files = std::collections::BTreeMap::new();
+    while let Some(chunk) = stream.message().await.unwrap() {
+        if chunk.directory {
+            dirs.push(chunk.path);
+        } else {
+            files
+                .entry(chunk.path)
+                .or_insert_with(Vec::new)
+                .extend(chunk.content);
+        }
+    }
+    assert_eq!(dirs, vec!["".to_string(), "empty".to_string()]);
+    assert_eq!(files["a.txt"], b"a".to_vec());
+    assert_eq!(files["big.bin"], vec![7u8; 200_000]);
+
+    let missing = client
+        .get_artifact(GetArtifactRequest {
+            digest: "0".repeat(64),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+}
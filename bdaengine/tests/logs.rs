@@ -0,0 +1,107 @@
+use bdacore::logic::binding::Binding;
+use bdaengine::executor::LocalExecutor;
+use bdaengine::queue::{Job, RunQueue};
+use bdaengine::service::BDAEngineService;
+use bdaproto::bda_client::BdaClient;
+use bdaproto::engine_client::EngineClient;
+use bdaproto::engine_server::EngineServer;
+use bdaproto::{Function, RunLog, StreamRunLogsRequest};
+use std::sync::Arc;
+use tempdir::TempDir;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+
+async fn serve(queue: Arc<RunQueue>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    // the datastore is only used to submit runs
+    let datastore = BdaClient::new(
+        Channel::from_static("http://127.0.0.1:7000")
+            .connect_lazy()
+            .unwrap(),
+    );
+    let service = BDAEngineService::new(datastore, queue);
+    tokio::spawn(
+        Server::builder()
+            .add_service(EngineServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    endpoint
+}
+
+fn lines(logs: &[RunLog]) -> Vec<(u64, &str, &str)> {
+    logs.iter()
+        .map(|l| (l.offset, l.stream.as_str(), l.line.as_str()))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_follow_logs() {
+    let tmp = TempDir::new("bdaengine").unwrap();
+    let executor = Arc::new(LocalExecutor::new(tmp.path().join("runs")));
+    let queue = Arc::new(RunQueue::open(tmp.path().join("queue"), executor, 1).unwrap());
+    tokio::spawn(queue.clone().run());
+    let endpoint = serve(queue.clone()).await;
+    let f = Function {
+        base_command: vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo one; sleep 1; echo two >&2; sleep 1; echo three".to_string(),
+        ],
+        ..Default::default()
+    };
+    let job = Job::new("/latest/default/function/f", &f, None, &Binding::default());
+    let id = queue.submit(job).unwrap().id;
+    let mut client = EngineClient::connect(endpoint).await.unwrap();
+
+    // a client disconnecting after the first line
+    let mut stream = client
+        .stream_run_logs(StreamRunLogsRequest {
+            id: id.clone(),
+            follow: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let first = stream.message().await.unwrap().unwrap();
+    assert_eq!((first.offset, first.line.as_str()), (0, "one"));
+    assert_eq!(first.attempt, 1);
+    assert!(first.time.is_some());
+    drop(stream);
+
+    // reattaching after the last line received follows the run until it finishes
+    let mut stream = client
+        .stream_run_logs(StreamRunLogsRequest {
+            id: id.clone(),
+            offset: first.offset + 1,
+            follow: true,
+            attempt: first.attempt,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut logs = Vec::new();
+    while let Some(log) = stream.message().await.unwrap() {
+        logs.push(log);
+    }
+    assert_eq!(
+        lines(&logs),
+        vec![(1, "stderr", "two"), (2, "stdout", "three")]
+    );
+    assert!(queue.get(&id).unwrap().state.finished());
+
+    let mut stream = client
+        .stream_run_logs(StreamRunLogsRequest {
+            id,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut logs = Vec::new();
+    while let Some(log) = stream.message().await.unwrap() {
+        logs.push(log);
+    }
+    assert_eq!(logs.len(), 3);
+}
@@ -0,0 +1,110 @@
+use bdacore::logic::binding::Binding;
+use bdaengine::coordinator::{BDACoordinatorService, Workers};
+use bdaengine::executor::LocalExecutor;
+use bdaengine::queue::{Job, JobState, RunQueue};
+use bdaengine::worker::Worker;
+use bdaproto::coordinator_client::CoordinatorClient;
+use bdaproto::coordinator_server::CoordinatorServer;
+use bdaproto::{Function, LeaseRunRequest, RegisterWorkerRequest};
+use std::{path::Path, sync::Arc, time::Duration};
+use tempdir::TempDir;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+async fn serve(queue: Arc<RunQueue>, workers: Arc<Workers>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let service = BDACoordinatorService::new(workers, queue);
+    tokio::spawn(
+        Server::builder()
+            .add_service(CoordinatorServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    endpoint
+}
+
+fn submit(queue: &RunQueue, capability: &str) -> String {
+    let f = Function {
+        base_command: vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo $BDA_RUN_ID".to_string(),
+        ],
+        runtime_capabilities: vec![capability.to_string()],
+        ..Default::default()
+    };
+    let job = Job::new("/latest/default/function/f", &f, None, &Binding::default());
+    queue.submit(job).unwrap().id
+}
+
+async fn start_worker(endpoint: &str, work_dir: &Path, name: &str) {
+    let client = CoordinatorClient::connect(endpoint.to_string())
+        .await
+        .unwrap();
+    let executor = Arc::new(LocalExecutor::new(work_dir.join(name)));
+    let worker = Worker::new(client, executor, name, &[name.to_string()], 2);
+    tokio::spawn(worker.run());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_workers() {
+    let tmp = TempDir::new("bdaengine").unwrap();
+    let executor = Arc::new(LocalExecutor::new(tmp.path().join("engine")));
+    let queue = Arc::new(RunQueue::open(tmp.path().join("queue"), executor, 0).unwrap());
+    let workers = Arc::new(Workers::new(queue.clone(), Duration::from_secs(1)));
+    tokio::spawn(workers.clone().run());
+    let endpoint = serve(queue.clone(), workers).await;
+
+    // a worker leasing a run and dying before completing it
+    let orphan = submit(&queue, "c");
+    let mut client = CoordinatorClient::connect(endpoint.clone()).await.unwrap();
+    let dead = client
+        .register_worker(RegisterWorkerRequest {
+            name: "dead".to_string(),
+            capabilities: vec!["c".to_string()],
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let lease = client
+        .lease_run(LeaseRunRequest {
+            worker_id: dead.worker_id,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .lease
+        .unwrap();
+    assert_eq!(lease.run, orphan);
+
+    let runs = vec![
+        submit(&queue, "a"),
+        submit(&queue, "b"),
+        submit(&queue, "a"),
+    ];
+    for name in ["a", "b", "c"] {
+        start_worker(&endpoint, tmp.path(), name).await;
+    }
+    for id in runs.iter().chain([&orphan]) {
+        let job = tokio::time::timeout(Duration::from_secs(20), async {
+            loop {
+                let job = queue.get(id).unwrap();
+                if job.state.finished() {
+                    return job;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(job.state, JobState::Succeeded, "{:?}", job.error);
+        let capability = &job.function.runtime_capabilities[0];
+        assert!(job.work_dir.starts_with(tmp.path().join(capability)));
+    }
+    let job = queue.get(&orphan).unwrap();
+    assert_eq!((job.attempts, job.reassigned), (2, 1));
+    assert_eq!(
+        job.work_dir,
+        tmp.path().join("c").join(format!("{}.2", orphan))
+    );
+}
@@ -2,6 +2,7 @@ pub mod llrb;
 pub mod lmdb;
 use crate::bql::{Rational, Value as BValue};
 use crate::flatserde::{FlatJsonFieldIterator, FlatJsonValueIterator};
+use crate::policy::IndexPolicy;
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 use serde::Deserialize;
@@ -46,6 +47,13 @@ pub enum BatchOp {
     Add(IndexKey, IndexValue),
     Del(IndexKey, IndexValue),
 }
+impl BatchOp {
+    pub fn key(&self) -> &IndexKey {
+        match self {
+            BatchOp::Add(key, _) | BatchOp::Del(key, _) => key,
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize, Hash)]
 pub enum IndexKey {
     FieldKey { field: String },
@@ -118,12 +126,23 @@ impl Batch {
     }
 
     pub fn add_data<V>(id: &str, data: V) -> Result<Self, Box<dyn Error>>
+    where
+        V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
+    {
+        Self::add_data_with_policy(id, data, &IndexPolicy::default())
+    }
+    pub fn add_data_with_policy<V>(
+        id: &str,
+        data: V,
+        policy: &IndexPolicy,
+    ) -> Result<Self, Box<dyn Error>>
     where
         V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
     {
         serde_json::to_value(&data)
             .map_err(|error| Box::new(error) as Box<dyn std::error::Error>)
             .and_then(|ref data| {
+                let rules = policy.rules_for(data);
                 Ok(FlatJsonValueIterator::new(data)
                     .map(|(k, v)| {
                         BatchOp::Add(
@@ -142,17 +161,29 @@ impl Batch {
                             IndexValue::IDStrValue(id.to_owned()),
                         )
                     }))
+                    .filter(|op| rules.accepts(op.key()))
                     .collect())
                 .and_then(|items| Ok(Batch { items }))
             })
     }
     pub fn del_data<V>(id: &str, data: V) -> Result<Self, Box<dyn Error>>
+    where
+        V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
+    {
+        Self::del_data_with_policy(id, data, &IndexPolicy::default())
+    }
+    pub fn del_data_with_policy<V>(
+        id: &str,
+        data: V,
+        policy: &IndexPolicy,
+    ) -> Result<Self, Box<dyn Error>>
     where
         V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
     {
         serde_json::to_value(&data)
             .map_err(|error| Box::new(error) as Box<dyn std::error::Error>)
             .and_then(|ref data| {
+                let rules = policy.rules_for(data);
                 Ok(FlatJsonValueIterator::new(data)
                     .map(|(k, v)| {
                         BatchOp::Del(
@@ -179,6 +210,7 @@ impl Batch {
                             IndexValue::IDStrValue(id.to_owned()),
                         )
                     }))
+                    .filter(|op| rules.accepts(op.key()))
                     .collect())
                 .and_then(|items| Ok(Batch { items }))
             })
@@ -1,18 +1,21 @@
 pub mod backend;
 pub mod bql;
 pub mod flatserde;
+pub mod policy;
 use backend::{Batch, IndexKey, IndexValue};
 use bql::{Value, BQL};
+use policy::IndexPolicy;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     error::Error,
     fmt::Debug,
     ops::{Bound, RangeBounds},
-    sync::Arc,
+    sync::{Arc, RwLock},
 };
 
 pub struct Index<T: backend::Backend> {
     backend: Arc<T>,
+    policy: RwLock<IndexPolicy>,
 }
 
 pub fn new<T: backend::Backend>(backend: Arc<T>) -> Index<T> {
@@ -21,32 +24,73 @@ pub fn new<T: backend::Backend>(backend: Arc<T>) -> Index<T> {
 
 impl<T: backend::Backend> Index<T> {
     pub fn new(backend: Arc<T>) -> Self {
-        Self { backend }
+        Self::with_policy(backend, IndexPolicy::default())
+    }
+
+    pub fn with_policy(backend: Arc<T>, policy: IndexPolicy) -> Self {
+        Self {
+            backend,
+            policy: RwLock::new(policy),
+        }
+    }
+
+    /// Replaces the index policy, reindexing the documents from the fields kept by the previous
+    /// policy to the ones kept by the new one. The documents must be all the documents of the
+    /// index, as they were inserted, so that removing them later finds every entry they have.
+    pub fn set_policy<V, I>(&self, policy: IndexPolicy, documents: I) -> Result<(), Box<dyn Error>>
+    where
+        V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
+        I: IntoIterator<Item = (String, V)>,
+    {
+        let mut current = self.policy.write().map_err(|e| e.to_string())?;
+        let mut batch = Batch::new();
+        for (id, data) in documents {
+            batch.append(&mut Batch::del_data_with_policy(
+                &id,
+                data.clone(),
+                &current,
+            )?);
+            batch.append(&mut Batch::add_data_with_policy(&id, data, &policy)?);
+        }
+        self.backend.update(batch)?;
+        *current = policy;
+        Ok(())
     }
 
     pub fn insert<V>(&self, id: &str, data: V) -> Result<(), Box<dyn Error>>
     where
         V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
     {
-        Batch::add_data(id, data).and_then(|batch| self.backend.update(batch))
+        let policy = self.policy.read().map_err(|e| e.to_string())?;
+        Batch::add_data_with_policy(id, data, &policy).and_then(|batch| self.backend.update(batch))
     }
 
     pub fn remove<V>(&self, id: &str, data: V) -> Result<(), Box<dyn Error>>
     where
         V: Clone + Debug + PartialEq<V> + Serialize + DeserializeOwned,
     {
-        Batch::del_data(id, data).and_then(|batch| self.backend.update(batch))
+        let policy = self.policy.read().map_err(|e| e.to_string())?;
+        Batch::del_data_with_policy(id, data, &policy).and_then(|batch| self.backend.update(batch))
     }
 
+    /// Searches the index, failing when the query uses fields left out by the index policy.
     pub fn search(
         &self,
         ast: Box<BQL>,
+    ) -> Result<Box<dyn Iterator<Item = Result<IndexValue, Box<dyn Error>>>>, Box<dyn Error>> {
+        self.policy.read().map_err(|e| e.to_string())?.check(&ast)?;
+        self.query(ast)
+    }
+
+    fn query(
+        &self,
+        ast: Box<BQL>,
     ) -> Result<Box<dyn Iterator<Item = Result<IndexValue, Box<dyn Error>>>>, Box<dyn Error>> {
         match *ast {
-            BQL::And(a, b) => Ok(self.and(self.search(a)?, self.search(b)?)),
-            BQL::Or(a, b) => Ok(self.or(self.search(a)?, self.search(b)?)),
-            BQL::Diff(a, b) => Ok(self.diff(self.search(a)?, self.search(b)?)),
-            BQL::Comp(a, b) => Ok(self.complement(self.search(a)?, self.search(b)?)),
+            BQL::And(a, b) => Ok(self.and(self.query(a)?, self.query(b)?)),
+            BQL::Or(a, b) => Ok(self.or(self.query(a)?, self.query(b)?)),
+            BQL::Diff(a, b) => Ok(self.diff(self.query(a)?, self.query(b)?)),
+            BQL::Comp(a, b) => Ok(self.complement(self.query(a)?, self.query(b)?)),
             BQL::IsPresent => self.is_present(),
             BQL::Eq { field, value } => self.eq(&field, &value),
             BQL::IsDefined { field } => self.is_defined(&field),
@@ -62,7 +106,7 @@ impl<T: backend::Backend> Index<T> {
                 | BQL::Diff(..)
                 | BQL::Comp(..)
                 | BQL::Not(..)
-                | BQL::IsPresent => Ok(self.diff(self.is_present()?, self.search(b)?)),
+                | BQL::IsPresent => Ok(self.diff(self.is_present()?, self.query(b)?)),
                 BQL::Eq { field: ref f, .. }
                 | BQL::IsDefined { field: ref f, .. }
                 | BQL::LT { field: ref f, .. }
@@ -71,7 +115,7 @@ impl<T: backend::Backend> Index<T> {
                 | BQL::GE { field: ref f, .. }
                 | BQL::All { field: ref f, .. }
                 | BQL::Any { field: ref f, .. } => {
-                    Ok(self.diff(self.is_defined(f)?, self.search(b)?))
+                    Ok(self.diff(self.is_defined(f)?, self.query(b)?))
                 }
             },
         }
@@ -404,6 +448,8 @@ impl Iterator for IndexValueMerge {
 #[cfg(test)]
 mod test_super {
     use super::*;
+    use backend::llrb::LLRBBackend;
+    use backend::{Backend, BatchOp};
     use mockall::predicate;
     use serde_json::json;
     #[test]
@@ -442,4 +488,96 @@ mod test_super {
             Err(_) => {}
         }
     }
+
+    #[test]
+    fn test_index_policy() {
+        let policy: IndexPolicy = serde_json::from_value(json!({
+            "exclude": [".keyc"],
+            "maxValueLength": 4
+        }))
+        .unwrap();
+        let id = "my_id";
+        let data = json!({"keya": "short", "keyb": "ab", "keyc": {"keyca": 1 as i64}});
+        let add_field = |field: &str| {
+            BatchOp::Add(
+                IndexKey::FieldKey {
+                    field: field.to_owned(),
+                },
+                IndexValue::IDStrValue(id.to_owned()),
+            )
+        };
+        let mut expected = vec![
+            BatchOp::Add(
+                IndexKey::ValueKey {
+                    field: ".keyb".to_owned(),
+                    value: Value::Text("ab".to_owned()),
+                },
+                IndexValue::IDStrValue(id.to_owned()),
+            ),
+            add_field("."),
+            add_field(".keya"),
+            add_field(".keyb"),
+        ];
+        expected.sort();
+        let mut backend = backend::MockBackend::new();
+        backend
+            .expect_update()
+            .times(1)
+            .withf(move |batch| {
+                let mut items = batch.iter().collect::<Vec<_>>();
+                items.sort();
+                items == expected
+            })
+            .returning(|_| Ok(()));
+        let index = Index::with_policy(Arc::new(backend), policy);
+        index.insert(id, data).unwrap();
+        let search = index.search(Box::new(BQL::IsDefined {
+            field: ".keyc.keyca".to_owned(),
+        }));
+        assert!(search.is_err());
+    }
+
+    #[test]
+    fn test_index_policy_change_then_remove() {
+        let narrow: IndexPolicy = serde_json::from_value(json!({"exclude": [".keyb"]})).unwrap();
+        let id = "my_id";
+        let data = json!({"keya": "a", "keyb": "b"});
+        let ids = |index: &Index<LLRBBackend>, field: &str| {
+            index
+                .search(Box::new(BQL::IsDefined {
+                    field: field.to_owned(),
+                }))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+        for (before, after) in [
+            (narrow.clone(), IndexPolicy::default()),
+            (IndexPolicy::default(), narrow),
+        ] {
+            let backend = Arc::new(LLRBBackend::new());
+            let index = Index::with_policy(backend.clone(), before);
+            index.insert(id, data.clone()).unwrap();
+            index
+                .set_policy(after.clone(), vec![(id.to_owned(), data.clone())])
+                .unwrap();
+            assert_eq!(
+                ids(&index, ".keya"),
+                vec![IndexValue::IDStrValue(id.to_owned())]
+            );
+            if after
+                .check(&BQL::IsDefined {
+                    field: ".keyb".to_owned(),
+                })
+                .is_ok()
+            {
+                assert_eq!(
+                    ids(&index, ".keyb"),
+                    vec![IndexValue::IDStrValue(id.to_owned())]
+                );
+            }
+            index.remove(id, data.clone()).unwrap();
+            assert_eq!(backend.key_scan(..).unwrap().count(), 0);
+        }
+    }
 }
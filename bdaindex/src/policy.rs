@@ -0,0 +1,260 @@
+use crate::backend::IndexKey;
+use crate::bql::{Value, BQL};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JValue;
+use std::collections::BTreeMap;
+
+/// Selects which fields of a document are indexed.
+///
+/// Patterns are dotted field paths, like `.attributes` or `.function.inputs.*`, where `*`
+/// matches a single segment. A pattern also matches every field below it. The `kinds` map holds
+/// overrides keyed by a field path; they apply to documents defining that field, like
+/// `.function`. Changing the policy of an existing index requires reindexing its documents.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexPolicy {
+    #[serde(flatten)]
+    pub rules: FieldRules,
+    #[serde(default)]
+    pub kinds: BTreeMap<String, FieldRules>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldRules {
+    /// When set, only fields matching one of these patterns are indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+    /// Fields matching one of these patterns are never indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+    /// Text values longer than this are not indexed, though their fields still are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_value_length: Option<usize>,
+    /// Fields indexed with their values whatever the other rules, see `IndexPolicy::requiring`.
+    #[serde(skip)]
+    pub required: Vec<String>,
+    /// Fields indexed as present, without their values, whatever the other rules.
+    #[serde(skip)]
+    pub required_fields: Vec<String>,
+}
+
+impl IndexPolicy {
+    /// Returns the policy with fields its owner queries itself always indexed: the `values`
+    /// patterns with their values, and the `fields` patterns as present. Kind overrides keep them.
+    pub fn requiring<V: ToString, F: ToString>(mut self, values: &[V], fields: &[F]) -> Self {
+        self.rules.required = values.iter().map(|v| v.to_string()).collect();
+        self.rules.required_fields = fields.iter().map(|f| f.to_string()).collect();
+        self
+    }
+
+    /// Returns the rules for a document, with the first matching kind override applied.
+    pub fn rules_for(&self, doc: &JValue) -> FieldRules {
+        self.kinds
+            .iter()
+            .find(|(field, _)| defines(doc, field))
+            .map(|(_, rules)| self.rules.merge(rules))
+            .unwrap_or_else(|| self.rules.clone())
+    }
+
+    /// Fails when the query references a field, or a value, that no rule indexes.
+    pub fn check(&self, ast: &BQL) -> Result<(), String> {
+        match ast {
+            BQL::And(a, b) | BQL::Or(a, b) | BQL::Diff(a, b) | BQL::Comp(a, b) => {
+                self.check(a).and_then(|_| self.check(b))
+            }
+            BQL::Not(b) => self.check(b),
+            BQL::IsPresent => Ok(()),
+            BQL::IsDefined { field } => self.queryable(field, |r| r.indexes_field(field)),
+            BQL::LT { field, .. }
+            | BQL::LE { field, .. }
+            | BQL::GT { field, .. }
+            | BQL::GE { field, .. } => self.queryable(field, |r| r.indexes_values(field)),
+            BQL::Eq { field, value } => self.queryable(field, |r| r.indexes_value(field, value)),
+            BQL::All { field, values } | BQL::Any { field, values } => values
+                .iter()
+                .try_for_each(|value| self.queryable(field, |r| r.indexes_value(field, value))),
+        }
+    }
+
+    fn queryable<F: Fn(&FieldRules) -> bool>(&self, field: &str, f: F) -> Result<(), String> {
+        let indexed = f(&self.rules) || self.kinds.values().any(|r| f(&self.rules.merge(r)));
+        if indexed {
+            Ok(())
+        } else {
+            Err(format!(
+                "field {} is not indexed by the index policy, \
+                 or the value is longer than maxValueLength",
+                field
+            ))
+        }
+    }
+}
+
+impl FieldRules {
+    fn merge(&self, other: &FieldRules) -> FieldRules {
+        FieldRules {
+            include: other.include.clone().or_else(|| self.include.clone()),
+            exclude: other.exclude.clone().or_else(|| self.exclude.clone()),
+            max_value_length: other.max_value_length.or(self.max_value_length),
+            required: self.required.clone(),
+            required_fields: self.required_fields.clone(),
+        }
+    }
+
+    pub fn accepts(&self, key: &IndexKey) -> bool {
+        match key {
+            IndexKey::FieldKey { field } => self.indexes_field(field),
+            IndexKey::ValueKey { field, value } => self.indexes_value(field, value),
+        }
+    }
+
+    /// A field is indexed when it is required or leads to a required field, or else when it is
+    /// included, or leads to an included field, and not excluded.
+    pub fn indexes_field(&self, field: &str) -> bool {
+        field == "."
+            || self
+                .required
+                .iter()
+                .any(|p| matches(p, field) || leads_to(p, field))
+            || self
+                .required_fields
+                .iter()
+                .any(|p| segments(p) == segments(field) || leads_to(p, field))
+            || (self.included(|p| matches(p, field) || leads_to(p, field)) && !self.excluded(field))
+    }
+
+    pub fn indexes_values(&self, field: &str) -> bool {
+        self.is_required(field) || (self.included(|p| matches(p, field)) && !self.excluded(field))
+    }
+
+    pub fn indexes_value(&self, field: &str, value: &Value) -> bool {
+        self.is_required(field)
+            || (self.indexes_values(field)
+                && match (value, self.max_value_length) {
+                    (Value::Text(s), Some(max)) => s.len() <= max,
+                    _ => true,
+                })
+    }
+
+    fn is_required(&self, field: &str) -> bool {
+        self.required.iter().any(|p| matches(p, field))
+    }
+
+    fn included<F: Fn(&str) -> bool>(&self, f: F) -> bool {
+        self.include
+            .as_ref()
+            .map_or(true, |patterns| patterns.iter().any(|p| f(p)))
+    }
+
+    fn excluded(&self, field: &str) -> bool {
+        self.exclude
+            .as_ref()
+            .map_or(false, |patterns| patterns.iter().any(|p| matches(p, field)))
+    }
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('.').filter(|s| !s.is_empty()).collect()
+}
+
+/// True when the pattern matches the field or one of its ancestors.
+fn matches(pattern: &str, field: &str) -> bool {
+    let (p, f) = (segments(pattern), segments(field));
+    p.len() <= f.len() && p.iter().zip(f.iter()).all(|(p, f)| *p == "*" || p == f)
+}
+
+/// True when the field is an ancestor of the fields matched by the pattern.
+fn leads_to(pattern: &str, field: &str) -> bool {
+    let (p, f) = (segments(pattern), segments(field));
+    f.len() < p.len() && p.iter().zip(f.iter()).all(|(p, f)| *p == "*" || p == f)
+}
+
+fn defines(doc: &JValue, field: &str) -> bool {
+    segments(field)
+        .into_iter()
+        .try_fold(doc, |doc, segment| doc.get(segment))
+        .is_some()
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+    use serde_json::json;
+
+    fn policy() -> IndexPolicy {
+        serde_json::from_value(json!({
+            "exclude": [".attributes"],
+            "maxValueLength": 8,
+            "kinds": {
+                ".function": {"include": [".name", ".function.inputs.*"]}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert!(matches(".attributes", ".attributes.key.value"));
+        assert!(matches(".function.*.name", ".function.inputs.name"));
+        assert!(!matches(".function.inputs", ".function"));
+        assert!(leads_to(".function.inputs", ".function"));
+        assert!(!leads_to(".function.inputs", ".name"));
+    }
+
+    #[test]
+    fn test_rules_for() {
+        let p = policy();
+        let runtime = p.rules_for(&json!({"name": "a", "runtime": {"container": {}}}));
+        assert!(runtime.indexes_field(".runtime.container"));
+        assert!(!runtime.indexes_field(".attributes.key"));
+        assert!(runtime.indexes_value(".name", &Value::Text("short".to_string())));
+        assert!(!runtime.indexes_value(".name", &Value::Text("a long name".to_string())));
+        let function = p.rules_for(&json!({"name": "a", "function": {}}));
+        assert!(function.indexes_field("."));
+        assert!(function.indexes_field(".function"));
+        assert!(function.indexes_values(".function.inputs.name"));
+        assert!(!function.indexes_values(".function.baseCommand"));
+        assert!(!function.indexes_field(".attributes"));
+    }
+
+    #[test]
+    fn test_requiring() {
+        let p = policy().requiring(&[".references"], &[".function", ".runtime.container"]);
+        let runtime = p.rules_for(&json!({"name": "a", "runtime": {"container": {}}}));
+        assert!(runtime.indexes_value(".references.name", &Value::Text("a long name".to_string())));
+        assert!(runtime.indexes_field(".runtime"));
+        assert!(runtime.indexes_field(".runtime.container"));
+        let function = p.rules_for(&json!({"name": "a", "function": {}}));
+        assert!(function.indexes_values(".references.kind"));
+        assert!(function.indexes_field(".function"));
+        assert!(!function.indexes_values(".function.baseCommand"));
+        assert!(!function.indexes_field(".function.baseCommand"));
+        let eq = BQL::Eq {
+            field: ".references.kind".to_string(),
+            value: Value::Text("function".to_string()),
+        };
+        assert_eq!(p.check(&eq), Ok(()));
+    }
+
+    #[test]
+    fn test_check() {
+        let p = policy();
+        let eq = |field: &str, v: &str| BQL::Eq {
+            field: field.to_string(),
+            value: Value::Text(v.to_string()),
+        };
+        assert_eq!(p.check(&eq(".name", "a")), Ok(()));
+        assert_eq!(p.check(&eq(".function.baseCommand", "ls")), Ok(()));
+        assert!(p.check(&eq(".attributes.key", "a")).is_err());
+        assert!(p.check(&eq(".name", "a long name")).is_err());
+        assert!(p
+            .check(&BQL::And(
+                Box::new(BQL::IsPresent),
+                Box::new(BQL::Not(Box::new(BQL::IsDefined {
+                    field: ".attributes".to_string()
+                })))
+            ))
+            .is_err());
+    }
+}
@@ -1,6 +1,7 @@
 ///Resource represents a resource in the BDA architecture
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Resource {
+    ///semver like 1.2.0, or latest. several versions are kept side by side
     #[prost(string, tag = "1")]
     pub version: ::prost::alloc::string::String,
     #[prost(string, tag = "2")]
@@ -13,7 +14,17 @@ pub struct Resource {
     pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(message, optional, tag = "6")]
     pub attributes: ::core::option::Option<::pbjson_types::Struct>,
-    #[prost(oneof = "resource::ResourceKind", tags = "50, 51")]
+    ///dependencies besides the ones referenced by its kind
+    #[prost(message, repeated, tag = "7")]
+    pub references: ::prost::alloc::vec::Vec<ResourceRef>,
+    ///key/value pairs matched by label selectors, like env=prod
+    #[prost(map = "string, string", tag = "8")]
+    pub labels:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(
+        oneof = "resource::ResourceKind",
+        tags = "50, 51, 52, 53, 54, 55, 56, 57, 58"
+    )]
     pub resource_kind: ::core::option::Option<resource::ResourceKind>,
 }
 /// Nested message and enum types in `Resource`.
@@ -24,8 +35,38 @@ pub mod resource {
         Function(super::Function),
         #[prost(message, tag = "51")]
         Runtime(super::Runtime),
+        #[prost(message, tag = "52")]
+        Dag(super::Dag),
+        #[prost(message, tag = "53")]
+        Secret(super::Secret),
+        #[prost(message, tag = "54")]
+        Config(super::Config),
+        #[prost(message, tag = "55")]
+        Bundle(super::Bundle),
+        #[prost(message, tag = "56")]
+        Listener(super::Listener),
+        #[prost(message, tag = "57")]
+        Deployer(super::Deployer),
+        #[prost(message, tag = "58")]
+        Applier(super::Applier),
     }
 }
+/// ResourceRef is a typed reference to another resource. The datastore tracks the references of
+/// every resource, the ones declared and the ones given by ids in its kind, like the functions of a
+/// dag, and rejects deleting a referenced resource unless told to cascade or orphan
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceRef {
+    #[prost(string, tag = "1")]
+    pub kind: ::prost::alloc::string::String,
+    ///blank is the namespace of the referencing resource
+    #[prost(string, tag = "2")]
+    pub namespace: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    ///version, latest or semver range like ^1.2. blank is latest
+    #[prost(string, tag = "4")]
+    pub version: ::prost::alloc::string::String,
+}
 /// Function is a resource that declares parameters and a procedure to be executed in order to apply
 /// transformations like build a source code or deploy an application
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -38,6 +79,43 @@ pub struct Function {
     pub base_command: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag = "4")]
     pub runtime_capabilities: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "5")]
+    pub run_policy: ::core::option::Option<RunPolicy>,
+    #[prost(message, repeated, tag = "6")]
+    pub secrets: ::prost::alloc::vec::Vec<SecretRef>,
+}
+/// SecretRef injects a value of a secret into the runs of a function, either as an environment
+/// variable or as a file
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SecretRef {
+    ///resource id of the secret, like /latest/default/secret/registry
+    #[prost(string, tag = "1")]
+    pub secret: ::prost::alloc::string::String,
+    ///key of the value in the secret data
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    ///environment variable receiving the value
+    #[prost(string, tag = "3")]
+    pub env: ::prost::alloc::string::String,
+    ///file receiving the value, relative to the run working directory
+    #[prost(string, tag = "4")]
+    pub path: ::prost::alloc::string::String,
+}
+/// RunPolicy controls how the engine runs a function
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunPolicy {
+    ///0 means no timeout
+    #[prost(uint32, tag = "1")]
+    pub timeout_seconds: u32,
+    ///attempts made after a failed one
+    #[prost(uint32, tag = "2")]
+    pub retries: u32,
+    ///delay before the first retry, doubled for each next retry
+    #[prost(uint32, tag = "3")]
+    pub backoff_seconds: u32,
+    ///delay between SIGTERM and SIGKILL when stopping, 0 means 10
+    #[prost(uint32, tag = "4")]
+    pub grace_period_seconds: u32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Parameter {
@@ -49,6 +127,12 @@ pub struct Parameter {
     pub parameter_kind: i32,
     #[prost(message, optional, tag = "4")]
     pub default_value: ::core::option::Option<::pbjson_types::Value>,
+    ///lowest NUMBER value, included. unbounded when unset
+    #[prost(message, optional, tag = "5")]
+    pub minimum: ::core::option::Option<::pbjson_types::Value>,
+    ///highest NUMBER value, included. unbounded when unset
+    #[prost(message, optional, tag = "6")]
+    pub maximum: ::core::option::Option<::pbjson_types::Value>,
 }
 /// Nested message and enum types in `Parameter`.
 pub mod parameter {
@@ -64,6 +148,54 @@ pub mod parameter {
         Path = 6,
     }
 }
+/// Dag is a resource that wires functions into a directed acyclic graph, where the outputs of a
+/// node feed the inputs of the nodes depending on it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Dag {
+    #[prost(message, repeated, tag = "1")]
+    pub nodes: ::prost::alloc::vec::Vec<DagNode>,
+    #[prost(message, repeated, tag = "2")]
+    pub edges: ::prost::alloc::vec::Vec<DagEdge>,
+    ///maximum number of nodes running at once, 0 means 1
+    #[prost(uint32, tag = "3")]
+    pub parallelism: u32,
+    #[prost(enumeration = "dag::FailurePolicy", tag = "4")]
+    pub failure_policy: i32,
+}
+/// Nested message and enum types in `Dag`.
+pub mod dag {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum FailurePolicy {
+        ///cancels the running nodes and skips the others when a node fails
+        FailFast = 0,
+        ///skips only the nodes depending on a failed node
+        Continue = 1,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DagNode {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    ///resource id of the function, like /latest/default/function/build
+    #[prost(string, tag = "2")]
+    pub function: ::prost::alloc::string::String,
+    ///constant inputs of the function
+    #[prost(message, optional, tag = "3")]
+    pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+}
+/// DagEdge feeds an output of the node `from` to an input of the node `to`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DagEdge {
+    #[prost(string, tag = "1")]
+    pub from: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub output: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub input: ::prost::alloc::string::String,
+}
 ///Runtime is a resource that describes an environment where procedures can be executed
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Runtime {
@@ -85,8 +217,239 @@ pub struct Container {
     #[prost(string, tag = "1")]
     pub dockerfile: ::prost::alloc::string::String,
 }
+/// Secret is a resource holding credentials like registry tokens or deploy keys. The datastore
+/// encrypts its values at rest, never indexes them and redacts them in responses
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Secret {
+    ///values by key, plaintext when put and redacted when read
+    #[prost(map = "string, string", tag = "1")]
+    pub data:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+/// Config is a resource holding typed values shared by the functions of its namespace. Parameters
+/// reference them in their default values, like ${config:deploy/region}, resolved when inputs are
+/// bound, first in the namespace of the function then in the default namespace
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Config {
+    #[prost(map = "string, message", tag = "1")]
+    pub values: ::std::collections::HashMap<::prost::alloc::string::String, ::pbjson_types::Value>,
+}
+/// Bundle is a resource packaging functions, runtimes and dags under a semantic version. Bundles are
+/// exported as a single archive, and importing one installs its resources and the bundle itself,
+/// recording which bundle version installed each resource
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bundle {
+    ///semantic version of the bundle, like 1.2.0
+    #[prost(string, tag = "1")]
+    pub semver: ::prost::alloc::string::String,
+    ///resource ids of the packaged resources
+    #[prost(string, repeated, tag = "2")]
+    pub resources: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Listener is a resource starting a run of a function or a dag each time one of its triggers
+/// fires. String inputs are templated from the payload of the event, like ${event.resource.name},
+/// a value made of a single reference keeping the type of the referenced value
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Listener {
+    ///resource id of the function or dag to run
+    #[prost(string, tag = "1")]
+    pub target: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub triggers: ::prost::alloc::vec::Vec<Trigger>,
+    ///inputs of the function, or node.input for dag nodes
+    #[prost(message, optional, tag = "3")]
+    pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+}
+/// Trigger is an event source of a listener, watched by the engine
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trigger {
+    #[prost(oneof = "trigger::TriggerKind", tags = "1, 2, 3, 4")]
+    pub trigger_kind: ::core::option::Option<trigger::TriggerKind>,
+}
+/// Nested message and enum types in `Trigger`.
+pub mod trigger {
+    /// Cron fires on a schedule of five fields: minute hour day-of-month month day-of-week, in UTC
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Cron {
+        #[prost(string, tag = "1")]
+        pub schedule: ::prost::alloc::string::String,
+    }
+    /// ResourceChange fires on the writes of the datastore to resources matching the filter
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ResourceChange {
+        ///blank is all resources
+        #[prost(string, tag = "1")]
+        pub bql: ::prost::alloc::string::String,
+    }
+    /// PathWatch fires when files are created, modified or removed under a path of the engine host
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PathWatch {
+        #[prost(string, tag = "1")]
+        pub path: ::prost::alloc::string::String,
+    }
+    /// Webhook fires on the HTTP POST requests to /hooks/<path> on the webhook address of the engine
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Webhook {
+        #[prost(string, tag = "1")]
+        pub path: ::prost::alloc::string::String,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum TriggerKind {
+        #[prost(message, tag = "1")]
+        Cron(super::trigger::Cron),
+        #[prost(message, tag = "2")]
+        ResourceChange(super::trigger::ResourceChange),
+        #[prost(message, tag = "3")]
+        PathWatch(super::trigger::PathWatch),
+        #[prost(message, tag = "4")]
+        Webhook(super::trigger::Webhook),
+    }
+}
+/// Deployer is a resource promoting bundles through environments, in the order of its stages. A
+/// bundle is deployed to a stage by a run of the deploy function, with the stage inputs and the
+/// bundle, semver and stage inputs, once it is deployed to the previous stage and approved when
+/// the stage has an approval gate
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Deployer {
+    ///resource id of the function deploying a bundle to an environment
+    #[prost(string, tag = "1")]
+    pub function: ::prost::alloc::string::String,
+    ///environments in promotion order, like dev, staging and prod
+    #[prost(message, repeated, tag = "2")]
+    pub stages: ::prost::alloc::vec::Vec<deployer::Stage>,
+}
+/// Nested message and enum types in `Deployer`.
+pub mod deployer {
+    /// Stage is an environment bundles are deployed to
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Stage {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        ///deployments to the stage wait for an approval
+        #[prost(bool, tag = "2")]
+        pub approval: bool,
+        ///inputs of the deploy function for the stage
+        #[prost(message, optional, tag = "3")]
+        pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+    }
+}
+/// Applier is a resource declaring the bundles wanted in the stages of a deployer. The engine
+/// reconciles it by promoting the wanted bundles when they differ from the deployed ones
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetVersionsRequest {}
+pub struct Applier {
+    ///resource id of the deployer
+    #[prost(string, tag = "1")]
+    pub deployer: ::prost::alloc::string::String,
+    ///resource id of the bundle wanted by stage name
+    #[prost(map = "string, string", tag = "2")]
+    pub stages:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+/// Deployment records a bundle deployed to a stage of a deployer by a run of its deploy function
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Deployment {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    ///resource id of the deployer
+    #[prost(string, tag = "2")]
+    pub deployer: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub stage: ::prost::alloc::string::String,
+    ///resource id of the bundle
+    #[prost(string, tag = "4")]
+    pub bundle: ::prost::alloc::string::String,
+    ///semantic version of the bundle when deployed
+    #[prost(string, tag = "5")]
+    pub semver: ::prost::alloc::string::String,
+    #[prost(enumeration = "deployment::Status", tag = "6")]
+    pub status: i32,
+    ///id of the run of the deploy function, blank until started
+    #[prost(string, tag = "7")]
+    pub run: ::prost::alloc::string::String,
+    ///deploys back the bundle deployed before the current one
+    #[prost(bool, tag = "8")]
+    pub rollback: bool,
+    #[prost(message, optional, tag = "9")]
+    pub created_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(message, optional, tag = "10")]
+    pub finished_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(string, tag = "11")]
+    pub error: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `Deployment`.
+pub mod deployment {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Status {
+        AwaitingApproval = 0,
+        Deploying = 1,
+        Deployed = 2,
+        Failed = 3,
+    }
+}
+/// Execution records a run of a Function: the bound inputs, the outcome and the produced outputs
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Execution {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    ///resource id of the executed function
+    #[prost(string, tag = "2")]
+    pub function: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+    #[prost(enumeration = "execution::Status", tag = "4")]
+    pub status: i32,
+    #[prost(message, optional, tag = "5")]
+    pub created_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(message, optional, tag = "6")]
+    pub started_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(message, optional, tag = "7")]
+    pub finished_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(int32, tag = "8")]
+    pub exit_code: i32,
+    #[prost(message, optional, tag = "9")]
+    pub outputs: ::core::option::Option<::pbjson_types::Struct>,
+    #[prost(string, tag = "10")]
+    pub work_dir: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub error: ::prost::alloc::string::String,
+    ///attempts started, retries included
+    #[prost(uint32, tag = "12")]
+    pub attempts: u32,
+    ///run whose cached outputs were reused instead of running
+    #[prost(string, tag = "13")]
+    pub cached_from: ::prost::alloc::string::String,
+    ///digests of the PATH outputs in the artifact store
+    #[prost(map = "string, string", tag = "14")]
+    pub artifacts:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+/// Nested message and enum types in `Execution`.
+pub mod execution {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Status {
+        Pending = 0,
+        Running = 1,
+        Succeeded = 2,
+        Failed = 3,
+        Cancelled = 4,
+        ///the engine stopped during the run and could not retry it
+        Lost = 5,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetVersionsRequest {
+    ///all for all or comma separated values. blank is all
+    #[prost(string, tag = "1")]
+    pub namespaces: ::prost::alloc::string::String,
+    ///all for all or comma separated values. blank is all
+    #[prost(string, tag = "2")]
+    pub kinds: ::prost::alloc::string::String,
+    ///all for all or comma separated values. blank is all
+    #[prost(string, tag = "3")]
+    pub names: ::prost::alloc::string::String,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetNamespacesRequest {}
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -98,8 +461,27 @@ pub struct GetResourcesResponse {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetVersionsResponse {
+    ///distinct versions of all the resources
     #[prost(string, repeated, tag = "1")]
     pub versions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    ///versions of the requested resources by name
+    #[prost(message, repeated, tag = "2")]
+    pub resources: ::prost::alloc::vec::Vec<ResourceVersions>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceVersions {
+    #[prost(string, tag = "1")]
+    pub namespace: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    ///semver versions first, in semver order
+    #[prost(string, repeated, tag = "4")]
+    pub versions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    ///version latest resolves to, blank when none
+    #[prost(string, tag = "5")]
+    pub latest: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetNamespacesResponse {
@@ -113,7 +495,7 @@ pub struct GetKindsResponse {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetResourcesRequest {
-    ///blank is latest
+    ///version, latest or semver range like ^1.2. blank is latest
     #[prost(string, tag = "1")]
     pub version: ::prost::alloc::string::String,
     ///all for all or comma separated values. blank is all
@@ -127,10 +509,19 @@ pub struct GetResourcesRequest {
     pub names: ::prost::alloc::string::String,
     #[prost(string, tag = "5")]
     pub bql: ::prost::alloc::string::String,
+    ///resource id, keeps the resources referencing it. blank is all
+    #[prost(string, tag = "6")]
+    pub references: ::prost::alloc::string::String,
+    ///resource id, keeps the resources it references. blank is all
+    #[prost(string, tag = "7")]
+    pub referenced_by: ::prost::alloc::string::String,
+    ///label selector like env in (prod,staging),!deprecated. blank is all
+    #[prost(string, tag = "8")]
+    pub labels: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DelResourcesRequest {
-    ///blank is latest
+    ///version, latest or semver range like ^1.2. blank is latest
     #[prost(string, tag = "1")]
     pub version: ::prost::alloc::string::String,
     ///all for all or comma separated values. blank is all
@@ -144,9 +535,15 @@ pub struct DelResourcesRequest {
     pub names: ::prost::alloc::string::String,
     #[prost(string, tag = "5")]
     pub bql: ::prost::alloc::string::String,
+    #[prost(enumeration = "DeletePolicy", tag = "6")]
+    pub policy: i32,
+    ///label selector like env in (prod,staging),!deprecated. blank is all
+    #[prost(string, tag = "7")]
+    pub labels: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetResourceRequest {
+    ///version, latest or semver range like ^1.2
     #[prost(string, tag = "1")]
     pub version: ::prost::alloc::string::String,
     #[prost(string, tag = "2")]
@@ -157,6 +554,82 @@ pub struct GetResourceRequest {
     pub name: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceHistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub revisions: ::prost::alloc::vec::Vec<ResourceRevision>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceRevision {
+    ///position of the change in the history of the resource, from 1
+    #[prost(uint64, tag = "1")]
+    pub revision: u64,
+    #[prost(message, optional, tag = "2")]
+    pub time: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(enumeration = "resource_revision::Operation", tag = "3")]
+    pub operation: i32,
+    ///content after the change, unset when deleted
+    #[prost(message, optional, tag = "4")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// Nested message and enum types in `ResourceRevision`.
+pub mod resource_revision {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Operation {
+        Create = 0,
+        Update = 1,
+        Delete = 2,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchResourcesRequest {
+    ///blank is all resources
+    #[prost(string, tag = "1")]
+    pub bql: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceEvent {
+    #[prost(enumeration = "resource_revision::Operation", tag = "1")]
+    pub operation: i32,
+    ///content after the change, or the deleted content
+    #[prost(message, optional, tag = "2")]
+    pub resource: ::core::option::Option<Resource>,
+    #[prost(message, optional, tag = "3")]
+    pub time: ::core::option::Option<::pbjson_types::Timestamp>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListInstalledRequest {
+    ///all for all or comma separated values. blank is all
+    #[prost(string, tag = "1")]
+    pub namespaces: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListInstalledResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub resources: ::prost::alloc::vec::Vec<InstalledResource>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InstalledResource {
+    ///resource id of the installed resource
+    #[prost(string, tag = "1")]
+    pub resource: ::prost::alloc::string::String,
+    ///resource id of the bundle that installed it
+    #[prost(string, tag = "2")]
+    pub bundle: ::prost::alloc::string::String,
+    ///version of the bundle that installed it
+    #[prost(string, tag = "3")]
+    pub semver: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSecretRequest {
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub namespace: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PutResourceRequest {
     #[prost(message, optional, tag = "2")]
     pub resource: ::core::option::Option<Resource>,
@@ -176,12 +649,251 @@ pub struct DelResourceRequest {
     pub kind: ::prost::alloc::string::String,
     #[prost(string, tag = "4")]
     pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "DeletePolicy", tag = "5")]
+    pub policy: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DelResourceResponse {
     #[prost(int32, tag = "1")]
     pub updates: i32,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitRunRequest {
+    ///blank is latest
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    ///blank is default
+    #[prost(string, tag = "2")]
+    pub namespace: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+    ///resource id of a container runtime, blank runs a local process
+    #[prost(string, tag = "5")]
+    pub runtime: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetRunRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRunsRequest {
+    ///resource id of the function. blank is all
+    #[prost(string, tag = "1")]
+    pub function: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRunsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub runs: ::prost::alloc::vec::Vec<Execution>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelRunRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamRunLogsRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    ///first line to stream, to reattach after the last line received
+    #[prost(uint64, tag = "2")]
+    pub offset: u64,
+    ///streams the new lines until the run finishes
+    #[prost(bool, tag = "3")]
+    pub follow: bool,
+    ///attempt the offset belongs to, 0 for the last one
+    #[prost(uint32, tag = "4")]
+    pub attempt: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunLog {
+    ///stdout or stderr
+    #[prost(string, tag = "1")]
+    pub stream: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub line: ::prost::alloc::string::String,
+    ///position of the line in the log of its attempt
+    #[prost(uint64, tag = "3")]
+    pub offset: u64,
+    #[prost(message, optional, tag = "4")]
+    pub time: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(uint32, tag = "5")]
+    pub attempt: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListArtifactsRequest {
+    ///lists the artifacts a run produced. blank is all
+    #[prost(string, tag = "1")]
+    pub run: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListArtifactsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub artifacts: ::prost::alloc::vec::Vec<Artifact>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Artifact {
+    ///sha256 of the content, the tree of a directory
+    #[prost(string, tag = "1")]
+    pub digest: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub size: u64,
+    #[prost(bool, tag = "3")]
+    pub directory: bool,
+    #[prost(message, optional, tag = "4")]
+    pub created_at: ::core::option::Option<::pbjson_types::Timestamp>,
+    ///output the artifact was produced as, when listing a run
+    #[prost(string, tag = "5")]
+    pub output: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetArtifactRequest {
+    #[prost(string, tag = "1")]
+    pub digest: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ArtifactChunk {
+    ///relative to the artifact, blank for a file artifact
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    ///path is a directory, sent before its content
+    #[prost(bool, tag = "2")]
+    pub directory: bool,
+    ///next part of the file content
+    #[prost(bytes = "vec", tag = "3")]
+    pub content: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PromoteRequest {
+    ///resource id of the deployer
+    #[prost(string, tag = "1")]
+    pub deployer: ::prost::alloc::string::String,
+    ///resource id of the bundle
+    #[prost(string, tag = "2")]
+    pub bundle: ::prost::alloc::string::String,
+    ///blank is the stage after the last one the bundle is deployed to
+    #[prost(string, tag = "3")]
+    pub stage: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApproveDeploymentRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RollbackRequest {
+    ///resource id of the deployer
+    #[prost(string, tag = "1")]
+    pub deployer: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub stage: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDeploymentsRequest {
+    ///resource id of the deployer, blank is all
+    #[prost(string, tag = "1")]
+    pub deployer: ::prost::alloc::string::String,
+    ///only the bundle deployed in each stage
+    #[prost(bool, tag = "2")]
+    pub current: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDeploymentsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub deployments: ::prost::alloc::vec::Vec<Deployment>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReconcileRequest {
+    ///resource id of the applier
+    #[prost(string, tag = "1")]
+    pub applier: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterWorkerRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    ///matched against the runtimeCapabilities of functions
+    #[prost(string, repeated, tag = "2")]
+    pub capabilities: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterWorkerResponse {
+    #[prost(string, tag = "1")]
+    pub worker_id: ::prost::alloc::string::String,
+    ///interval between heartbeats, workers missing 3 are dead
+    #[prost(uint32, tag = "2")]
+    pub heartbeat_seconds: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatRequest {
+    #[prost(string, tag = "1")]
+    pub worker_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatResponse {
+    ///ids of the runs the worker must cancel
+    #[prost(string, repeated, tag = "1")]
+    pub cancel: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaseRunRequest {
+    #[prost(string, tag = "1")]
+    pub worker_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaseRunResponse {
+    ///unset when no queued run matches the worker
+    #[prost(message, optional, tag = "1")]
+    pub lease: ::core::option::Option<RunLease>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunLease {
+    ///id of the run, as in Execution
+    #[prost(string, tag = "1")]
+    pub run: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub attempt: u32,
+    ///resource id of the function
+    #[prost(string, tag = "3")]
+    pub function: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub definition: ::core::option::Option<Function>,
+    #[prost(message, optional, tag = "5")]
+    pub inputs: ::core::option::Option<::pbjson_types::Struct>,
+    ///resource id of the container runtime, blank runs a local process
+    #[prost(string, tag = "6")]
+    pub runtime: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub container: ::core::option::Option<Container>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompleteRunRequest {
+    #[prost(string, tag = "1")]
+    pub worker_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub run: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub attempt: u32,
+    ///outcome of the attempt
+    #[prost(message, optional, tag = "4")]
+    pub execution: ::core::option::Option<Execution>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompleteRunResponse {}
+/// DeletePolicy tells what happens to the resources referencing a deleted resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DeletePolicy {
+    ///the delete fails while resources not deleted with it reference it
+    Reject = 0,
+    ///the resources referencing it are deleted too, recursively
+    Cascade = 1,
+    ///the resources referencing it are kept, their references left dangling
+    Orphan = 2,
+}
 #[doc = r" Generated client implementations."]
 pub mod bda_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -368,10 +1080,74 @@ pub mod bda_client {
             let path = http::uri::PathAndQuery::from_static("/bda.BDA/PutResource");
             self.inner.unary(request.into_request(), path, codec).await
         }
-    }
-}
-#[doc = r" Generated server implementations."]
-pub mod bda_server {
+        #[doc = r" GetResourceHistory returns the changes made to a resource, oldest first"]
+        pub async fn get_resource_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceHistoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.BDA/GetResourceHistory");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        #[doc = r" ListInstalled returns the resources installed by bundles, with the bundle version that"]
+        #[doc = r" installed them"]
+        pub async fn list_installed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListInstalledRequest>,
+        ) -> Result<tonic::Response<super::ListInstalledResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.BDA/ListInstalled");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        #[doc = r" WatchResources streams the writes to resources matching the filter, as they happen"]
+        pub async fn watch_resources(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchResourcesRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::ResourceEvent>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.BDA/WatchResources");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        #[doc = r" GetSecret returns a secret with its values encrypted, for engines holding the secret key"]
+        pub async fn get_secret(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSecretRequest>,
+        ) -> Result<tonic::Response<super::Secret>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.BDA/GetSecret");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+#[doc = r" Generated server implementations."]
+pub mod bda_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
     #[doc = "Generated trait containing gRPC methods that should be implemented for use with BdaServer."]
@@ -417,6 +1193,31 @@ pub mod bda_server {
             &self,
             request: tonic::Request<super::PutResourceRequest>,
         ) -> Result<tonic::Response<super::PutResourceResponse>, tonic::Status>;
+        #[doc = r" GetResourceHistory returns the changes made to a resource, oldest first"]
+        async fn get_resource_history(
+            &self,
+            request: tonic::Request<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceHistoryResponse>, tonic::Status>;
+        #[doc = r" ListInstalled returns the resources installed by bundles, with the bundle version that"]
+        #[doc = r" installed them"]
+        async fn list_installed(
+            &self,
+            request: tonic::Request<super::ListInstalledRequest>,
+        ) -> Result<tonic::Response<super::ListInstalledResponse>, tonic::Status>;
+        #[doc = "Server streaming response type for the WatchResources method."]
+        type WatchResourcesStream: futures_core::Stream<Item = Result<super::ResourceEvent, tonic::Status>>
+            + Send
+            + 'static;
+        #[doc = r" WatchResources streams the writes to resources matching the filter, as they happen"]
+        async fn watch_resources(
+            &self,
+            request: tonic::Request<super::WatchResourcesRequest>,
+        ) -> Result<tonic::Response<Self::WatchResourcesStream>, tonic::Status>;
+        #[doc = r" GetSecret returns a secret with its values encrypted, for engines holding the secret key"]
+        async fn get_secret(
+            &self,
+            request: tonic::Request<super::GetSecretRequest>,
+        ) -> Result<tonic::Response<super::Secret>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct BdaServer<T: Bda> {
@@ -740,6 +1541,134 @@ pub mod bda_server {
                     };
                     Box::pin(fut)
                 }
+                "/bda.BDA/GetResourceHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetResourceHistorySvc<T: Bda>(pub Arc<T>);
+                    impl<T: Bda> tonic::server::UnaryService<super::GetResourceRequest> for GetResourceHistorySvc<T> {
+                        type Response = super::GetResourceHistoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetResourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_resource_history(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetResourceHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.BDA/ListInstalled" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListInstalledSvc<T: Bda>(pub Arc<T>);
+                    impl<T: Bda> tonic::server::UnaryService<super::ListInstalledRequest> for ListInstalledSvc<T> {
+                        type Response = super::ListInstalledResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListInstalledRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_installed(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListInstalledSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.BDA/WatchResources" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchResourcesSvc<T: Bda>(pub Arc<T>);
+                    impl<T: Bda> tonic::server::ServerStreamingService<super::WatchResourcesRequest>
+                        for WatchResourcesSvc<T>
+                    {
+                        type Response = super::ResourceEvent;
+                        type ResponseStream = T::WatchResourcesStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WatchResourcesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch_resources(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchResourcesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.BDA/GetSecret" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSecretSvc<T: Bda>(pub Arc<T>);
+                    impl<T: Bda> tonic::server::UnaryService<super::GetSecretRequest> for GetSecretSvc<T> {
+                        type Response = super::Secret;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSecretRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_secret(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSecretSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
@@ -775,3 +1704,1104 @@ pub mod bda_server {
         const NAME: &'static str = "bda.BDA";
     }
 }
+#[doc = r" Generated client implementations."]
+pub mod engine_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct EngineClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl EngineClient<tonic::transport::Channel> {
+        #[doc = r" Attempt to create a new client by connecting to a given endpoint."]
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> EngineClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::ResponseBody: Body + Send + 'static,
+        T::Error: Into<StdError>,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> EngineClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            EngineClient::new(InterceptedService::new(inner, interceptor))
+        }
+        #[doc = r" Compress requests with `gzip`."]
+        #[doc = r""]
+        #[doc = r" This requires the server to support it otherwise it might respond with an"]
+        #[doc = r" error."]
+        pub fn send_gzip(mut self) -> Self {
+            self.inner = self.inner.send_gzip();
+            self
+        }
+        #[doc = r" Enable decompressing responses with `gzip`."]
+        pub fn accept_gzip(mut self) -> Self {
+            self.inner = self.inner.accept_gzip();
+            self
+        }
+        pub async fn submit_run(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubmitRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/SubmitRun");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_run(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/GetRun");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_runs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListRunsRequest>,
+        ) -> Result<tonic::Response<super::ListRunsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/ListRuns");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn cancel_run(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/CancelRun");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn stream_run_logs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamRunLogsRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::RunLog>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/StreamRunLogs");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn list_artifacts(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListArtifactsRequest>,
+        ) -> Result<tonic::Response<super::ListArtifactsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/ListArtifacts");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_artifact(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetArtifactRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::ArtifactChunk>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/GetArtifact");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn promote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PromoteRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/Promote");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn approve_deployment(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ApproveDeploymentRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/ApproveDeployment");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn rollback(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RollbackRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/Rollback");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_deployments(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListDeploymentsRequest>,
+        ) -> Result<tonic::Response<super::ListDeploymentsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/ListDeployments");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn reconcile(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReconcileRequest>,
+        ) -> Result<tonic::Response<super::ListDeploymentsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Engine/Reconcile");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+#[doc = r" Generated server implementations."]
+pub mod engine_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with EngineServer."]
+    #[async_trait]
+    pub trait Engine: Send + Sync + 'static {
+        async fn submit_run(
+            &self,
+            request: tonic::Request<super::SubmitRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status>;
+        async fn get_run(
+            &self,
+            request: tonic::Request<super::GetRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status>;
+        async fn list_runs(
+            &self,
+            request: tonic::Request<super::ListRunsRequest>,
+        ) -> Result<tonic::Response<super::ListRunsResponse>, tonic::Status>;
+        async fn cancel_run(
+            &self,
+            request: tonic::Request<super::CancelRunRequest>,
+        ) -> Result<tonic::Response<super::Execution>, tonic::Status>;
+        #[doc = "Server streaming response type for the StreamRunLogs method."]
+        type StreamRunLogsStream: futures_core::Stream<Item = Result<super::RunLog, tonic::Status>>
+            + Send
+            + 'static;
+        async fn stream_run_logs(
+            &self,
+            request: tonic::Request<super::StreamRunLogsRequest>,
+        ) -> Result<tonic::Response<Self::StreamRunLogsStream>, tonic::Status>;
+        async fn list_artifacts(
+            &self,
+            request: tonic::Request<super::ListArtifactsRequest>,
+        ) -> Result<tonic::Response<super::ListArtifactsResponse>, tonic::Status>;
+        #[doc = "Server streaming response type for the GetArtifact method."]
+        type GetArtifactStream: futures_core::Stream<Item = Result<super::ArtifactChunk, tonic::Status>>
+            + Send
+            + 'static;
+        async fn get_artifact(
+            &self,
+            request: tonic::Request<super::GetArtifactRequest>,
+        ) -> Result<tonic::Response<Self::GetArtifactStream>, tonic::Status>;
+        async fn promote(
+            &self,
+            request: tonic::Request<super::PromoteRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status>;
+        async fn approve_deployment(
+            &self,
+            request: tonic::Request<super::ApproveDeploymentRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status>;
+        async fn rollback(
+            &self,
+            request: tonic::Request<super::RollbackRequest>,
+        ) -> Result<tonic::Response<super::Deployment>, tonic::Status>;
+        async fn list_deployments(
+            &self,
+            request: tonic::Request<super::ListDeploymentsRequest>,
+        ) -> Result<tonic::Response<super::ListDeploymentsResponse>, tonic::Status>;
+        async fn reconcile(
+            &self,
+            request: tonic::Request<super::ReconcileRequest>,
+        ) -> Result<tonic::Response<super::ListDeploymentsResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct EngineServer<T: Engine> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Engine> EngineServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for EngineServer<T>
+    where
+        T: Engine,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/bda.Engine/SubmitRun" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubmitRunSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::SubmitRunRequest> for SubmitRunSvc<T> {
+                        type Response = super::Execution;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubmitRunRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).submit_run(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SubmitRunSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/GetRun" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetRunSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::GetRunRequest> for GetRunSvc<T> {
+                        type Response = super::Execution;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRunRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_run(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetRunSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/ListRuns" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListRunsSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::ListRunsRequest> for ListRunsSvc<T> {
+                        type Response = super::ListRunsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListRunsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_runs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListRunsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/CancelRun" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelRunSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::CancelRunRequest> for CancelRunSvc<T> {
+                        type Response = super::Execution;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelRunRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel_run(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CancelRunSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/StreamRunLogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamRunLogsSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine>
+                        tonic::server::ServerStreamingService<super::StreamRunLogsRequest>
+                        for StreamRunLogsSvc<T>
+                    {
+                        type Response = super::RunLog;
+                        type ResponseStream = T::StreamRunLogsStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamRunLogsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).stream_run_logs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamRunLogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/ListArtifacts" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListArtifactsSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::ListArtifactsRequest> for ListArtifactsSvc<T> {
+                        type Response = super::ListArtifactsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListArtifactsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_artifacts(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListArtifactsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/GetArtifact" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetArtifactSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::ServerStreamingService<super::GetArtifactRequest>
+                        for GetArtifactSvc<T>
+                    {
+                        type Response = super::ArtifactChunk;
+                        type ResponseStream = T::GetArtifactStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetArtifactRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_artifact(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetArtifactSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/Promote" => {
+                    #[allow(non_camel_case_types)]
+                    struct PromoteSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::PromoteRequest> for PromoteSvc<T> {
+                        type Response = super::Deployment;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PromoteRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).promote(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PromoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/ApproveDeployment" => {
+                    #[allow(non_camel_case_types)]
+                    struct ApproveDeploymentSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::ApproveDeploymentRequest>
+                        for ApproveDeploymentSvc<T>
+                    {
+                        type Response = super::Deployment;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ApproveDeploymentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).approve_deployment(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ApproveDeploymentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/Rollback" => {
+                    #[allow(non_camel_case_types)]
+                    struct RollbackSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::RollbackRequest> for RollbackSvc<T> {
+                        type Response = super::Deployment;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RollbackRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).rollback(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RollbackSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/ListDeployments" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListDeploymentsSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::ListDeploymentsRequest>
+                        for ListDeploymentsSvc<T>
+                    {
+                        type Response = super::ListDeploymentsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListDeploymentsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_deployments(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListDeploymentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Engine/Reconcile" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReconcileSvc<T: Engine>(pub Arc<T>);
+                    impl<T: Engine> tonic::server::UnaryService<super::ReconcileRequest> for ReconcileSvc<T> {
+                        type Response = super::ListDeploymentsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReconcileRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reconcile(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReconcileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: Engine> Clone for EngineServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Engine> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Engine> tonic::transport::NamedService for EngineServer<T> {
+        const NAME: &'static str = "bda.Engine";
+    }
+}
+#[doc = r" Generated client implementations."]
+pub mod coordinator_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct CoordinatorClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl CoordinatorClient<tonic::transport::Channel> {
+        #[doc = r" Attempt to create a new client by connecting to a given endpoint."]
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> CoordinatorClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::ResponseBody: Body + Send + 'static,
+        T::Error: Into<StdError>,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> CoordinatorClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            CoordinatorClient::new(InterceptedService::new(inner, interceptor))
+        }
+        #[doc = r" Compress requests with `gzip`."]
+        #[doc = r""]
+        #[doc = r" This requires the server to support it otherwise it might respond with an"]
+        #[doc = r" error."]
+        pub fn send_gzip(mut self) -> Self {
+            self.inner = self.inner.send_gzip();
+            self
+        }
+        #[doc = r" Enable decompressing responses with `gzip`."]
+        pub fn accept_gzip(mut self) -> Self {
+            self.inner = self.inner.accept_gzip();
+            self
+        }
+        pub async fn register_worker(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RegisterWorkerRequest>,
+        ) -> Result<tonic::Response<super::RegisterWorkerResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Coordinator/RegisterWorker");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn heartbeat(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HeartbeatRequest>,
+        ) -> Result<tonic::Response<super::HeartbeatResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Coordinator/Heartbeat");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn lease_run(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LeaseRunRequest>,
+        ) -> Result<tonic::Response<super::LeaseRunResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Coordinator/LeaseRun");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn complete_run(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CompleteRunRequest>,
+        ) -> Result<tonic::Response<super::CompleteRunResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/bda.Coordinator/CompleteRun");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+#[doc = r" Generated server implementations."]
+pub mod coordinator_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with CoordinatorServer."]
+    #[async_trait]
+    pub trait Coordinator: Send + Sync + 'static {
+        async fn register_worker(
+            &self,
+            request: tonic::Request<super::RegisterWorkerRequest>,
+        ) -> Result<tonic::Response<super::RegisterWorkerResponse>, tonic::Status>;
+        async fn heartbeat(
+            &self,
+            request: tonic::Request<super::HeartbeatRequest>,
+        ) -> Result<tonic::Response<super::HeartbeatResponse>, tonic::Status>;
+        async fn lease_run(
+            &self,
+            request: tonic::Request<super::LeaseRunRequest>,
+        ) -> Result<tonic::Response<super::LeaseRunResponse>, tonic::Status>;
+        async fn complete_run(
+            &self,
+            request: tonic::Request<super::CompleteRunRequest>,
+        ) -> Result<tonic::Response<super::CompleteRunResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct CoordinatorServer<T: Coordinator> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Coordinator> CoordinatorServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CoordinatorServer<T>
+    where
+        T: Coordinator,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/bda.Coordinator/RegisterWorker" => {
+                    #[allow(non_camel_case_types)]
+                    struct RegisterWorkerSvc<T: Coordinator>(pub Arc<T>);
+                    impl<T: Coordinator> tonic::server::UnaryService<super::RegisterWorkerRequest>
+                        for RegisterWorkerSvc<T>
+                    {
+                        type Response = super::RegisterWorkerResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RegisterWorkerRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).register_worker(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RegisterWorkerSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Coordinator/Heartbeat" => {
+                    #[allow(non_camel_case_types)]
+                    struct HeartbeatSvc<T: Coordinator>(pub Arc<T>);
+                    impl<T: Coordinator> tonic::server::UnaryService<super::HeartbeatRequest> for HeartbeatSvc<T> {
+                        type Response = super::HeartbeatResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HeartbeatRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).heartbeat(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = HeartbeatSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Coordinator/LeaseRun" => {
+                    #[allow(non_camel_case_types)]
+                    struct LeaseRunSvc<T: Coordinator>(pub Arc<T>);
+                    impl<T: Coordinator> tonic::server::UnaryService<super::LeaseRunRequest> for LeaseRunSvc<T> {
+                        type Response = super::LeaseRunResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LeaseRunRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).lease_run(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = LeaseRunSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bda.Coordinator/CompleteRun" => {
+                    #[allow(non_camel_case_types)]
+                    struct CompleteRunSvc<T: Coordinator>(pub Arc<T>);
+                    impl<T: Coordinator> tonic::server::UnaryService<super::CompleteRunRequest> for CompleteRunSvc<T> {
+                        type Response = super::CompleteRunResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CompleteRunRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).complete_run(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CompleteRunSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: Coordinator> Clone for CoordinatorServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Coordinator> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Coordinator> tonic::transport::NamedService for CoordinatorServer<T> {
+        const NAME: &'static str = "bda.Coordinator";
+    }
+}
@@ -1,4 +1,4 @@
-impl serde::Serialize for Container {
+impl serde::Serialize for Applier {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -6,29 +6,37 @@ impl serde::Serialize for Container {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.dockerfile.is_empty() {
+        if !self.deployer.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.Container", len)?;
-        if !self.dockerfile.is_empty() {
-            struct_ser.serialize_field("dockerfile", &self.dockerfile)?;
+        if !self.stages.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Applier", len)?;
+        if !self.deployer.is_empty() {
+            struct_ser.serialize_field("deployer", &self.deployer)?;
+        }
+        if !self.stages.is_empty() {
+            struct_ser.serialize_field("stages", &self.stages)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for Container {
+impl<'de> serde::Deserialize<'de> for Applier {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "dockerfile",
+            "deployer",
+            "stages",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Dockerfile,
+            Deployer,
+            Stages,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -49,7 +57,8 @@ impl<'de> serde::Deserialize<'de> for Container {
                         E: serde::de::Error,
                     {
                         match value {
-                            "dockerfile" => Ok(GeneratedField::Dockerfile),
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "stages" => Ok(GeneratedField::Stages),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -59,36 +68,46 @@ impl<'de> serde::Deserialize<'de> for Container {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Container;
+            type Value = Applier;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.Container")
+                formatter.write_str("struct bda.Applier")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<Container, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Applier, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut dockerfile = None;
+                let mut deployer = None;
+                let mut stages = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Dockerfile => {
-                            if dockerfile.is_some() {
-                                return Err(serde::de::Error::duplicate_field("dockerfile"));
+                        GeneratedField::Deployer => {
+                            if deployer.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
                             }
-                            dockerfile = Some(map.next_value()?);
+                            deployer = Some(map.next_value()?);
+                        }
+                        GeneratedField::Stages => {
+                            if stages.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stages"));
+                            }
+                            stages = Some(
+                                map.next_value::<std::collections::HashMap<_, _>>()?
+                            );
                         }
                     }
                 }
-                Ok(Container {
-                    dockerfile: dockerfile.unwrap_or_default(),
+                Ok(Applier {
+                    deployer: deployer.unwrap_or_default(),
+                    stages: stages.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.Container", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Applier", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for DelResourceRequest {
+impl serde::Serialize for ApproveDeploymentRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -96,53 +115,29 @@ impl serde::Serialize for DelResourceRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.version.is_empty() {
-            len += 1;
-        }
-        if !self.namespace.is_empty() {
-            len += 1;
-        }
-        if !self.kind.is_empty() {
-            len += 1;
-        }
-        if !self.name.is_empty() {
+        if !self.id.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.DelResourceRequest", len)?;
-        if !self.version.is_empty() {
-            struct_ser.serialize_field("version", &self.version)?;
-        }
-        if !self.namespace.is_empty() {
-            struct_ser.serialize_field("namespace", &self.namespace)?;
-        }
-        if !self.kind.is_empty() {
-            struct_ser.serialize_field("kind", &self.kind)?;
-        }
-        if !self.name.is_empty() {
-            struct_ser.serialize_field("name", &self.name)?;
+        let mut struct_ser = serializer.serialize_struct("bda.ApproveDeploymentRequest", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for DelResourceRequest {
+impl<'de> serde::Deserialize<'de> for ApproveDeploymentRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "version",
-            "namespace",
-            "kind",
-            "name",
+            "id",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Version,
-            Namespace,
-            Kind,
-            Name,
+            Id,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -163,10 +158,7 @@ impl<'de> serde::Deserialize<'de> for DelResourceRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "version" => Ok(GeneratedField::Version),
-                            "namespace" => Ok(GeneratedField::Namespace),
-                            "kind" => Ok(GeneratedField::Kind),
-                            "name" => Ok(GeneratedField::Name),
+                            "id" => Ok(GeneratedField::Id),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -176,60 +168,36 @@ impl<'de> serde::Deserialize<'de> for DelResourceRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = DelResourceRequest;
+            type Value = ApproveDeploymentRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.DelResourceRequest")
+                formatter.write_str("struct bda.ApproveDeploymentRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourceRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ApproveDeploymentRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut version = None;
-                let mut namespace = None;
-                let mut kind = None;
-                let mut name = None;
+                let mut id = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Version => {
-                            if version.is_some() {
-                                return Err(serde::de::Error::duplicate_field("version"));
-                            }
-                            version = Some(map.next_value()?);
-                        }
-                        GeneratedField::Namespace => {
-                            if namespace.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespace"));
-                            }
-                            namespace = Some(map.next_value()?);
-                        }
-                        GeneratedField::Kind => {
-                            if kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("kind"));
-                            }
-                            kind = Some(map.next_value()?);
-                        }
-                        GeneratedField::Name => {
-                            if name.is_some() {
-                                return Err(serde::de::Error::duplicate_field("name"));
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
                             }
-                            name = Some(map.next_value()?);
+                            id = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(DelResourceRequest {
-                    version: version.unwrap_or_default(),
-                    namespace: namespace.unwrap_or_default(),
-                    kind: kind.unwrap_or_default(),
-                    name: name.unwrap_or_default(),
+                Ok(ApproveDeploymentRequest {
+                    id: id.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.DelResourceRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.ApproveDeploymentRequest", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for DelResourceResponse {
+impl serde::Serialize for Artifact {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -237,29 +205,61 @@ impl serde::Serialize for DelResourceResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.updates != 0 {
+        if !self.digest.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.DelResourceResponse", len)?;
-        if self.updates != 0 {
-            struct_ser.serialize_field("updates", &self.updates)?;
+        if self.size != 0 {
+            len += 1;
+        }
+        if self.directory {
+            len += 1;
+        }
+        if self.created_at.is_some() {
+            len += 1;
+        }
+        if !self.output.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Artifact", len)?;
+        if !self.digest.is_empty() {
+            struct_ser.serialize_field("digest", &self.digest)?;
+        }
+        if self.size != 0 {
+            struct_ser.serialize_field("size", ToString::to_string(&self.size).as_str())?;
+        }
+        if self.directory {
+            struct_ser.serialize_field("directory", &self.directory)?;
+        }
+        if let Some(v) = self.created_at.as_ref() {
+            struct_ser.serialize_field("createdAt", v)?;
+        }
+        if !self.output.is_empty() {
+            struct_ser.serialize_field("output", &self.output)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for DelResourceResponse {
+impl<'de> serde::Deserialize<'de> for Artifact {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "updates",
+            "digest",
+            "size",
+            "directory",
+            "createdAt",
+            "output",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Updates,
+            Digest,
+            Size,
+            Directory,
+            CreatedAt,
+            Output,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -280,7 +280,11 @@ impl<'de> serde::Deserialize<'de> for DelResourceResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "updates" => Ok(GeneratedField::Updates),
+                            "digest" => Ok(GeneratedField::Digest),
+                            "size" => Ok(GeneratedField::Size),
+                            "directory" => Ok(GeneratedField::Directory),
+                            "createdAt" => Ok(GeneratedField::CreatedAt),
+                            "output" => Ok(GeneratedField::Output),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -290,38 +294,70 @@ impl<'de> serde::Deserialize<'de> for DelResourceResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = DelResourceResponse;
+            type Value = Artifact;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.DelResourceResponse")
+                formatter.write_str("struct bda.Artifact")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourceResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Artifact, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut updates = None;
+                let mut digest = None;
+                let mut size = None;
+                let mut directory = None;
+                let mut created_at = None;
+                let mut output = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Updates => {
-                            if updates.is_some() {
-                                return Err(serde::de::Error::duplicate_field("updates"));
+                        GeneratedField::Digest => {
+                            if digest.is_some() {
+                                return Err(serde::de::Error::duplicate_field("digest"));
                             }
-                            updates = Some(
+                            digest = Some(map.next_value()?);
+                        }
+                        GeneratedField::Size => {
+                            if size.is_some() {
+                                return Err(serde::de::Error::duplicate_field("size"));
+                            }
+                            size = Some(
                                 map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
                             );
                         }
+                        GeneratedField::Directory => {
+                            if directory.is_some() {
+                                return Err(serde::de::Error::duplicate_field("directory"));
+                            }
+                            directory = Some(map.next_value()?);
+                        }
+                        GeneratedField::CreatedAt => {
+                            if created_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("createdAt"));
+                            }
+                            created_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::Output => {
+                            if output.is_some() {
+                                return Err(serde::de::Error::duplicate_field("output"));
+                            }
+                            output = Some(map.next_value()?);
+                        }
                     }
                 }
-                Ok(DelResourceResponse {
-                    updates: updates.unwrap_or_default(),
+                Ok(Artifact {
+                    digest: digest.unwrap_or_default(),
+                    size: size.unwrap_or_default(),
+                    directory: directory.unwrap_or_default(),
+                    created_at,
+                    output: output.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.DelResourceResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Artifact", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for DelResourcesRequest {
+impl serde::Serialize for ArtifactChunk {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -329,61 +365,45 @@ impl serde::Serialize for DelResourcesRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.version.is_empty() {
-            len += 1;
-        }
-        if !self.namespaces.is_empty() {
-            len += 1;
-        }
-        if !self.kinds.is_empty() {
+        if !self.path.is_empty() {
             len += 1;
         }
-        if !self.names.is_empty() {
+        if self.directory {
             len += 1;
         }
-        if !self.bql.is_empty() {
+        if !self.content.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.DelResourcesRequest", len)?;
-        if !self.version.is_empty() {
-            struct_ser.serialize_field("version", &self.version)?;
-        }
-        if !self.namespaces.is_empty() {
-            struct_ser.serialize_field("namespaces", &self.namespaces)?;
-        }
-        if !self.kinds.is_empty() {
-            struct_ser.serialize_field("kinds", &self.kinds)?;
+        let mut struct_ser = serializer.serialize_struct("bda.ArtifactChunk", len)?;
+        if !self.path.is_empty() {
+            struct_ser.serialize_field("path", &self.path)?;
         }
-        if !self.names.is_empty() {
-            struct_ser.serialize_field("names", &self.names)?;
+        if self.directory {
+            struct_ser.serialize_field("directory", &self.directory)?;
         }
-        if !self.bql.is_empty() {
-            struct_ser.serialize_field("bql", &self.bql)?;
+        if !self.content.is_empty() {
+            struct_ser.serialize_field("content", pbjson::private::base64::encode(&self.content).as_str())?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for DelResourcesRequest {
+impl<'de> serde::Deserialize<'de> for ArtifactChunk {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "version",
-            "namespaces",
-            "kinds",
-            "names",
-            "bql",
+            "path",
+            "directory",
+            "content",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Version,
-            Namespaces,
-            Kinds,
-            Names,
-            Bql,
+            Path,
+            Directory,
+            Content,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -404,11 +424,9 @@ impl<'de> serde::Deserialize<'de> for DelResourcesRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "version" => Ok(GeneratedField::Version),
-                            "namespaces" => Ok(GeneratedField::Namespaces),
-                            "kinds" => Ok(GeneratedField::Kinds),
-                            "names" => Ok(GeneratedField::Names),
-                            "bql" => Ok(GeneratedField::Bql),
+                            "path" => Ok(GeneratedField::Path),
+                            "directory" => Ok(GeneratedField::Directory),
+                            "content" => Ok(GeneratedField::Content),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -418,68 +436,54 @@ impl<'de> serde::Deserialize<'de> for DelResourcesRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = DelResourcesRequest;
+            type Value = ArtifactChunk;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.DelResourcesRequest")
+                formatter.write_str("struct bda.ArtifactChunk")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourcesRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ArtifactChunk, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut version = None;
-                let mut namespaces = None;
-                let mut kinds = None;
-                let mut names = None;
-                let mut bql = None;
+                let mut path = None;
+                let mut directory = None;
+                let mut content = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Version => {
-                            if version.is_some() {
-                                return Err(serde::de::Error::duplicate_field("version"));
-                            }
-                            version = Some(map.next_value()?);
-                        }
-                        GeneratedField::Namespaces => {
-                            if namespaces.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespaces"));
-                            }
-                            namespaces = Some(map.next_value()?);
-                        }
-                        GeneratedField::Kinds => {
-                            if kinds.is_some() {
-                                return Err(serde::de::Error::duplicate_field("kinds"));
+                        GeneratedField::Path => {
+                            if path.is_some() {
+                                return Err(serde::de::Error::duplicate_field("path"));
                             }
-                            kinds = Some(map.next_value()?);
+                            path = Some(map.next_value()?);
                         }
-                        GeneratedField::Names => {
-                            if names.is_some() {
-                                return Err(serde::de::Error::duplicate_field("names"));
+                        GeneratedField::Directory => {
+                            if directory.is_some() {
+                                return Err(serde::de::Error::duplicate_field("directory"));
                             }
-                            names = Some(map.next_value()?);
+                            directory = Some(map.next_value()?);
                         }
-                        GeneratedField::Bql => {
-                            if bql.is_some() {
-                                return Err(serde::de::Error::duplicate_field("bql"));
+                        GeneratedField::Content => {
+                            if content.is_some() {
+                                return Err(serde::de::Error::duplicate_field("content"));
                             }
-                            bql = Some(map.next_value()?);
+                            content = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0
+                            );
                         }
                     }
                 }
-                Ok(DelResourcesRequest {
-                    version: version.unwrap_or_default(),
-                    namespaces: namespaces.unwrap_or_default(),
-                    kinds: kinds.unwrap_or_default(),
-                    names: names.unwrap_or_default(),
-                    bql: bql.unwrap_or_default(),
+                Ok(ArtifactChunk {
+                    path: path.unwrap_or_default(),
+                    directory: directory.unwrap_or_default(),
+                    content: content.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.DelResourcesRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.ArtifactChunk", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for Function {
+impl serde::Serialize for Bundle {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -487,53 +491,37 @@ impl serde::Serialize for Function {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.inputs.is_empty() {
-            len += 1;
-        }
-        if !self.outputs.is_empty() {
-            len += 1;
-        }
-        if !self.base_command.is_empty() {
+        if !self.semver.is_empty() {
             len += 1;
         }
-        if !self.runtime_capabilities.is_empty() {
+        if !self.resources.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.Function", len)?;
-        if !self.inputs.is_empty() {
-            struct_ser.serialize_field("inputs", &self.inputs)?;
-        }
-        if !self.outputs.is_empty() {
-            struct_ser.serialize_field("outputs", &self.outputs)?;
-        }
-        if !self.base_command.is_empty() {
-            struct_ser.serialize_field("baseCommand", &self.base_command)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Bundle", len)?;
+        if !self.semver.is_empty() {
+            struct_ser.serialize_field("semver", &self.semver)?;
         }
-        if !self.runtime_capabilities.is_empty() {
-            struct_ser.serialize_field("runtimeCapabilities", &self.runtime_capabilities)?;
+        if !self.resources.is_empty() {
+            struct_ser.serialize_field("resources", &self.resources)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for Function {
+impl<'de> serde::Deserialize<'de> for Bundle {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "inputs",
-            "outputs",
-            "baseCommand",
-            "runtimeCapabilities",
+            "semver",
+            "resources",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Inputs,
-            Outputs,
-            BaseCommand,
-            RuntimeCapabilities,
+            Semver,
+            Resources,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -554,10 +542,8 @@ impl<'de> serde::Deserialize<'de> for Function {
                         E: serde::de::Error,
                     {
                         match value {
-                            "inputs" => Ok(GeneratedField::Inputs),
-                            "outputs" => Ok(GeneratedField::Outputs),
-                            "baseCommand" => Ok(GeneratedField::BaseCommand),
-                            "runtimeCapabilities" => Ok(GeneratedField::RuntimeCapabilities),
+                            "semver" => Ok(GeneratedField::Semver),
+                            "resources" => Ok(GeneratedField::Resources),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -567,82 +553,74 @@ impl<'de> serde::Deserialize<'de> for Function {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Function;
+            type Value = Bundle;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.Function")
+                formatter.write_str("struct bda.Bundle")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<Function, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Bundle, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut inputs = None;
-                let mut outputs = None;
-                let mut base_command = None;
-                let mut runtime_capabilities = None;
+                let mut semver = None;
+                let mut resources = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Inputs => {
-                            if inputs.is_some() {
-                                return Err(serde::de::Error::duplicate_field("inputs"));
-                            }
-                            inputs = Some(map.next_value()?);
-                        }
-                        GeneratedField::Outputs => {
-                            if outputs.is_some() {
-                                return Err(serde::de::Error::duplicate_field("outputs"));
+                        GeneratedField::Semver => {
+                            if semver.is_some() {
+                                return Err(serde::de::Error::duplicate_field("semver"));
                             }
-                            outputs = Some(map.next_value()?);
-                        }
-                        GeneratedField::BaseCommand => {
-                            if base_command.is_some() {
-                                return Err(serde::de::Error::duplicate_field("baseCommand"));
-                            }
-                            base_command = Some(map.next_value()?);
+                            semver = Some(map.next_value()?);
                         }
-                        GeneratedField::RuntimeCapabilities => {
-                            if runtime_capabilities.is_some() {
-                                return Err(serde::de::Error::duplicate_field("runtimeCapabilities"));
+                        GeneratedField::Resources => {
+                            if resources.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resources"));
                             }
-                            runtime_capabilities = Some(map.next_value()?);
+                            resources = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(Function {
-                    inputs: inputs.unwrap_or_default(),
-                    outputs: outputs.unwrap_or_default(),
-                    base_command: base_command.unwrap_or_default(),
-                    runtime_capabilities: runtime_capabilities.unwrap_or_default(),
+                Ok(Bundle {
+                    semver: semver.unwrap_or_default(),
+                    resources: resources.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.Function", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Bundle", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetKindsRequest {
+impl serde::Serialize for CancelRunRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let len = 0;
-        let struct_ser = serializer.serialize_struct("bda.GetKindsRequest", len)?;
+        let mut len = 0;
+        if !self.id.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.CancelRunRequest", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
+        }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetKindsRequest {
+impl<'de> serde::Deserialize<'de> for CancelRunRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
+            "id",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
+            Id,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -662,7 +640,10 @@ impl<'de> serde::Deserialize<'de> for GetKindsRequest {
                     where
                         E: serde::de::Error,
                     {
-                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                        match value {
+                            "id" => Ok(GeneratedField::Id),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
                     }
                 }
                 deserializer.deserialize_identifier(GeneratedVisitor)
@@ -670,25 +651,36 @@ impl<'de> serde::Deserialize<'de> for GetKindsRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetKindsRequest;
+            type Value = CancelRunRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetKindsRequest")
+                formatter.write_str("struct bda.CancelRunRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetKindsRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CancelRunRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                while map.next_key::<GeneratedField>()?.is_some() {}
-                Ok(GetKindsRequest {
+                let mut id = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(CancelRunRequest {
+                    id: id.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetKindsRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.CancelRunRequest", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetKindsResponse {
+impl serde::Serialize for CompleteRunRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -696,29 +688,53 @@ impl serde::Serialize for GetKindsResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.kinds.is_empty() {
+        if !self.worker_id.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetKindsResponse", len)?;
-        if !self.kinds.is_empty() {
-            struct_ser.serialize_field("kinds", &self.kinds)?;
+        if !self.run.is_empty() {
+            len += 1;
+        }
+        if self.attempt != 0 {
+            len += 1;
+        }
+        if self.execution.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.CompleteRunRequest", len)?;
+        if !self.worker_id.is_empty() {
+            struct_ser.serialize_field("workerId", &self.worker_id)?;
+        }
+        if !self.run.is_empty() {
+            struct_ser.serialize_field("run", &self.run)?;
+        }
+        if self.attempt != 0 {
+            struct_ser.serialize_field("attempt", &self.attempt)?;
+        }
+        if let Some(v) = self.execution.as_ref() {
+            struct_ser.serialize_field("execution", v)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetKindsResponse {
+impl<'de> serde::Deserialize<'de> for CompleteRunRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "kinds",
+            "workerId",
+            "run",
+            "attempt",
+            "execution",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Kinds,
+            WorkerId,
+            Run,
+            Attempt,
+            Execution,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -739,7 +755,10 @@ impl<'de> serde::Deserialize<'de> for GetKindsResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "kinds" => Ok(GeneratedField::Kinds),
+                            "workerId" => Ok(GeneratedField::WorkerId),
+                            "run" => Ok(GeneratedField::Run),
+                            "attempt" => Ok(GeneratedField::Attempt),
+                            "execution" => Ok(GeneratedField::Execution),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -749,36 +768,62 @@ impl<'de> serde::Deserialize<'de> for GetKindsResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetKindsResponse;
+            type Value = CompleteRunRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetKindsResponse")
+                formatter.write_str("struct bda.CompleteRunRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetKindsResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CompleteRunRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut kinds = None;
+                let mut worker_id = None;
+                let mut run = None;
+                let mut attempt = None;
+                let mut execution = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Kinds => {
-                            if kinds.is_some() {
-                                return Err(serde::de::Error::duplicate_field("kinds"));
+                        GeneratedField::WorkerId => {
+                            if worker_id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("workerId"));
                             }
-                            kinds = Some(map.next_value()?);
+                            worker_id = Some(map.next_value()?);
+                        }
+                        GeneratedField::Run => {
+                            if run.is_some() {
+                                return Err(serde::de::Error::duplicate_field("run"));
+                            }
+                            run = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attempt => {
+                            if attempt.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempt"));
+                            }
+                            attempt = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Execution => {
+                            if execution.is_some() {
+                                return Err(serde::de::Error::duplicate_field("execution"));
+                            }
+                            execution = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(GetKindsResponse {
-                    kinds: kinds.unwrap_or_default(),
+                Ok(CompleteRunRequest {
+                    worker_id: worker_id.unwrap_or_default(),
+                    run: run.unwrap_or_default(),
+                    attempt: attempt.unwrap_or_default(),
+                    execution,
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetKindsResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.CompleteRunRequest", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetNamespacesRequest {
+impl serde::Serialize for CompleteRunResponse {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -786,11 +831,11 @@ impl serde::Serialize for GetNamespacesRequest {
     {
         use serde::ser::SerializeStruct;
         let len = 0;
-        let struct_ser = serializer.serialize_struct("bda.GetNamespacesRequest", len)?;
+        let struct_ser = serializer.serialize_struct("bda.CompleteRunResponse", len)?;
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetNamespacesRequest {
+impl<'de> serde::Deserialize<'de> for CompleteRunResponse {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
@@ -828,25 +873,25 @@ impl<'de> serde::Deserialize<'de> for GetNamespacesRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetNamespacesRequest;
+            type Value = CompleteRunResponse;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetNamespacesRequest")
+                formatter.write_str("struct bda.CompleteRunResponse")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetNamespacesRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CompleteRunResponse, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
                 while map.next_key::<GeneratedField>()?.is_some() {}
-                Ok(GetNamespacesRequest {
+                Ok(CompleteRunResponse {
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetNamespacesRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.CompleteRunResponse", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetNamespacesResponse {
+impl serde::Serialize for Config {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -854,29 +899,29 @@ impl serde::Serialize for GetNamespacesResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.namespaces.is_empty() {
+        if !self.values.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetNamespacesResponse", len)?;
-        if !self.namespaces.is_empty() {
-            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Config", len)?;
+        if !self.values.is_empty() {
+            struct_ser.serialize_field("values", &self.values)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetNamespacesResponse {
+impl<'de> serde::Deserialize<'de> for Config {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "namespaces",
+            "values",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Namespaces,
+            Values,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -897,7 +942,7 @@ impl<'de> serde::Deserialize<'de> for GetNamespacesResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            "values" => Ok(GeneratedField::Values),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -907,36 +952,38 @@ impl<'de> serde::Deserialize<'de> for GetNamespacesResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetNamespacesResponse;
+            type Value = Config;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetNamespacesResponse")
+                formatter.write_str("struct bda.Config")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetNamespacesResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Config, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut namespaces = None;
+                let mut values = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Namespaces => {
-                            if namespaces.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                        GeneratedField::Values => {
+                            if values.is_some() {
+                                return Err(serde::de::Error::duplicate_field("values"));
                             }
-                            namespaces = Some(map.next_value()?);
+                            values = Some(
+                                map.next_value::<std::collections::HashMap<_, _>>()?
+                            );
                         }
                     }
                 }
-                Ok(GetNamespacesResponse {
-                    namespaces: namespaces.unwrap_or_default(),
+                Ok(Config {
+                    values: values.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetNamespacesResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Config", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetResourceRequest {
+impl serde::Serialize for Container {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -944,53 +991,29 @@ impl serde::Serialize for GetResourceRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.version.is_empty() {
-            len += 1;
-        }
-        if !self.namespace.is_empty() {
-            len += 1;
-        }
-        if !self.kind.is_empty() {
-            len += 1;
-        }
-        if !self.name.is_empty() {
+        if !self.dockerfile.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetResourceRequest", len)?;
-        if !self.version.is_empty() {
-            struct_ser.serialize_field("version", &self.version)?;
-        }
-        if !self.namespace.is_empty() {
-            struct_ser.serialize_field("namespace", &self.namespace)?;
-        }
-        if !self.kind.is_empty() {
-            struct_ser.serialize_field("kind", &self.kind)?;
-        }
-        if !self.name.is_empty() {
-            struct_ser.serialize_field("name", &self.name)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Container", len)?;
+        if !self.dockerfile.is_empty() {
+            struct_ser.serialize_field("dockerfile", &self.dockerfile)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetResourceRequest {
+impl<'de> serde::Deserialize<'de> for Container {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "version",
-            "namespace",
-            "kind",
-            "name",
+            "dockerfile",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Version,
-            Namespace,
-            Kind,
-            Name,
+            Dockerfile,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1011,10 +1034,7 @@ impl<'de> serde::Deserialize<'de> for GetResourceRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "version" => Ok(GeneratedField::Version),
-                            "namespace" => Ok(GeneratedField::Namespace),
-                            "kind" => Ok(GeneratedField::Kind),
-                            "name" => Ok(GeneratedField::Name),
+                            "dockerfile" => Ok(GeneratedField::Dockerfile),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1024,60 +1044,36 @@ impl<'de> serde::Deserialize<'de> for GetResourceRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetResourceRequest;
+            type Value = Container;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetResourceRequest")
+                formatter.write_str("struct bda.Container")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourceRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Container, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut version = None;
-                let mut namespace = None;
-                let mut kind = None;
-                let mut name = None;
+                let mut dockerfile = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Version => {
-                            if version.is_some() {
-                                return Err(serde::de::Error::duplicate_field("version"));
-                            }
-                            version = Some(map.next_value()?);
-                        }
-                        GeneratedField::Namespace => {
-                            if namespace.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespace"));
-                            }
-                            namespace = Some(map.next_value()?);
-                        }
-                        GeneratedField::Kind => {
-                            if kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("kind"));
-                            }
-                            kind = Some(map.next_value()?);
-                        }
-                        GeneratedField::Name => {
-                            if name.is_some() {
-                                return Err(serde::de::Error::duplicate_field("name"));
+                        GeneratedField::Dockerfile => {
+                            if dockerfile.is_some() {
+                                return Err(serde::de::Error::duplicate_field("dockerfile"));
                             }
-                            name = Some(map.next_value()?);
+                            dockerfile = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(GetResourceRequest {
-                    version: version.unwrap_or_default(),
-                    namespace: namespace.unwrap_or_default(),
-                    kind: kind.unwrap_or_default(),
-                    name: name.unwrap_or_default(),
+                Ok(Container {
+                    dockerfile: dockerfile.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetResourceRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Container", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetResourcesRequest {
+impl serde::Serialize for Dag {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1085,61 +1081,55 @@ impl serde::Serialize for GetResourcesRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.version.is_empty() {
-            len += 1;
-        }
-        if !self.namespaces.is_empty() {
+        if !self.nodes.is_empty() {
             len += 1;
         }
-        if !self.kinds.is_empty() {
+        if !self.edges.is_empty() {
             len += 1;
         }
-        if !self.names.is_empty() {
+        if self.parallelism != 0 {
             len += 1;
         }
-        if !self.bql.is_empty() {
+        if self.failure_policy != 0 {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetResourcesRequest", len)?;
-        if !self.version.is_empty() {
-            struct_ser.serialize_field("version", &self.version)?;
-        }
-        if !self.namespaces.is_empty() {
-            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Dag", len)?;
+        if !self.nodes.is_empty() {
+            struct_ser.serialize_field("nodes", &self.nodes)?;
         }
-        if !self.kinds.is_empty() {
-            struct_ser.serialize_field("kinds", &self.kinds)?;
+        if !self.edges.is_empty() {
+            struct_ser.serialize_field("edges", &self.edges)?;
         }
-        if !self.names.is_empty() {
-            struct_ser.serialize_field("names", &self.names)?;
+        if self.parallelism != 0 {
+            struct_ser.serialize_field("parallelism", &self.parallelism)?;
         }
-        if !self.bql.is_empty() {
-            struct_ser.serialize_field("bql", &self.bql)?;
+        if self.failure_policy != 0 {
+            let v = dag::FailurePolicy::from_i32(self.failure_policy)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.failure_policy)))?;
+            struct_ser.serialize_field("failurePolicy", &v)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetResourcesRequest {
+impl<'de> serde::Deserialize<'de> for Dag {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "version",
-            "namespaces",
-            "kinds",
-            "names",
-            "bql",
+            "nodes",
+            "edges",
+            "parallelism",
+            "failurePolicy",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Version,
-            Namespaces,
-            Kinds,
-            Names,
-            Bql,
+            Nodes,
+            Edges,
+            Parallelism,
+            FailurePolicy,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1160,11 +1150,10 @@ impl<'de> serde::Deserialize<'de> for GetResourcesRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "version" => Ok(GeneratedField::Version),
-                            "namespaces" => Ok(GeneratedField::Namespaces),
-                            "kinds" => Ok(GeneratedField::Kinds),
-                            "names" => Ok(GeneratedField::Names),
-                            "bql" => Ok(GeneratedField::Bql),
+                            "nodes" => Ok(GeneratedField::Nodes),
+                            "edges" => Ok(GeneratedField::Edges),
+                            "parallelism" => Ok(GeneratedField::Parallelism),
+                            "failurePolicy" => Ok(GeneratedField::FailurePolicy),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1174,68 +1163,135 @@ impl<'de> serde::Deserialize<'de> for GetResourcesRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetResourcesRequest;
+            type Value = Dag;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetResourcesRequest")
+                formatter.write_str("struct bda.Dag")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourcesRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Dag, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut version = None;
-                let mut namespaces = None;
-                let mut kinds = None;
-                let mut names = None;
-                let mut bql = None;
+                let mut nodes = None;
+                let mut edges = None;
+                let mut parallelism = None;
+                let mut failure_policy = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Version => {
-                            if version.is_some() {
-                                return Err(serde::de::Error::duplicate_field("version"));
-                            }
-                            version = Some(map.next_value()?);
-                        }
-                        GeneratedField::Namespaces => {
-                            if namespaces.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                        GeneratedField::Nodes => {
+                            if nodes.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nodes"));
                             }
-                            namespaces = Some(map.next_value()?);
+                            nodes = Some(map.next_value()?);
                         }
-                        GeneratedField::Kinds => {
-                            if kinds.is_some() {
-                                return Err(serde::de::Error::duplicate_field("kinds"));
+                        GeneratedField::Edges => {
+                            if edges.is_some() {
+                                return Err(serde::de::Error::duplicate_field("edges"));
                             }
-                            kinds = Some(map.next_value()?);
+                            edges = Some(map.next_value()?);
                         }
-                        GeneratedField::Names => {
-                            if names.is_some() {
-                                return Err(serde::de::Error::duplicate_field("names"));
+                        GeneratedField::Parallelism => {
+                            if parallelism.is_some() {
+                                return Err(serde::de::Error::duplicate_field("parallelism"));
                             }
-                            names = Some(map.next_value()?);
+                            parallelism = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
                         }
-                        GeneratedField::Bql => {
-                            if bql.is_some() {
-                                return Err(serde::de::Error::duplicate_field("bql"));
+                        GeneratedField::FailurePolicy => {
+                            if failure_policy.is_some() {
+                                return Err(serde::de::Error::duplicate_field("failurePolicy"));
                             }
-                            bql = Some(map.next_value()?);
+                            failure_policy = Some(map.next_value::<dag::FailurePolicy>()? as i32);
                         }
                     }
                 }
-                Ok(GetResourcesRequest {
-                    version: version.unwrap_or_default(),
-                    namespaces: namespaces.unwrap_or_default(),
-                    kinds: kinds.unwrap_or_default(),
-                    names: names.unwrap_or_default(),
-                    bql: bql.unwrap_or_default(),
+                Ok(Dag {
+                    nodes: nodes.unwrap_or_default(),
+                    edges: edges.unwrap_or_default(),
+                    parallelism: parallelism.unwrap_or_default(),
+                    failure_policy: failure_policy.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetResourcesRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Dag", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetResourcesResponse {
+impl serde::Serialize for dag::FailurePolicy {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::FailFast => "FAIL_FAST",
+            Self::Continue => "CONTINUE",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for dag::FailurePolicy {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "FAIL_FAST",
+            "CONTINUE",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = dag::FailurePolicy;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(dag::FailurePolicy::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(dag::FailurePolicy::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "FAIL_FAST" => Ok(dag::FailurePolicy::FailFast),
+                    "CONTINUE" => Ok(dag::FailurePolicy::Continue),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for DagEdge {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1243,29 +1299,53 @@ impl serde::Serialize for GetResourcesResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.resources.is_empty() {
+        if !self.from.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetResourcesResponse", len)?;
-        if !self.resources.is_empty() {
-            struct_ser.serialize_field("resources", &self.resources)?;
+        if !self.output.is_empty() {
+            len += 1;
+        }
+        if !self.to.is_empty() {
+            len += 1;
+        }
+        if !self.input.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.DagEdge", len)?;
+        if !self.from.is_empty() {
+            struct_ser.serialize_field("from", &self.from)?;
+        }
+        if !self.output.is_empty() {
+            struct_ser.serialize_field("output", &self.output)?;
+        }
+        if !self.to.is_empty() {
+            struct_ser.serialize_field("to", &self.to)?;
+        }
+        if !self.input.is_empty() {
+            struct_ser.serialize_field("input", &self.input)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetResourcesResponse {
+impl<'de> serde::Deserialize<'de> for DagEdge {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "resources",
+            "from",
+            "output",
+            "to",
+            "input",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Resources,
+            From,
+            Output,
+            To,
+            Input,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1286,7 +1366,10 @@ impl<'de> serde::Deserialize<'de> for GetResourcesResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "resources" => Ok(GeneratedField::Resources),
+                            "from" => Ok(GeneratedField::From),
+                            "output" => Ok(GeneratedField::Output),
+                            "to" => Ok(GeneratedField::To),
+                            "input" => Ok(GeneratedField::Input),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1296,58 +1379,106 @@ impl<'de> serde::Deserialize<'de> for GetResourcesResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetResourcesResponse;
+            type Value = DagEdge;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetResourcesResponse")
+                formatter.write_str("struct bda.DagEdge")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourcesResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<DagEdge, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut resources = None;
+                let mut from = None;
+                let mut output = None;
+                let mut to = None;
+                let mut input = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Resources => {
-                            if resources.is_some() {
-                                return Err(serde::de::Error::duplicate_field("resources"));
+                        GeneratedField::From => {
+                            if from.is_some() {
+                                return Err(serde::de::Error::duplicate_field("from"));
                             }
-                            resources = Some(map.next_value()?);
+                            from = Some(map.next_value()?);
+                        }
+                        GeneratedField::Output => {
+                            if output.is_some() {
+                                return Err(serde::de::Error::duplicate_field("output"));
+                            }
+                            output = Some(map.next_value()?);
+                        }
+                        GeneratedField::To => {
+                            if to.is_some() {
+                                return Err(serde::de::Error::duplicate_field("to"));
+                            }
+                            to = Some(map.next_value()?);
+                        }
+                        GeneratedField::Input => {
+                            if input.is_some() {
+                                return Err(serde::de::Error::duplicate_field("input"));
+                            }
+                            input = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(GetResourcesResponse {
-                    resources: resources.unwrap_or_default(),
+                Ok(DagEdge {
+                    from: from.unwrap_or_default(),
+                    output: output.unwrap_or_default(),
+                    to: to.unwrap_or_default(),
+                    input: input.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetResourcesResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.DagEdge", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetVersionsRequest {
+impl serde::Serialize for DagNode {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let len = 0;
-        let struct_ser = serializer.serialize_struct("bda.GetVersionsRequest", len)?;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.function.is_empty() {
+            len += 1;
+        }
+        if self.inputs.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.DagNode", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.function.is_empty() {
+            struct_ser.serialize_field("function", &self.function)?;
+        }
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
+        }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetVersionsRequest {
+impl<'de> serde::Deserialize<'de> for DagNode {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
+            "name",
+            "function",
+            "inputs",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
+            Name,
+            Function,
+            Inputs,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1367,7 +1498,12 @@ impl<'de> serde::Deserialize<'de> for GetVersionsRequest {
                     where
                         E: serde::de::Error,
                     {
-                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "function" => Ok(GeneratedField::Function),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
                     }
                 }
                 deserializer.deserialize_identifier(GeneratedVisitor)
@@ -1375,25 +1511,52 @@ impl<'de> serde::Deserialize<'de> for GetVersionsRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetVersionsRequest;
+            type Value = DagNode;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetVersionsRequest")
+                formatter.write_str("struct bda.DagNode")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetVersionsRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<DagNode, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                while map.next_key::<GeneratedField>()?.is_some() {}
-                Ok(GetVersionsRequest {
+                let mut name = None;
+                let mut function = None;
+                let mut inputs = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Function => {
+                            if function.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(DagNode {
+                    name: name.unwrap_or_default(),
+                    function: function.unwrap_or_default(),
+                    inputs,
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetVersionsRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.DagNode", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for GetVersionsResponse {
+impl serde::Serialize for DelResourceRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1401,29 +1564,63 @@ impl serde::Serialize for GetVersionsResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.versions.is_empty() {
+        if !self.version.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.GetVersionsResponse", len)?;
-        if !self.versions.is_empty() {
-            struct_ser.serialize_field("versions", &self.versions)?;
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if self.policy != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.DelResourceRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if self.policy != 0 {
+            let v = DeletePolicy::from_i32(self.policy)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.policy)))?;
+            struct_ser.serialize_field("policy", &v)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetVersionsResponse {
+impl<'de> serde::Deserialize<'de> for DelResourceRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "versions",
+            "version",
+            "namespace",
+            "kind",
+            "name",
+            "policy",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Versions,
+            Version,
+            Namespace,
+            Kind,
+            Name,
+            Policy,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1444,7 +1641,11 @@ impl<'de> serde::Deserialize<'de> for GetVersionsResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "versions" => Ok(GeneratedField::Versions),
+                            "version" => Ok(GeneratedField::Version),
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "kind" => Ok(GeneratedField::Kind),
+                            "name" => Ok(GeneratedField::Name),
+                            "policy" => Ok(GeneratedField::Policy),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1454,36 +1655,6881 @@ impl<'de> serde::Deserialize<'de> for GetVersionsResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetVersionsResponse;
+            type Value = DelResourceRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.GetVersionsResponse")
+                formatter.write_str("struct bda.DelResourceRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetVersionsResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourceRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut versions = None;
+                let mut version = None;
+                let mut namespace = None;
+                let mut kind = None;
+                let mut name = None;
+                let mut policy = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Versions => {
-                            if versions.is_some() {
-                                return Err(serde::de::Error::duplicate_field("versions"));
-                            }
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kind => {
+                            if kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Policy => {
+                            if policy.is_some() {
+                                return Err(serde::de::Error::duplicate_field("policy"));
+                            }
+                            policy = Some(map.next_value::<DeletePolicy>()? as i32);
+                        }
+                    }
+                }
+                Ok(DelResourceRequest {
+                    version: version.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
+                    kind: kind.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                    policy: policy.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.DelResourceRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for DelResourceResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.updates != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.DelResourceResponse", len)?;
+        if self.updates != 0 {
+            struct_ser.serialize_field("updates", &self.updates)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for DelResourceResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "updates",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Updates,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "updates" => Ok(GeneratedField::Updates),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = DelResourceResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.DelResourceResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourceResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut updates = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Updates => {
+                            if updates.is_some() {
+                                return Err(serde::de::Error::duplicate_field("updates"));
+                            }
+                            updates = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(DelResourceResponse {
+                    updates: updates.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.DelResourceResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for DelResourcesRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if !self.namespaces.is_empty() {
+            len += 1;
+        }
+        if !self.kinds.is_empty() {
+            len += 1;
+        }
+        if !self.names.is_empty() {
+            len += 1;
+        }
+        if !self.bql.is_empty() {
+            len += 1;
+        }
+        if self.policy != 0 {
+            len += 1;
+        }
+        if !self.labels.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.DelResourcesRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespaces.is_empty() {
+            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        }
+        if !self.kinds.is_empty() {
+            struct_ser.serialize_field("kinds", &self.kinds)?;
+        }
+        if !self.names.is_empty() {
+            struct_ser.serialize_field("names", &self.names)?;
+        }
+        if !self.bql.is_empty() {
+            struct_ser.serialize_field("bql", &self.bql)?;
+        }
+        if self.policy != 0 {
+            let v = DeletePolicy::from_i32(self.policy)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.policy)))?;
+            struct_ser.serialize_field("policy", &v)?;
+        }
+        if !self.labels.is_empty() {
+            struct_ser.serialize_field("labels", &self.labels)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for DelResourcesRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "namespaces",
+            "kinds",
+            "names",
+            "bql",
+            "policy",
+            "labels",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Namespaces,
+            Kinds,
+            Names,
+            Bql,
+            Policy,
+            Labels,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            "kinds" => Ok(GeneratedField::Kinds),
+                            "names" => Ok(GeneratedField::Names),
+                            "bql" => Ok(GeneratedField::Bql),
+                            "policy" => Ok(GeneratedField::Policy),
+                            "labels" => Ok(GeneratedField::Labels),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = DelResourcesRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.DelResourcesRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<DelResourcesRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut namespaces = None;
+                let mut kinds = None;
+                let mut names = None;
+                let mut bql = None;
+                let mut policy = None;
+                let mut labels = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespaces => {
+                            if namespaces.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                            }
+                            namespaces = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kinds => {
+                            if kinds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kinds"));
+                            }
+                            kinds = Some(map.next_value()?);
+                        }
+                        GeneratedField::Names => {
+                            if names.is_some() {
+                                return Err(serde::de::Error::duplicate_field("names"));
+                            }
+                            names = Some(map.next_value()?);
+                        }
+                        GeneratedField::Bql => {
+                            if bql.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bql"));
+                            }
+                            bql = Some(map.next_value()?);
+                        }
+                        GeneratedField::Policy => {
+                            if policy.is_some() {
+                                return Err(serde::de::Error::duplicate_field("policy"));
+                            }
+                            policy = Some(map.next_value::<DeletePolicy>()? as i32);
+                        }
+                        GeneratedField::Labels => {
+                            if labels.is_some() {
+                                return Err(serde::de::Error::duplicate_field("labels"));
+                            }
+                            labels = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(DelResourcesRequest {
+                    version: version.unwrap_or_default(),
+                    namespaces: namespaces.unwrap_or_default(),
+                    kinds: kinds.unwrap_or_default(),
+                    names: names.unwrap_or_default(),
+                    bql: bql.unwrap_or_default(),
+                    policy: policy.unwrap_or_default(),
+                    labels: labels.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.DelResourcesRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for DeletePolicy {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Reject => "REJECT",
+            Self::Cascade => "CASCADE",
+            Self::Orphan => "ORPHAN",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for DeletePolicy {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "REJECT",
+            "CASCADE",
+            "ORPHAN",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = DeletePolicy;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(DeletePolicy::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(DeletePolicy::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "REJECT" => Ok(DeletePolicy::Reject),
+                    "CASCADE" => Ok(DeletePolicy::Cascade),
+                    "ORPHAN" => Ok(DeletePolicy::Orphan),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Deployer {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.function.is_empty() {
+            len += 1;
+        }
+        if !self.stages.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Deployer", len)?;
+        if !self.function.is_empty() {
+            struct_ser.serialize_field("function", &self.function)?;
+        }
+        if !self.stages.is_empty() {
+            struct_ser.serialize_field("stages", &self.stages)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Deployer {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "function",
+            "stages",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Function,
+            Stages,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "function" => Ok(GeneratedField::Function),
+                            "stages" => Ok(GeneratedField::Stages),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Deployer;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Deployer")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Deployer, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut function = None;
+                let mut stages = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Function => {
+                            if function.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function = Some(map.next_value()?);
+                        }
+                        GeneratedField::Stages => {
+                            if stages.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stages"));
+                            }
+                            stages = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Deployer {
+                    function: function.unwrap_or_default(),
+                    stages: stages.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Deployer", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for deployer::Stage {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if self.approval {
+            len += 1;
+        }
+        if self.inputs.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Deployer.Stage", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if self.approval {
+            struct_ser.serialize_field("approval", &self.approval)?;
+        }
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for deployer::Stage {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "name",
+            "approval",
+            "inputs",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Name,
+            Approval,
+            Inputs,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "approval" => Ok(GeneratedField::Approval),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = deployer::Stage;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Deployer.Stage")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<deployer::Stage, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut name = None;
+                let mut approval = None;
+                let mut inputs = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Approval => {
+                            if approval.is_some() {
+                                return Err(serde::de::Error::duplicate_field("approval"));
+                            }
+                            approval = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(deployer::Stage {
+                    name: name.unwrap_or_default(),
+                    approval: approval.unwrap_or_default(),
+                    inputs,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Deployer.Stage", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Deployment {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.id.is_empty() {
+            len += 1;
+        }
+        if !self.deployer.is_empty() {
+            len += 1;
+        }
+        if !self.stage.is_empty() {
+            len += 1;
+        }
+        if !self.bundle.is_empty() {
+            len += 1;
+        }
+        if !self.semver.is_empty() {
+            len += 1;
+        }
+        if self.status != 0 {
+            len += 1;
+        }
+        if !self.run.is_empty() {
+            len += 1;
+        }
+        if self.rollback {
+            len += 1;
+        }
+        if self.created_at.is_some() {
+            len += 1;
+        }
+        if self.finished_at.is_some() {
+            len += 1;
+        }
+        if !self.error.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Deployment", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
+        }
+        if !self.deployer.is_empty() {
+            struct_ser.serialize_field("deployer", &self.deployer)?;
+        }
+        if !self.stage.is_empty() {
+            struct_ser.serialize_field("stage", &self.stage)?;
+        }
+        if !self.bundle.is_empty() {
+            struct_ser.serialize_field("bundle", &self.bundle)?;
+        }
+        if !self.semver.is_empty() {
+            struct_ser.serialize_field("semver", &self.semver)?;
+        }
+        if self.status != 0 {
+            let v = deployment::Status::from_i32(self.status)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.status)))?;
+            struct_ser.serialize_field("status", &v)?;
+        }
+        if !self.run.is_empty() {
+            struct_ser.serialize_field("run", &self.run)?;
+        }
+        if self.rollback {
+            struct_ser.serialize_field("rollback", &self.rollback)?;
+        }
+        if let Some(v) = self.created_at.as_ref() {
+            struct_ser.serialize_field("createdAt", v)?;
+        }
+        if let Some(v) = self.finished_at.as_ref() {
+            struct_ser.serialize_field("finishedAt", v)?;
+        }
+        if !self.error.is_empty() {
+            struct_ser.serialize_field("error", &self.error)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Deployment {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "id",
+            "deployer",
+            "stage",
+            "bundle",
+            "semver",
+            "status",
+            "run",
+            "rollback",
+            "createdAt",
+            "finishedAt",
+            "error",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Id,
+            Deployer,
+            Stage,
+            Bundle,
+            Semver,
+            Status,
+            Run,
+            Rollback,
+            CreatedAt,
+            FinishedAt,
+            Error,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "id" => Ok(GeneratedField::Id),
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "stage" => Ok(GeneratedField::Stage),
+                            "bundle" => Ok(GeneratedField::Bundle),
+                            "semver" => Ok(GeneratedField::Semver),
+                            "status" => Ok(GeneratedField::Status),
+                            "run" => Ok(GeneratedField::Run),
+                            "rollback" => Ok(GeneratedField::Rollback),
+                            "createdAt" => Ok(GeneratedField::CreatedAt),
+                            "finishedAt" => Ok(GeneratedField::FinishedAt),
+                            "error" => Ok(GeneratedField::Error),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Deployment;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Deployment")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Deployment, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut id = None;
+                let mut deployer = None;
+                let mut stage = None;
+                let mut bundle = None;
+                let mut semver = None;
+                let mut status = None;
+                let mut run = None;
+                let mut rollback = None;
+                let mut created_at = None;
+                let mut finished_at = None;
+                let mut error = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                        GeneratedField::Deployer => {
+                            if deployer.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
+                            }
+                            deployer = Some(map.next_value()?);
+                        }
+                        GeneratedField::Stage => {
+                            if stage.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stage"));
+                            }
+                            stage = Some(map.next_value()?);
+                        }
+                        GeneratedField::Bundle => {
+                            if bundle.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bundle"));
+                            }
+                            bundle = Some(map.next_value()?);
+                        }
+                        GeneratedField::Semver => {
+                            if semver.is_some() {
+                                return Err(serde::de::Error::duplicate_field("semver"));
+                            }
+                            semver = Some(map.next_value()?);
+                        }
+                        GeneratedField::Status => {
+                            if status.is_some() {
+                                return Err(serde::de::Error::duplicate_field("status"));
+                            }
+                            status = Some(map.next_value::<deployment::Status>()? as i32);
+                        }
+                        GeneratedField::Run => {
+                            if run.is_some() {
+                                return Err(serde::de::Error::duplicate_field("run"));
+                            }
+                            run = Some(map.next_value()?);
+                        }
+                        GeneratedField::Rollback => {
+                            if rollback.is_some() {
+                                return Err(serde::de::Error::duplicate_field("rollback"));
+                            }
+                            rollback = Some(map.next_value()?);
+                        }
+                        GeneratedField::CreatedAt => {
+                            if created_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("createdAt"));
+                            }
+                            created_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::FinishedAt => {
+                            if finished_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finishedAt"));
+                            }
+                            finished_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::Error => {
+                            if error.is_some() {
+                                return Err(serde::de::Error::duplicate_field("error"));
+                            }
+                            error = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Deployment {
+                    id: id.unwrap_or_default(),
+                    deployer: deployer.unwrap_or_default(),
+                    stage: stage.unwrap_or_default(),
+                    bundle: bundle.unwrap_or_default(),
+                    semver: semver.unwrap_or_default(),
+                    status: status.unwrap_or_default(),
+                    run: run.unwrap_or_default(),
+                    rollback: rollback.unwrap_or_default(),
+                    created_at,
+                    finished_at,
+                    error: error.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Deployment", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for deployment::Status {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::AwaitingApproval => "AWAITING_APPROVAL",
+            Self::Deploying => "DEPLOYING",
+            Self::Deployed => "DEPLOYED",
+            Self::Failed => "FAILED",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for deployment::Status {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "AWAITING_APPROVAL",
+            "DEPLOYING",
+            "DEPLOYED",
+            "FAILED",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = deployment::Status;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(deployment::Status::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(deployment::Status::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "AWAITING_APPROVAL" => Ok(deployment::Status::AwaitingApproval),
+                    "DEPLOYING" => Ok(deployment::Status::Deploying),
+                    "DEPLOYED" => Ok(deployment::Status::Deployed),
+                    "FAILED" => Ok(deployment::Status::Failed),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Execution {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.id.is_empty() {
+            len += 1;
+        }
+        if !self.function.is_empty() {
+            len += 1;
+        }
+        if self.inputs.is_some() {
+            len += 1;
+        }
+        if self.status != 0 {
+            len += 1;
+        }
+        if self.created_at.is_some() {
+            len += 1;
+        }
+        if self.started_at.is_some() {
+            len += 1;
+        }
+        if self.finished_at.is_some() {
+            len += 1;
+        }
+        if self.exit_code != 0 {
+            len += 1;
+        }
+        if self.outputs.is_some() {
+            len += 1;
+        }
+        if !self.work_dir.is_empty() {
+            len += 1;
+        }
+        if !self.error.is_empty() {
+            len += 1;
+        }
+        if self.attempts != 0 {
+            len += 1;
+        }
+        if !self.cached_from.is_empty() {
+            len += 1;
+        }
+        if !self.artifacts.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Execution", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
+        }
+        if !self.function.is_empty() {
+            struct_ser.serialize_field("function", &self.function)?;
+        }
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
+        }
+        if self.status != 0 {
+            let v = execution::Status::from_i32(self.status)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.status)))?;
+            struct_ser.serialize_field("status", &v)?;
+        }
+        if let Some(v) = self.created_at.as_ref() {
+            struct_ser.serialize_field("createdAt", v)?;
+        }
+        if let Some(v) = self.started_at.as_ref() {
+            struct_ser.serialize_field("startedAt", v)?;
+        }
+        if let Some(v) = self.finished_at.as_ref() {
+            struct_ser.serialize_field("finishedAt", v)?;
+        }
+        if self.exit_code != 0 {
+            struct_ser.serialize_field("exitCode", &self.exit_code)?;
+        }
+        if let Some(v) = self.outputs.as_ref() {
+            struct_ser.serialize_field("outputs", v)?;
+        }
+        if !self.work_dir.is_empty() {
+            struct_ser.serialize_field("workDir", &self.work_dir)?;
+        }
+        if !self.error.is_empty() {
+            struct_ser.serialize_field("error", &self.error)?;
+        }
+        if self.attempts != 0 {
+            struct_ser.serialize_field("attempts", &self.attempts)?;
+        }
+        if !self.cached_from.is_empty() {
+            struct_ser.serialize_field("cachedFrom", &self.cached_from)?;
+        }
+        if !self.artifacts.is_empty() {
+            struct_ser.serialize_field("artifacts", &self.artifacts)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Execution {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "id",
+            "function",
+            "inputs",
+            "status",
+            "createdAt",
+            "startedAt",
+            "finishedAt",
+            "exitCode",
+            "outputs",
+            "workDir",
+            "error",
+            "attempts",
+            "cachedFrom",
+            "artifacts",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Id,
+            Function,
+            Inputs,
+            Status,
+            CreatedAt,
+            StartedAt,
+            FinishedAt,
+            ExitCode,
+            Outputs,
+            WorkDir,
+            Error,
+            Attempts,
+            CachedFrom,
+            Artifacts,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "id" => Ok(GeneratedField::Id),
+                            "function" => Ok(GeneratedField::Function),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            "status" => Ok(GeneratedField::Status),
+                            "createdAt" => Ok(GeneratedField::CreatedAt),
+                            "startedAt" => Ok(GeneratedField::StartedAt),
+                            "finishedAt" => Ok(GeneratedField::FinishedAt),
+                            "exitCode" => Ok(GeneratedField::ExitCode),
+                            "outputs" => Ok(GeneratedField::Outputs),
+                            "workDir" => Ok(GeneratedField::WorkDir),
+                            "error" => Ok(GeneratedField::Error),
+                            "attempts" => Ok(GeneratedField::Attempts),
+                            "cachedFrom" => Ok(GeneratedField::CachedFrom),
+                            "artifacts" => Ok(GeneratedField::Artifacts),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Execution;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Execution")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Execution, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut id = None;
+                let mut function = None;
+                let mut inputs = None;
+                let mut status = None;
+                let mut created_at = None;
+                let mut started_at = None;
+                let mut finished_at = None;
+                let mut exit_code = None;
+                let mut outputs = None;
+                let mut work_dir = None;
+                let mut error = None;
+                let mut attempts = None;
+                let mut cached_from = None;
+                let mut artifacts = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                        GeneratedField::Function => {
+                            if function.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                        GeneratedField::Status => {
+                            if status.is_some() {
+                                return Err(serde::de::Error::duplicate_field("status"));
+                            }
+                            status = Some(map.next_value::<execution::Status>()? as i32);
+                        }
+                        GeneratedField::CreatedAt => {
+                            if created_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("createdAt"));
+                            }
+                            created_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::StartedAt => {
+                            if started_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("startedAt"));
+                            }
+                            started_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::FinishedAt => {
+                            if finished_at.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finishedAt"));
+                            }
+                            finished_at = Some(map.next_value()?);
+                        }
+                        GeneratedField::ExitCode => {
+                            if exit_code.is_some() {
+                                return Err(serde::de::Error::duplicate_field("exitCode"));
+                            }
+                            exit_code = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Outputs => {
+                            if outputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("outputs"));
+                            }
+                            outputs = Some(map.next_value()?);
+                        }
+                        GeneratedField::WorkDir => {
+                            if work_dir.is_some() {
+                                return Err(serde::de::Error::duplicate_field("workDir"));
+                            }
+                            work_dir = Some(map.next_value()?);
+                        }
+                        GeneratedField::Error => {
+                            if error.is_some() {
+                                return Err(serde::de::Error::duplicate_field("error"));
+                            }
+                            error = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attempts => {
+                            if attempts.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempts"));
+                            }
+                            attempts = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::CachedFrom => {
+                            if cached_from.is_some() {
+                                return Err(serde::de::Error::duplicate_field("cachedFrom"));
+                            }
+                            cached_from = Some(map.next_value()?);
+                        }
+                        GeneratedField::Artifacts => {
+                            if artifacts.is_some() {
+                                return Err(serde::de::Error::duplicate_field("artifacts"));
+                            }
+                            artifacts = Some(
+                                map.next_value::<std::collections::HashMap<_, _>>()?
+                            );
+                        }
+                    }
+                }
+                Ok(Execution {
+                    id: id.unwrap_or_default(),
+                    function: function.unwrap_or_default(),
+                    inputs,
+                    status: status.unwrap_or_default(),
+                    created_at,
+                    started_at,
+                    finished_at,
+                    exit_code: exit_code.unwrap_or_default(),
+                    outputs,
+                    work_dir: work_dir.unwrap_or_default(),
+                    error: error.unwrap_or_default(),
+                    attempts: attempts.unwrap_or_default(),
+                    cached_from: cached_from.unwrap_or_default(),
+                    artifacts: artifacts.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Execution", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for execution::Status {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Pending => "PENDING",
+            Self::Running => "RUNNING",
+            Self::Succeeded => "SUCCEEDED",
+            Self::Failed => "FAILED",
+            Self::Cancelled => "CANCELLED",
+            Self::Lost => "LOST",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for execution::Status {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "PENDING",
+            "RUNNING",
+            "SUCCEEDED",
+            "FAILED",
+            "CANCELLED",
+            "LOST",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = execution::Status;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(execution::Status::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(execution::Status::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "PENDING" => Ok(execution::Status::Pending),
+                    "RUNNING" => Ok(execution::Status::Running),
+                    "SUCCEEDED" => Ok(execution::Status::Succeeded),
+                    "FAILED" => Ok(execution::Status::Failed),
+                    "CANCELLED" => Ok(execution::Status::Cancelled),
+                    "LOST" => Ok(execution::Status::Lost),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Function {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.inputs.is_empty() {
+            len += 1;
+        }
+        if !self.outputs.is_empty() {
+            len += 1;
+        }
+        if !self.base_command.is_empty() {
+            len += 1;
+        }
+        if !self.runtime_capabilities.is_empty() {
+            len += 1;
+        }
+        if self.run_policy.is_some() {
+            len += 1;
+        }
+        if !self.secrets.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Function", len)?;
+        if !self.inputs.is_empty() {
+            struct_ser.serialize_field("inputs", &self.inputs)?;
+        }
+        if !self.outputs.is_empty() {
+            struct_ser.serialize_field("outputs", &self.outputs)?;
+        }
+        if !self.base_command.is_empty() {
+            struct_ser.serialize_field("baseCommand", &self.base_command)?;
+        }
+        if !self.runtime_capabilities.is_empty() {
+            struct_ser.serialize_field("runtimeCapabilities", &self.runtime_capabilities)?;
+        }
+        if let Some(v) = self.run_policy.as_ref() {
+            struct_ser.serialize_field("runPolicy", v)?;
+        }
+        if !self.secrets.is_empty() {
+            struct_ser.serialize_field("secrets", &self.secrets)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Function {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "inputs",
+            "outputs",
+            "baseCommand",
+            "runtimeCapabilities",
+            "runPolicy",
+            "secrets",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Inputs,
+            Outputs,
+            BaseCommand,
+            RuntimeCapabilities,
+            RunPolicy,
+            Secrets,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            "outputs" => Ok(GeneratedField::Outputs),
+                            "baseCommand" => Ok(GeneratedField::BaseCommand),
+                            "runtimeCapabilities" => Ok(GeneratedField::RuntimeCapabilities),
+                            "runPolicy" => Ok(GeneratedField::RunPolicy),
+                            "secrets" => Ok(GeneratedField::Secrets),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Function;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Function")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Function, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut inputs = None;
+                let mut outputs = None;
+                let mut base_command = None;
+                let mut runtime_capabilities = None;
+                let mut run_policy = None;
+                let mut secrets = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                        GeneratedField::Outputs => {
+                            if outputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("outputs"));
+                            }
+                            outputs = Some(map.next_value()?);
+                        }
+                        GeneratedField::BaseCommand => {
+                            if base_command.is_some() {
+                                return Err(serde::de::Error::duplicate_field("baseCommand"));
+                            }
+                            base_command = Some(map.next_value()?);
+                        }
+                        GeneratedField::RuntimeCapabilities => {
+                            if runtime_capabilities.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runtimeCapabilities"));
+                            }
+                            runtime_capabilities = Some(map.next_value()?);
+                        }
+                        GeneratedField::RunPolicy => {
+                            if run_policy.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runPolicy"));
+                            }
+                            run_policy = Some(map.next_value()?);
+                        }
+                        GeneratedField::Secrets => {
+                            if secrets.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secrets"));
+                            }
+                            secrets = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Function {
+                    inputs: inputs.unwrap_or_default(),
+                    outputs: outputs.unwrap_or_default(),
+                    base_command: base_command.unwrap_or_default(),
+                    runtime_capabilities: runtime_capabilities.unwrap_or_default(),
+                    run_policy,
+                    secrets: secrets.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Function", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetArtifactRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.digest.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetArtifactRequest", len)?;
+        if !self.digest.is_empty() {
+            struct_ser.serialize_field("digest", &self.digest)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetArtifactRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "digest",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Digest,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "digest" => Ok(GeneratedField::Digest),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetArtifactRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetArtifactRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetArtifactRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut digest = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Digest => {
+                            if digest.is_some() {
+                                return Err(serde::de::Error::duplicate_field("digest"));
+                            }
+                            digest = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetArtifactRequest {
+                    digest: digest.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetArtifactRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetKindsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser = serializer.serialize_struct("bda.GetKindsRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetKindsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetKindsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetKindsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetKindsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {}
+                Ok(GetKindsRequest {
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetKindsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetKindsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.kinds.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetKindsResponse", len)?;
+        if !self.kinds.is_empty() {
+            struct_ser.serialize_field("kinds", &self.kinds)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetKindsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "kinds",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Kinds,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "kinds" => Ok(GeneratedField::Kinds),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetKindsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetKindsResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetKindsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut kinds = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Kinds => {
+                            if kinds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kinds"));
+                            }
+                            kinds = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetKindsResponse {
+                    kinds: kinds.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetKindsResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetNamespacesRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser = serializer.serialize_struct("bda.GetNamespacesRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetNamespacesRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetNamespacesRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetNamespacesRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetNamespacesRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {}
+                Ok(GetNamespacesRequest {
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetNamespacesRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetNamespacesResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.namespaces.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetNamespacesResponse", len)?;
+        if !self.namespaces.is_empty() {
+            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetNamespacesResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "namespaces",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Namespaces,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetNamespacesResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetNamespacesResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetNamespacesResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut namespaces = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Namespaces => {
+                            if namespaces.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                            }
+                            namespaces = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetNamespacesResponse {
+                    namespaces: namespaces.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetNamespacesResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetResourceHistoryResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.revisions.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetResourceHistoryResponse", len)?;
+        if !self.revisions.is_empty() {
+            struct_ser.serialize_field("revisions", &self.revisions)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetResourceHistoryResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "revisions",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Revisions,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "revisions" => Ok(GeneratedField::Revisions),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetResourceHistoryResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetResourceHistoryResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourceHistoryResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut revisions = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Revisions => {
+                            if revisions.is_some() {
+                                return Err(serde::de::Error::duplicate_field("revisions"));
+                            }
+                            revisions = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetResourceHistoryResponse {
+                    revisions: revisions.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetResourceHistoryResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetResourceRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetResourceRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetResourceRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "namespace",
+            "kind",
+            "name",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Namespace,
+            Kind,
+            Name,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "kind" => Ok(GeneratedField::Kind),
+                            "name" => Ok(GeneratedField::Name),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetResourceRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetResourceRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourceRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut namespace = None;
+                let mut kind = None;
+                let mut name = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kind => {
+                            if kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetResourceRequest {
+                    version: version.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
+                    kind: kind.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetResourceRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetResourcesRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if !self.namespaces.is_empty() {
+            len += 1;
+        }
+        if !self.kinds.is_empty() {
+            len += 1;
+        }
+        if !self.names.is_empty() {
+            len += 1;
+        }
+        if !self.bql.is_empty() {
+            len += 1;
+        }
+        if !self.references.is_empty() {
+            len += 1;
+        }
+        if !self.referenced_by.is_empty() {
+            len += 1;
+        }
+        if !self.labels.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetResourcesRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespaces.is_empty() {
+            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        }
+        if !self.kinds.is_empty() {
+            struct_ser.serialize_field("kinds", &self.kinds)?;
+        }
+        if !self.names.is_empty() {
+            struct_ser.serialize_field("names", &self.names)?;
+        }
+        if !self.bql.is_empty() {
+            struct_ser.serialize_field("bql", &self.bql)?;
+        }
+        if !self.references.is_empty() {
+            struct_ser.serialize_field("references", &self.references)?;
+        }
+        if !self.referenced_by.is_empty() {
+            struct_ser.serialize_field("referencedBy", &self.referenced_by)?;
+        }
+        if !self.labels.is_empty() {
+            struct_ser.serialize_field("labels", &self.labels)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetResourcesRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "namespaces",
+            "kinds",
+            "names",
+            "bql",
+            "references",
+            "referencedBy",
+            "labels",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Namespaces,
+            Kinds,
+            Names,
+            Bql,
+            References,
+            ReferencedBy,
+            Labels,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            "kinds" => Ok(GeneratedField::Kinds),
+                            "names" => Ok(GeneratedField::Names),
+                            "bql" => Ok(GeneratedField::Bql),
+                            "references" => Ok(GeneratedField::References),
+                            "referencedBy" => Ok(GeneratedField::ReferencedBy),
+                            "labels" => Ok(GeneratedField::Labels),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetResourcesRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetResourcesRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourcesRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut namespaces = None;
+                let mut kinds = None;
+                let mut names = None;
+                let mut bql = None;
+                let mut references = None;
+                let mut referenced_by = None;
+                let mut labels = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespaces => {
+                            if namespaces.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                            }
+                            namespaces = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kinds => {
+                            if kinds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kinds"));
+                            }
+                            kinds = Some(map.next_value()?);
+                        }
+                        GeneratedField::Names => {
+                            if names.is_some() {
+                                return Err(serde::de::Error::duplicate_field("names"));
+                            }
+                            names = Some(map.next_value()?);
+                        }
+                        GeneratedField::Bql => {
+                            if bql.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bql"));
+                            }
+                            bql = Some(map.next_value()?);
+                        }
+                        GeneratedField::References => {
+                            if references.is_some() {
+                                return Err(serde::de::Error::duplicate_field("references"));
+                            }
+                            references = Some(map.next_value()?);
+                        }
+                        GeneratedField::ReferencedBy => {
+                            if referenced_by.is_some() {
+                                return Err(serde::de::Error::duplicate_field("referencedBy"));
+                            }
+                            referenced_by = Some(map.next_value()?);
+                        }
+                        GeneratedField::Labels => {
+                            if labels.is_some() {
+                                return Err(serde::de::Error::duplicate_field("labels"));
+                            }
+                            labels = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetResourcesRequest {
+                    version: version.unwrap_or_default(),
+                    namespaces: namespaces.unwrap_or_default(),
+                    kinds: kinds.unwrap_or_default(),
+                    names: names.unwrap_or_default(),
+                    bql: bql.unwrap_or_default(),
+                    references: references.unwrap_or_default(),
+                    referenced_by: referenced_by.unwrap_or_default(),
+                    labels: labels.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetResourcesRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetResourcesResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.resources.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetResourcesResponse", len)?;
+        if !self.resources.is_empty() {
+            struct_ser.serialize_field("resources", &self.resources)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetResourcesResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "resources",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Resources,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "resources" => Ok(GeneratedField::Resources),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetResourcesResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetResourcesResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetResourcesResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut resources = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Resources => {
+                            if resources.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resources"));
+                            }
+                            resources = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetResourcesResponse {
+                    resources: resources.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetResourcesResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetRunRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.id.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetRunRequest", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetRunRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "id",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Id,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "id" => Ok(GeneratedField::Id),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetRunRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetRunRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetRunRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut id = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetRunRequest {
+                    id: id.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetRunRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetSecretRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetSecretRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetSecretRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "namespace",
+            "name",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Namespace,
+            Name,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "name" => Ok(GeneratedField::Name),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetSecretRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetSecretRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetSecretRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut namespace = None;
+                let mut name = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetSecretRequest {
+                    version: version.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetSecretRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetVersionsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.namespaces.is_empty() {
+            len += 1;
+        }
+        if !self.kinds.is_empty() {
+            len += 1;
+        }
+        if !self.names.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetVersionsRequest", len)?;
+        if !self.namespaces.is_empty() {
+            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        }
+        if !self.kinds.is_empty() {
+            struct_ser.serialize_field("kinds", &self.kinds)?;
+        }
+        if !self.names.is_empty() {
+            struct_ser.serialize_field("names", &self.names)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetVersionsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "namespaces",
+            "kinds",
+            "names",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Namespaces,
+            Kinds,
+            Names,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            "kinds" => Ok(GeneratedField::Kinds),
+                            "names" => Ok(GeneratedField::Names),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetVersionsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetVersionsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetVersionsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut namespaces = None;
+                let mut kinds = None;
+                let mut names = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Namespaces => {
+                            if namespaces.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                            }
+                            namespaces = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kinds => {
+                            if kinds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kinds"));
+                            }
+                            kinds = Some(map.next_value()?);
+                        }
+                        GeneratedField::Names => {
+                            if names.is_some() {
+                                return Err(serde::de::Error::duplicate_field("names"));
+                            }
+                            names = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetVersionsRequest {
+                    namespaces: namespaces.unwrap_or_default(),
+                    kinds: kinds.unwrap_or_default(),
+                    names: names.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetVersionsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetVersionsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.versions.is_empty() {
+            len += 1;
+        }
+        if !self.resources.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.GetVersionsResponse", len)?;
+        if !self.versions.is_empty() {
+            struct_ser.serialize_field("versions", &self.versions)?;
+        }
+        if !self.resources.is_empty() {
+            struct_ser.serialize_field("resources", &self.resources)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetVersionsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "versions",
+            "resources",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Versions,
+            Resources,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "versions" => Ok(GeneratedField::Versions),
+                            "resources" => Ok(GeneratedField::Resources),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetVersionsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.GetVersionsResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetVersionsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut versions = None;
+                let mut resources = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Versions => {
+                            if versions.is_some() {
+                                return Err(serde::de::Error::duplicate_field("versions"));
+                            }
+                            versions = Some(map.next_value()?);
+                        }
+                        GeneratedField::Resources => {
+                            if resources.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resources"));
+                            }
+                            resources = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetVersionsResponse {
+                    versions: versions.unwrap_or_default(),
+                    resources: resources.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.GetVersionsResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for HeartbeatRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.worker_id.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.HeartbeatRequest", len)?;
+        if !self.worker_id.is_empty() {
+            struct_ser.serialize_field("workerId", &self.worker_id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for HeartbeatRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "workerId",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            WorkerId,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "workerId" => Ok(GeneratedField::WorkerId),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = HeartbeatRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.HeartbeatRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<HeartbeatRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut worker_id = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::WorkerId => {
+                            if worker_id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("workerId"));
+                            }
+                            worker_id = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(HeartbeatRequest {
+                    worker_id: worker_id.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.HeartbeatRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for HeartbeatResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.cancel.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.HeartbeatResponse", len)?;
+        if !self.cancel.is_empty() {
+            struct_ser.serialize_field("cancel", &self.cancel)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for HeartbeatResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "cancel",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Cancel,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "cancel" => Ok(GeneratedField::Cancel),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = HeartbeatResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.HeartbeatResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<HeartbeatResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut cancel = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Cancel => {
+                            if cancel.is_some() {
+                                return Err(serde::de::Error::duplicate_field("cancel"));
+                            }
+                            cancel = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(HeartbeatResponse {
+                    cancel: cancel.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.HeartbeatResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for InstalledResource {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.resource.is_empty() {
+            len += 1;
+        }
+        if !self.bundle.is_empty() {
+            len += 1;
+        }
+        if !self.semver.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.InstalledResource", len)?;
+        if !self.resource.is_empty() {
+            struct_ser.serialize_field("resource", &self.resource)?;
+        }
+        if !self.bundle.is_empty() {
+            struct_ser.serialize_field("bundle", &self.bundle)?;
+        }
+        if !self.semver.is_empty() {
+            struct_ser.serialize_field("semver", &self.semver)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for InstalledResource {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "resource",
+            "bundle",
+            "semver",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Resource,
+            Bundle,
+            Semver,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "resource" => Ok(GeneratedField::Resource),
+                            "bundle" => Ok(GeneratedField::Bundle),
+                            "semver" => Ok(GeneratedField::Semver),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = InstalledResource;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.InstalledResource")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<InstalledResource, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut resource = None;
+                let mut bundle = None;
+                let mut semver = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Resource => {
+                            if resource.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resource"));
+                            }
+                            resource = Some(map.next_value()?);
+                        }
+                        GeneratedField::Bundle => {
+                            if bundle.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bundle"));
+                            }
+                            bundle = Some(map.next_value()?);
+                        }
+                        GeneratedField::Semver => {
+                            if semver.is_some() {
+                                return Err(serde::de::Error::duplicate_field("semver"));
+                            }
+                            semver = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(InstalledResource {
+                    resource: resource.unwrap_or_default(),
+                    bundle: bundle.unwrap_or_default(),
+                    semver: semver.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.InstalledResource", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for LeaseRunRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.worker_id.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.LeaseRunRequest", len)?;
+        if !self.worker_id.is_empty() {
+            struct_ser.serialize_field("workerId", &self.worker_id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for LeaseRunRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "workerId",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            WorkerId,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "workerId" => Ok(GeneratedField::WorkerId),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = LeaseRunRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.LeaseRunRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<LeaseRunRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut worker_id = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::WorkerId => {
+                            if worker_id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("workerId"));
+                            }
+                            worker_id = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(LeaseRunRequest {
+                    worker_id: worker_id.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.LeaseRunRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for LeaseRunResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.lease.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.LeaseRunResponse", len)?;
+        if let Some(v) = self.lease.as_ref() {
+            struct_ser.serialize_field("lease", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for LeaseRunResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "lease",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Lease,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "lease" => Ok(GeneratedField::Lease),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = LeaseRunResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.LeaseRunResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<LeaseRunResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut lease = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Lease => {
+                            if lease.is_some() {
+                                return Err(serde::de::Error::duplicate_field("lease"));
+                            }
+                            lease = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(LeaseRunResponse {
+                    lease,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.LeaseRunResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListArtifactsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.run.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListArtifactsRequest", len)?;
+        if !self.run.is_empty() {
+            struct_ser.serialize_field("run", &self.run)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListArtifactsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "run",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Run,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "run" => Ok(GeneratedField::Run),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListArtifactsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListArtifactsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListArtifactsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut run = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Run => {
+                            if run.is_some() {
+                                return Err(serde::de::Error::duplicate_field("run"));
+                            }
+                            run = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListArtifactsRequest {
+                    run: run.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListArtifactsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListArtifactsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.artifacts.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListArtifactsResponse", len)?;
+        if !self.artifacts.is_empty() {
+            struct_ser.serialize_field("artifacts", &self.artifacts)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListArtifactsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "artifacts",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Artifacts,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "artifacts" => Ok(GeneratedField::Artifacts),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListArtifactsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListArtifactsResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListArtifactsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut artifacts = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Artifacts => {
+                            if artifacts.is_some() {
+                                return Err(serde::de::Error::duplicate_field("artifacts"));
+                            }
+                            artifacts = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListArtifactsResponse {
+                    artifacts: artifacts.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListArtifactsResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListDeploymentsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.deployer.is_empty() {
+            len += 1;
+        }
+        if self.current {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListDeploymentsRequest", len)?;
+        if !self.deployer.is_empty() {
+            struct_ser.serialize_field("deployer", &self.deployer)?;
+        }
+        if self.current {
+            struct_ser.serialize_field("current", &self.current)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListDeploymentsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "deployer",
+            "current",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Deployer,
+            Current,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "current" => Ok(GeneratedField::Current),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListDeploymentsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListDeploymentsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListDeploymentsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut deployer = None;
+                let mut current = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Deployer => {
+                            if deployer.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
+                            }
+                            deployer = Some(map.next_value()?);
+                        }
+                        GeneratedField::Current => {
+                            if current.is_some() {
+                                return Err(serde::de::Error::duplicate_field("current"));
+                            }
+                            current = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListDeploymentsRequest {
+                    deployer: deployer.unwrap_or_default(),
+                    current: current.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListDeploymentsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListDeploymentsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.deployments.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListDeploymentsResponse", len)?;
+        if !self.deployments.is_empty() {
+            struct_ser.serialize_field("deployments", &self.deployments)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListDeploymentsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "deployments",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Deployments,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "deployments" => Ok(GeneratedField::Deployments),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListDeploymentsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListDeploymentsResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListDeploymentsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut deployments = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Deployments => {
+                            if deployments.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployments"));
+                            }
+                            deployments = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListDeploymentsResponse {
+                    deployments: deployments.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListDeploymentsResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListInstalledRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.namespaces.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListInstalledRequest", len)?;
+        if !self.namespaces.is_empty() {
+            struct_ser.serialize_field("namespaces", &self.namespaces)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListInstalledRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "namespaces",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Namespaces,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "namespaces" => Ok(GeneratedField::Namespaces),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListInstalledRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListInstalledRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListInstalledRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut namespaces = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Namespaces => {
+                            if namespaces.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespaces"));
+                            }
+                            namespaces = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListInstalledRequest {
+                    namespaces: namespaces.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListInstalledRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListInstalledResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.resources.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListInstalledResponse", len)?;
+        if !self.resources.is_empty() {
+            struct_ser.serialize_field("resources", &self.resources)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListInstalledResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "resources",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Resources,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "resources" => Ok(GeneratedField::Resources),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListInstalledResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListInstalledResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListInstalledResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut resources = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Resources => {
+                            if resources.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resources"));
+                            }
+                            resources = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListInstalledResponse {
+                    resources: resources.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListInstalledResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListRunsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.function.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListRunsRequest", len)?;
+        if !self.function.is_empty() {
+            struct_ser.serialize_field("function", &self.function)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListRunsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "function",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Function,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "function" => Ok(GeneratedField::Function),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListRunsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListRunsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListRunsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut function = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Function => {
+                            if function.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListRunsRequest {
+                    function: function.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListRunsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ListRunsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.runs.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ListRunsResponse", len)?;
+        if !self.runs.is_empty() {
+            struct_ser.serialize_field("runs", &self.runs)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ListRunsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "runs",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Runs,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "runs" => Ok(GeneratedField::Runs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ListRunsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ListRunsResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ListRunsResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut runs = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Runs => {
+                            if runs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runs"));
+                            }
+                            runs = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ListRunsResponse {
+                    runs: runs.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ListRunsResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Listener {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.target.is_empty() {
+            len += 1;
+        }
+        if !self.triggers.is_empty() {
+            len += 1;
+        }
+        if self.inputs.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Listener", len)?;
+        if !self.target.is_empty() {
+            struct_ser.serialize_field("target", &self.target)?;
+        }
+        if !self.triggers.is_empty() {
+            struct_ser.serialize_field("triggers", &self.triggers)?;
+        }
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Listener {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "target",
+            "triggers",
+            "inputs",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Target,
+            Triggers,
+            Inputs,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "target" => Ok(GeneratedField::Target),
+                            "triggers" => Ok(GeneratedField::Triggers),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Listener;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Listener")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Listener, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut target = None;
+                let mut triggers = None;
+                let mut inputs = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Target => {
+                            if target.is_some() {
+                                return Err(serde::de::Error::duplicate_field("target"));
+                            }
+                            target = Some(map.next_value()?);
+                        }
+                        GeneratedField::Triggers => {
+                            if triggers.is_some() {
+                                return Err(serde::de::Error::duplicate_field("triggers"));
+                            }
+                            triggers = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Listener {
+                    target: target.unwrap_or_default(),
+                    triggers: triggers.unwrap_or_default(),
+                    inputs,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Listener", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Parameter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.description.is_empty() {
+            len += 1;
+        }
+        if self.parameter_kind != 0 {
+            len += 1;
+        }
+        if self.default_value.is_some() {
+            len += 1;
+        }
+        if self.minimum.is_some() {
+            len += 1;
+        }
+        if self.maximum.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Parameter", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.description.is_empty() {
+            struct_ser.serialize_field("description", &self.description)?;
+        }
+        if self.parameter_kind != 0 {
+            let v = parameter::ParameterKind::from_i32(self.parameter_kind)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.parameter_kind)))?;
+            struct_ser.serialize_field("parameterKind", &v)?;
+        }
+        if let Some(v) = self.default_value.as_ref() {
+            struct_ser.serialize_field("defaultValue", v)?;
+        }
+        if let Some(v) = self.minimum.as_ref() {
+            struct_ser.serialize_field("minimum", v)?;
+        }
+        if let Some(v) = self.maximum.as_ref() {
+            struct_ser.serialize_field("maximum", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Parameter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "name",
+            "description",
+            "parameterKind",
+            "defaultValue",
+            "minimum",
+            "maximum",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Name,
+            Description,
+            ParameterKind,
+            DefaultValue,
+            Minimum,
+            Maximum,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "description" => Ok(GeneratedField::Description),
+                            "parameterKind" => Ok(GeneratedField::ParameterKind),
+                            "defaultValue" => Ok(GeneratedField::DefaultValue),
+                            "minimum" => Ok(GeneratedField::Minimum),
+                            "maximum" => Ok(GeneratedField::Maximum),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Parameter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Parameter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Parameter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut name = None;
+                let mut description = None;
+                let mut parameter_kind = None;
+                let mut default_value = None;
+                let mut minimum = None;
+                let mut maximum = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Description => {
+                            if description.is_some() {
+                                return Err(serde::de::Error::duplicate_field("description"));
+                            }
+                            description = Some(map.next_value()?);
+                        }
+                        GeneratedField::ParameterKind => {
+                            if parameter_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("parameterKind"));
+                            }
+                            parameter_kind = Some(map.next_value::<parameter::ParameterKind>()? as i32);
+                        }
+                        GeneratedField::DefaultValue => {
+                            if default_value.is_some() {
+                                return Err(serde::de::Error::duplicate_field("defaultValue"));
+                            }
+                            default_value = Some(map.next_value()?);
+                        }
+                        GeneratedField::Minimum => {
+                            if minimum.is_some() {
+                                return Err(serde::de::Error::duplicate_field("minimum"));
+                            }
+                            minimum = Some(map.next_value()?);
+                        }
+                        GeneratedField::Maximum => {
+                            if maximum.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maximum"));
+                            }
+                            maximum = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Parameter {
+                    name: name.unwrap_or_default(),
+                    description: description.unwrap_or_default(),
+                    parameter_kind: parameter_kind.unwrap_or_default(),
+                    default_value,
+                    minimum,
+                    maximum,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Parameter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for parameter::ParameterKind {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Generic => "GENERIC",
+            Self::Number => "NUMBER",
+            Self::Boolean => "BOOLEAN",
+            Self::Text => "TEXT",
+            Self::Json => "JSON",
+            Self::Url => "URL",
+            Self::Path => "PATH",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for parameter::ParameterKind {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "GENERIC",
+            "NUMBER",
+            "BOOLEAN",
+            "TEXT",
+            "JSON",
+            "URL",
+            "PATH",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = parameter::ParameterKind;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(parameter::ParameterKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(parameter::ParameterKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "GENERIC" => Ok(parameter::ParameterKind::Generic),
+                    "NUMBER" => Ok(parameter::ParameterKind::Number),
+                    "BOOLEAN" => Ok(parameter::ParameterKind::Boolean),
+                    "TEXT" => Ok(parameter::ParameterKind::Text),
+                    "JSON" => Ok(parameter::ParameterKind::Json),
+                    "URL" => Ok(parameter::ParameterKind::Url),
+                    "PATH" => Ok(parameter::ParameterKind::Path),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for PromoteRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.deployer.is_empty() {
+            len += 1;
+        }
+        if !self.bundle.is_empty() {
+            len += 1;
+        }
+        if !self.stage.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.PromoteRequest", len)?;
+        if !self.deployer.is_empty() {
+            struct_ser.serialize_field("deployer", &self.deployer)?;
+        }
+        if !self.bundle.is_empty() {
+            struct_ser.serialize_field("bundle", &self.bundle)?;
+        }
+        if !self.stage.is_empty() {
+            struct_ser.serialize_field("stage", &self.stage)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for PromoteRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "deployer",
+            "bundle",
+            "stage",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Deployer,
+            Bundle,
+            Stage,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "bundle" => Ok(GeneratedField::Bundle),
+                            "stage" => Ok(GeneratedField::Stage),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PromoteRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.PromoteRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<PromoteRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut deployer = None;
+                let mut bundle = None;
+                let mut stage = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Deployer => {
+                            if deployer.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
+                            }
+                            deployer = Some(map.next_value()?);
+                        }
+                        GeneratedField::Bundle => {
+                            if bundle.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bundle"));
+                            }
+                            bundle = Some(map.next_value()?);
+                        }
+                        GeneratedField::Stage => {
+                            if stage.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stage"));
+                            }
+                            stage = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(PromoteRequest {
+                    deployer: deployer.unwrap_or_default(),
+                    bundle: bundle.unwrap_or_default(),
+                    stage: stage.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.PromoteRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for PutResourceRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.resource.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.PutResourceRequest", len)?;
+        if let Some(v) = self.resource.as_ref() {
+            struct_ser.serialize_field("resource", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for PutResourceRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "resource",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Resource,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "resource" => Ok(GeneratedField::Resource),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PutResourceRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.PutResourceRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<PutResourceRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut resource = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Resource => {
+                            if resource.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resource"));
+                            }
+                            resource = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(PutResourceRequest {
+                    resource,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.PutResourceRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for PutResourceResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.updates != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.PutResourceResponse", len)?;
+        if self.updates != 0 {
+            struct_ser.serialize_field("updates", &self.updates)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for PutResourceResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "updates",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Updates,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "updates" => Ok(GeneratedField::Updates),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PutResourceResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.PutResourceResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<PutResourceResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut updates = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Updates => {
+                            if updates.is_some() {
+                                return Err(serde::de::Error::duplicate_field("updates"));
+                            }
+                            updates = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(PutResourceResponse {
+                    updates: updates.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.PutResourceResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ReconcileRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.applier.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ReconcileRequest", len)?;
+        if !self.applier.is_empty() {
+            struct_ser.serialize_field("applier", &self.applier)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ReconcileRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "applier",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Applier,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "applier" => Ok(GeneratedField::Applier),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ReconcileRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ReconcileRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ReconcileRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut applier = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Applier => {
+                            if applier.is_some() {
+                                return Err(serde::de::Error::duplicate_field("applier"));
+                            }
+                            applier = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ReconcileRequest {
+                    applier: applier.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ReconcileRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RegisterWorkerRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.capabilities.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RegisterWorkerRequest", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.capabilities.is_empty() {
+            struct_ser.serialize_field("capabilities", &self.capabilities)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RegisterWorkerRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "name",
+            "capabilities",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Name,
+            Capabilities,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "capabilities" => Ok(GeneratedField::Capabilities),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RegisterWorkerRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RegisterWorkerRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RegisterWorkerRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut name = None;
+                let mut capabilities = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Capabilities => {
+                            if capabilities.is_some() {
+                                return Err(serde::de::Error::duplicate_field("capabilities"));
+                            }
+                            capabilities = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(RegisterWorkerRequest {
+                    name: name.unwrap_or_default(),
+                    capabilities: capabilities.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RegisterWorkerRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RegisterWorkerResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.worker_id.is_empty() {
+            len += 1;
+        }
+        if self.heartbeat_seconds != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RegisterWorkerResponse", len)?;
+        if !self.worker_id.is_empty() {
+            struct_ser.serialize_field("workerId", &self.worker_id)?;
+        }
+        if self.heartbeat_seconds != 0 {
+            struct_ser.serialize_field("heartbeatSeconds", &self.heartbeat_seconds)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RegisterWorkerResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "workerId",
+            "heartbeatSeconds",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            WorkerId,
+            HeartbeatSeconds,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "workerId" => Ok(GeneratedField::WorkerId),
+                            "heartbeatSeconds" => Ok(GeneratedField::HeartbeatSeconds),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RegisterWorkerResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RegisterWorkerResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RegisterWorkerResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut worker_id = None;
+                let mut heartbeat_seconds = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::WorkerId => {
+                            if worker_id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("workerId"));
+                            }
+                            worker_id = Some(map.next_value()?);
+                        }
+                        GeneratedField::HeartbeatSeconds => {
+                            if heartbeat_seconds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("heartbeatSeconds"));
+                            }
+                            heartbeat_seconds = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(RegisterWorkerResponse {
+                    worker_id: worker_id.unwrap_or_default(),
+                    heartbeat_seconds: heartbeat_seconds.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RegisterWorkerResponse", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Resource {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.description.is_empty() {
+            len += 1;
+        }
+        if !self.tags.is_empty() {
+            len += 1;
+        }
+        if self.attributes.is_some() {
+            len += 1;
+        }
+        if !self.references.is_empty() {
+            len += 1;
+        }
+        if !self.labels.is_empty() {
+            len += 1;
+        }
+        if self.resource_kind.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Resource", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.description.is_empty() {
+            struct_ser.serialize_field("description", &self.description)?;
+        }
+        if !self.tags.is_empty() {
+            struct_ser.serialize_field("tags", &self.tags)?;
+        }
+        if let Some(v) = self.attributes.as_ref() {
+            struct_ser.serialize_field("attributes", v)?;
+        }
+        if !self.references.is_empty() {
+            struct_ser.serialize_field("references", &self.references)?;
+        }
+        if !self.labels.is_empty() {
+            struct_ser.serialize_field("labels", &self.labels)?;
+        }
+        if let Some(v) = self.resource_kind.as_ref() {
+            match v {
+                resource::ResourceKind::Function(v) => {
+                    struct_ser.serialize_field("function", v)?;
+                }
+                resource::ResourceKind::Runtime(v) => {
+                    struct_ser.serialize_field("runtime", v)?;
+                }
+                resource::ResourceKind::Dag(v) => {
+                    struct_ser.serialize_field("dag", v)?;
+                }
+                resource::ResourceKind::Secret(v) => {
+                    struct_ser.serialize_field("secret", v)?;
+                }
+                resource::ResourceKind::Config(v) => {
+                    struct_ser.serialize_field("config", v)?;
+                }
+                resource::ResourceKind::Bundle(v) => {
+                    struct_ser.serialize_field("bundle", v)?;
+                }
+                resource::ResourceKind::Listener(v) => {
+                    struct_ser.serialize_field("listener", v)?;
+                }
+                resource::ResourceKind::Deployer(v) => {
+                    struct_ser.serialize_field("deployer", v)?;
+                }
+                resource::ResourceKind::Applier(v) => {
+                    struct_ser.serialize_field("applier", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Resource {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "namespace",
+            "name",
+            "description",
+            "tags",
+            "attributes",
+            "references",
+            "labels",
+            "function",
+            "runtime",
+            "dag",
+            "secret",
+            "config",
+            "bundle",
+            "listener",
+            "deployer",
+            "applier",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Namespace,
+            Name,
+            Description,
+            Tags,
+            Attributes,
+            References,
+            Labels,
+            Function,
+            Runtime,
+            Dag,
+            Secret,
+            Config,
+            Bundle,
+            Listener,
+            Deployer,
+            Applier,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "name" => Ok(GeneratedField::Name),
+                            "description" => Ok(GeneratedField::Description),
+                            "tags" => Ok(GeneratedField::Tags),
+                            "attributes" => Ok(GeneratedField::Attributes),
+                            "references" => Ok(GeneratedField::References),
+                            "labels" => Ok(GeneratedField::Labels),
+                            "function" => Ok(GeneratedField::Function),
+                            "runtime" => Ok(GeneratedField::Runtime),
+                            "dag" => Ok(GeneratedField::Dag),
+                            "secret" => Ok(GeneratedField::Secret),
+                            "config" => Ok(GeneratedField::Config),
+                            "bundle" => Ok(GeneratedField::Bundle),
+                            "listener" => Ok(GeneratedField::Listener),
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "applier" => Ok(GeneratedField::Applier),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Resource;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Resource")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Resource, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut namespace = None;
+                let mut name = None;
+                let mut description = None;
+                let mut tags = None;
+                let mut attributes = None;
+                let mut references = None;
+                let mut labels = None;
+                let mut resource_kind = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Description => {
+                            if description.is_some() {
+                                return Err(serde::de::Error::duplicate_field("description"));
+                            }
+                            description = Some(map.next_value()?);
+                        }
+                        GeneratedField::Tags => {
+                            if tags.is_some() {
+                                return Err(serde::de::Error::duplicate_field("tags"));
+                            }
+                            tags = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attributes => {
+                            if attributes.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attributes"));
+                            }
+                            attributes = Some(map.next_value()?);
+                        }
+                        GeneratedField::References => {
+                            if references.is_some() {
+                                return Err(serde::de::Error::duplicate_field("references"));
+                            }
+                            references = Some(map.next_value()?);
+                        }
+                        GeneratedField::Labels => {
+                            if labels.is_some() {
+                                return Err(serde::de::Error::duplicate_field("labels"));
+                            }
+                            labels = Some(
+                                map.next_value::<std::collections::HashMap<_, _>>()?
+                            );
+                        }
+                        GeneratedField::Function => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Function(map.next_value()?));
+                        }
+                        GeneratedField::Runtime => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runtime"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Runtime(map.next_value()?));
+                        }
+                        GeneratedField::Dag => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("dag"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Dag(map.next_value()?));
+                        }
+                        GeneratedField::Secret => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secret"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Secret(map.next_value()?));
+                        }
+                        GeneratedField::Config => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("config"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Config(map.next_value()?));
+                        }
+                        GeneratedField::Bundle => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bundle"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Bundle(map.next_value()?));
+                        }
+                        GeneratedField::Listener => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("listener"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Listener(map.next_value()?));
+                        }
+                        GeneratedField::Deployer => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Deployer(map.next_value()?));
+                        }
+                        GeneratedField::Applier => {
+                            if resource_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("applier"));
+                            }
+                            resource_kind = Some(resource::ResourceKind::Applier(map.next_value()?));
+                        }
+                    }
+                }
+                Ok(Resource {
+                    version: version.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                    description: description.unwrap_or_default(),
+                    tags: tags.unwrap_or_default(),
+                    attributes,
+                    references: references.unwrap_or_default(),
+                    labels: labels.unwrap_or_default(),
+                    resource_kind,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Resource", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ResourceEvent {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.operation != 0 {
+            len += 1;
+        }
+        if self.resource.is_some() {
+            len += 1;
+        }
+        if self.time.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ResourceEvent", len)?;
+        if self.operation != 0 {
+            let v = resource_revision::Operation::from_i32(self.operation)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.operation)))?;
+            struct_ser.serialize_field("operation", &v)?;
+        }
+        if let Some(v) = self.resource.as_ref() {
+            struct_ser.serialize_field("resource", v)?;
+        }
+        if let Some(v) = self.time.as_ref() {
+            struct_ser.serialize_field("time", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ResourceEvent {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "operation",
+            "resource",
+            "time",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Operation,
+            Resource,
+            Time,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "operation" => Ok(GeneratedField::Operation),
+                            "resource" => Ok(GeneratedField::Resource),
+                            "time" => Ok(GeneratedField::Time),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ResourceEvent;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ResourceEvent")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ResourceEvent, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut operation = None;
+                let mut resource = None;
+                let mut time = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Operation => {
+                            if operation.is_some() {
+                                return Err(serde::de::Error::duplicate_field("operation"));
+                            }
+                            operation = Some(map.next_value::<resource_revision::Operation>()? as i32);
+                        }
+                        GeneratedField::Resource => {
+                            if resource.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resource"));
+                            }
+                            resource = Some(map.next_value()?);
+                        }
+                        GeneratedField::Time => {
+                            if time.is_some() {
+                                return Err(serde::de::Error::duplicate_field("time"));
+                            }
+                            time = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ResourceEvent {
+                    operation: operation.unwrap_or_default(),
+                    resource,
+                    time,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ResourceEvent", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ResourceRef {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ResourceRef", len)?;
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ResourceRef {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "kind",
+            "namespace",
+            "name",
+            "version",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Kind,
+            Namespace,
+            Name,
+            Version,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "kind" => Ok(GeneratedField::Kind),
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "name" => Ok(GeneratedField::Name),
+                            "version" => Ok(GeneratedField::Version),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ResourceRef;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ResourceRef")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ResourceRef, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut kind = None;
+                let mut namespace = None;
+                let mut name = None;
+                let mut version = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Kind => {
+                            if kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ResourceRef {
+                    kind: kind.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                    version: version.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ResourceRef", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ResourceRevision {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.revision != 0 {
+            len += 1;
+        }
+        if self.time.is_some() {
+            len += 1;
+        }
+        if self.operation != 0 {
+            len += 1;
+        }
+        if self.resource.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ResourceRevision", len)?;
+        if self.revision != 0 {
+            struct_ser.serialize_field("revision", ToString::to_string(&self.revision).as_str())?;
+        }
+        if let Some(v) = self.time.as_ref() {
+            struct_ser.serialize_field("time", v)?;
+        }
+        if self.operation != 0 {
+            let v = resource_revision::Operation::from_i32(self.operation)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.operation)))?;
+            struct_ser.serialize_field("operation", &v)?;
+        }
+        if let Some(v) = self.resource.as_ref() {
+            struct_ser.serialize_field("resource", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ResourceRevision {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "revision",
+            "time",
+            "operation",
+            "resource",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Revision,
+            Time,
+            Operation,
+            Resource,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "revision" => Ok(GeneratedField::Revision),
+                            "time" => Ok(GeneratedField::Time),
+                            "operation" => Ok(GeneratedField::Operation),
+                            "resource" => Ok(GeneratedField::Resource),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ResourceRevision;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ResourceRevision")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ResourceRevision, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut revision = None;
+                let mut time = None;
+                let mut operation = None;
+                let mut resource = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Revision => {
+                            if revision.is_some() {
+                                return Err(serde::de::Error::duplicate_field("revision"));
+                            }
+                            revision = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Time => {
+                            if time.is_some() {
+                                return Err(serde::de::Error::duplicate_field("time"));
+                            }
+                            time = Some(map.next_value()?);
+                        }
+                        GeneratedField::Operation => {
+                            if operation.is_some() {
+                                return Err(serde::de::Error::duplicate_field("operation"));
+                            }
+                            operation = Some(map.next_value::<resource_revision::Operation>()? as i32);
+                        }
+                        GeneratedField::Resource => {
+                            if resource.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resource"));
+                            }
+                            resource = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ResourceRevision {
+                    revision: revision.unwrap_or_default(),
+                    time,
+                    operation: operation.unwrap_or_default(),
+                    resource,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ResourceRevision", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for resource_revision::Operation {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Create => "CREATE",
+            Self::Update => "UPDATE",
+            Self::Delete => "DELETE",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for resource_revision::Operation {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "CREATE",
+            "UPDATE",
+            "DELETE",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = resource_revision::Operation;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(resource_revision::Operation::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(resource_revision::Operation::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "CREATE" => Ok(resource_revision::Operation::Create),
+                    "UPDATE" => Ok(resource_revision::Operation::Update),
+                    "DELETE" => Ok(resource_revision::Operation::Delete),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ResourceVersions {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.namespace.is_empty() {
+            len += 1;
+        }
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.versions.is_empty() {
+            len += 1;
+        }
+        if !self.latest.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.ResourceVersions", len)?;
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.versions.is_empty() {
+            struct_ser.serialize_field("versions", &self.versions)?;
+        }
+        if !self.latest.is_empty() {
+            struct_ser.serialize_field("latest", &self.latest)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ResourceVersions {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "namespace",
+            "kind",
+            "name",
+            "versions",
+            "latest",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Namespace,
+            Kind,
+            Name,
+            Versions,
+            Latest,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "namespace" => Ok(GeneratedField::Namespace),
+                            "kind" => Ok(GeneratedField::Kind),
+                            "name" => Ok(GeneratedField::Name),
+                            "versions" => Ok(GeneratedField::Versions),
+                            "latest" => Ok(GeneratedField::Latest),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ResourceVersions;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.ResourceVersions")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ResourceVersions, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut namespace = None;
+                let mut kind = None;
+                let mut name = None;
+                let mut versions = None;
+                let mut latest = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
+                        GeneratedField::Kind => {
+                            if kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind = Some(map.next_value()?);
+                        }
+                        GeneratedField::Name => {
+                            if name.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name = Some(map.next_value()?);
+                        }
+                        GeneratedField::Versions => {
+                            if versions.is_some() {
+                                return Err(serde::de::Error::duplicate_field("versions"));
+                            }
                             versions = Some(map.next_value()?);
                         }
+                        GeneratedField::Latest => {
+                            if latest.is_some() {
+                                return Err(serde::de::Error::duplicate_field("latest"));
+                            }
+                            latest = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ResourceVersions {
+                    namespace: namespace.unwrap_or_default(),
+                    kind: kind.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                    versions: versions.unwrap_or_default(),
+                    latest: latest.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.ResourceVersions", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RollbackRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.deployer.is_empty() {
+            len += 1;
+        }
+        if !self.stage.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RollbackRequest", len)?;
+        if !self.deployer.is_empty() {
+            struct_ser.serialize_field("deployer", &self.deployer)?;
+        }
+        if !self.stage.is_empty() {
+            struct_ser.serialize_field("stage", &self.stage)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RollbackRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "deployer",
+            "stage",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Deployer,
+            Stage,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "deployer" => Ok(GeneratedField::Deployer),
+                            "stage" => Ok(GeneratedField::Stage),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RollbackRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RollbackRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RollbackRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut deployer = None;
+                let mut stage = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Deployer => {
+                            if deployer.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deployer"));
+                            }
+                            deployer = Some(map.next_value()?);
+                        }
+                        GeneratedField::Stage => {
+                            if stage.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stage"));
+                            }
+                            stage = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(RollbackRequest {
+                    deployer: deployer.unwrap_or_default(),
+                    stage: stage.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RollbackRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RunLease {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.run.is_empty() {
+            len += 1;
+        }
+        if self.attempt != 0 {
+            len += 1;
+        }
+        if !self.function.is_empty() {
+            len += 1;
+        }
+        if self.definition.is_some() {
+            len += 1;
+        }
+        if self.inputs.is_some() {
+            len += 1;
+        }
+        if !self.runtime.is_empty() {
+            len += 1;
+        }
+        if self.container.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RunLease", len)?;
+        if !self.run.is_empty() {
+            struct_ser.serialize_field("run", &self.run)?;
+        }
+        if self.attempt != 0 {
+            struct_ser.serialize_field("attempt", &self.attempt)?;
+        }
+        if !self.function.is_empty() {
+            struct_ser.serialize_field("function", &self.function)?;
+        }
+        if let Some(v) = self.definition.as_ref() {
+            struct_ser.serialize_field("definition", v)?;
+        }
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
+        }
+        if !self.runtime.is_empty() {
+            struct_ser.serialize_field("runtime", &self.runtime)?;
+        }
+        if let Some(v) = self.container.as_ref() {
+            struct_ser.serialize_field("container", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RunLease {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "run",
+            "attempt",
+            "function",
+            "definition",
+            "inputs",
+            "runtime",
+            "container",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Run,
+            Attempt,
+            Function,
+            Definition,
+            Inputs,
+            Runtime,
+            Container,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "run" => Ok(GeneratedField::Run),
+                            "attempt" => Ok(GeneratedField::Attempt),
+                            "function" => Ok(GeneratedField::Function),
+                            "definition" => Ok(GeneratedField::Definition),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            "runtime" => Ok(GeneratedField::Runtime),
+                            "container" => Ok(GeneratedField::Container),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RunLease;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RunLease")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RunLease, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut run = None;
+                let mut attempt = None;
+                let mut function = None;
+                let mut definition = None;
+                let mut inputs = None;
+                let mut runtime = None;
+                let mut container = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Run => {
+                            if run.is_some() {
+                                return Err(serde::de::Error::duplicate_field("run"));
+                            }
+                            run = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attempt => {
+                            if attempt.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempt"));
+                            }
+                            attempt = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Function => {
+                            if function.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function = Some(map.next_value()?);
+                        }
+                        GeneratedField::Definition => {
+                            if definition.is_some() {
+                                return Err(serde::de::Error::duplicate_field("definition"));
+                            }
+                            definition = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs = Some(map.next_value()?);
+                        }
+                        GeneratedField::Runtime => {
+                            if runtime.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runtime"));
+                            }
+                            runtime = Some(map.next_value()?);
+                        }
+                        GeneratedField::Container => {
+                            if container.is_some() {
+                                return Err(serde::de::Error::duplicate_field("container"));
+                            }
+                            container = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(RunLease {
+                    run: run.unwrap_or_default(),
+                    attempt: attempt.unwrap_or_default(),
+                    function: function.unwrap_or_default(),
+                    definition,
+                    inputs,
+                    runtime: runtime.unwrap_or_default(),
+                    container,
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RunLease", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RunLog {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.stream.is_empty() {
+            len += 1;
+        }
+        if !self.line.is_empty() {
+            len += 1;
+        }
+        if self.offset != 0 {
+            len += 1;
+        }
+        if self.time.is_some() {
+            len += 1;
+        }
+        if self.attempt != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RunLog", len)?;
+        if !self.stream.is_empty() {
+            struct_ser.serialize_field("stream", &self.stream)?;
+        }
+        if !self.line.is_empty() {
+            struct_ser.serialize_field("line", &self.line)?;
+        }
+        if self.offset != 0 {
+            struct_ser.serialize_field("offset", ToString::to_string(&self.offset).as_str())?;
+        }
+        if let Some(v) = self.time.as_ref() {
+            struct_ser.serialize_field("time", v)?;
+        }
+        if self.attempt != 0 {
+            struct_ser.serialize_field("attempt", &self.attempt)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RunLog {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "stream",
+            "line",
+            "offset",
+            "time",
+            "attempt",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Stream,
+            Line,
+            Offset,
+            Time,
+            Attempt,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "stream" => Ok(GeneratedField::Stream),
+                            "line" => Ok(GeneratedField::Line),
+                            "offset" => Ok(GeneratedField::Offset),
+                            "time" => Ok(GeneratedField::Time),
+                            "attempt" => Ok(GeneratedField::Attempt),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RunLog;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RunLog")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RunLog, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut stream = None;
+                let mut line = None;
+                let mut offset = None;
+                let mut time = None;
+                let mut attempt = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Stream => {
+                            if stream.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stream"));
+                            }
+                            stream = Some(map.next_value()?);
+                        }
+                        GeneratedField::Line => {
+                            if line.is_some() {
+                                return Err(serde::de::Error::duplicate_field("line"));
+                            }
+                            line = Some(map.next_value()?);
+                        }
+                        GeneratedField::Offset => {
+                            if offset.is_some() {
+                                return Err(serde::de::Error::duplicate_field("offset"));
+                            }
+                            offset = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Time => {
+                            if time.is_some() {
+                                return Err(serde::de::Error::duplicate_field("time"));
+                            }
+                            time = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attempt => {
+                            if attempt.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempt"));
+                            }
+                            attempt = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(RunLog {
+                    stream: stream.unwrap_or_default(),
+                    line: line.unwrap_or_default(),
+                    offset: offset.unwrap_or_default(),
+                    time,
+                    attempt: attempt.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RunLog", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for RunPolicy {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.timeout_seconds != 0 {
+            len += 1;
+        }
+        if self.retries != 0 {
+            len += 1;
+        }
+        if self.backoff_seconds != 0 {
+            len += 1;
+        }
+        if self.grace_period_seconds != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.RunPolicy", len)?;
+        if self.timeout_seconds != 0 {
+            struct_ser.serialize_field("timeoutSeconds", &self.timeout_seconds)?;
+        }
+        if self.retries != 0 {
+            struct_ser.serialize_field("retries", &self.retries)?;
+        }
+        if self.backoff_seconds != 0 {
+            struct_ser.serialize_field("backoffSeconds", &self.backoff_seconds)?;
+        }
+        if self.grace_period_seconds != 0 {
+            struct_ser.serialize_field("gracePeriodSeconds", &self.grace_period_seconds)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RunPolicy {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "timeoutSeconds",
+            "retries",
+            "backoffSeconds",
+            "gracePeriodSeconds",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TimeoutSeconds,
+            Retries,
+            BackoffSeconds,
+            GracePeriodSeconds,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "timeoutSeconds" => Ok(GeneratedField::TimeoutSeconds),
+                            "retries" => Ok(GeneratedField::Retries),
+                            "backoffSeconds" => Ok(GeneratedField::BackoffSeconds),
+                            "gracePeriodSeconds" => Ok(GeneratedField::GracePeriodSeconds),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RunPolicy;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.RunPolicy")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<RunPolicy, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut timeout_seconds = None;
+                let mut retries = None;
+                let mut backoff_seconds = None;
+                let mut grace_period_seconds = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::TimeoutSeconds => {
+                            if timeout_seconds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("timeoutSeconds"));
+                            }
+                            timeout_seconds = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Retries => {
+                            if retries.is_some() {
+                                return Err(serde::de::Error::duplicate_field("retries"));
+                            }
+                            retries = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::BackoffSeconds => {
+                            if backoff_seconds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("backoffSeconds"));
+                            }
+                            backoff_seconds = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::GracePeriodSeconds => {
+                            if grace_period_seconds.is_some() {
+                                return Err(serde::de::Error::duplicate_field("gracePeriodSeconds"));
+                            }
+                            grace_period_seconds = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(RunPolicy {
+                    timeout_seconds: timeout_seconds.unwrap_or_default(),
+                    retries: retries.unwrap_or_default(),
+                    backoff_seconds: backoff_seconds.unwrap_or_default(),
+                    grace_period_seconds: grace_period_seconds.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.RunPolicy", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Runtime {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.capabilities.is_empty() {
+            len += 1;
+        }
+        if self.runtime_kind.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Runtime", len)?;
+        if !self.capabilities.is_empty() {
+            struct_ser.serialize_field("capabilities", &self.capabilities)?;
+        }
+        if let Some(v) = self.runtime_kind.as_ref() {
+            match v {
+                runtime::RuntimeKind::Container(v) => {
+                    struct_ser.serialize_field("container", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Runtime {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "capabilities",
+            "container",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Capabilities,
+            Container,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "capabilities" => Ok(GeneratedField::Capabilities),
+                            "container" => Ok(GeneratedField::Container),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Runtime;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Runtime")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Runtime, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut capabilities = None;
+                let mut runtime_kind = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Capabilities => {
+                            if capabilities.is_some() {
+                                return Err(serde::de::Error::duplicate_field("capabilities"));
+                            }
+                            capabilities = Some(map.next_value()?);
+                        }
+                        GeneratedField::Container => {
+                            if runtime_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("container"));
+                            }
+                            runtime_kind = Some(runtime::RuntimeKind::Container(map.next_value()?));
+                        }
                     }
                 }
-                Ok(GetVersionsResponse {
-                    versions: versions.unwrap_or_default(),
+                Ok(Runtime {
+                    capabilities: capabilities.unwrap_or_default(),
+                    runtime_kind,
                 })
             }
         }
-        deserializer.deserialize_struct("bda.GetVersionsResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Runtime", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for Parameter {
+impl serde::Serialize for Secret {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1491,55 +8537,439 @@ impl serde::Serialize for Parameter {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.name.is_empty() {
+        if !self.data.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Secret", len)?;
+        if !self.data.is_empty() {
+            struct_ser.serialize_field("data", &self.data)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Secret {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "data",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Data,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "data" => Ok(GeneratedField::Data),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Secret;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Secret")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Secret, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut data = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Data => {
+                            if data.is_some() {
+                                return Err(serde::de::Error::duplicate_field("data"));
+                            }
+                            data = Some(
+                                map.next_value::<std::collections::HashMap<_, _>>()?
+                            );
+                        }
+                    }
+                }
+                Ok(Secret {
+                    data: data.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.Secret", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SecretRef {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.secret.is_empty() {
+            len += 1;
+        }
+        if !self.key.is_empty() {
+            len += 1;
+        }
+        if !self.env.is_empty() {
+            len += 1;
+        }
+        if !self.path.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.SecretRef", len)?;
+        if !self.secret.is_empty() {
+            struct_ser.serialize_field("secret", &self.secret)?;
+        }
+        if !self.key.is_empty() {
+            struct_ser.serialize_field("key", &self.key)?;
+        }
+        if !self.env.is_empty() {
+            struct_ser.serialize_field("env", &self.env)?;
+        }
+        if !self.path.is_empty() {
+            struct_ser.serialize_field("path", &self.path)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SecretRef {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "secret",
+            "key",
+            "env",
+            "path",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Secret,
+            Key,
+            Env,
+            Path,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "secret" => Ok(GeneratedField::Secret),
+                            "key" => Ok(GeneratedField::Key),
+                            "env" => Ok(GeneratedField::Env),
+                            "path" => Ok(GeneratedField::Path),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SecretRef;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.SecretRef")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SecretRef, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut secret = None;
+                let mut key = None;
+                let mut env = None;
+                let mut path = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Secret => {
+                            if secret.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secret"));
+                            }
+                            secret = Some(map.next_value()?);
+                        }
+                        GeneratedField::Key => {
+                            if key.is_some() {
+                                return Err(serde::de::Error::duplicate_field("key"));
+                            }
+                            key = Some(map.next_value()?);
+                        }
+                        GeneratedField::Env => {
+                            if env.is_some() {
+                                return Err(serde::de::Error::duplicate_field("env"));
+                            }
+                            env = Some(map.next_value()?);
+                        }
+                        GeneratedField::Path => {
+                            if path.is_some() {
+                                return Err(serde::de::Error::duplicate_field("path"));
+                            }
+                            path = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SecretRef {
+                    secret: secret.unwrap_or_default(),
+                    key: key.unwrap_or_default(),
+                    env: env.unwrap_or_default(),
+                    path: path.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.SecretRef", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for StreamRunLogsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.id.is_empty() {
+            len += 1;
+        }
+        if self.offset != 0 {
+            len += 1;
+        }
+        if self.follow {
+            len += 1;
+        }
+        if self.attempt != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.StreamRunLogsRequest", len)?;
+        if !self.id.is_empty() {
+            struct_ser.serialize_field("id", &self.id)?;
+        }
+        if self.offset != 0 {
+            struct_ser.serialize_field("offset", ToString::to_string(&self.offset).as_str())?;
+        }
+        if self.follow {
+            struct_ser.serialize_field("follow", &self.follow)?;
+        }
+        if self.attempt != 0 {
+            struct_ser.serialize_field("attempt", &self.attempt)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for StreamRunLogsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "id",
+            "offset",
+            "follow",
+            "attempt",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Id,
+            Offset,
+            Follow,
+            Attempt,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "id" => Ok(GeneratedField::Id),
+                            "offset" => Ok(GeneratedField::Offset),
+                            "follow" => Ok(GeneratedField::Follow),
+                            "attempt" => Ok(GeneratedField::Attempt),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = StreamRunLogsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.StreamRunLogsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<StreamRunLogsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut id = None;
+                let mut offset = None;
+                let mut follow = None;
+                let mut attempt = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Id => {
+                            if id.is_some() {
+                                return Err(serde::de::Error::duplicate_field("id"));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                        GeneratedField::Offset => {
+                            if offset.is_some() {
+                                return Err(serde::de::Error::duplicate_field("offset"));
+                            }
+                            offset = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                        GeneratedField::Follow => {
+                            if follow.is_some() {
+                                return Err(serde::de::Error::duplicate_field("follow"));
+                            }
+                            follow = Some(map.next_value()?);
+                        }
+                        GeneratedField::Attempt => {
+                            if attempt.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attempt"));
+                            }
+                            attempt = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
+                            );
+                        }
+                    }
+                }
+                Ok(StreamRunLogsRequest {
+                    id: id.unwrap_or_default(),
+                    offset: offset.unwrap_or_default(),
+                    follow: follow.unwrap_or_default(),
+                    attempt: attempt.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("bda.StreamRunLogsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SubmitRunRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
             len += 1;
         }
-        if !self.description.is_empty() {
+        if !self.namespace.is_empty() {
             len += 1;
         }
-        if self.parameter_kind != 0 {
+        if !self.name.is_empty() {
             len += 1;
         }
-        if self.default_value.is_some() {
+        if self.inputs.is_some() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.Parameter", len)?;
+        if !self.runtime.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.SubmitRunRequest", len)?;
+        if !self.version.is_empty() {
+            struct_ser.serialize_field("version", &self.version)?;
+        }
+        if !self.namespace.is_empty() {
+            struct_ser.serialize_field("namespace", &self.namespace)?;
+        }
         if !self.name.is_empty() {
             struct_ser.serialize_field("name", &self.name)?;
         }
-        if !self.description.is_empty() {
-            struct_ser.serialize_field("description", &self.description)?;
-        }
-        if self.parameter_kind != 0 {
-            let v = parameter::ParameterKind::from_i32(self.parameter_kind)
-                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.parameter_kind)))?;
-            struct_ser.serialize_field("parameterKind", &v)?;
+        if let Some(v) = self.inputs.as_ref() {
+            struct_ser.serialize_field("inputs", v)?;
         }
-        if let Some(v) = self.default_value.as_ref() {
-            struct_ser.serialize_field("defaultValue", v)?;
+        if !self.runtime.is_empty() {
+            struct_ser.serialize_field("runtime", &self.runtime)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for Parameter {
+impl<'de> serde::Deserialize<'de> for SubmitRunRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
+            "version",
+            "namespace",
             "name",
-            "description",
-            "parameterKind",
-            "defaultValue",
+            "inputs",
+            "runtime",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
+            Version,
+            Namespace,
             Name,
-            Description,
-            ParameterKind,
-            DefaultValue,
+            Inputs,
+            Runtime,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1560,10 +8990,11 @@ impl<'de> serde::Deserialize<'de> for Parameter {
                         E: serde::de::Error,
                     {
                         match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "namespace" => Ok(GeneratedField::Namespace),
                             "name" => Ok(GeneratedField::Name),
-                            "description" => Ok(GeneratedField::Description),
-                            "parameterKind" => Ok(GeneratedField::ParameterKind),
-                            "defaultValue" => Ok(GeneratedField::DefaultValue),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            "runtime" => Ok(GeneratedField::Runtime),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1573,148 +9004,288 @@ impl<'de> serde::Deserialize<'de> for Parameter {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Parameter;
+            type Value = SubmitRunRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.Parameter")
+                formatter.write_str("struct bda.SubmitRunRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<Parameter, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SubmitRunRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
+                let mut version = None;
+                let mut namespace = None;
                 let mut name = None;
-                let mut description = None;
-                let mut parameter_kind = None;
-                let mut default_value = None;
+                let mut inputs = None;
+                let mut runtime = None;
                 while let Some(k) = map.next_key()? {
                     match k {
+                        GeneratedField::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
+                        GeneratedField::Namespace => {
+                            if namespace.is_some() {
+                                return Err(serde::de::Error::duplicate_field("namespace"));
+                            }
+                            namespace = Some(map.next_value()?);
+                        }
                         GeneratedField::Name => {
                             if name.is_some() {
                                 return Err(serde::de::Error::duplicate_field("name"));
                             }
                             name = Some(map.next_value()?);
                         }
-                        GeneratedField::Description => {
-                            if description.is_some() {
-                                return Err(serde::de::Error::duplicate_field("description"));
-                            }
-                            description = Some(map.next_value()?);
-                        }
-                        GeneratedField::ParameterKind => {
-                            if parameter_kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("parameterKind"));
+                        GeneratedField::Inputs => {
+                            if inputs.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
                             }
-                            parameter_kind = Some(map.next_value::<parameter::ParameterKind>()? as i32);
+                            inputs = Some(map.next_value()?);
                         }
-                        GeneratedField::DefaultValue => {
-                            if default_value.is_some() {
-                                return Err(serde::de::Error::duplicate_field("defaultValue"));
+                        GeneratedField::Runtime => {
+                            if runtime.is_some() {
+                                return Err(serde::de::Error::duplicate_field("runtime"));
                             }
-                            default_value = Some(map.next_value()?);
+                            runtime = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(Parameter {
+                Ok(SubmitRunRequest {
+                    version: version.unwrap_or_default(),
+                    namespace: namespace.unwrap_or_default(),
                     name: name.unwrap_or_default(),
-                    description: description.unwrap_or_default(),
-                    parameter_kind: parameter_kind.unwrap_or_default(),
-                    default_value,
+                    inputs,
+                    runtime: runtime.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.Parameter", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.SubmitRunRequest", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for parameter::ParameterKind {
+impl serde::Serialize for Trigger {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let variant = match self {
-            Self::Generic => "GENERIC",
-            Self::Number => "NUMBER",
-            Self::Boolean => "BOOLEAN",
-            Self::Text => "TEXT",
-            Self::Json => "JSON",
-            Self::Url => "URL",
-            Self::Path => "PATH",
-        };
-        serializer.serialize_str(variant)
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.trigger_kind.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Trigger", len)?;
+        if let Some(v) = self.trigger_kind.as_ref() {
+            match v {
+                trigger::TriggerKind::Cron(v) => {
+                    struct_ser.serialize_field("cron", v)?;
+                }
+                trigger::TriggerKind::ResourceChange(v) => {
+                    struct_ser.serialize_field("resourceChange", v)?;
+                }
+                trigger::TriggerKind::PathWatch(v) => {
+                    struct_ser.serialize_field("pathWatch", v)?;
+                }
+                trigger::TriggerKind::Webhook(v) => {
+                    struct_ser.serialize_field("webhook", v)?;
+                }
+            }
+        }
+        struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for parameter::ParameterKind {
+impl<'de> serde::Deserialize<'de> for Trigger {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "GENERIC",
-            "NUMBER",
-            "BOOLEAN",
-            "TEXT",
-            "JSON",
-            "URL",
-            "PATH",
+            "cron",
+            "resourceChange",
+            "pathWatch",
+            "webhook",
         ];
 
-        struct GeneratedVisitor;
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Cron,
+            ResourceChange,
+            PathWatch,
+            Webhook,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
 
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "cron" => Ok(GeneratedField::Cron),
+                            "resourceChange" => Ok(GeneratedField::ResourceChange),
+                            "pathWatch" => Ok(GeneratedField::PathWatch),
+                            "webhook" => Ok(GeneratedField::Webhook),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = parameter::ParameterKind;
+            type Value = Trigger;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(formatter, "expected one of: {:?}", &FIELDS)
+                formatter.write_str("struct bda.Trigger")
             }
 
-            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
-            where
-                E: serde::de::Error,
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Trigger, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
             {
-                use std::convert::TryFrom;
-                i32::try_from(v)
-                    .ok()
-                    .and_then(parameter::ParameterKind::from_i32)
-                    .ok_or_else(|| {
-                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
-                    })
+                let mut trigger_kind = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Cron => {
+                            if trigger_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("cron"));
+                            }
+                            trigger_kind = Some(trigger::TriggerKind::Cron(map.next_value()?));
+                        }
+                        GeneratedField::ResourceChange => {
+                            if trigger_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resourceChange"));
+                            }
+                            trigger_kind = Some(trigger::TriggerKind::ResourceChange(map.next_value()?));
+                        }
+                        GeneratedField::PathWatch => {
+                            if trigger_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("pathWatch"));
+                            }
+                            trigger_kind = Some(trigger::TriggerKind::PathWatch(map.next_value()?));
+                        }
+                        GeneratedField::Webhook => {
+                            if trigger_kind.is_some() {
+                                return Err(serde::de::Error::duplicate_field("webhook"));
+                            }
+                            trigger_kind = Some(trigger::TriggerKind::Webhook(map.next_value()?));
+                        }
+                    }
+                }
+                Ok(Trigger {
+                    trigger_kind,
+                })
             }
+        }
+        deserializer.deserialize_struct("bda.Trigger", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for trigger::Cron {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.schedule.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("bda.Trigger.Cron", len)?;
+        if !self.schedule.is_empty() {
+            struct_ser.serialize_field("schedule", &self.schedule)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for trigger::Cron {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "schedule",
+        ];
 
-            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Schedule,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
             where
-                E: serde::de::Error,
+                D: serde::Deserializer<'de>,
             {
-                use std::convert::TryFrom;
-                i32::try_from(v)
-                    .ok()
-                    .and_then(parameter::ParameterKind::from_i32)
-                    .ok_or_else(|| {
-                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
-                    })
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "schedule" => Ok(GeneratedField::Schedule),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
             }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = trigger::Cron;
 
-            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                match value {
-                    "GENERIC" => Ok(parameter::ParameterKind::Generic),
-                    "NUMBER" => Ok(parameter::ParameterKind::Number),
-                    "BOOLEAN" => Ok(parameter::ParameterKind::Boolean),
-                    "TEXT" => Ok(parameter::ParameterKind::Text),
-                    "JSON" => Ok(parameter::ParameterKind::Json),
-                    "URL" => Ok(parameter::ParameterKind::Url),
-                    "PATH" => Ok(parameter::ParameterKind::Path),
-                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct bda.Trigger.Cron")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<trigger::Cron, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut schedule = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Schedule => {
+                            if schedule.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schedule"));
+                            }
+                            schedule = Some(map.next_value()?);
+                        }
+                    }
                 }
+                Ok(trigger::Cron {
+                    schedule: schedule.unwrap_or_default(),
+                })
             }
         }
-        deserializer.deserialize_any(GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Trigger.Cron", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for PutResourceRequest {
+impl serde::Serialize for trigger::PathWatch {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1722,29 +9293,29 @@ impl serde::Serialize for PutResourceRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.resource.is_some() {
+        if !self.path.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.PutResourceRequest", len)?;
-        if let Some(v) = self.resource.as_ref() {
-            struct_ser.serialize_field("resource", v)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Trigger.PathWatch", len)?;
+        if !self.path.is_empty() {
+            struct_ser.serialize_field("path", &self.path)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for PutResourceRequest {
+impl<'de> serde::Deserialize<'de> for trigger::PathWatch {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "resource",
+            "path",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Resource,
+            Path,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1765,7 +9336,7 @@ impl<'de> serde::Deserialize<'de> for PutResourceRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "resource" => Ok(GeneratedField::Resource),
+                            "path" => Ok(GeneratedField::Path),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1775,36 +9346,36 @@ impl<'de> serde::Deserialize<'de> for PutResourceRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = PutResourceRequest;
+            type Value = trigger::PathWatch;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.PutResourceRequest")
+                formatter.write_str("struct bda.Trigger.PathWatch")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<PutResourceRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<trigger::PathWatch, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut resource = None;
+                let mut path = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Resource => {
-                            if resource.is_some() {
-                                return Err(serde::de::Error::duplicate_field("resource"));
+                        GeneratedField::Path => {
+                            if path.is_some() {
+                                return Err(serde::de::Error::duplicate_field("path"));
                             }
-                            resource = Some(map.next_value()?);
+                            path = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(PutResourceRequest {
-                    resource,
+                Ok(trigger::PathWatch {
+                    path: path.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.PutResourceRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Trigger.PathWatch", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for PutResourceResponse {
+impl serde::Serialize for trigger::ResourceChange {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1812,29 +9383,29 @@ impl serde::Serialize for PutResourceResponse {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.updates != 0 {
+        if !self.bql.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.PutResourceResponse", len)?;
-        if self.updates != 0 {
-            struct_ser.serialize_field("updates", &self.updates)?;
+        let mut struct_ser = serializer.serialize_struct("bda.Trigger.ResourceChange", len)?;
+        if !self.bql.is_empty() {
+            struct_ser.serialize_field("bql", &self.bql)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for PutResourceResponse {
+impl<'de> serde::Deserialize<'de> for trigger::ResourceChange {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "updates",
+            "bql",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Updates,
+            Bql,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1855,7 +9426,7 @@ impl<'de> serde::Deserialize<'de> for PutResourceResponse {
                         E: serde::de::Error,
                     {
                         match value {
-                            "updates" => Ok(GeneratedField::Updates),
+                            "bql" => Ok(GeneratedField::Bql),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1865,38 +9436,36 @@ impl<'de> serde::Deserialize<'de> for PutResourceResponse {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = PutResourceResponse;
+            type Value = trigger::ResourceChange;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.PutResourceResponse")
+                formatter.write_str("struct bda.Trigger.ResourceChange")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<PutResourceResponse, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<trigger::ResourceChange, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut updates = None;
+                let mut bql = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Updates => {
-                            if updates.is_some() {
-                                return Err(serde::de::Error::duplicate_field("updates"));
+                        GeneratedField::Bql => {
+                            if bql.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bql"));
                             }
-                            updates = Some(
-                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0
-                            );
+                            bql = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(PutResourceResponse {
-                    updates: updates.unwrap_or_default(),
+                Ok(trigger::ResourceChange {
+                    bql: bql.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.PutResourceResponse", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Trigger.ResourceChange", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for Resource {
+impl serde::Serialize for trigger::Webhook {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1904,86 +9473,29 @@ impl serde::Serialize for Resource {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.version.is_empty() {
-            len += 1;
-        }
-        if !self.namespace.is_empty() {
-            len += 1;
-        }
-        if !self.name.is_empty() {
-            len += 1;
-        }
-        if !self.description.is_empty() {
-            len += 1;
-        }
-        if !self.tags.is_empty() {
-            len += 1;
-        }
-        if self.attributes.is_some() {
-            len += 1;
-        }
-        if self.resource_kind.is_some() {
+        if !self.path.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.Resource", len)?;
-        if !self.version.is_empty() {
-            struct_ser.serialize_field("version", &self.version)?;
-        }
-        if !self.namespace.is_empty() {
-            struct_ser.serialize_field("namespace", &self.namespace)?;
-        }
-        if !self.name.is_empty() {
-            struct_ser.serialize_field("name", &self.name)?;
-        }
-        if !self.description.is_empty() {
-            struct_ser.serialize_field("description", &self.description)?;
-        }
-        if !self.tags.is_empty() {
-            struct_ser.serialize_field("tags", &self.tags)?;
-        }
-        if let Some(v) = self.attributes.as_ref() {
-            struct_ser.serialize_field("attributes", v)?;
-        }
-        if let Some(v) = self.resource_kind.as_ref() {
-            match v {
-                resource::ResourceKind::Function(v) => {
-                    struct_ser.serialize_field("function", v)?;
-                }
-                resource::ResourceKind::Runtime(v) => {
-                    struct_ser.serialize_field("runtime", v)?;
-                }
-            }
+        let mut struct_ser = serializer.serialize_struct("bda.Trigger.Webhook", len)?;
+        if !self.path.is_empty() {
+            struct_ser.serialize_field("path", &self.path)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for Resource {
+impl<'de> serde::Deserialize<'de> for trigger::Webhook {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "version",
-            "namespace",
-            "name",
-            "description",
-            "tags",
-            "attributes",
-            "function",
-            "runtime",
+            "path",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Version,
-            Namespace,
-            Name,
-            Description,
-            Tags,
-            Attributes,
-            Function,
-            Runtime,
+            Path,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -2004,14 +9516,7 @@ impl<'de> serde::Deserialize<'de> for Resource {
                         E: serde::de::Error,
                     {
                         match value {
-                            "version" => Ok(GeneratedField::Version),
-                            "namespace" => Ok(GeneratedField::Namespace),
-                            "name" => Ok(GeneratedField::Name),
-                            "description" => Ok(GeneratedField::Description),
-                            "tags" => Ok(GeneratedField::Tags),
-                            "attributes" => Ok(GeneratedField::Attributes),
-                            "function" => Ok(GeneratedField::Function),
-                            "runtime" => Ok(GeneratedField::Runtime),
+                            "path" => Ok(GeneratedField::Path),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -2021,90 +9526,36 @@ impl<'de> serde::Deserialize<'de> for Resource {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Resource;
+            type Value = trigger::Webhook;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.Resource")
+                formatter.write_str("struct bda.Trigger.Webhook")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<Resource, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<trigger::Webhook, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut version = None;
-                let mut namespace = None;
-                let mut name = None;
-                let mut description = None;
-                let mut tags = None;
-                let mut attributes = None;
-                let mut resource_kind = None;
+                let mut path = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Version => {
-                            if version.is_some() {
-                                return Err(serde::de::Error::duplicate_field("version"));
-                            }
-                            version = Some(map.next_value()?);
-                        }
-                        GeneratedField::Namespace => {
-                            if namespace.is_some() {
-                                return Err(serde::de::Error::duplicate_field("namespace"));
-                            }
-                            namespace = Some(map.next_value()?);
-                        }
-                        GeneratedField::Name => {
-                            if name.is_some() {
-                                return Err(serde::de::Error::duplicate_field("name"));
-                            }
-                            name = Some(map.next_value()?);
-                        }
-                        GeneratedField::Description => {
-                            if description.is_some() {
-                                return Err(serde::de::Error::duplicate_field("description"));
-                            }
-                            description = Some(map.next_value()?);
-                        }
-                        GeneratedField::Tags => {
-                            if tags.is_some() {
-                                return Err(serde::de::Error::duplicate_field("tags"));
-                            }
-                            tags = Some(map.next_value()?);
-                        }
-                        GeneratedField::Attributes => {
-                            if attributes.is_some() {
-                                return Err(serde::de::Error::duplicate_field("attributes"));
-                            }
-                            attributes = Some(map.next_value()?);
-                        }
-                        GeneratedField::Function => {
-                            if resource_kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("function"));
-                            }
-                            resource_kind = Some(resource::ResourceKind::Function(map.next_value()?));
-                        }
-                        GeneratedField::Runtime => {
-                            if resource_kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("runtime"));
+                        GeneratedField::Path => {
+                            if path.is_some() {
+                                return Err(serde::de::Error::duplicate_field("path"));
                             }
-                            resource_kind = Some(resource::ResourceKind::Runtime(map.next_value()?));
+                            path = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(Resource {
-                    version: version.unwrap_or_default(),
-                    namespace: namespace.unwrap_or_default(),
-                    name: name.unwrap_or_default(),
-                    description: description.unwrap_or_default(),
-                    tags: tags.unwrap_or_default(),
-                    attributes,
-                    resource_kind,
+                Ok(trigger::Webhook {
+                    path: path.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.Resource", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.Trigger.Webhook", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for Runtime {
+impl serde::Serialize for WatchResourcesRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -2112,41 +9563,29 @@ impl serde::Serialize for Runtime {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.capabilities.is_empty() {
-            len += 1;
-        }
-        if self.runtime_kind.is_some() {
+        if !self.bql.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("bda.Runtime", len)?;
-        if !self.capabilities.is_empty() {
-            struct_ser.serialize_field("capabilities", &self.capabilities)?;
-        }
-        if let Some(v) = self.runtime_kind.as_ref() {
-            match v {
-                runtime::RuntimeKind::Container(v) => {
-                    struct_ser.serialize_field("container", v)?;
-                }
-            }
+        let mut struct_ser = serializer.serialize_struct("bda.WatchResourcesRequest", len)?;
+        if !self.bql.is_empty() {
+            struct_ser.serialize_field("bql", &self.bql)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for Runtime {
+impl<'de> serde::Deserialize<'de> for WatchResourcesRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "capabilities",
-            "container",
+            "bql",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Capabilities,
-            Container,
+            Bql,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -2167,8 +9606,7 @@ impl<'de> serde::Deserialize<'de> for Runtime {
                         E: serde::de::Error,
                     {
                         match value {
-                            "capabilities" => Ok(GeneratedField::Capabilities),
-                            "container" => Ok(GeneratedField::Container),
+                            "bql" => Ok(GeneratedField::Bql),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -2178,40 +9616,32 @@ impl<'de> serde::Deserialize<'de> for Runtime {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Runtime;
+            type Value = WatchResourcesRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct bda.Runtime")
+                formatter.write_str("struct bda.WatchResourcesRequest")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<Runtime, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<WatchResourcesRequest, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut capabilities = None;
-                let mut runtime_kind = None;
+                let mut bql = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Capabilities => {
-                            if capabilities.is_some() {
-                                return Err(serde::de::Error::duplicate_field("capabilities"));
-                            }
-                            capabilities = Some(map.next_value()?);
-                        }
-                        GeneratedField::Container => {
-                            if runtime_kind.is_some() {
-                                return Err(serde::de::Error::duplicate_field("container"));
+                        GeneratedField::Bql => {
+                            if bql.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bql"));
                             }
-                            runtime_kind = Some(runtime::RuntimeKind::Container(map.next_value()?));
+                            bql = Some(map.next_value()?);
                         }
                     }
                 }
-                Ok(Runtime {
-                    capabilities: capabilities.unwrap_or_default(),
-                    runtime_kind,
+                Ok(WatchResourcesRequest {
+                    bql: bql.unwrap_or_default(),
                 })
             }
         }
-        deserializer.deserialize_struct("bda.Runtime", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("bda.WatchResourcesRequest", FIELDS, GeneratedVisitor)
     }
 }
@@ -7,6 +7,8 @@ fn test_serde() {
         version: String::from("latest"),
         tags: vec![String::from("tag1"), String::from("tag2")],
         attributes: None,
+        references: vec![],
+        labels: std::collections::HashMap::new(),
         resource_kind: Some(bdaproto::resource::ResourceKind::Runtime(
             bdaproto::Runtime {
                 capabilities: vec!["git".to_owned(), "linux".to_owned()],